@@ -38,10 +38,16 @@ const entityService = resourceContainer.get("entity_service");
 const windowsService = resourceContainer.get("windows_service");
 const customService = resourceContainer.get("custom_service");
 const inputService = resourceContainer.get("input_service");
+const componentSchemaService = resourceContainer.get("component_schema_service");
 const frameService = resourceContainer.get("frame_service");
 
 customService.hello("World");
 
+// The script components are declared so the scenes that store them can be checked
+componentSchemaService.registerScriptComponent("Move");
+componentSchemaService.registerScriptComponent("Velocity");
+componentSchemaService.registerScriptComponent("TestVec");
+
 systemService.addStartupSystem("test startup 0", () => {
     console.log("Je commence tout");
 
@@ -119,27 +125,13 @@ systemService.addSystem("test 2", () => {
         .with("Translate2d")
         .with("Move")
         .forEach((entity, translate, move) => {
-            let vel = new Vector2d({ x: 0, y: 0 });
-
-            if (inputService.isPressed("Run Left")) {
-                vel.x -= move.velocity;
-            }
-
-            if (inputService.isPressed("Run Right")) {
-                vel.x += move.velocity;
-            }
-
-            if (inputService.isPressed("Jump")) {
-                vel.y += move.velocity;
-            }
-
-            if (inputService.isPressed("Down")) {
-                vel.y -= move.velocity;
-            }
+            let vel = inputService.getVector2d("Move").mul(move.velocity);
 
             translate.vec = translate.vec.add(vel.mul(frameService.delta));
         });
-});
+}, new SystemParams({
+    after: ["test 1"],
+}));
 
 systemService.addSystem("test 3", () => {
     entityService
@@ -151,4 +143,35 @@ systemService.addSystem("test 3", () => {
                 rotate.angle += move.velocity * frameService.delta;
             }
         });
-});
\ No newline at end of file
+});
+
+systemService.addSystem("rebind jump", () => {
+    if (inputService.isPressedThisFrame("Rebind Jump")) {
+        inputService.listenNextInput();
+    }
+
+    const binding = inputService.takeListenedInput();
+    if (binding) {
+        const conflicts = inputService.rebind("Jump", 0, binding);
+        if (conflicts && conflicts.length > 0) {
+            console.log(`${binding} is also used by ${conflicts.join(", ")}`);
+        }
+
+        inputService.saveInputOverrides();
+    }
+}, new SystemParams({
+    ignorePause: true,
+}));
+
+systemService.addSystem("text input", () => {
+    if (inputService.isPressedThisFrame("Toggle Text Input")) {
+        inputService.setTextInputEnabled(!inputService.isTextInputEnabled());
+    }
+
+    const text = inputService.getTextThisFrame();
+    if (text.length > 0) {
+        console.log(`Typed ${text}`);
+    }
+}, new SystemParams({
+    ignorePause: true,
+}));
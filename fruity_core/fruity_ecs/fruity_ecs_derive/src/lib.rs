@@ -5,8 +5,9 @@ use syn::Data;
 use syn::DeriveInput;
 use syn::Fields;
 use syn::Index;
+use syn::LitInt;
 
-#[proc_macro_derive(Component)]
+#[proc_macro_derive(Component, attributes(schema_version))]
 pub fn derive_component(input: TokenStream) -> TokenStream {
     let derive_component_trait = derive_component_trait(input.clone());
     let derive_introspect_object_trait = derive_introspect_object_trait(input.clone());
@@ -27,9 +28,19 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
 
 
 fn derive_component_trait(input: TokenStream) -> TokenStream {
-    let DeriveInput { ident, .. } = parse_macro_input!(input);
+    let DeriveInput { ident, attrs, .. } = parse_macro_input!(input);
     let struct_name = ident.to_string();
 
+    // The schema version is declared with #[schema_version(2)]
+    let schema_version = attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("schema_version"))
+        .map(|attr| match attr.parse_args::<LitInt>() {
+            Ok(version) => version.base10_parse::<u32>().unwrap(),
+            Err(_) => panic!("Expected a version like #[schema_version(2)]"),
+        })
+        .unwrap_or(1);
+
     let output = quote! {
         impl fruity_ecs::component::component::Component for #ident {
             fn get_collection(&self) -> Box<dyn fruity_ecs::entity::archetype::component_collection::ComponentCollection> {
@@ -39,6 +50,10 @@ fn derive_component_trait(input: TokenStream) -> TokenStream {
             fn duplicate(&self) -> Box<dyn fruity_ecs::component::component::Component> {
                 Box::new(self.clone())
             }
+
+            fn get_schema_version(&self) -> u32 {
+                #schema_version
+            }
         }
 
         impl fruity_ecs::component::component::StaticComponent for #ident {
@@ -1,3 +1,5 @@
+use crate::component::component_schema_service::DEFAULT_SCHEMA_VERSION;
+use crate::component::component_schema_service::SCHEMA_VERSION_FIELD;
 use crate::component::serialized_component::SerializedComponent;
 use crate::entity::archetype::component_collection::ComponentCollection;
 use fruity_any::*;
@@ -29,13 +31,27 @@ pub trait Component: IntrospectObject + Debug {
 
     /// Create a new component that is a clone of self
     fn duplicate(&self) -> Box<dyn Component>;
+
+    /// Get the schema version of the component, it is written into the snapshots so the
+    /// components saved with a previous schema can be migrated
+    fn get_schema_version(&self) -> u32 {
+        DEFAULT_SCHEMA_VERSION
+    }
 }
 
 impl Serialize for &dyn Component {
     fn serialize(&self) -> Option<Serialized> {
         let native_serialized =
             Serialized::NativeObject(Box::new(AnyComponent::from_box(self.duplicate())));
-        let serialized = native_serialized.serialize_native_objects();
+        let mut serialized = native_serialized.serialize_native_objects();
+
+        if let Serialized::SerializedObject { fields, .. } = &mut serialized {
+            fields.insert(
+                SCHEMA_VERSION_FIELD.to_string(),
+                Serialized::U32(self.get_schema_version()),
+            );
+        }
+
         Some(serialized)
     }
 }
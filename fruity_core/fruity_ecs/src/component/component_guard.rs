@@ -9,6 +9,7 @@ use std::marker::PhantomData;
 use std::ops::Deref;
 use std::ops::DerefMut;
 use std::rc::Rc;
+use std::sync::atomic::Ordering;
 
 #[derive(Clone)]
 pub(crate) enum InternalReadGuard<'a> {
@@ -171,6 +172,10 @@ impl<'a> DerefMut for ComponentWriteGuard<'a> {
 
         let component = storage.collection.get(&self.component_index).unwrap();
 
+        // Date the change, it is used by the queries to detect changed components
+        let tick = self.archetype_reader.change_tick.load(Ordering::Relaxed);
+        storage.ticks[self.component_index].mark_changed(tick);
+
         // Safe cause it is protected by self._guard
         #[allow(mutable_transmutes)]
         unsafe {
@@ -0,0 +1,539 @@
+use crate::component::component::AnyComponent;
+use fruity_any::*;
+use fruity_core::convert::FruityTryFrom;
+use fruity_core::introspect::log_introspect_error;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodCaller;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::object_factory_service::ObjectFactoryService;
+use fruity_core::resource::resource::Resource;
+use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::serialize::serialized::Callback;
+use fruity_core::serialize::serialized::Serialized;
+use fruity_core::utils::introspect::cast_introspect_mut;
+use fruity_core::utils::introspect::ArgumentCaster;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+/// The field where the schema version of a component is written into the snapshots
+pub const SCHEMA_VERSION_FIELD: &str = "$schema_version";
+
+/// The schema version of the components that don't declare one, and of the components saved
+/// before the versions were written
+pub const DEFAULT_SCHEMA_VERSION: u32 = 1;
+
+/// A function that transforms the fields of a serialized component from a version to the next one
+pub type ComponentMigration =
+    Box<dyn Fn(HashMap<String, Serialized>) -> HashMap<String, Serialized> + Send + Sync>;
+
+/// A problem met while reading a serialized component
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComponentSchemaDiagnostic {
+    /// No component is registered with this class name, neither as a native component nor as
+    /// a script component, the component is kept as serialized, the class name is provided
+    UnknownClass(String),
+    /// The component has no field with this name, the value is ignored, the class name and the
+    /// field are provided
+    UnknownField(String, String),
+    /// The component has been saved with a newer schema than the one that is known, the class
+    /// name, the saved version and the known version are provided
+    NewerSchemaVersion(String, u32, u32),
+    /// No migration is registered to upgrade the component from a version, the class name and
+    /// the version are provided
+    MissingMigration(String, u32),
+}
+
+impl Display for ComponentSchemaDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComponentSchemaDiagnostic::UnknownClass(class_name) => write!(
+                f,
+                "Component \"{}\" is not registered, it is kept as a serialized component",
+                class_name
+            ),
+            ComponentSchemaDiagnostic::UnknownField(class_name, field) => write!(
+                f,
+                "Component \"{}\" has no field \"{}\", the value is ignored",
+                class_name, field
+            ),
+            ComponentSchemaDiagnostic::NewerSchemaVersion(class_name, version, known_version) => {
+                write!(
+                    f,
+                    "Component \"{}\" has been saved with the schema version {} but only the version {} is known",
+                    class_name, version, known_version
+                )
+            }
+            ComponentSchemaDiagnostic::MissingMigration(class_name, version) => write!(
+                f,
+                "Component \"{}\" cannot be migrated from the schema version {}, no migration is registered",
+                class_name, version
+            ),
+        }
+    }
+}
+
+/// A service to store the migrations of the components
+///
+/// When a component schema changes, for example when a field is renamed, the component declares
+/// a new schema version with #[schema_version(2)] and a migration is registered to transform the
+/// components that have been saved with the previous version
+///
+/// The script components are not known by the object factory, the scripts declare them with
+/// [’ComponentSchemaService::register_script_component’]
+#[derive(FruityAny)]
+pub struct ComponentSchemaService {
+    migrations: HashMap<(String, u32), ComponentMigration>,
+    script_components: HashMap<String, u32>,
+}
+
+impl ComponentSchemaService {
+    /// Returns a ComponentSchemaService
+    pub fn new(_resource_container: ResourceContainer) -> Self {
+        Self {
+            migrations: HashMap::new(),
+            script_components: HashMap::new(),
+        }
+    }
+
+    /// Register a component class that is implemented by a script
+    ///
+    /// # Arguments
+    /// * `class_name` - The component class name
+    /// * `schema_version` - The current schema version of the component
+    ///
+    pub fn register_script_component(&mut self, class_name: &str, schema_version: u32) {
+        self.script_components
+            .insert(class_name.to_string(), schema_version);
+    }
+
+    /// Get the schema version of a component class that is implemented by a script, None if
+    /// the class is not registered as a script component
+    ///
+    /// # Arguments
+    /// * `class_name` - The component class name
+    ///
+    pub fn get_script_component_version(&self, class_name: &str) -> Option<u32> {
+        self.script_components.get(class_name).copied()
+    }
+
+    /// Register a migration
+    ///
+    /// # Arguments
+    /// * `class_name` - The component class name
+    /// * `from_version` - The version that is upgraded, the migration returns the next version
+    /// * `migration` - The function that transforms the component fields
+    ///
+    pub fn register_migration(
+        &mut self,
+        class_name: &str,
+        from_version: u32,
+        migration: impl Fn(HashMap<String, Serialized>) -> HashMap<String, Serialized>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.migrations
+            .insert((class_name.to_string(), from_version), Box::new(migration));
+    }
+
+    /// Upgrade a serialized component to the schema version of the registered component
+    /// The schema version field is removed from the native components, so the result can be
+    /// deserialized, the script components keep the version they have been migrated to so it is
+    /// written again when they are saved
+    /// Returns the problems met, the component is migrated as far as possible
+    ///
+    /// # Arguments
+    /// * `serialized_component` - The serialized component
+    /// * `object_factory_service` - The object factory where the components are registered
+    ///
+    pub fn migrate_component(
+        &self,
+        serialized_component: &mut Serialized,
+        object_factory_service: &ObjectFactoryService,
+    ) -> Vec<ComponentSchemaDiagnostic> {
+        let (class_name, fields) =
+            if let Serialized::SerializedObject { class_name, fields } = serialized_component {
+                (class_name, fields)
+            } else {
+                return Vec::new();
+            };
+
+        // A default component is instantiated to know its current schema, the fields of the
+        // script components are not known
+        // The unknown components keep their version so it is written again when they are saved
+        let (current_version, field_names) =
+            if let Some(schema_version) = self.script_components.get(class_name) {
+                (*schema_version, None)
+            } else {
+                let component = match object_factory_service.instantiate(class_name, Vec::new()) {
+                    Some(component @ Serialized::NativeObject(_)) => {
+                        AnyComponent::fruity_try_from(component).ok()
+                    }
+                    _ => None,
+                };
+
+                if let Some(component) = component {
+                    let field_names = component
+                        .get_field_infos()
+                        .into_iter()
+                        .map(|field_info| field_info.name)
+                        .collect::<Vec<_>>();
+
+                    (component.get_schema_version(), Some(field_names))
+                } else {
+                    return vec![ComponentSchemaDiagnostic::UnknownClass(class_name.clone())];
+                }
+            };
+
+        let mut version = fields
+            .remove(SCHEMA_VERSION_FIELD)
+            .and_then(|version| u32::fruity_try_from(version).ok())
+            .unwrap_or(DEFAULT_SCHEMA_VERSION);
+
+        let mut diagnostics = Vec::new();
+        if version > current_version {
+            diagnostics.push(ComponentSchemaDiagnostic::NewerSchemaVersion(
+                class_name.clone(),
+                version,
+                current_version,
+            ));
+        }
+
+        while version < current_version {
+            match self.migrations.get(&(class_name.clone(), version)) {
+                Some(migration) => {
+                    *fields = migration(std::mem::take(fields));
+                    version += 1;
+                }
+                None => {
+                    diagnostics.push(ComponentSchemaDiagnostic::MissingMigration(
+                        class_name.clone(),
+                        version,
+                    ));
+                    break;
+                }
+            }
+        }
+
+        // A newer version is kept, that way the component is not saved with an older version
+        if field_names.is_none() {
+            fields.insert(SCHEMA_VERSION_FIELD.to_string(), Serialized::U32(version));
+        }
+
+        if let Some(field_names) = field_names {
+            let mut unknown_fields = fields
+                .keys()
+                .filter(|field| !field_names.contains(field))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            unknown_fields.sort();
+            diagnostics.extend(
+                unknown_fields.into_iter().map(|field| {
+                    ComponentSchemaDiagnostic::UnknownField(class_name.clone(), field)
+                }),
+            );
+        }
+
+        diagnostics
+    }
+
+    /// Upgrade all the components of a snapshot, see [’ComponentSchemaService::migrate_component’]
+    /// Returns the problems met, each problem is reported once
+    ///
+    /// # Arguments
+    /// * `serialized_entities` - The serialized entities, as produced by [’EntityService::snapshot’]
+    /// * `object_factory_service` - The object factory where the components are registered
+    ///
+    pub fn migrate_entities(
+        &self,
+        serialized_entities: &mut [Serialized],
+        object_factory_service: &ObjectFactoryService,
+    ) -> Vec<ComponentSchemaDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        serialized_entities
+            .iter_mut()
+            .filter_map(|serialized_entity| match serialized_entity {
+                Serialized::SerializedObject { fields, .. } => match fields.get_mut("components") {
+                    Some(Serialized::Array(components)) => Some(components),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .flat_map(|components| components.iter_mut())
+            .for_each(|component| {
+                self.migrate_component(component, object_factory_service)
+                    .into_iter()
+                    .for_each(|diagnostic| {
+                        if !diagnostics.contains(&diagnostic) {
+                            diagnostics.push(diagnostic);
+                        }
+                    });
+            });
+
+        diagnostics
+    }
+}
+
+impl Debug for ComponentSchemaService {
+    fn fmt(&self, _: &mut Formatter) -> Result<(), std::fmt::Error> {
+        Ok(())
+    }
+}
+
+impl IntrospectObject for ComponentSchemaService {
+    fn get_class_name(&self) -> String {
+        "ComponentSchemaService".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![
+            MethodInfo {
+                name: "register_script_component".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<ComponentSchemaService>(this);
+
+                    let mut caster = ArgumentCaster::new("register_script_component", args);
+                    let arg1 = caster.cast_next::<String>()?;
+                    let arg2 = caster.cast_next_optional::<u32>();
+
+                    this.register_script_component(&arg1, arg2.unwrap_or(DEFAULT_SCHEMA_VERSION));
+
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "register_migration".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<ComponentSchemaService>(this);
+
+                    let mut caster = ArgumentCaster::new("register_migration", args);
+                    let arg1 = caster.cast_next::<String>()?;
+                    let arg2 = caster.cast_next::<u32>()?;
+                    let arg3 = caster.cast_next::<Callback>()?;
+
+                    let class_name = arg1.clone();
+                    let callback = arg3.callback;
+                    this.register_migration(&arg1, arg2, move |fields| {
+                        let serialized = Serialized::SerializedObject {
+                            class_name: class_name.clone(),
+                            fields: fields.clone(),
+                        };
+
+                        // The fields are kept unchanged if the script fails
+                        match callback(vec![serialized]) {
+                            Ok(Some(Serialized::SerializedObject { fields, .. })) => fields,
+                            Ok(_) => {
+                                log::error!(
+                                    "The migration of \"{}\" should return an object",
+                                    &class_name
+                                );
+                                fields
+                            }
+                            Err(err) => {
+                                log_introspect_error(&err);
+                                fields
+                            }
+                        }
+                    });
+
+                    Ok(None)
+                })),
+            },
+        ]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
+
+impl Resource for ComponentSchemaService {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::component::Component;
+    use crate::entity::archetype::component_array::ComponentArray;
+    use crate::entity::archetype::component_collection::ComponentCollection;
+    use fruity_core::introspect::SetterCaller;
+    use maplit::hashmap;
+
+    // The version 1 stored the position into a "pos" field
+    #[derive(Debug, Clone, Default, FruityAny)]
+    struct Position {
+        position: f32,
+    }
+
+    impl Component for Position {
+        fn get_collection(&self) -> Box<dyn ComponentCollection> {
+            Box::new(ComponentArray::<Position>::new())
+        }
+
+        fn duplicate(&self) -> Box<dyn Component> {
+            Box::new(self.clone())
+        }
+
+        fn get_schema_version(&self) -> u32 {
+            2
+        }
+    }
+
+    impl IntrospectObject for Position {
+        fn get_class_name(&self) -> String {
+            "Position".to_string()
+        }
+
+        fn get_method_infos(&self) -> Vec<MethodInfo> {
+            vec![]
+        }
+
+        fn get_field_infos(&self) -> Vec<FieldInfo> {
+            vec![FieldInfo {
+                name: "position".to_string(),
+                serializable: true,
+                getter: Arc::new(|this| {
+                    Serialized::F32(this.downcast_ref::<Position>().unwrap().position)
+                }),
+                setter: SetterCaller::None,
+            }]
+        }
+    }
+
+    fn object_factory_service() -> ObjectFactoryService {
+        let mut object_factory_service = ObjectFactoryService::new(ResourceContainer::new());
+        object_factory_service.register_func("Position", |_, _| {
+            Ok(Serialized::NativeObject(Box::new(AnyComponent::new(
+                Position::default(),
+            ))))
+        });
+
+        object_factory_service
+    }
+
+    fn position(fields: HashMap<String, Serialized>) -> Serialized {
+        Serialized::SerializedObject {
+            class_name: "Position".to_string(),
+            fields,
+        }
+    }
+
+    fn rename_pos(mut fields: HashMap<String, Serialized>) -> HashMap<String, Serialized> {
+        if let Some(value) = fields.remove("pos") {
+            fields.insert("position".to_string(), value);
+        }
+
+        fields
+    }
+
+    #[test]
+    fn migrate_component_applies_the_migrations() {
+        let object_factory_service = object_factory_service();
+        let mut component_schema_service = ComponentSchemaService::new(ResourceContainer::new());
+        component_schema_service.register_migration("Position", 1, rename_pos);
+
+        // The components saved before the versions were written are read as the version 1
+        let mut component = position(hashmap! {
+            "pos".to_string() => Serialized::F32(2.0),
+        });
+        let diagnostics =
+            component_schema_service.migrate_component(&mut component, &object_factory_service);
+
+        assert_eq!(diagnostics, vec![]);
+        assert!(matches!(
+            component,
+            Serialized::SerializedObject { ref fields, .. }
+                if fields.len() == 1 && matches!(fields.get("position"), Some(Serialized::F32(value)) if *value == 2.0)
+        ));
+
+        // The current version is not migrated
+        let mut component = position(hashmap! {
+            SCHEMA_VERSION_FIELD.to_string() => Serialized::U32(2),
+            "position".to_string() => Serialized::F32(3.0),
+        });
+        let diagnostics =
+            component_schema_service.migrate_component(&mut component, &object_factory_service);
+
+        assert_eq!(diagnostics, vec![]);
+        assert!(matches!(
+            component,
+            Serialized::SerializedObject { ref fields, .. } if fields.len() == 1
+        ));
+    }
+
+    #[test]
+    fn migrate_entities_reports_the_problems() {
+        let object_factory_service = object_factory_service();
+        let mut component_schema_service = ComponentSchemaService::new(ResourceContainer::new());
+        component_schema_service.register_script_component("Move", 1);
+
+        let entity = |components: Vec<Serialized>| Serialized::SerializedObject {
+            class_name: "Entity".to_string(),
+            fields: hashmap! {
+                "components".to_string() => Serialized::Array(components),
+            },
+        };
+
+        let mut entities = vec![
+            entity(vec![
+                position(hashmap! {
+                    "pos".to_string() => Serialized::F32(1.0),
+                }),
+                Serialized::SerializedObject {
+                    class_name: "Move".to_string(),
+                    fields: hashmap! {
+                        "velocity".to_string() => Serialized::F32(1.0),
+                    },
+                },
+            ]),
+            entity(vec![
+                position(hashmap! {
+                    SCHEMA_VERSION_FIELD.to_string() => Serialized::U32(3),
+                }),
+                Serialized::SerializedObject {
+                    class_name: "Unknown".to_string(),
+                    fields: hashmap! {
+                        SCHEMA_VERSION_FIELD.to_string() => Serialized::U32(4),
+                    },
+                },
+                Serialized::SerializedObject {
+                    class_name: "Unknown".to_string(),
+                    fields: HashMap::new(),
+                },
+            ]),
+        ];
+
+        let diagnostics =
+            component_schema_service.migrate_entities(&mut entities, &object_factory_service);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                ComponentSchemaDiagnostic::MissingMigration("Position".to_string(), 1),
+                ComponentSchemaDiagnostic::UnknownField("Position".to_string(), "pos".to_string()),
+                ComponentSchemaDiagnostic::NewerSchemaVersion("Position".to_string(), 3, 2),
+                ComponentSchemaDiagnostic::UnknownClass("Unknown".to_string()),
+            ]
+        );
+
+        // The unknown components keep the version they have been saved with
+        assert!(matches!(
+            &entities[1],
+            Serialized::SerializedObject { fields, .. }
+                if matches!(
+                    fields.get("components"),
+                    Some(Serialized::Array(components))
+                        if matches!(
+                            &components[1],
+                            Serialized::SerializedObject { fields, .. }
+                                if fields.contains_key(SCHEMA_VERSION_FIELD)
+                        )
+                )
+        ));
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// The ticks at which a component has been added and changed for the last time
+///
+/// The ticks are compared to the tick of the last run of a system to know if a component
+/// has been added or changed since
+#[derive(Debug, Default)]
+pub struct ComponentTicks {
+    added: AtomicU64,
+    changed: AtomicU64,
+}
+
+impl ComponentTicks {
+    /// Returns ComponentTicks for a component added at a given tick
+    ///
+    /// # Arguments
+    /// * `tick` - The current change tick
+    ///
+    pub fn new(tick: u64) -> ComponentTicks {
+        ComponentTicks {
+            added: AtomicU64::new(tick),
+            changed: AtomicU64::new(tick),
+        }
+    }
+
+    /// Get the tick at which the component has been added
+    pub fn get_added(&self) -> u64 {
+        self.added.load(Ordering::Relaxed)
+    }
+
+    /// Get the tick at which the component has been changed for the last time
+    pub fn get_changed(&self) -> u64 {
+        self.changed.load(Ordering::Relaxed)
+    }
+
+    /// Has the component been added after a given tick
+    ///
+    /// # Arguments
+    /// * `last_run_tick` - The tick to compare with
+    ///
+    pub fn is_added(&self, last_run_tick: u64) -> bool {
+        self.get_added() > last_run_tick
+    }
+
+    /// Has the component been changed after a given tick, an added component is also changed
+    ///
+    /// # Arguments
+    /// * `last_run_tick` - The tick to compare with
+    ///
+    pub fn is_changed(&self, last_run_tick: u64) -> bool {
+        self.get_changed() > last_run_tick
+    }
+
+    pub(crate) fn mark_changed(&self, tick: u64) {
+        self.changed.store(tick, Ordering::Relaxed);
+    }
+
+    pub(crate) fn copy_from(&self, other: &ComponentTicks) {
+        self.added.store(other.get_added(), Ordering::Relaxed);
+        self.changed.store(other.get_changed(), Ordering::Relaxed);
+    }
+}
+
+impl Clone for ComponentTicks {
+    fn clone(&self) -> Self {
+        ComponentTicks {
+            added: AtomicU64::new(self.get_added()),
+            changed: AtomicU64::new(self.get_changed()),
+        }
+    }
+}
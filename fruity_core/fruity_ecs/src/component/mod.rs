@@ -9,3 +9,9 @@ pub mod component_guard;
 
 /// Provides a component wrapper for a serialized object
 pub mod serialized_component;
+
+/// Provides the change detection ticks of a component
+pub mod component_ticks;
+
+/// Provides the schema versions and the migrations of the serialized components
+pub mod component_schema_service;
@@ -1,7 +1,10 @@
 use crate::component::component::Component;
+use crate::component::component_schema_service::DEFAULT_SCHEMA_VERSION;
+use crate::component::component_schema_service::SCHEMA_VERSION_FIELD;
 use crate::entity::archetype::component_array::ComponentArray;
 use crate::entity::archetype::component_collection::ComponentCollection;
 use fruity_any::FruityAny;
+use fruity_core::convert::FruityTryFrom;
 use fruity_core::introspect::FieldInfo;
 use fruity_core::introspect::IntrospectObject;
 use fruity_core::introspect::MethodInfo;
@@ -11,16 +14,39 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 /// A wrapper for components that come from scripting languages as serialized
+///
+/// The schema version a component has been loaded with is kept apart from its fields, so it is
+/// written back when the component is saved again
 #[derive(Debug, Clone, FruityAny)]
 pub struct SerializedComponent {
     class_name: String,
     fields: HashMap<String, Serialized>,
+    schema_version: Option<u32>,
 }
 
 impl SerializedComponent {
-    /// Returns a SerializedComponent
-    pub fn new(class_name: String, fields: HashMap<String, Serialized>) -> SerializedComponent {
-        SerializedComponent { class_name, fields }
+    /// Returns a SerializedComponent, the schema version field is taken out of the fields
+    ///
+    /// # Arguments
+    /// * `class_name` - The component class name
+    /// * `fields` - The component fields
+    ///
+    pub fn new(class_name: String, mut fields: HashMap<String, Serialized>) -> SerializedComponent {
+        let schema_version = fields
+            .remove(SCHEMA_VERSION_FIELD)
+            .and_then(|version| u32::fruity_try_from(version).ok());
+
+        SerializedComponent {
+            class_name,
+            fields,
+            schema_version,
+        }
+    }
+
+    /// Get the schema version the component has been loaded with, None if the component has
+    /// been created at runtime
+    pub fn get_saved_schema_version(&self) -> Option<u32> {
+        self.schema_version
     }
 }
 
@@ -32,6 +58,10 @@ impl Component for SerializedComponent {
     fn duplicate(&self) -> Box<dyn Component> {
         Box::new(self.clone())
     }
+
+    fn get_schema_version(&self) -> u32 {
+        self.schema_version.unwrap_or(DEFAULT_SCHEMA_VERSION)
+    }
 }
 
 impl IntrospectObject for SerializedComponent {
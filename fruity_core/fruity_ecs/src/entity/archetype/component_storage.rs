@@ -1,31 +1,40 @@
 use crate::component::component::AnyComponent;
+use crate::component::component_ticks::ComponentTicks;
 use crate::entity::archetype::component_collection::ComponentCollection;
 use crate::entity::archetype::Component;
 
 pub(crate) struct ComponentStorage {
     pub(crate) collection: Box<dyn ComponentCollection>,
     pub(crate) components_per_entity: usize,
+    pub(crate) ticks: Vec<ComponentTicks>,
 }
 
 impl ComponentStorage {
-    pub(crate) fn new(components: Vec<AnyComponent>) -> Self {
+    pub(crate) fn new(components: Vec<AnyComponent>, tick: u64) -> Self {
         let components_per_entity = components.len();
         let first_component = components.get(0).unwrap();
         let mut collection = first_component.get_collection();
+        let ticks = components
+            .iter()
+            .map(|_| ComponentTicks::new(tick))
+            .collect::<Vec<_>>();
         collection.add_many(components);
 
         ComponentStorage {
             collection,
             components_per_entity,
+            ticks,
         }
     }
 
-    pub(crate) fn add(&mut self, components: Vec<AnyComponent>) {
+    pub(crate) fn add(&mut self, components: Vec<AnyComponent>, tick: u64) {
         // Check the components count
         if components.len() != self.components_per_entity {
             panic!("Try to instantiate a component array from a component array with the wrong size of elements");
         }
 
+        self.ticks
+            .extend(components.iter().map(|_| ComponentTicks::new(tick)));
         self.collection.add_many(components);
     }
 
@@ -35,4 +44,11 @@ impl ComponentStorage {
 
         (start_index..end_index).filter_map(|index| self.collection.get(&index))
     }
+
+    pub(crate) fn get_ticks(&self, entity_id: usize) -> &[ComponentTicks] {
+        let start_index = entity_id * self.components_per_entity;
+        let end_index = start_index + self.components_per_entity;
+
+        &self.ticks[start_index..end_index]
+    }
 }
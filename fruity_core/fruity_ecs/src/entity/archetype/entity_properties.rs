@@ -1,4 +1,7 @@
+use crate::entity::archetype::EntityGuid;
 use crate::entity::archetype::EntityId;
+use fruity_core::convert::FruityInto;
+use fruity_core::convert::FruityTryFrom;
 use fruity_core::object_factory_service::ObjectFactoryService;
 use fruity_core::serialize::serialized::Serialized;
 use fruity_core::serialize::Deserialize;
@@ -11,6 +14,9 @@ pub struct EntityProperties {
     /// The entity id
     pub entity_id: EntityId,
 
+    /// The entity guid, persists across save and load
+    pub guid: EntityGuid,
+
     /// the entity name
     pub name: String,
 
@@ -24,6 +30,7 @@ impl Serialize for EntityProperties {
             class_name: "EntityProperties".to_string(),
             fields: hashmap! {
                 "entity_id".to_string() => Serialized::U64(self.entity_id),
+                "guid".to_string() => self.guid.fruity_into(),
                 "name".to_string() => Serialized::String(self.name.clone()),
                 "enabled".to_string() => Serialized::Bool(self.enabled),
             },
@@ -51,6 +58,10 @@ impl Deserialize for EntityProperties {
                             None
                         }
                     })??,
+                    guid: fields
+                        .get("guid")
+                        .and_then(|guid| EntityGuid::fruity_try_from(guid.clone()).ok())
+                        .unwrap_or_default(),
                     name: fields.get("name").map(|name| {
                         if let Serialized::String(name) = name {
                             Some(name.clone())
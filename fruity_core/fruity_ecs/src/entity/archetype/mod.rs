@@ -1,12 +1,15 @@
 use crate::component::component::AnyComponent;
 use crate::component::component::Component;
 use crate::component::component_reference::ComponentReference;
+use crate::component::component_ticks::ComponentTicks;
 use crate::entity::archetype::component_storage::ComponentStorage;
 use crate::entity::archetype::entity_properties::EntityProperties;
 use crate::entity::entity::get_type_identifier_by_any;
+use crate::entity::entity::EntityGuid;
 use crate::entity::entity::EntityId;
 use crate::entity::entity::EntityTypeIdentifier;
 use crate::entity::entity_reference::EntityReference;
+use crate::EntityService;
 use crate::ExtensionComponentService;
 use fruity_core::resource::resource_reference::ResourceReference;
 use fruity_core::RwLock;
@@ -14,6 +17,8 @@ use itertools::Itertools;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::ops::Deref;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 /// This store all the information that are common accross all entities
@@ -35,6 +40,7 @@ pub(crate) struct ArchetypeArcRwLock(Arc<RwLock<Archetype>>);
 /// Stored as a Struct Of Array
 pub struct Archetype {
     extension_component_service: ResourceReference<ExtensionComponentService>,
+    pub(crate) change_tick: Arc<AtomicU64>,
     pub(crate) identifier: EntityTypeIdentifier,
 
     // Indexes with dead memory
@@ -42,6 +48,7 @@ pub struct Archetype {
 
     // Store all the component properties into a index persisting storage
     pub(crate) entity_id_array: Vec<EntityId>,
+    pub(crate) guid_array: Vec<EntityGuid>,
     pub(crate) name_array: Vec<String>,
     pub(crate) enabled_array: Vec<bool>,
     pub(crate) lock_array: Vec<RwLock<()>>,
@@ -52,13 +59,17 @@ impl Archetype {
     /// Returns an Archetype and inject the first entity inside
     ///
     /// # Arguments
+    /// * `change_tick` - The change tick of the entity service, used to date the component changes
     /// * `entity_id` - The first entity id
+    /// * `guid` - The first entity guid
     /// * `name` - The first entity name
     /// * `components` - The first entity components
     ///
     pub fn new(
         extension_component_service: ResourceReference<ExtensionComponentService>,
+        change_tick: Arc<AtomicU64>,
         entity_id: EntityId,
+        guid: EntityGuid,
         name: &str,
         enabled: bool,
         mut components: Vec<AnyComponent>,
@@ -83,17 +94,20 @@ impl Archetype {
         components.append(&mut extensions_component);
 
         // Build the archetype component containers
+        let tick = change_tick.load(Ordering::Relaxed);
         let grouped_components = Self::group_components_by_type(components);
         let mut component_storages = BTreeMap::new();
         for (class_name, components) in grouped_components {
-            component_storages.insert(class_name, ComponentStorage::new(components));
+            component_storages.insert(class_name, ComponentStorage::new(components, tick));
         }
 
         Archetype {
             extension_component_service,
+            change_tick,
             identifier: identifier,
             erased_indexes: RwLock::new(vec![]),
             entity_id_array: vec![entity_id],
+            guid_array: vec![guid],
             name_array: vec![name.to_string()],
             enabled_array: vec![enabled],
             lock_array: vec![RwLock::new(())],
@@ -119,6 +133,13 @@ impl Archetype {
         self.component_storages.get(component_type_identifier)
     }
 
+    /// Get the entity service that stores the archetype
+    pub(crate) fn get_entity_service(&self) -> ResourceReference<EntityService> {
+        self.extension_component_service
+            .resource_container
+            .require::<EntityService>()
+    }
+
     /// Get entity count
     pub fn len(&self) -> usize {
         self.entity_id_array.len()
@@ -128,12 +149,14 @@ impl Archetype {
     ///
     /// # Arguments
     /// * `entity_id` - The first entity id
+    /// * `guid` - The first entity guid
     /// * `name` - The first entity name
     /// * `components` - The first entity components
     ///
     pub fn add(
         &mut self,
         entity_id: EntityId,
+        guid: EntityGuid,
         name: &str,
         enabled: bool,
         mut components: Vec<AnyComponent>,
@@ -142,6 +165,7 @@ impl Archetype {
 
         // Store the entity properties
         self.entity_id_array.push(entity_id);
+        self.guid_array.push(guid);
         self.name_array.push(name.to_string());
         self.enabled_array.push(enabled);
         self.lock_array.push(RwLock::new(()));
@@ -163,15 +187,58 @@ impl Archetype {
         components.append(&mut extensions_component);
 
         // Store all the components
+        let tick = self.change_tick.load(Ordering::Relaxed);
         let grouped_components = Self::group_components_by_type(components);
         for (class_name, components) in grouped_components {
             let component_array = self.component_storages.get_mut(&class_name);
             if let Some(component_array) = component_array {
-                component_array.add(components);
+                component_array.add(components, tick);
             }
         }
     }
 
+    /// Get the change ticks of all the components of an entity
+    /// The ticks are in the same order than the components returned by [’Archetype::remove’]
+    ///
+    /// # Arguments
+    /// * `index` - The entity index
+    ///
+    pub(crate) fn get_entity_ticks(&self, index: usize) -> Vec<(String, ComponentTicks)> {
+        self.component_storages
+            .iter()
+            .map(|(class_name, storage)| {
+                storage
+                    .get_ticks(index)
+                    .iter()
+                    .map(move |ticks| (class_name.clone(), ticks.clone()))
+            })
+            .flatten()
+            .collect::<Vec<_>>()
+    }
+
+    /// Restore the change ticks of the components of an entity, used when an entity is moved
+    /// from an archetype to an other
+    ///
+    /// # Arguments
+    /// * `index` - The entity index
+    /// * `ticks` - The ticks, as returned by [’Archetype::get_entity_ticks’]
+    ///
+    pub(crate) fn set_entity_ticks(&self, index: usize, ticks: &[(String, ComponentTicks)]) {
+        self.component_storages
+            .iter()
+            .for_each(|(class_name, storage)| {
+                storage
+                    .get_ticks(index)
+                    .iter()
+                    .zip(
+                        ticks
+                            .iter()
+                            .filter(|(tick_class_name, _)| tick_class_name == class_name),
+                    )
+                    .for_each(|(storage_ticks, (_, ticks))| storage_ticks.copy_from(ticks));
+            });
+    }
+
     /// Remove an entity based on its id
     ///
     /// # Arguments
@@ -191,6 +258,7 @@ impl Archetype {
 
         // Get the entity properties from the storage
         let entity_id = *self.entity_id_array.get(index).unwrap();
+        let guid = *self.guid_array.get(index).unwrap();
         let name = self.name_array.get(index).unwrap().clone();
         let enabled = *self.enabled_array.get(index).unwrap();
 
@@ -208,6 +276,7 @@ impl Archetype {
         (
             EntityProperties {
                 entity_id,
+                guid,
                 name,
                 enabled,
             },
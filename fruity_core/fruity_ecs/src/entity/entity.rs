@@ -1,7 +1,18 @@
 use crate::component::component::AnyComponent;
 use crate::component::component::Component;
+use fruity_core::convert::FruityInto;
+use fruity_core::convert::FruityTryFrom;
+use fruity_core::serialize::serialized::Serialized;
+use std::collections::hash_map::RandomState;
 use std::fmt::Debug;
+use std::fmt::Display;
+use std::hash::BuildHasher;
 use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 /// An identifier to an entity type, is composed be the identifier of the contained components
 #[derive(Debug, Clone)]
@@ -41,6 +52,89 @@ impl EntityTypeIdentifier {
 /// An identifier for an entity
 pub type EntityId = u64;
 
+/// A globally unique identifier for an entity
+///
+/// Unlike the [’EntityId’], the guid is stored in the snapshots and stays the same
+/// across save and load
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntityGuid(pub u128);
+
+impl EntityGuid {
+    /// Generate a new random guid
+    pub fn generate() -> EntityGuid {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+
+        // Each RandomState is seeded with different random keys
+        let hash = |seed: u8| {
+            let mut hasher = RandomState::new().build_hasher();
+            (seed, counter, time).hash(&mut hasher);
+            hasher.finish()
+        };
+
+        EntityGuid(((hash(0) as u128) << 64) | hash(1) as u128)
+    }
+
+    /// Is the guid empty, an empty guid don't reference any entity
+    pub fn is_nil(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Parse a guid formatted as xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+    ///
+    /// # Arguments
+    /// * `value` - The formatted guid
+    ///
+    pub fn parse(value: &str) -> Option<EntityGuid> {
+        let digits = value.replace("-", "");
+        if digits.len() != 32 {
+            return None;
+        }
+
+        u128::from_str_radix(&digits, 16).ok().map(EntityGuid)
+    }
+}
+
+impl Display for EntityGuid {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = self.0;
+        write!(
+            formatter,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            (value >> 96) as u32,
+            (value >> 80) as u16,
+            (value >> 64) as u16,
+            (value >> 48) as u16,
+            value & 0xffff_ffff_ffff,
+        )
+    }
+}
+
+impl FruityTryFrom<Serialized> for EntityGuid {
+    type Error = String;
+
+    fn fruity_try_from(value: Serialized) -> Result<Self, Self::Error> {
+        match value {
+            Serialized::String(value) => match EntityGuid::parse(&value) {
+                Some(value) => Ok(value),
+                None => Err(format!("Couldn't convert {} to entity guid", value)),
+            },
+            _ => Err(format!("Couldn't convert {:?} to entity guid", value)),
+        }
+    }
+}
+
+impl FruityInto<Serialized> for EntityGuid {
+    fn fruity_into(self) -> Serialized {
+        Serialized::String(self.to_string())
+    }
+}
+
 /// Get the entity type identifier from a list of components
 pub fn get_type_identifier_by_any(components: &[AnyComponent]) -> EntityTypeIdentifier {
     let identifier = components
@@ -60,3 +154,26 @@ pub fn get_type_identifier(components: &[&dyn Component]) -> EntityTypeIdentifie
 
     EntityTypeIdentifier(identifier)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::entity::EntityGuid;
+
+    #[test]
+    fn entity_guid_format_test() {
+        let guid = EntityGuid(0x0123456789abcdef0011223344556677);
+
+        assert_eq!(guid.to_string(), "01234567-89ab-cdef-0011-223344556677");
+        assert_eq!(EntityGuid::parse(&guid.to_string()), Some(guid));
+        assert_eq!(EntityGuid::parse("0123"), None);
+    }
+
+    #[test]
+    fn entity_guid_generate_test() {
+        let first = EntityGuid::generate();
+        let second = EntityGuid::generate();
+
+        assert!(!first.is_nil());
+        assert_ne!(first, second);
+    }
+}
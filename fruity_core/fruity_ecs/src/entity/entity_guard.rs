@@ -5,8 +5,11 @@ use crate::component::component_guard::ComponentWriteGuard;
 use crate::component::component_guard::InternalReadGuard;
 use crate::component::component_guard::TypedComponentReadGuard;
 use crate::component::component_guard::TypedComponentWriteGuard;
+use crate::component::component_ticks::ComponentTicks;
 use crate::entity::archetype::Archetype;
+use crate::entity::entity::EntityGuid;
 use crate::entity::entity::EntityId;
+use crate::entity::entity_reference::EntityRelations;
 use std::fmt::Debug;
 use std::rc::Rc;
 use fruity_core::RwLockReadGuard;
@@ -23,6 +26,7 @@ pub struct EntityReadGuard<'a> {
     pub(crate) _guard: Rc<RwLockReadGuard<'a, ()>>,
     pub(crate) archetype_reader: Rc<RwLockReadGuard<'a, Archetype>>,
     pub(crate) entity_id: usize,
+    pub(crate) relations: Option<Rc<EntityRelations>>,
 }
 
 impl<'a> Debug for EntityReadGuard<'a> {
@@ -41,6 +45,26 @@ impl<'a> EntityReadGuard<'a> {
             .unwrap()
     }
 
+    /// Get the entity guid
+    pub fn get_guid(&self) -> EntityGuid {
+        *self
+            .archetype_reader
+            .guid_array
+            .get(self.entity_id)
+            .unwrap()
+    }
+
+    /// Get the parent and the children of the entity, they are only known if they have been
+    /// read before the entity was locked, see [’QueryParam::require_relations’]
+    pub fn get_relations(&self) -> Option<&EntityRelations> {
+        self.relations.as_deref()
+    }
+
+    pub(crate) fn with_relations(mut self, relations: Option<Rc<EntityRelations>>) -> Self {
+        self.relations = relations;
+        self
+    }
+
     /// Get the entity name
     pub fn get_name(&self) -> String {
         self.archetype_reader
@@ -123,6 +147,15 @@ impl<'a> EntityReadGuard<'a> {
         }
     }
 
+    /// Get the change ticks of the components with a given type
+    ///
+    /// # Arguments
+    /// * `component_identifier` - The component identifier
+    ///
+    pub fn get_component_ticks(&self, component_identifier: &str) -> Vec<ComponentTicks> {
+        get_component_ticks(&self.archetype_reader, self.entity_id, component_identifier)
+    }
+
     /// Read a single component with a given type
     pub fn read_single_component<T: Component + StaticComponent>(
         &self,
@@ -142,6 +175,7 @@ pub struct EntityWriteGuard<'a> {
     pub(crate) entity_id: usize,
     pub(crate) _guard: Rc<RwLockWriteGuard<'a, ()>>,
     pub(crate) archetype_reader: Rc<RwLockReadGuard<'a, Archetype>>,
+    pub(crate) relations: Option<Rc<EntityRelations>>,
 }
 
 impl<'a> Debug for EntityWriteGuard<'a> {
@@ -160,6 +194,26 @@ impl<'a> EntityWriteGuard<'a> {
             .unwrap()
     }
 
+    /// Get the entity guid
+    pub fn get_guid(&self) -> EntityGuid {
+        *self
+            .archetype_reader
+            .guid_array
+            .get(self.entity_id)
+            .unwrap()
+    }
+
+    /// Get the parent and the children of the entity, they are only known if they have been
+    /// read before the entity was locked, see [’QueryParam::require_relations’]
+    pub fn get_relations(&self) -> Option<&EntityRelations> {
+        self.relations.as_deref()
+    }
+
+    pub(crate) fn with_relations(mut self, relations: Option<Rc<EntityRelations>>) -> Self {
+        self.relations = relations;
+        self
+    }
+
     /// Get the entity name
     pub fn get_name(&self) -> String {
         self.archetype_reader
@@ -261,6 +315,15 @@ impl<'a> EntityWriteGuard<'a> {
         }
     }
 
+    /// Get the change ticks of the components with a given type
+    ///
+    /// # Arguments
+    /// * `component_identifier` - The component identifier
+    ///
+    pub fn get_component_ticks(&self, component_identifier: &str) -> Vec<ComponentTicks> {
+        get_component_ticks(&self.archetype_reader, self.entity_id, component_identifier)
+    }
+
     /// Read a single component with a given type
     pub fn read_single_component<T: Component + StaticComponent>(
         &self,
@@ -320,3 +383,14 @@ impl<'a> EntityWriteGuard<'a> {
         self.iter_components_mut().next()
     }
 }
+
+fn get_component_ticks(
+    archetype: &Archetype,
+    entity_id: usize,
+    component_identifier: &str,
+) -> Vec<ComponentTicks> {
+    archetype
+        .get_storage_from_type(component_identifier)
+        .map(|storage| storage.get_ticks(entity_id).to_vec())
+        .unwrap_or_default()
+}
@@ -0,0 +1,119 @@
+use crate::entity::entity::EntityId;
+use std::collections::HashMap;
+
+/// An index over the parent/children relations between the entities
+///
+/// The ECS don't know how the relations are stored into the components, the index is filled by
+/// the extension that owns them (fruity_hierarchy for example) with [’EntityHierarchy::set_parent’]
+#[derive(Debug, Clone, Default)]
+pub struct EntityHierarchy {
+    parents: HashMap<EntityId, EntityId>,
+    children: HashMap<EntityId, Vec<EntityId>>,
+}
+
+impl EntityHierarchy {
+    /// Get the parent of an entity
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    ///
+    pub fn get_parent(&self, entity_id: EntityId) -> Option<EntityId> {
+        self.parents.get(&entity_id).copied()
+    }
+
+    /// Get the children of an entity, in the order they have been attached
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    ///
+    pub fn get_children(&self, entity_id: EntityId) -> &[EntityId] {
+        self.children
+            .get(&entity_id)
+            .map(|children| children.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Set the parent of an entity, the entity is detached from its previous parent
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    /// * `parent_id` - The new parent id, None to make the entity a root
+    ///
+    pub fn set_parent(&mut self, entity_id: EntityId, parent_id: Option<EntityId>) {
+        if self.get_parent(entity_id) == parent_id {
+            return;
+        }
+
+        if let Some(previous_parent_id) = self.parents.remove(&entity_id) {
+            self.detach_child(previous_parent_id, entity_id);
+        }
+
+        if let Some(parent_id) = parent_id {
+            self.parents.insert(entity_id, parent_id);
+            self.children.entry(parent_id).or_default().push(entity_id);
+        }
+    }
+
+    /// Remove an entity from the index, its children become roots
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    ///
+    pub fn remove(&mut self, entity_id: EntityId) {
+        self.set_parent(entity_id, None);
+
+        if let Some(children) = self.children.remove(&entity_id) {
+            children.iter().for_each(|child_id| {
+                self.parents.remove(child_id);
+            });
+        }
+    }
+
+    /// Remove all the relations
+    pub fn clear(&mut self) {
+        self.parents.clear();
+        self.children.clear();
+    }
+
+    fn detach_child(&mut self, parent_id: EntityId, entity_id: EntityId) {
+        if let Some(children) = self.children.get_mut(&parent_id) {
+            children.retain(|child_id| *child_id != entity_id);
+
+            if children.is_empty() {
+                self.children.remove(&parent_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_parent_moves_the_child() {
+        let mut hierarchy = EntityHierarchy::default();
+        hierarchy.set_parent(2, Some(1));
+        hierarchy.set_parent(3, Some(1));
+        hierarchy.set_parent(2, Some(3));
+
+        assert_eq!(hierarchy.get_children(1), &[3]);
+        assert_eq!(hierarchy.get_children(3), &[2]);
+        assert_eq!(hierarchy.get_parent(2), Some(3));
+
+        hierarchy.set_parent(2, None);
+        assert_eq!(hierarchy.get_parent(2), None);
+        assert!(hierarchy.get_children(3).is_empty());
+    }
+
+    #[test]
+    fn remove_detaches_the_children() {
+        let mut hierarchy = EntityHierarchy::default();
+        hierarchy.set_parent(2, Some(1));
+        hierarchy.set_parent(3, Some(2));
+        hierarchy.remove(2);
+
+        assert!(hierarchy.get_children(1).is_empty());
+        assert_eq!(hierarchy.get_parent(3), None);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::component::component::Component;
+use crate::component::component::StaticComponent;
+use crate::entity::archetype::Archetype;
+use crate::entity::entity_query::QueryParam;
+use crate::entity::entity_query::RequestedEntityGuard;
+use crate::entity::entity_reference::EntityReference;
+use fruity_core::inject::InjectAccess;
+use std::marker::PhantomData;
+
+/// Keep only the entities with a component that has been changed since the last run of the system
+/// An added component is also seen as changed
+pub struct Changed<T: Component + StaticComponent + 'static> {
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T: Component + StaticComponent + 'static> QueryParam<'a> for Changed<T> {
+    type Item = ();
+
+    fn filter_archetype(archetype: &Archetype) -> bool {
+        archetype
+            .identifier
+            .contains(&T::get_component_name().to_string())
+    }
+
+    fn require_read() -> bool {
+        true
+    }
+
+    fn require_write() -> bool {
+        false
+    }
+
+    fn get_access() -> InjectAccess {
+        InjectAccess::read(T::get_component_name())
+    }
+
+    fn filter_entity(entity_guard: &RequestedEntityGuard<'a>, last_run_tick: u64) -> bool {
+        entity_guard
+            .get_component_ticks(T::get_component_name())
+            .iter()
+            .any(|ticks| ticks.is_changed(last_run_tick))
+    }
+
+    fn iter_entity_components(
+        _entity_reference: EntityReference,
+        _entity_guard: &'a RequestedEntityGuard<'a>,
+    ) -> Box<dyn Iterator<Item = Self::Item> + 'a> {
+        Box::new(vec![()].into_iter())
+    }
+}
+
+/// Keep only the entities with a component that has been added since the last run of the system
+pub struct Added<T: Component + StaticComponent + 'static> {
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T: Component + StaticComponent + 'static> QueryParam<'a> for Added<T> {
+    type Item = ();
+
+    fn filter_archetype(archetype: &Archetype) -> bool {
+        archetype
+            .identifier
+            .contains(&T::get_component_name().to_string())
+    }
+
+    fn require_read() -> bool {
+        true
+    }
+
+    fn require_write() -> bool {
+        false
+    }
+
+    fn get_access() -> InjectAccess {
+        InjectAccess::read(T::get_component_name())
+    }
+
+    fn filter_entity(entity_guard: &RequestedEntityGuard<'a>, last_run_tick: u64) -> bool {
+        entity_guard
+            .get_component_ticks(T::get_component_name())
+            .iter()
+            .any(|ticks| ticks.is_added(last_run_tick))
+    }
+
+    fn iter_entity_components(
+        _entity_reference: EntityReference,
+        _entity_guard: &'a RequestedEntityGuard<'a>,
+    ) -> Box<dyn Iterator<Item = Self::Item> + 'a> {
+        Box::new(vec![()].into_iter())
+    }
+}
+
+/// Keep the entities that pass at least one of two change filters, like
+/// Or<Changed<Translate2d>, Changed<Rotate2d>>
+///
+/// Only the [’Changed’] and [’Added’] filters, or an other Or, should be combined, the other
+/// query params don't filter the entities
+pub struct Or<A, B> {
+    _phantom: PhantomData<(A, B)>,
+}
+
+impl<'a, A: QueryParam<'a>, B: QueryParam<'a>> QueryParam<'a> for Or<A, B> {
+    type Item = ();
+
+    fn filter_archetype(archetype: &Archetype) -> bool {
+        A::filter_archetype(archetype) || B::filter_archetype(archetype)
+    }
+
+    fn require_read() -> bool {
+        A::require_read() || B::require_read()
+    }
+
+    fn require_write() -> bool {
+        false
+    }
+
+    fn get_access() -> InjectAccess {
+        let mut access = A::get_access();
+        access.merge(&B::get_access());
+        access
+    }
+
+    fn filter_entity(entity_guard: &RequestedEntityGuard<'a>, last_run_tick: u64) -> bool {
+        A::filter_entity(entity_guard, last_run_tick)
+            || B::filter_entity(entity_guard, last_run_tick)
+    }
+
+    fn iter_entity_components(
+        _entity_reference: EntityReference,
+        _entity_guard: &'a RequestedEntityGuard<'a>,
+    ) -> Box<dyn Iterator<Item = Self::Item> + 'a> {
+        Box::new(vec![()].into_iter())
+    }
+}
@@ -0,0 +1,286 @@
+use crate::component::component::Component;
+use crate::component::component::StaticComponent;
+use crate::component::component_reference::ComponentReference;
+use crate::entity::archetype::Archetype;
+use crate::entity::entity_query::QueryParam;
+use crate::entity::entity_query::RequestedEntityGuard;
+use crate::entity::entity_reference::EntityReference;
+use fruity_core::inject::InjectAccess;
+use std::marker::PhantomData;
+
+/// The components with a given type of the entity parent
+/// Entities without parent or with a parent that don't have the component are filtered out
+pub struct WithParent<T> {
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T: Component + StaticComponent + 'static> QueryParam<'a> for WithParent<T> {
+    type Item = ComponentReference;
+
+    fn filter_archetype(_archetype: &Archetype) -> bool {
+        true
+    }
+
+    fn require_read() -> bool {
+        true
+    }
+
+    fn require_write() -> bool {
+        false
+    }
+
+    fn require_relations() -> bool {
+        true
+    }
+
+    fn get_access() -> InjectAccess {
+        InjectAccess::read(T::get_component_name())
+    }
+
+    fn iter_entity_components(
+        _entity_reference: EntityReference,
+        entity_guard: &'a RequestedEntityGuard<'a>,
+    ) -> Box<dyn Iterator<Item = Self::Item> + 'a> {
+        let parent = entity_guard
+            .get_relations()
+            .and_then(|relations| relations.parent.clone());
+
+        match parent {
+            Some(parent) => Box::new(
+                parent
+                    .get_components_by_type_identifier(T::get_component_name())
+                    .into_iter(),
+            ),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+/// The components with a given type of the entity parent, or None if the entity has no parent
+/// or if its parent doesn't have the component
+pub struct WithOptionalParent<T> {
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T: Component + StaticComponent + 'static> QueryParam<'a> for WithOptionalParent<T> {
+    type Item = Option<ComponentReference>;
+
+    fn filter_archetype(_archetype: &Archetype) -> bool {
+        true
+    }
+
+    fn require_read() -> bool {
+        true
+    }
+
+    fn require_write() -> bool {
+        false
+    }
+
+    fn require_relations() -> bool {
+        true
+    }
+
+    fn get_access() -> InjectAccess {
+        InjectAccess::read(T::get_component_name())
+    }
+
+    fn iter_entity_components(
+        entity_reference: EntityReference,
+        entity_guard: &'a RequestedEntityGuard<'a>,
+    ) -> Box<dyn Iterator<Item = Self::Item> + 'a> {
+        let mut iter = WithParent::<T>::iter_entity_components(entity_reference, entity_guard)
+            .map(Some)
+            .peekable();
+
+        match iter.peek() {
+            Some(_) => Box::new(iter),
+            None => Box::new(vec![None].into_iter()),
+        }
+    }
+}
+
+/// The children of the entity, in the order they have been attached
+/// Entities without children are filtered out
+pub struct WithChildren;
+
+impl<'a> QueryParam<'a> for WithChildren {
+    type Item = Vec<EntityReference>;
+
+    fn filter_archetype(_archetype: &Archetype) -> bool {
+        true
+    }
+
+    fn require_read() -> bool {
+        true
+    }
+
+    fn require_write() -> bool {
+        false
+    }
+
+    fn require_relations() -> bool {
+        true
+    }
+
+    fn iter_entity_components(
+        _entity_reference: EntityReference,
+        entity_guard: &'a RequestedEntityGuard<'a>,
+    ) -> Box<dyn Iterator<Item = Self::Item> + 'a> {
+        let children = entity_guard
+            .get_relations()
+            .map(|relations| relations.children.clone())
+            .unwrap_or_default();
+
+        if children.is_empty() {
+            Box::new(std::iter::empty())
+        } else {
+            Box::new(std::iter::once(children))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::component::AnyComponent;
+    use crate::entity::entity_query::with::WithMut;
+    use crate::entity::entity_query::with::WithName;
+    use crate::entity::entity_service::EntityService;
+    use crate::Component;
+    use fruity_any::*;
+    use fruity_core::world::World;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Clone, Default, Component, FruityAny)]
+    struct Label {
+        text: String,
+    }
+
+    fn label(text: &str) -> Vec<AnyComponent> {
+        vec![AnyComponent::new(Label {
+            text: text.to_string(),
+        })]
+    }
+
+    // root
+    // ├ child_1
+    // │ └ grandchild
+    // └ child_2
+    fn create_hierarchy(world: &World) {
+        let entity_service = world.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        let root = entity_service.create("root", true, label("Root"));
+        let child_1 = entity_service.create("child_1", true, label("Child 1"));
+        let child_2 = entity_service.create("child_2", true, vec![]);
+        let grandchild = entity_service.create("grandchild", true, label("Grandchild"));
+
+        entity_service.set_parent(child_1, Some(root));
+        entity_service.set_parent(child_2, Some(root));
+        entity_service.set_parent(grandchild, Some(child_1));
+    }
+
+    #[test]
+    fn with_parent_query_test() {
+        let world = World::new();
+        crate::initialize(world.resource_container.clone());
+        create_hierarchy(&world);
+
+        let entity_service = world.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        let result = Mutex::new(Vec::new());
+        entity_service
+            .query::<(WithName, WithParent<Label>)>()
+            .for_each(|(name, parent_label)| {
+                let parent_label = parent_label.read_typed::<Label>().unwrap().text.clone();
+                result.lock().unwrap().push((name, parent_label));
+            });
+
+        // The root has no parent, so it's filtered out
+        let mut result = result.into_inner().unwrap();
+        result.sort();
+        assert_eq!(
+            result,
+            vec![
+                ("child_1".to_string(), "Root".to_string()),
+                ("child_2".to_string(), "Root".to_string()),
+                ("grandchild".to_string(), "Child 1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn with_optional_parent_query_test() {
+        let world = World::new();
+        crate::initialize(world.resource_container.clone());
+        create_hierarchy(&world);
+
+        let entity_service = world.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        let result = Mutex::new(Vec::new());
+        entity_service
+            .query::<(WithName, WithOptionalParent<Label>)>()
+            .for_each(|(name, parent_label)| {
+                let parent_label = parent_label
+                    .map(|parent_label| parent_label.read_typed::<Label>().unwrap().text.clone());
+                result.lock().unwrap().push((name, parent_label));
+            });
+
+        // The root has no parent, so it's kept without parent label
+        let mut result = result.into_inner().unwrap();
+        result.sort();
+        assert_eq!(
+            result,
+            vec![
+                ("child_1".to_string(), Some("Root".to_string())),
+                ("child_2".to_string(), Some("Root".to_string())),
+                ("grandchild".to_string(), Some("Child 1".to_string())),
+                ("root".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn with_children_query_test() {
+        let world = World::new();
+        crate::initialize(world.resource_container.clone());
+        create_hierarchy(&world);
+
+        let entity_service = world.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        // The entities are written while the relations are read, the entity service must not
+        // be locked while the entities are
+        let result = Mutex::new(Vec::new());
+        entity_service
+            .query::<(WithMut<Label>, WithChildren)>()
+            .for_each(|(mut label, children)| {
+                label.text = format!("{} with children", label.text);
+
+                let children = children
+                    .iter()
+                    .map(|child| child.read().get_name())
+                    .collect::<Vec<_>>();
+                result.lock().unwrap().push((label.text.clone(), children));
+            });
+
+        let mut result = result.into_inner().unwrap();
+        result.sort();
+        assert_eq!(
+            result,
+            vec![
+                (
+                    "Child 1 with children".to_string(),
+                    vec!["grandchild".to_string()]
+                ),
+                (
+                    "Root with children".to_string(),
+                    vec!["child_1".to_string(), "child_2".to_string()]
+                ),
+            ]
+        );
+    }
+}
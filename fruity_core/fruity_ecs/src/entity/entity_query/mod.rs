@@ -1,10 +1,13 @@
+use crate::component::component_ticks::ComponentTicks;
 use crate::entity::archetype::Archetype;
 use crate::entity::archetype::ArchetypeArcRwLock;
 use crate::entity::entity::EntityId;
 use crate::entity::entity_guard::EntityReadGuard;
 use crate::entity::entity_guard::EntityWriteGuard;
 use crate::entity::entity_reference::EntityReference;
+use crate::entity::entity_reference::EntityRelations;
 use crate::EntityService;
+use fruity_core::inject::InjectAccess;
 use fruity_core::inject::Injectable;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::signal::ObserverHandler;
@@ -13,6 +16,7 @@ use fruity_core::RwLock;
 use rayon::iter::ParallelBridge;
 use rayon::iter::ParallelIterator;
 use std::marker::PhantomData;
+use std::rc::Rc;
 use std::sync::Arc;
 
 /// Queries for scripting languages
@@ -27,6 +31,12 @@ pub mod with;
 /// Queries for without stuffs
 pub mod without;
 
+/// Queries for change detection stuffs
+pub mod changed;
+
+/// Queries for parent/children relations
+pub mod hierarchy;
+
 /// An enum to pass a guard into the [’QueryInjectable’]
 #[derive(Clone)]
 pub enum RequestedEntityGuard<'a> {
@@ -52,6 +62,27 @@ pub trait QueryParam<'a> {
     /// Does this require a write guard over the reference
     fn require_write() -> bool;
 
+    /// Does this read the parent or the children of the entity, they are read before the entity
+    /// is locked and are provided by the guard, see [’EntityReadGuard::get_relations’]
+    fn require_relations() -> bool {
+        false
+    }
+
+    /// Get the components accessed by the query, used to know which systems can be run in parallel
+    fn get_access() -> InjectAccess {
+        InjectAccess::default()
+    }
+
+    /// A filter over the entities, used for the change detection
+    ///
+    /// # Arguments
+    /// * `entity_guard` - The guard over the entity
+    /// * `last_run_tick` - The change tick of the last run of the system that uses the query
+    ///
+    fn filter_entity(_entity_guard: &RequestedEntityGuard<'a>, _last_run_tick: u64) -> bool {
+        true
+    }
+
     /// Iter over the queried components into a given entity
     fn iter_entity_components(
         entity_reference: EntityReference,
@@ -59,11 +90,58 @@ pub trait QueryParam<'a> {
     ) -> Box<dyn Iterator<Item = Self::Item> + 'a>;
 }
 
+impl<'a> RequestedEntityGuard<'a> {
+    /// Get the change ticks of the components with a given type
+    ///
+    /// # Arguments
+    /// * `component_identifier` - The component identifier
+    ///
+    pub fn get_component_ticks(&self, component_identifier: &str) -> Vec<ComponentTicks> {
+        match self {
+            RequestedEntityGuard::Read(entity_guard) => {
+                entity_guard.get_component_ticks(component_identifier)
+            }
+            RequestedEntityGuard::Write(entity_guard) => {
+                entity_guard.get_component_ticks(component_identifier)
+            }
+            RequestedEntityGuard::None => Vec::new(),
+        }
+    }
+
+    /// Get the parent and the children of the entity, if they are required by the query
+    pub fn get_relations(&self) -> Option<&EntityRelations> {
+        match self {
+            RequestedEntityGuard::Read(entity_guard) => entity_guard.get_relations(),
+            RequestedEntityGuard::Write(entity_guard) => entity_guard.get_relations(),
+            RequestedEntityGuard::None => None,
+        }
+    }
+}
+
+// Lock an entity as the query params require it
+// The relations are read before, so the entity service is never locked while an entity is
+fn lock_entity<'a, T: QueryParam<'a>>(entity: &EntityReference) -> RequestedEntityGuard<'_> {
+    let relations = if T::require_relations() {
+        Some(Rc::new(entity.get_relations()))
+    } else {
+        None
+    };
+
+    if T::require_write() {
+        RequestedEntityGuard::Write(entity.write().with_relations(relations))
+    } else if T::require_read() {
+        RequestedEntityGuard::Read(entity.read().with_relations(relations))
+    } else {
+        RequestedEntityGuard::None
+    }
+}
+
 /// A query over entities
 pub struct Query<T> {
     pub(crate) archetypes: Arc<RwLock<Vec<ArchetypeArcRwLock>>>,
     pub(crate) on_entity_created: Signal<EntityReference>,
     pub(crate) on_entity_deleted: Signal<EntityId>,
+    pub(crate) last_run_tick: u64,
     pub(crate) _param_phantom: PhantomData<T>,
 }
 
@@ -73,6 +151,7 @@ impl<T> Clone for Query<T> {
             archetypes: self.archetypes.clone(),
             on_entity_created: self.on_entity_created.clone(),
             on_entity_deleted: self.on_entity_deleted.clone(),
+            last_run_tick: self.last_run_tick,
             _param_phantom: PhantomData {},
         }
     }
@@ -85,32 +164,46 @@ unsafe impl<T> Sync for Query<T> {}
 unsafe impl<T> Send for Query<T> {}
 
 impl<'a, T: QueryParam<'a> + 'static> Query<T> {
+    /// Get the change tick the [’Changed’] and [’Added’] filters compare with
+    pub fn get_last_run_tick(&self) -> u64 {
+        self.last_run_tick
+    }
+
+    /// Set the change tick the [’Changed’] and [’Added’] filters compare with
+    ///
+    /// # Arguments
+    /// * `last_run_tick` - The change tick, components changed after it pass the filters
+    ///
+    pub fn since(mut self, last_run_tick: u64) -> Self {
+        self.last_run_tick = last_run_tick;
+        self
+    }
+
     /// Call a function for every entities of an query
     pub fn for_each(&self, callback: impl Fn(T::Item) + Send + Sync) {
-        let archetypes = self.archetypes.read();
-        let archetype_iter = archetypes
-            .iter()
-            .filter(|archetype| T::filter_archetype(&archetype.read()));
-
-        let entities = archetype_iter
-            .map(|archetype| archetype.iter(false))
-            .flatten()
-            .collect::<Vec<_>>();
+        // The archetypes are released before the entities are locked, the entity service reads
+        // them to get the relations
+        let entities = {
+            let archetypes = self.archetypes.read();
+            archetypes
+                .iter()
+                .filter(|archetype| T::filter_archetype(&archetype.read()))
+                .flat_map(|archetype| archetype.iter(false))
+                .collect::<Vec<_>>()
+        };
 
         entities.into_iter().par_bridge().for_each(|entity| {
-            let entity_guard = if T::require_write() {
-                RequestedEntityGuard::Write(entity.write())
-            } else if T::require_read() {
-                RequestedEntityGuard::Read(entity.read())
-            } else {
-                RequestedEntityGuard::None
-            };
+            let entity_guard = lock_entity::<T>(&entity);
 
             // TODO: Find a way to remove it
             let entity_guard = unsafe {
                 std::mem::transmute::<&RequestedEntityGuard, &RequestedEntityGuard>(&entity_guard)
             };
 
+            if !T::filter_entity(entity_guard, self.last_run_tick) {
+                return;
+            }
+
             T::iter_entity_components(entity.clone(), &entity_guard)
                 .for_each(|param| callback(param))
         });
@@ -122,6 +215,7 @@ impl<'a, T: QueryParam<'a> + 'static> Query<T> {
         callback: impl Fn(T::Item) -> Option<Box<dyn Fn() + Send + Sync>> + Send + Sync + 'static,
     ) -> ObserverHandler<EntityReference> {
         let on_entity_deleted = self.on_entity_deleted.clone();
+        let last_run_tick = self.last_run_tick;
         self.on_entity_created.add_observer(move |entity| {
             if T::filter_archetype(&entity.archetype.read()) {
                 let entity_id = {
@@ -129,13 +223,7 @@ impl<'a, T: QueryParam<'a> + 'static> Query<T> {
                     entity_reader.get_entity_id()
                 };
 
-                let entity_guard = if T::require_write() {
-                    RequestedEntityGuard::Write(entity.write())
-                } else if T::require_read() {
-                    RequestedEntityGuard::Read(entity.read())
-                } else {
-                    RequestedEntityGuard::None
-                };
+                let entity_guard = lock_entity::<T>(entity);
 
                 // TODO: Find a way to remove it
                 let entity_guard = unsafe {
@@ -144,6 +232,10 @@ impl<'a, T: QueryParam<'a> + 'static> Query<T> {
                     )
                 };
 
+                if !T::filter_entity(entity_guard, last_run_tick) {
+                    return;
+                }
+
                 T::iter_entity_components(entity.clone(), &entity_guard).for_each(|param| {
                     let dispose_callback = callback(param);
 
@@ -170,4 +262,8 @@ impl<'a, T: QueryParam<'a> + 'static> Injectable for Query<T> {
 
         entity_service.query::<T>()
     }
+
+    fn get_access() -> InjectAccess {
+        T::get_access()
+    }
 }
@@ -1,6 +1,8 @@
 use crate::entity::archetype::Archetype;
 use crate::entity::archetype::ArchetypeArcRwLock;
 use crate::entity::entity_query::serialized::params::With;
+use crate::entity::entity_query::serialized::params::WithAdded;
+use crate::entity::entity_query::serialized::params::WithChanged;
 use crate::entity::entity_query::serialized::params::WithEnabled;
 use crate::entity::entity_query::serialized::params::WithEntity;
 use crate::entity::entity_query::serialized::params::WithId;
@@ -39,6 +41,7 @@ pub(crate) struct SerializedQuery {
     pub archetypes: Arc<RwLock<Vec<ArchetypeArcRwLock>>>,
     pub on_entity_created: Signal<EntityReference>,
     pub on_entity_deleted: Signal<EntityId>,
+    pub last_run_tick: u64,
     pub params: Vec<Box<dyn SerializedQueryParam>>,
 }
 
@@ -48,6 +51,7 @@ impl Clone for SerializedQuery {
             archetypes: self.archetypes.clone(),
             on_entity_created: self.on_entity_created.clone(),
             on_entity_deleted: self.on_entity_deleted.clone(),
+            last_run_tick: self.last_run_tick,
             params: self
                 .params
                 .iter()
@@ -95,6 +99,20 @@ impl SerializedQuery {
         }));
     }
 
+    pub fn with_changed(&mut self, component_identifier: &str) {
+        self.params.push(Box::new(WithChanged {
+            identifier: component_identifier.to_string(),
+            last_run_tick: self.last_run_tick,
+        }));
+    }
+
+    pub fn with_added(&mut self, component_identifier: &str) {
+        self.params.push(Box::new(WithAdded {
+            identifier: component_identifier.to_string(),
+            last_run_tick: self.last_run_tick,
+        }));
+    }
+
     pub fn for_each(&self, callback: impl Fn(&[Serialized]) + Send + Sync) {
         let archetypes = self.archetypes.read();
         let archetype_filter = self.archetype_filter();
@@ -263,6 +281,32 @@ impl IntrospectObject for SerializedQuery {
                     Ok(Some(Serialized::NativeObject(this.duplicate())))
                 })),
             },
+            MethodInfo {
+                name: "with_changed".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<SerializedQuery>(this);
+
+                    let mut caster = ArgumentCaster::new("with_changed", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    this.with_changed(&arg1);
+
+                    Ok(Some(Serialized::NativeObject(this.duplicate())))
+                })),
+            },
+            MethodInfo {
+                name: "with_added".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<SerializedQuery>(this);
+
+                    let mut caster = ArgumentCaster::new("with_added", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    this.with_added(&arg1);
+
+                    Ok(Some(Serialized::NativeObject(this.duplicate())))
+                })),
+            },
             MethodInfo {
                 name: "for_each".to_string(),
                 call: MethodCaller::Mut(Arc::new(|this, args| {
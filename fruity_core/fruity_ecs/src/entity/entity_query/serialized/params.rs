@@ -127,3 +127,65 @@ impl SerializedQueryParam for WithOptional {
         }
     }
 }
+
+#[derive(FruityAny, Clone)]
+pub struct WithChanged {
+    pub identifier: String,
+    pub last_run_tick: u64,
+}
+
+impl SerializedQueryParam for WithChanged {
+    fn duplicate(&self) -> Box<dyn SerializedQueryParam> {
+        Box::new(self.clone())
+    }
+
+    fn filter_archetype(&self, archetype: &Archetype) -> bool {
+        archetype.identifier.contains(&self.identifier)
+    }
+
+    fn get_entity_components(&self, entity_reference: EntityReference) -> Vec<Serialized> {
+        let ticks = {
+            let entity_reader = entity_reference.read();
+            entity_reader.get_component_ticks(&self.identifier)
+        };
+
+        entity_reference
+            .get_components_by_type_identifier(&self.identifier)
+            .into_iter()
+            .zip(ticks.iter())
+            .filter(|(_, ticks)| ticks.is_changed(self.last_run_tick))
+            .map(|(component, _)| component.fruity_into())
+            .collect::<Vec<_>>()
+    }
+}
+
+#[derive(FruityAny, Clone)]
+pub struct WithAdded {
+    pub identifier: String,
+    pub last_run_tick: u64,
+}
+
+impl SerializedQueryParam for WithAdded {
+    fn duplicate(&self) -> Box<dyn SerializedQueryParam> {
+        Box::new(self.clone())
+    }
+
+    fn filter_archetype(&self, archetype: &Archetype) -> bool {
+        archetype.identifier.contains(&self.identifier)
+    }
+
+    fn get_entity_components(&self, entity_reference: EntityReference) -> Vec<Serialized> {
+        let ticks = {
+            let entity_reader = entity_reference.read();
+            entity_reader.get_component_ticks(&self.identifier)
+        };
+
+        entity_reference
+            .get_components_by_type_identifier(&self.identifier)
+            .into_iter()
+            .zip(ticks.iter())
+            .filter(|(_, ticks)| ticks.is_added(self.last_run_tick))
+            .map(|(component, _)| component.fruity_into())
+            .collect::<Vec<_>>()
+    }
+}
@@ -2,6 +2,7 @@ use crate::entity::archetype::Archetype;
 use crate::entity::entity_query::QueryParam;
 use crate::entity::entity_query::RequestedEntityGuard;
 use crate::entity::entity_reference::EntityReference;
+use fruity_core::inject::InjectAccess;
 
 macro_rules! tuple_impl_generics {
     ($t1:ident, $($tn:ident),+) => {
@@ -31,6 +32,19 @@ macro_rules! tuple_impl_generics {
                 $t1::require_write() || $ ($tn::require_write())||*
             }
 
+            fn require_relations() -> bool {
+                $t1::require_relations() || $ ($tn::require_relations())||*
+            }
+
+            fn get_access() -> InjectAccess {
+                vec![$t1::get_access(), $ ($tn::get_access()),*].into_iter().collect()
+            }
+
+            fn filter_entity(entity_guard: &RequestedEntityGuard<'a>, last_run_tick: u64) -> bool {
+                <($ ($tn),*)>::filter_entity(entity_guard, last_run_tick)
+                    && $t1::filter_entity(entity_guard, last_run_tick)
+            }
+
             fn iter_entity_components(
                 entity_reference: EntityReference,
                 entity_guard: &'a RequestedEntityGuard<'a>,
@@ -7,6 +7,7 @@ use crate::entity::entity::EntityId;
 use crate::entity::entity_query::QueryParam;
 use crate::entity::entity_query::RequestedEntityGuard;
 use crate::entity::entity_reference::EntityReference;
+use fruity_core::inject::InjectAccess;
 use std::marker::PhantomData;
 
 /// The entity reference
@@ -150,6 +151,10 @@ impl<'a, T: Component + StaticComponent + 'static> QueryParam<'a> for With<T> {
         false
     }
 
+    fn get_access() -> InjectAccess {
+        InjectAccess::read(T::get_component_name())
+    }
+
     fn iter_entity_components(
         _entity_reference: EntityReference,
         entity_guard: &'a RequestedEntityGuard<'a>,
@@ -188,6 +193,10 @@ impl<'a, T: Component + StaticComponent + 'static> QueryParam<'a> for WithMut<T>
         true
     }
 
+    fn get_access() -> InjectAccess {
+        InjectAccess::write(T::get_component_name())
+    }
+
     fn iter_entity_components(
         _entity_reference: EntityReference,
         entity_guard: &'a RequestedEntityGuard<'a>,
@@ -222,6 +231,10 @@ impl<'a, T: Component + StaticComponent + 'static> QueryParam<'a> for WithOption
         false
     }
 
+    fn get_access() -> InjectAccess {
+        InjectAccess::read(T::get_component_name())
+    }
+
     fn iter_entity_components(
         _entity_reference: EntityReference,
         entity_guard: &'a RequestedEntityGuard<'a>,
@@ -270,6 +283,10 @@ impl<'a, T: Component + StaticComponent + 'static> QueryParam<'a> for WithOption
         true
     }
 
+    fn get_access() -> InjectAccess {
+        InjectAccess::write(T::get_component_name())
+    }
+
     fn iter_entity_components(
         _entity_reference: EntityReference,
         entity_guard: &'a RequestedEntityGuard<'a>,
@@ -318,6 +335,15 @@ impl<'a, T: Component + StaticComponent + 'static, E: Component + StaticComponen
         false
     }
 
+    fn get_access() -> InjectAccess {
+        vec![
+            InjectAccess::read(T::get_component_name()),
+            InjectAccess::read(E::get_component_name()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn iter_entity_components(
         _entity_reference: EntityReference,
         entity_guard: &'a RequestedEntityGuard<'a>,
@@ -366,6 +392,15 @@ impl<'a, T: Component + StaticComponent + 'static, E: Component + StaticComponen
         true
     }
 
+    fn get_access() -> InjectAccess {
+        vec![
+            InjectAccess::write(T::get_component_name()),
+            InjectAccess::write(E::get_component_name()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn iter_entity_components(
         _entity_reference: EntityReference,
         entity_guard: &'a RequestedEntityGuard<'a>,
@@ -411,6 +446,15 @@ impl<'a, T: Component + StaticComponent + 'static, E: Component + StaticComponen
         false
     }
 
+    fn get_access() -> InjectAccess {
+        vec![
+            InjectAccess::read(T::get_component_name()),
+            InjectAccess::read(E::get_component_name()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn iter_entity_components(
         _entity_reference: EntityReference,
         entity_guard: &'a RequestedEntityGuard<'a>,
@@ -469,6 +513,15 @@ impl<'a, T: Component + StaticComponent + 'static, E: Component + StaticComponen
         true
     }
 
+    fn get_access() -> InjectAccess {
+        vec![
+            InjectAccess::write(T::get_component_name()),
+            InjectAccess::write(E::get_component_name()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn iter_entity_components(
         _entity_reference: EntityReference,
         entity_guard: &'a RequestedEntityGuard<'a>,
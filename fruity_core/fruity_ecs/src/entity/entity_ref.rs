@@ -0,0 +1,201 @@
+use crate::entity::entity::EntityGuid;
+use crate::entity::entity::EntityId;
+use crate::entity::entity_reference::EntityReference;
+use fruity_any::*;
+use fruity_core::convert::FruityInto;
+use fruity_core::convert::FruityTryFrom;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::introspect::SetterCaller;
+use fruity_core::serialize::serialized::Serialized;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A reference to an entity that can be stored in a component field
+///
+/// The entity id is used while the application runs, the guid is what is saved, when a snapshot
+/// is restored the entity id is found back from the guid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, FruityAny)]
+pub struct EntityRef {
+    /// The entity id, only valid while the application runs
+    pub entity_id: EntityId,
+
+    /// The entity guid
+    pub guid: EntityGuid,
+}
+
+impl EntityRef {
+    /// Returns an EntityRef
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    /// * `guid` - The entity guid
+    ///
+    pub fn new(entity_id: EntityId, guid: EntityGuid) -> EntityRef {
+        EntityRef { entity_id, guid }
+    }
+
+    /// Serialize the reference as it should be stored in a file, only the guid is kept
+    pub fn serialize_persistent(&self) -> Serialized {
+        let mut fields = HashMap::new();
+        fields.insert("guid".to_string(), self.guid.fruity_into());
+
+        Serialized::SerializedObject {
+            class_name: "EntityRef".to_string(),
+            fields,
+        }
+    }
+}
+
+/// Rewrite all the entity references stored in a serialized value
+///
+/// # Arguments
+/// * `serialized` - The serialized value
+/// * `remap` - A function that returns the new serialized reference
+///
+pub(crate) fn remap_entity_refs(
+    serialized: &mut Serialized,
+    remap: &dyn Fn(EntityRef) -> Serialized,
+) {
+    match serialized {
+        Serialized::SerializedObject { class_name, .. } if class_name == "EntityRef" => {
+            if let Ok(entity_ref) = EntityRef::fruity_try_from(serialized.clone()) {
+                *serialized = remap(entity_ref);
+            }
+        }
+        Serialized::SerializedObject { fields, .. } => fields
+            .values_mut()
+            .for_each(|value| remap_entity_refs(value, remap)),
+        Serialized::Array(elements) => elements
+            .iter_mut()
+            .for_each(|value| remap_entity_refs(value, remap)),
+        _ => (),
+    }
+}
+
+impl FruityTryFrom<Serialized> for EntityRef {
+    type Error = String;
+
+    fn fruity_try_from(value: Serialized) -> Result<Self, Self::Error> {
+        match value {
+            Serialized::SerializedObject { fields, .. } => Ok(EntityRef {
+                entity_id: fields
+                    .get("entity_id")
+                    .and_then(|entity_id| EntityId::fruity_try_from(entity_id.clone()).ok())
+                    .unwrap_or_default(),
+                guid: fields
+                    .get("guid")
+                    .and_then(|guid| EntityGuid::fruity_try_from(guid.clone()).ok())
+                    .unwrap_or_default(),
+            }),
+            Serialized::String(value) => match EntityGuid::parse(&value) {
+                Some(guid) => Ok(EntityRef { entity_id: 0, guid }),
+                None => Err(format!("Couldn't convert {} to entity ref", value)),
+            },
+            Serialized::NativeObject(value) => {
+                match value.as_any_box().downcast::<EntityReference>() {
+                    Ok(value) => {
+                        let entity = value.read();
+                        Ok(EntityRef {
+                            entity_id: entity.get_entity_id(),
+                            guid: entity.get_guid(),
+                        })
+                    }
+                    Err(_) => Err(format!("Couldn't convert a native object to entity ref")),
+                }
+            }
+            value => match EntityId::fruity_try_from(value) {
+                Ok(entity_id) => Ok(EntityRef {
+                    entity_id,
+                    guid: EntityGuid::default(),
+                }),
+                Err(err) => Err(err),
+            },
+        }
+    }
+}
+
+impl FruityInto<Serialized> for EntityRef {
+    fn fruity_into(self) -> Serialized {
+        let mut fields = HashMap::new();
+        fields.insert("entity_id".to_string(), Serialized::U64(self.entity_id));
+        fields.insert("guid".to_string(), self.guid.fruity_into());
+
+        Serialized::SerializedObject {
+            class_name: "EntityRef".to_string(),
+            fields,
+        }
+    }
+}
+
+impl IntrospectObject for EntityRef {
+    fn get_class_name(&self) -> String {
+        "EntityRef".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![
+            FieldInfo {
+                name: "entity_id".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    let this = this.downcast_ref::<EntityRef>().unwrap();
+                    this.entity_id.fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+            FieldInfo {
+                name: "guid".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    let this = this.downcast_ref::<EntityRef>().unwrap();
+                    this.guid.fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maplit::hashmap;
+
+    #[test]
+    fn remap_entity_refs_test() {
+        let mut serialized = Serialized::SerializedObject {
+            class_name: "Follow".to_string(),
+            fields: hashmap! {
+                "target".to_string() => EntityRef::new(1, EntityGuid(10)).fruity_into(),
+                "targets".to_string() => Serialized::Array(vec![
+                    EntityRef::new(2, EntityGuid(20)).serialize_persistent(),
+                ]),
+                "count".to_string() => Serialized::U64(1),
+            },
+        };
+
+        remap_entity_refs(&mut serialized, &|entity_ref| {
+            EntityRef::new(entity_ref.entity_id + 100, entity_ref.guid).fruity_into()
+        });
+
+        if let Serialized::SerializedObject { fields, .. } = serialized {
+            assert_eq!(
+                EntityRef::fruity_try_from(fields["target"].clone()),
+                Ok(EntityRef::new(101, EntityGuid(10)))
+            );
+            assert_eq!(
+                Vec::<EntityRef>::fruity_try_from(fields["targets"].clone()),
+                Ok(vec![EntityRef::new(100, EntityGuid(20))])
+            );
+            assert!(matches!(fields["count"], Serialized::U64(1)));
+        } else {
+            panic!("The component should stay a serialized object");
+        }
+    }
+}
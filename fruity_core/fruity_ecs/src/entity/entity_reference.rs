@@ -1,5 +1,6 @@
 use crate::component::component_reference::ComponentReference;
 use crate::entity::archetype::ArchetypeArcRwLock;
+use crate::entity::entity::EntityId;
 use crate::entity::entity_guard::EntityReadGuard;
 use crate::entity::entity_guard::EntityWriteGuard;
 use fruity_any::*;
@@ -17,6 +18,17 @@ use std::fmt::Debug;
 use std::rc::Rc;
 use std::sync::Arc;
 
+/// The parent and the children of an entity, read from the hierarchy index of the
+/// [’EntityService’]
+#[derive(Clone, Default)]
+pub struct EntityRelations {
+    /// The parent of the entity
+    pub parent: Option<EntityReference>,
+
+    /// The children of the entity, in the order they have been attached
+    pub children: Vec<EntityReference>,
+}
+
 /// A reference over an entity stored into an Archetype
 #[derive(Clone, FruityAny)]
 pub struct EntityReference {
@@ -42,6 +54,7 @@ impl EntityReference {
             entity_id: self.entity_id,
             _guard: Rc::new(guard),
             archetype_reader: Rc::new(archetype_reader),
+            relations: None,
         }
     }
 
@@ -62,6 +75,46 @@ impl EntityReference {
             entity_id: self.entity_id,
             _guard: Rc::new(guard),
             archetype_reader: Rc::new(archetype_reader),
+            relations: None,
+        }
+    }
+
+    /// Get the entity id, the entity is not locked
+    pub fn get_entity_id(&self) -> EntityId {
+        let archetype_reader = self.archetype.read();
+        *archetype_reader.entity_id_array.get(self.entity_id).unwrap()
+    }
+
+    /// Get the parent of the entity, see [’EntityService::get_parent’]
+    ///
+    /// The entity service is locked, so it should not be called while an entity is locked
+    pub fn get_parent(&self) -> Option<EntityReference> {
+        self.get_relations().parent
+    }
+
+    /// Get the children of the entity, see [’EntityService::get_children’]
+    ///
+    /// The entity service is locked, so it should not be called while an entity is locked
+    pub fn get_children(&self) -> Vec<EntityReference> {
+        self.get_relations().children
+    }
+
+    /// Get the parent and the children of the entity
+    ///
+    /// The entity service is locked, so it should not be called while an entity is locked
+    pub fn get_relations(&self) -> EntityRelations {
+        let (entity_id, entity_service) = {
+            let archetype_reader = self.archetype.read();
+            (
+                *archetype_reader.entity_id_array.get(self.entity_id).unwrap(),
+                archetype_reader.get_entity_service(),
+            )
+        };
+
+        let entity_service = entity_service.read();
+        EntityRelations {
+            parent: entity_service.get_parent(entity_id),
+            children: entity_service.get_children(entity_id),
         }
     }
 
@@ -108,6 +161,16 @@ impl IntrospectObject for EntityReference {
                     Ok(Some(result.fruity_into()))
                 })),
             },
+            MethodInfo {
+                name: "get_guid".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<EntityReference>(this);
+                    let this = this.read();
+                    let result = this.get_guid();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
             MethodInfo {
                 name: "get_name".to_string(),
                 call: MethodCaller::Const(Arc::new(|this, _args| {
@@ -128,6 +191,24 @@ impl IntrospectObject for EntityReference {
                     Ok(Some(result.fruity_into()))
                 })),
             },
+            MethodInfo {
+                name: "parent".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<EntityReference>(this);
+                    let result = this.get_parent();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "children".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<EntityReference>(this);
+                    let result = this.get_children();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
             // TODO: Complete that
         ]
     }
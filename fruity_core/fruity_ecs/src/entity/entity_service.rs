@@ -1,12 +1,23 @@
 use crate::component::component::AnyComponent;
+use crate::component::component::Component;
+use crate::component::component_schema_service::ComponentSchemaDiagnostic;
+use crate::component::component_schema_service::ComponentSchemaService;
+use crate::component::component_schema_service::SCHEMA_VERSION_FIELD;
+use crate::component::component_ticks::ComponentTicks;
+use crate::component::serialized_component::SerializedComponent;
 use crate::entity::archetype::Archetype;
 use crate::entity::archetype::ArchetypeArcRwLock;
 use crate::entity::entity::get_type_identifier_by_any;
+use crate::entity::entity::EntityGuid;
 use crate::entity::entity::EntityId;
 use crate::entity::entity::EntityTypeIdentifier;
+use crate::entity::entity_hierarchy::EntityHierarchy;
 use crate::entity::entity_query::serialized::SerializedQuery;
 use crate::entity::entity_query::Query;
 use crate::entity::entity_query::QueryParam;
+use crate::entity::entity_ref::remap_entity_refs;
+use crate::entity::entity_ref::EntityRef;
+use crate::system::system_service::get_system_last_run_tick;
 use crate::ExtensionComponentService;
 use std::marker::PhantomData;
 // use crate::entity::entity_query_inject::QueryInject;
@@ -23,20 +34,24 @@ use fruity_core::introspect::SetterCaller;
 use fruity_core::object_factory_service::ObjectFactoryService;
 use fruity_core::resource::resource::Resource;
 use fruity_core::resource::resource_reference::ResourceReference;
+use fruity_core::serialize::deserialize_file;
+use fruity_core::serialize::serialize_file;
 use fruity_core::serialize::serialized::Serialized;
-use fruity_core::serialize::yaml::deserialize_yaml;
 use fruity_core::serialize::Deserialize;
 use fruity_core::serialize::Serialize;
 use fruity_core::signal::Signal;
 use fruity_core::utils::introspect::cast_introspect_ref;
 use fruity_core::utils::introspect::ArgumentCaster;
+use fruity_core::vfs::virtual_file_system::VirtualFileSystem;
 use fruity_core::Mutex;
 use fruity_core::RwLock;
+use itertools::Itertools;
 use maplit::hashmap;
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::fs::File;
 use std::ops::Deref;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 /// An error over entity deletion
@@ -45,14 +60,24 @@ pub enum RemoveEntityError {
     NotFound,
 }
 
+/// An identifier of a scene loaded with [’EntityService::load_additive’]
+pub type SceneHandle = u64;
+
 /// A storage for every entities, use [’Archetypes’] to store entities of different types
 #[derive(FruityAny)]
 pub struct EntityService {
     id_incrementer: Mutex<u64>,
     index_map: RwLock<HashMap<EntityId, (usize, usize)>>,
+    guid_map: RwLock<HashMap<EntityGuid, EntityId>>,
+    scene_incrementer: Mutex<SceneHandle>,
+    scene_map: RwLock<HashMap<EntityId, SceneHandle>>,
+    hierarchy: RwLock<EntityHierarchy>,
     archetypes: Arc<RwLock<Vec<ArchetypeArcRwLock>>>,
+    change_tick: Arc<AtomicU64>,
     object_factory_service: ResourceReference<ObjectFactoryService>,
+    component_schema_service: ResourceReference<ComponentSchemaService>,
     extension_component_service: ResourceReference<ExtensionComponentService>,
+    file_system: VirtualFileSystem,
 
     /// Signal notified when an entity is created
     pub on_created: Signal<EntityReference>,
@@ -77,9 +102,16 @@ impl EntityService {
         EntityService {
             id_incrementer: Mutex::new(0),
             index_map: RwLock::new(HashMap::new()),
+            guid_map: RwLock::new(HashMap::new()),
+            scene_incrementer: Mutex::new(0),
+            scene_map: RwLock::new(HashMap::new()),
+            hierarchy: RwLock::new(EntityHierarchy::default()),
             archetypes: Arc::new(RwLock::new(Vec::new())),
+            change_tick: Arc::new(AtomicU64::new(1)),
             object_factory_service: resource_container.require::<ObjectFactoryService>(),
+            component_schema_service: resource_container.require::<ComponentSchemaService>(),
             extension_component_service: resource_container.require::<ExtensionComponentService>(),
+            file_system: resource_container.get_file_system(),
             on_created: Signal::new(),
             on_deleted: Signal::new(),
         }
@@ -101,6 +133,49 @@ impl EntityService {
             })
     }
 
+    /// Get an entity from its guid
+    ///
+    /// # Arguments
+    /// * `guid` - The entity guid
+    ///
+    pub fn get_entity_by_guid(&self, guid: EntityGuid) -> Option<EntityReference> {
+        let entity_id = {
+            let guid_map = self.guid_map.read();
+            *guid_map.get(&guid)?
+        };
+
+        self.get_entity(entity_id)
+    }
+
+    /// Get a reference to an entity that can be stored into a component
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    ///
+    pub fn get_entity_ref(&self, entity_id: EntityId) -> Option<EntityRef> {
+        let entity = self.get_entity(entity_id)?;
+        let guid = entity.read().get_guid();
+
+        Some(EntityRef::new(entity_id, guid))
+    }
+
+    /// Resolve the entity id of a reference, the guid is used when it is known
+    ///
+    /// # Arguments
+    /// * `entity_ref` - The entity reference
+    ///
+    pub fn resolve_entity_ref(&self, entity_ref: &EntityRef) -> Option<EntityId> {
+        if entity_ref.guid.is_nil() {
+            let index_map = self.index_map.read();
+            index_map
+                .contains_key(&entity_ref.entity_id)
+                .then(|| entity_ref.entity_id)
+        } else {
+            let guid_map = self.guid_map.read();
+            guid_map.get(&entity_ref.guid).copied()
+        }
+    }
+
     /// Iterate over all entities
     pub fn iter_all_entities(&self) -> impl Iterator<Item = EntityReference> + '_ {
         let archetypes = self.archetypes.read();
@@ -115,6 +190,7 @@ impl EntityService {
     }
 
     /// Create a query over entities
+    /// The [’Changed’] and [’Added’] filters are relative to the last run of the running system
     ///
     /// # Arguments
     /// * `entity_identifier` - The entity type identifier
@@ -125,6 +201,7 @@ impl EntityService {
             archetypes: self.archetypes.clone(),
             on_entity_created: self.on_created.clone(),
             on_entity_deleted: self.on_deleted.clone(),
+            last_run_tick: get_system_last_run_tick(),
             _param_phantom: PhantomData {},
         }
     }
@@ -139,12 +216,7 @@ impl EntityService {
     /// * `components` - The components that will be added
     ///
     pub fn create(&self, name: &str, enabled: bool, components: Vec<AnyComponent>) -> EntityId {
-        let entity_id = {
-            let mut id_incrementer = self.id_incrementer.lock();
-            *id_incrementer += 1;
-            *id_incrementer
-        };
-
+        let entity_id = self.generate_id();
         self.create_with_id(entity_id, name, enabled, components)
     }
 
@@ -163,6 +235,28 @@ impl EntityService {
         entity_id: EntityId,
         name: &str,
         enabled: bool,
+        components: Vec<AnyComponent>,
+    ) -> EntityId {
+        self.create_with_guid(entity_id, EntityGuid::generate(), name, enabled, components)
+    }
+
+    /// Add a new entity in the storage with a known guid
+    /// Create the archetype if it don't exists
+    /// Returns the newly created entity id
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    /// * `guid` - The entity guid, should be unique
+    /// * `name` - The name of the entity
+    /// * `enabled` - Is the entity active
+    /// * `components` - The components that will be added
+    ///
+    pub fn create_with_guid(
+        &self,
+        entity_id: EntityId,
+        guid: EntityGuid,
+        name: &str,
+        enabled: bool,
         mut components: Vec<AnyComponent>,
     ) -> EntityId {
         // Generate an id for the entity
@@ -180,7 +274,9 @@ impl EntityService {
         let indexes = match self.archetype_by_identifier(archetype_identifier) {
             Some((archetype_index, archetype)) => {
                 let archetype_entity_id = archetype.read().len();
-                archetype.write().add(entity_id, name, enabled, components);
+                archetype
+                    .write()
+                    .add(entity_id, guid, name, enabled, components);
 
                 (archetype_index, archetype_entity_id)
             }
@@ -189,7 +285,9 @@ impl EntityService {
                 let archetype_index = archetypes.len();
                 let archetype = Archetype::new(
                     self.extension_component_service.clone(),
+                    self.change_tick.clone(),
                     entity_id,
+                    guid,
                     name,
                     enabled,
                     components,
@@ -203,6 +301,9 @@ impl EntityService {
         {
             let mut index_map = self.index_map.write();
             index_map.insert(entity_id, indexes);
+
+            let mut guid_map = self.guid_map.write();
+            guid_map.insert(guid, entity_id);
         }
 
         // Notify that entity is created
@@ -232,15 +333,31 @@ impl EntityService {
 
         if let Some(indexes) = indexes {
             // Delete the entity
-            {
+            let old_entity = {
                 let archetypes = self.archetypes.read();
                 let archetype = archetypes.get(indexes.0).unwrap();
-                archetype.read().remove(indexes.1);
+                let (old_entity, _) = archetype.read().remove(indexes.1);
+                old_entity
+            };
+
+            {
+                let mut guid_map = self.guid_map.write();
+                guid_map.remove(&old_entity.guid);
+
+                let mut scene_map = self.scene_map.write();
+                scene_map.remove(&entity_id);
             }
 
             // Propagate the deleted signal
             self.on_deleted.notify(entity_id);
 
+            // The observers may need the children of the deleted entity, so it's removed from
+            // the hierarchy after the signal
+            {
+                let mut hierarchy = self.hierarchy.write();
+                hierarchy.remove(entity_id);
+            }
+
             Ok(())
         } else {
             Err(RemoveEntityError::NotFound)
@@ -264,7 +381,7 @@ impl EntityService {
         };
 
         if let Some(indexes) = indexes {
-            let (old_entity, mut old_components) = {
+            let (old_entity, mut old_components, old_ticks) = {
                 let archetypes = self.archetypes.read();
                 let archetypes = unsafe {
                     std::mem::transmute::<&Vec<ArchetypeArcRwLock>, &Vec<ArchetypeArcRwLock>>(
@@ -273,18 +390,26 @@ impl EntityService {
                 };
 
                 let archetype = archetypes.get(indexes.0).unwrap();
-                archetype.write().remove(indexes.1)
+                let archetype_writer = archetype.write();
+                let old_ticks = archetype_writer.get_entity_ticks(indexes.1);
+                let (old_entity, old_components) = archetype_writer.remove(indexes.1);
+
+                (old_entity, old_components, old_ticks)
             };
 
             old_components.append(&mut components);
 
-            self.create_with_id(
+            self.create_with_guid(
                 entity_id,
+                old_entity.guid,
                 &old_entity.name,
                 old_entity.enabled,
                 old_components,
             );
 
+            // The components that were already there keep their change ticks
+            self.set_entity_ticks(entity_id, &old_ticks);
+
             Ok(())
         } else {
             Err(RemoveEntityError::NotFound)
@@ -308,7 +433,7 @@ impl EntityService {
         };
 
         if let Some(indexes) = indexes {
-            let (old_entity, mut old_components) = {
+            let (old_entity, mut old_components, mut old_ticks) = {
                 let archetypes = self.archetypes.read();
                 let archetypes = unsafe {
                     std::mem::transmute::<&Vec<ArchetypeArcRwLock>, &Vec<ArchetypeArcRwLock>>(
@@ -317,24 +442,59 @@ impl EntityService {
                 };
 
                 let archetype = archetypes.get(indexes.0).unwrap();
-                archetype.write().remove(indexes.1)
+                let archetype_writer = archetype.write();
+                let old_ticks = archetype_writer.get_entity_ticks(indexes.1);
+                let (old_entity, old_components) = archetype_writer.remove(indexes.1);
+
+                (old_entity, old_components, old_ticks)
             };
 
             old_components.remove(component_index);
+            old_ticks.remove(component_index);
 
-            self.create_with_id(
+            self.create_with_guid(
                 entity_id,
+                old_entity.guid,
                 &old_entity.name,
                 old_entity.enabled,
                 old_components,
             );
 
+            // The components that were already there keep their change ticks
+            self.set_entity_ticks(entity_id, &old_ticks);
+
             Ok(())
         } else {
             Err(RemoveEntityError::NotFound)
         }
     }
 
+    fn set_entity_ticks(&self, entity_id: EntityId, ticks: &[(String, ComponentTicks)]) {
+        let indexes = {
+            let index_map = self.index_map.read();
+            index_map.get(&entity_id).copied()
+        };
+
+        if let Some((archetype_index, entity_index)) = indexes {
+            let archetypes = self.archetypes.read();
+            let archetype = archetypes.get(archetype_index).unwrap();
+            archetype.read().set_entity_ticks(entity_index, ticks);
+        }
+    }
+
+    /// Get the current change tick
+    ///
+    /// The component changes are dated with this tick, see [’ComponentTicks’]
+    pub fn get_change_tick(&self) -> u64 {
+        self.change_tick.load(Ordering::Relaxed)
+    }
+
+    /// Increment the change tick, should be called every time a batch of systems is started
+    /// Returns the new tick, the changes made by the started systems are dated with it
+    pub fn increment_change_tick(&self) -> u64 {
+        self.change_tick.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
     fn archetype_by_identifier(
         &self,
         entity_identifier: EntityTypeIdentifier,
@@ -366,109 +526,460 @@ impl EntityService {
 
         // Get the writers
         let mut index_map = self.index_map.write();
+        let mut guid_map = self.guid_map.write();
+        let mut scene_map = self.scene_map.write();
+        let mut hierarchy = self.hierarchy.write();
         let mut id_incrementer = self.id_incrementer.lock();
         let mut archetypes = self.archetypes.write();
 
         // Clear all entities
         index_map.clear();
+        guid_map.clear();
+        scene_map.clear();
+        hierarchy.clear();
         *id_incrementer = 0;
         archetypes.clear();
     }
 
     /// Create a snapshot over all the entities
+    ///
+    /// The entities are ordered by id, so a same scene always produces the same snapshot
+    /// The entity references stored into the components only keep the guid of the entity
     pub fn snapshot(&self) -> EntityServiceSnapshot {
-        let serialized_entities = self
-            .iter_all_entities()
-            .filter_map(|entity| {
+        let mut entities = self.iter_all_entities().collect::<Vec<_>>();
+        entities.sort_by_cached_key(|entity| entity.read().get_entity_id());
+
+        EntityServiceSnapshot(Serialized::Array(self.serialize_entities(entities)))
+    }
+
+    /// Copy entities, the copies get new ids and new guids
+    /// The entity references that target a copied entity are remapped to its copy, the other
+    /// references are kept, so a copied child of a non copied entity keeps the same parent
+    /// Returns the ids of the copies, in the order of the given ids
+    ///
+    /// # Arguments
+    /// * `entity_ids` - The ids of the entities to copy
+    ///
+    pub fn duplicate(&self, entity_ids: &[EntityId]) -> Vec<EntityId> {
+        let entities = entity_ids
+            .iter()
+            .filter_map(|entity_id| self.get_entity(*entity_id))
+            .collect::<Vec<_>>();
+
+        // Every id and guid is already used, so the restore gives new ones to the copies
+        let serialized_entities = self.serialize_entities(entities);
+        let (entity_ids, diagnostics) = self.restore_entities(&serialized_entities, None);
+        diagnostics
+            .into_iter()
+            .for_each(|diagnostic| log::warn!("{}", diagnostic));
+
+        entity_ids
+    }
+
+    // Serialize entities as they are stored in a snapshot
+    fn serialize_entities(&self, entities: Vec<EntityReference>) -> Vec<Serialized> {
+        let guids_by_id = {
+            let guid_map = self.guid_map.read();
+            guid_map
+                .iter()
+                .map(|(guid, entity_id)| (*entity_id, *guid))
+                .collect::<HashMap<_, _>>()
+        };
+
+        let persist_entity_ref = |entity_ref: EntityRef| {
+            let guid = if entity_ref.guid.is_nil() {
+                guids_by_id
+                    .get(&entity_ref.entity_id)
+                    .copied()
+                    .unwrap_or_default()
+            } else {
+                entity_ref.guid
+            };
+
+            EntityRef::new(0, guid).serialize_persistent()
+        };
+
+        // The script components created at runtime have the version registered by the script
+        let component_schema_service = self.component_schema_service.read();
+        let serialize_component = |component: &dyn Component| {
+            let mut serialized_component = component.serialize()?;
+
+            let script_version = component
+                .as_any_ref()
+                .downcast_ref::<SerializedComponent>()
+                .filter(|component| component.get_saved_schema_version().is_none())
+                .and_then(|component| {
+                    component_schema_service
+                        .get_script_component_version(&component.get_class_name())
+                });
+
+            if let (Some(script_version), Serialized::SerializedObject { fields, .. }) =
+                (script_version, &mut serialized_component)
+            {
+                fields.insert(
+                    SCHEMA_VERSION_FIELD.to_string(),
+                    Serialized::U32(script_version),
+                );
+            }
+
+            Some(serialized_component)
+        };
+
+        entities
+            .into_iter()
+            .map(|entity| {
                 let entity = entity.read();
                 let serialized_components = Serialized::Array(
                     entity
                         .read_all_components()
-                        .into_iter()
-                        .filter_map(|component| component.deref().serialize())
+                        .filter_map(|component| serialize_component(component.deref()))
+                        .map(|mut serialized_component| {
+                            remap_entity_refs(&mut serialized_component, &persist_entity_ref);
+                            serialized_component
+                        })
                         .collect::<Vec<_>>(),
                 );
 
-                let serialized_entity = Serialized::SerializedObject {
+                Serialized::SerializedObject {
                     class_name: "Entity".to_string(),
                     fields: hashmap! {
                         "entity_id".to_string() => Serialized::U64(entity.get_entity_id()),
+                        "guid".to_string() => entity.get_guid().fruity_into(),
                         "name".to_string() => Serialized::String(entity.get_name()),
                         "enabled".to_string() => Serialized::Bool(entity.is_enabled()),
                         "components".to_string() => serialized_components,
                     },
-                };
-
-                Some(serialized_entity)
+                }
             })
-            .collect::<Vec<_>>();
+            .collect::<Vec<_>>()
+    }
 
-        EntityServiceSnapshot(Serialized::Array(serialized_entities))
+    /// Save a snapshot of all the entities into a file, the format is deduced from the file extension
+    ///
+    /// # Arguments
+    /// * `filepath` - The file path
+    ///
+    pub fn save_to_file(&self, filepath: &str) -> Result<(), std::io::Error> {
+        let snapshot = self.snapshot();
+        serialize_file(&self.file_system, filepath, &snapshot.0)
     }
 
-    /// Restore an entity snapshot from a file
+    /// Restore an entity snapshot from a file, the format is deduced from the file extension
+    /// Returns the problems met while reading the components, see [’EntityService::restore’]
     ///
     /// # Arguments
     /// * `filepath` - The file path
     ///
-    pub fn restore_from_file(&self, filepath: &str) {
-        if let Ok(mut reader) = File::open(&filepath) {
-            if let Some(snapshot) = deserialize_yaml(&mut reader) {
-                self.restore(&EntityServiceSnapshot(snapshot));
-            }
+    pub fn restore_from_file(&self, filepath: &str) -> Vec<ComponentSchemaDiagnostic> {
+        if let Some(snapshot) = deserialize_file(&self.file_system, filepath) {
+            self.restore(&EntityServiceSnapshot(snapshot))
+        } else {
+            Vec::new()
         }
     }
 
     /// Restore an entity snapshot
     ///
+    /// The entity references stored into the components are resolved from the entity guids
+    /// The components saved with a previous schema version are migrated, the problems met while
+    /// reading them are returned, like the unknown components or fields
+    ///
     /// # Arguments
     /// * `snapshot` - The snapshot
     ///
-    pub fn restore(&self, snapshot: &EntityServiceSnapshot) {
+    pub fn restore(&self, snapshot: &EntityServiceSnapshot) -> Vec<ComponentSchemaDiagnostic> {
         self.clear();
 
         if let Serialized::Array(entities) = &snapshot.0 {
-            entities
-                .iter()
-                .for_each(|serialized_entity| self.restore_entity(serialized_entity));
+            self.restore_entities(entities, None).1
+        } else {
+            Vec::new()
         }
     }
 
-    fn restore_entity(&self, serialized_entity: &Serialized) {
+    /// Load the entities of a scene file without removing the existing ones
+    /// Returns a handle that can be used to unload the scene
+    ///
+    /// # Arguments
+    /// * `filepath` - The file path, the format is deduced from the file extension
+    ///
+    pub fn load_additive(&self, filepath: &str) -> Option<SceneHandle> {
+        let snapshot = deserialize_file(&self.file_system, filepath)?;
+        Some(self.restore_additive(&EntityServiceSnapshot(snapshot)))
+    }
+
+    /// Restore an entity snapshot without removing the existing entities
+    /// The ids and the guids that are already used are replaced
+    /// Returns a handle that can be used to unload the restored entities
+    ///
+    /// # Arguments
+    /// * `snapshot` - The snapshot
+    ///
+    pub fn restore_additive(&self, snapshot: &EntityServiceSnapshot) -> SceneHandle {
+        let scene = {
+            let mut scene_incrementer = self.scene_incrementer.lock();
+            *scene_incrementer += 1;
+            *scene_incrementer
+        };
+
+        if let Serialized::Array(entities) = &snapshot.0 {
+            self.restore_entities(entities, Some(scene))
+                .1
+                .into_iter()
+                .for_each(|diagnostic| log::warn!("{}", diagnostic));
+        }
+
+        scene
+    }
+
+    /// Remove all the entities that have been loaded with a scene
+    ///
+    /// # Arguments
+    /// * `scene` - The scene handle, returned by [’EntityService::load_additive’]
+    ///
+    pub fn unload_scene(&self, scene: SceneHandle) {
+        self.get_scene_entities(scene)
+            .into_iter()
+            .for_each(|entity_id| {
+                // The entity may already be removed by an other entity deletion
+                self.remove(entity_id).ok();
+            });
+    }
+
+    /// Get the scene an entity has been loaded with
+    /// Returns None if the entity has not been loaded additively
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    ///
+    pub fn get_entity_scene(&self, entity_id: EntityId) -> Option<SceneHandle> {
+        let scene_map = self.scene_map.read();
+        scene_map.get(&entity_id).copied()
+    }
+
+    /// Get the parent of an entity
+    /// The relations are not deduced from the components, they are registered with
+    /// [’EntityService::set_parent’] by the extension that stores them
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    ///
+    pub fn get_parent(&self, entity_id: EntityId) -> Option<EntityReference> {
+        let parent_id = {
+            let hierarchy = self.hierarchy.read();
+            hierarchy.get_parent(entity_id)
+        };
+
+        parent_id.and_then(|parent_id| self.get_entity(parent_id))
+    }
+
+    /// Get the children of an entity, in the order they have been attached
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    ///
+    pub fn get_children(&self, entity_id: EntityId) -> Vec<EntityReference> {
+        let children_ids = {
+            let hierarchy = self.hierarchy.read();
+            hierarchy.get_children(entity_id).to_vec()
+        };
+
+        children_ids
+            .into_iter()
+            .filter_map(|child_id| self.get_entity(child_id))
+            .collect::<Vec<_>>()
+    }
+
+    /// Get the ids of the children of an entity, in the order they have been attached
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    ///
+    pub fn get_children_ids(&self, entity_id: EntityId) -> Vec<EntityId> {
+        let hierarchy = self.hierarchy.read();
+        hierarchy.get_children(entity_id).to_vec()
+    }
+
+    /// Register the parent of an entity into the hierarchy index
+    ///
+    /// # Arguments
+    /// * `entity_id` - The entity id
+    /// * `parent_id` - The parent id, None if the entity is a root
+    ///
+    pub fn set_parent(&self, entity_id: EntityId, parent_id: Option<EntityId>) {
+        let mut hierarchy = self.hierarchy.write();
+        hierarchy.set_parent(entity_id, parent_id);
+    }
+
+    /// Get the entities that have been loaded with a scene, ordered by id
+    ///
+    /// # Arguments
+    /// * `scene` - The scene handle
+    ///
+    pub fn get_scene_entities(&self, scene: SceneHandle) -> Vec<EntityId> {
+        let scene_map = self.scene_map.read();
+        scene_map
+            .iter()
+            .filter(|(_, entity_scene)| **entity_scene == scene)
+            .map(|(entity_id, _)| *entity_id)
+            .sorted()
+            .collect::<Vec<_>>()
+    }
+
+    fn restore_entities(
+        &self,
+        serialized_entities: &[Serialized],
+        scene: Option<SceneHandle>,
+    ) -> (Vec<EntityId>, Vec<ComponentSchemaDiagnostic>) {
         let object_factory_service = self.object_factory_service.read();
 
-        if let Serialized::SerializedObject { fields, .. } = serialized_entity {
-            let entity_id =
-                if let Ok(entity_id) = EntityId::fruity_try_from(fields.get("entity_id")) {
-                    entity_id
+        // Upgrade the components saved with a previous schema
+        let mut serialized_entities = serialized_entities.to_vec();
+        let diagnostics = {
+            let component_schema_service = self.component_schema_service.read();
+            component_schema_service
+                .migrate_entities(&mut serialized_entities, &object_factory_service)
+        };
+
+        let entities = serialized_entities
+            .iter()
+            .filter_map(|serialized_entity| RestoredEntity::read(serialized_entity))
+            .collect::<Vec<_>>();
+
+        // The generated ids should not collide with the ones stored in the snapshot
+        {
+            let max_entity_id = entities
+                .iter()
+                .map(|entity| entity.entity_id)
+                .max()
+                .unwrap_or_default();
+
+            let mut id_incrementer = self.id_incrementer.lock();
+            *id_incrementer = u64::max(max_entity_id, *id_incrementer);
+        }
+
+        // First pass, give an id and a guid to every entity
+        // An id or a guid that is already used is replaced
+        let mut ids_map = HashMap::<EntityId, EntityRef>::new();
+        let mut guids_map = HashMap::<EntityGuid, EntityRef>::new();
+        let entities = entities
+            .into_iter()
+            .map(|entity| {
+                let entity_id = if ids_map.contains_key(&entity.entity_id)
+                    || self.get_entity(entity.entity_id).is_some()
+                {
+                    self.generate_id()
                 } else {
-                    return;
+                    entity.entity_id
                 };
 
-            let name = if let Ok(name) = String::fruity_try_from(fields.get("name")) {
-                name
-            } else {
-                return;
-            };
+                let guid = match entity.guid {
+                    Some(guid)
+                        if !guids_map.contains_key(&guid)
+                            && self.get_entity_by_guid(guid).is_none() =>
+                    {
+                        guid
+                    }
+                    _ => EntityGuid::generate(),
+                };
 
-            let enabled = if let Ok(enabled) = bool::fruity_try_from(fields.get("enabled")) {
-                enabled
+                let entity_ref = EntityRef::new(entity_id, guid);
+                ids_map.entry(entity.entity_id).or_insert(entity_ref);
+                if let Some(old_guid) = entity.guid {
+                    guids_map.entry(old_guid).or_insert(entity_ref);
+                }
+
+                (entity_ref, entity)
+            })
+            .collect::<Vec<_>>();
+
+        // Second pass, resolve the entity references and create the entities
+        let resolve_entity_ref = |entity_ref: EntityRef| {
+            let resolved = if let Some(resolved) = guids_map.get(&entity_ref.guid) {
+                *resolved
+            } else if let Some(resolved) = ids_map.get(&entity_ref.entity_id) {
+                *resolved
             } else {
-                return;
+                let entity_id = self.resolve_entity_ref(&entity_ref).unwrap_or_default();
+                EntityRef::new(entity_id, entity_ref.guid)
             };
 
-            let components = if let Some(Serialized::Array(components)) = fields.get("components") {
-                components
-                    .iter()
+            resolved.fruity_into()
+        };
+
+        let entity_ids = entities
+            .into_iter()
+            .map(|(entity_ref, mut entity)| {
+                let components = entity
+                    .components
+                    .iter_mut()
                     .filter_map(|serialized_component| {
+                        remap_entity_refs(serialized_component, &resolve_entity_ref);
                         AnyComponent::deserialize(serialized_component, &object_factory_service)
                     })
-                    .collect::<Vec<_>>()
+                    .collect::<Vec<_>>();
+
+                // Tag the entity before creating it so the observers can read its scene
+                if let Some(scene) = scene {
+                    let mut scene_map = self.scene_map.write();
+                    scene_map.insert(entity_ref.entity_id, scene);
+                }
+
+                self.create_with_guid(
+                    entity_ref.entity_id,
+                    entity_ref.guid,
+                    &entity.name,
+                    entity.enabled,
+                    components,
+                );
+
+                entity_ref.entity_id
+            })
+            .collect::<Vec<_>>();
+
+        (entity_ids, diagnostics)
+    }
+
+    fn generate_id(&self) -> EntityId {
+        let mut id_incrementer = self.id_incrementer.lock();
+        *id_incrementer += 1;
+        *id_incrementer
+    }
+}
+
+struct RestoredEntity {
+    entity_id: EntityId,
+    guid: Option<EntityGuid>,
+    name: String,
+    enabled: bool,
+    components: Vec<Serialized>,
+}
+
+impl RestoredEntity {
+    fn read(serialized_entity: &Serialized) -> Option<RestoredEntity> {
+        if let Serialized::SerializedObject { fields, .. } = serialized_entity {
+            let entity_id = EntityId::fruity_try_from(fields.get("entity_id")).ok()?;
+            let guid = fields
+                .get("guid")
+                .and_then(|guid| EntityGuid::fruity_try_from(guid.clone()).ok())
+                .filter(|guid| !guid.is_nil());
+            let name = String::fruity_try_from(fields.get("name")).ok()?;
+            let enabled = bool::fruity_try_from(fields.get("enabled")).ok()?;
+            let components = if let Some(Serialized::Array(components)) = fields.get("components") {
+                components.clone()
             } else {
-                return;
+                return None;
             };
 
-            self.create_with_id(entity_id, &name, enabled, components);
+            Some(RestoredEntity {
+                entity_id,
+                guid,
+                name,
+                enabled,
+                components,
+            })
+        } else {
+            None
         }
     }
 }
@@ -492,6 +1003,54 @@ impl IntrospectObject for EntityService {
                     Ok(Some(result.fruity_into()))
                 })),
             },
+            MethodInfo {
+                name: "get_entity_by_guid".to_string(),
+                call: MethodCaller::Const(Arc::new(move |this, args| {
+                    let this = cast_introspect_ref::<EntityService>(this);
+
+                    let mut caster = ArgumentCaster::new("get_entity_by_guid", args);
+                    let arg1 = caster.cast_next::<EntityGuid>()?;
+
+                    let result = this.get_entity_by_guid(arg1);
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "get_entity_ref".to_string(),
+                call: MethodCaller::Const(Arc::new(move |this, args| {
+                    let this = cast_introspect_ref::<EntityService>(this);
+
+                    let mut caster = ArgumentCaster::new("get_entity_ref", args);
+                    let arg1 = caster.cast_next::<EntityId>()?;
+
+                    let result = this.get_entity_ref(arg1);
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "get_parent".to_string(),
+                call: MethodCaller::Const(Arc::new(move |this, args| {
+                    let this = cast_introspect_ref::<EntityService>(this);
+
+                    let mut caster = ArgumentCaster::new("get_parent", args);
+                    let arg1 = caster.cast_next::<EntityId>()?;
+
+                    let result = this.get_parent(arg1);
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "get_children".to_string(),
+                call: MethodCaller::Const(Arc::new(move |this, args| {
+                    let this = cast_introspect_ref::<EntityService>(this);
+
+                    let mut caster = ArgumentCaster::new("get_children", args);
+                    let arg1 = caster.cast_next::<EntityId>()?;
+
+                    let result = this.get_children(arg1);
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
             MethodInfo {
                 name: "query".to_string(),
                 call: MethodCaller::Const(Arc::new(move |this, _args| {
@@ -501,6 +1060,7 @@ impl IntrospectObject for EntityService {
                         archetypes: this.archetypes.clone(),
                         on_entity_created: this.on_created.clone(),
                         on_entity_deleted: this.on_deleted.clone(),
+                        last_run_tick: get_system_last_run_tick(),
                         params: vec![],
                     };
 
@@ -521,6 +1081,42 @@ impl IntrospectObject for EntityService {
                     Ok(Some(id.fruity_into()))
                 })),
             },
+            MethodInfo {
+                name: "load_additive".to_string(),
+                call: MethodCaller::Const(Arc::new(move |this, args| {
+                    let this = cast_introspect_ref::<EntityService>(this);
+
+                    let mut caster = ArgumentCaster::new("load_additive", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    let result = this.load_additive(&arg1);
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "duplicate".to_string(),
+                call: MethodCaller::Const(Arc::new(move |this, args| {
+                    let this = cast_introspect_ref::<EntityService>(this);
+
+                    let mut caster = ArgumentCaster::new("duplicate", args);
+                    let arg1 = caster.cast_next::<Vec<EntityId>>()?;
+
+                    let result = this.duplicate(&arg1);
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "unload_scene".to_string(),
+                call: MethodCaller::Const(Arc::new(move |this, args| {
+                    let this = cast_introspect_ref::<EntityService>(this);
+
+                    let mut caster = ArgumentCaster::new("unload_scene", args);
+                    let arg1 = caster.cast_next::<SceneHandle>()?;
+                    this.unload_scene(arg1);
+
+                    Ok(None)
+                })),
+            },
             MethodInfo {
                 name: "remove".to_string(),
                 call: MethodCaller::Const(Arc::new(move |this, args| {
@@ -559,3 +1155,187 @@ impl IntrospectObject for EntityService {
 }
 
 impl Resource for EntityService {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fruity_core::world::World;
+
+    fn entity_names(entity_service: &EntityService) -> Vec<String> {
+        entity_service
+            .iter_all_entities()
+            .map(|entity| entity.read().get_name())
+            .sorted()
+            .collect()
+    }
+
+    fn save_scene(entity_service: &EntityService, filepath: &str, names: &[&str]) {
+        entity_service.clear();
+        names.iter().for_each(|name| {
+            entity_service.create(name, true, vec![]);
+        });
+
+        entity_service.save_to_file(filepath).unwrap();
+        entity_service.clear();
+    }
+
+    #[test]
+    fn unload_scene_only_removes_the_scene_entities_test() {
+        let world = World::new();
+        crate::initialize(world.resource_container.clone());
+        let entity_service = world.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        let directory =
+            std::env::temp_dir().join(format!("fruity_ecs_scenes_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let scene_a_path = directory.join("scene_a.frsc").to_string_lossy().to_string();
+        let scene_b_path = directory.join("scene_b.frsc").to_string_lossy().to_string();
+        save_scene(&entity_service, &scene_a_path, &["A1", "A2"]);
+        save_scene(&entity_service, &scene_b_path, &["B1"]);
+
+        let persistent_id = entity_service.create("Persistent", true, vec![]);
+        let scene_a = entity_service.load_additive(&scene_a_path).unwrap();
+        let scene_b = entity_service.load_additive(&scene_b_path).unwrap();
+        std::fs::remove_dir_all(&directory).ok();
+
+        assert_ne!(scene_a, scene_b);
+        assert_eq!(
+            entity_names(&entity_service),
+            vec!["A1", "A2", "B1", "Persistent"]
+        );
+        assert_eq!(entity_service.get_scene_entities(scene_a).len(), 2);
+        assert_eq!(entity_service.get_scene_entities(scene_b).len(), 1);
+        assert_eq!(entity_service.get_entity_scene(persistent_id), None);
+
+        entity_service.unload_scene(scene_a);
+        assert_eq!(entity_names(&entity_service), vec!["B1", "Persistent"]);
+        assert!(entity_service.get_scene_entities(scene_a).is_empty());
+        assert_eq!(entity_service.get_scene_entities(scene_b).len(), 1);
+    }
+
+    #[test]
+    fn restore_additive_gives_new_ids_to_the_used_ones_test() {
+        let world = World::new();
+        crate::initialize(world.resource_container.clone());
+        let entity_service = world.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        entity_service.create("Scene", true, vec![]);
+        let snapshot = entity_service.snapshot();
+
+        // The same scene is loaded twice over the existing entity
+        let scene_a = entity_service.restore_additive(&snapshot);
+        let scene_b = entity_service.restore_additive(&snapshot);
+        let scene_a_entities = entity_service.get_scene_entities(scene_a);
+        let scene_b_entities = entity_service.get_scene_entities(scene_b);
+
+        assert_eq!(
+            entity_names(&entity_service),
+            vec!["Scene", "Scene", "Scene"]
+        );
+        assert_eq!(scene_a_entities.len(), 1);
+        assert_eq!(scene_b_entities.len(), 1);
+        assert_ne!(scene_a_entities, scene_b_entities);
+
+        entity_service.unload_scene(scene_b);
+        assert_eq!(entity_names(&entity_service), vec!["Scene", "Scene"]);
+        assert_eq!(entity_service.get_scene_entities(scene_a), scene_a_entities);
+    }
+
+    // Get the schema version and the speed of the "Move" script component of a snapshot
+    fn saved_move_component(snapshot: &EntityServiceSnapshot) -> (u32, f32) {
+        let entities = Vec::<Serialized>::fruity_try_from(snapshot.0.clone()).unwrap();
+        let mut fields = match &entities[0] {
+            Serialized::SerializedObject { fields, .. } => fields.clone(),
+            _ => panic!("The entity should be an object"),
+        };
+        let components =
+            Vec::<Serialized>::fruity_try_from(fields.remove("components").unwrap()).unwrap();
+
+        match &components[0] {
+            Serialized::SerializedObject { fields, .. } => (
+                u32::fruity_try_from(fields.get(SCHEMA_VERSION_FIELD).unwrap().clone()).unwrap(),
+                f32::fruity_try_from(fields.get("speed").unwrap().clone()).unwrap(),
+            ),
+            _ => panic!("The component should be an object"),
+        }
+    }
+
+    #[test]
+    fn script_components_are_migrated_once_test() {
+        let world = World::new();
+        crate::initialize(world.resource_container.clone());
+        let entity_service = world.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        // The version 2 stores the speed in meters instead of centimeters
+        let migrations = Arc::new(AtomicU64::new(0));
+        {
+            let migrations = migrations.clone();
+            let component_schema_service =
+                world.resource_container.require::<ComponentSchemaService>();
+            let mut component_schema_service = component_schema_service.write();
+            component_schema_service.register_script_component("Move", 2);
+            component_schema_service.register_migration("Move", 1, move |mut fields| {
+                migrations.fetch_add(1, Ordering::Relaxed);
+                let speed = f32::fruity_try_from(fields.remove("speed").unwrap()).unwrap();
+                fields.insert("speed".to_string(), Serialized::F32(speed / 100.0));
+                fields
+            });
+        }
+
+        // A component created by the script at runtime is saved with the registered version
+        entity_service.create(
+            "Runtime",
+            true,
+            vec![AnyComponent::new(SerializedComponent::new(
+                "Move".to_string(),
+                hashmap! { "speed".to_string() => Serialized::F32(2.0) },
+            ))],
+        );
+        assert_eq!(saved_move_component(&entity_service.snapshot()), (2, 2.0));
+
+        // A component saved with the version 1 is migrated once, then saved with the version 2
+        entity_service.clear();
+        entity_service.create(
+            "Saved",
+            true,
+            vec![AnyComponent::new(SerializedComponent::new(
+                "Move".to_string(),
+                hashmap! {
+                    SCHEMA_VERSION_FIELD.to_string() => Serialized::U32(1),
+                    "speed".to_string() => Serialized::F32(200.0),
+                },
+            ))],
+        );
+        let snapshot = entity_service.snapshot();
+        assert_eq!(saved_move_component(&snapshot), (1, 200.0));
+
+        entity_service.restore(&snapshot);
+        let snapshot = entity_service.snapshot();
+        assert_eq!(saved_move_component(&snapshot), (2, 2.0));
+
+        entity_service.restore(&snapshot);
+        assert_eq!(saved_move_component(&entity_service.snapshot()), (2, 2.0));
+        assert_eq!(migrations.load(Ordering::Relaxed), 1);
+
+        // A component saved with a newer version keeps it
+        entity_service.clear();
+        entity_service.create(
+            "Newer",
+            true,
+            vec![AnyComponent::new(SerializedComponent::new(
+                "Move".to_string(),
+                hashmap! {
+                    SCHEMA_VERSION_FIELD.to_string() => Serialized::U32(3),
+                    "speed".to_string() => Serialized::F32(1.0),
+                },
+            ))],
+        );
+        let snapshot = entity_service.snapshot();
+        entity_service.restore(&snapshot);
+        assert_eq!(saved_move_component(&entity_service.snapshot()), (3, 1.0));
+        assert_eq!(migrations.load(Ordering::Relaxed), 1);
+    }
+}
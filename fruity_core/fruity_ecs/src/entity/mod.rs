@@ -7,6 +7,9 @@ pub mod entity_query;
 /// Provides a reference to an entity
 pub mod entity_reference;
 
+/// Provides a reference to an entity that can be stored into a component and persisted
+pub mod entity_ref;
+
 /// Provides guards for an entity
 pub mod entity_guard;
 
@@ -15,3 +18,9 @@ pub mod entity_service;
 
 /// Provides a collections to store entities
 pub mod archetype;
+
+/// Provides a diff and a three way merge between entity snapshots
+pub mod snapshot_diff;
+
+/// Provides an index over the parent/children relations between entities
+pub mod entity_hierarchy;
@@ -0,0 +1,911 @@
+use crate::entity::entity::EntityGuid;
+use crate::entity::entity::EntityId;
+use crate::entity::entity_service::EntityServiceSnapshot;
+use fruity_core::convert::FruityInto;
+use fruity_core::convert::FruityTryFrom;
+use fruity_core::serialize::serialized::Serialized;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+/// Identify a component inside an entity
+///
+/// An entity can store many components of the same class, they are identified by their
+/// position between the components that share this class
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ComponentKey {
+    /// The component class name
+    pub class_name: String,
+
+    /// The position of the component between the components of the same class
+    pub index: usize,
+}
+
+/// A location in a snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotPath {
+    /// The entity id
+    pub entity_id: EntityId,
+
+    /// The entity guid, none if the entity has been saved without guid
+    pub guid: Option<EntityGuid>,
+
+    /// The component, none if the path targets the entity itself
+    pub component: Option<ComponentKey>,
+
+    /// The field, none if the path targets the whole entity or component
+    pub field: Option<String>,
+}
+
+/// A change over a field of an entity or of a component
+#[derive(Debug, Clone)]
+pub struct FieldDiff {
+    /// The field name
+    pub name: String,
+
+    /// The value before the change, none if the field has been added
+    pub old_value: Option<Serialized>,
+
+    /// The value after the change, none if the field has been removed
+    pub new_value: Option<Serialized>,
+}
+
+/// The changes over the fields of a component
+#[derive(Debug, Clone)]
+pub struct ComponentDiff {
+    /// The modified component
+    pub component: ComponentKey,
+
+    /// The modified fields
+    pub fields: Vec<FieldDiff>,
+}
+
+/// The changes over an entity
+#[derive(Debug, Clone)]
+pub struct EntityDiff {
+    /// The entity id
+    pub entity_id: EntityId,
+
+    /// The entity guid, none if the entity has been saved without guid
+    pub guid: Option<EntityGuid>,
+
+    /// The modified entity properties, like the name or the enabled state
+    pub properties: Vec<FieldDiff>,
+
+    /// The components that have been added
+    pub added_components: Vec<ComponentKey>,
+
+    /// The components that have been removed
+    pub removed_components: Vec<ComponentKey>,
+
+    /// The components that have been modified
+    pub modified_components: Vec<ComponentDiff>,
+}
+
+/// The differences between two [’EntityServiceSnapshot’]
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotDiff {
+    /// The entities that have been added
+    pub added_entities: Vec<EntityId>,
+
+    /// The entities that have been removed
+    pub removed_entities: Vec<EntityId>,
+
+    /// The entities that have been modified
+    pub modified_entities: Vec<EntityDiff>,
+}
+
+impl SnapshotDiff {
+    /// Returns true if both snapshots are the same
+    pub fn is_empty(&self) -> bool {
+        self.added_entities.is_empty()
+            && self.removed_entities.is_empty()
+            && self.modified_entities.is_empty()
+    }
+}
+
+/// The kind of a merge conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeConflictKind {
+    /// Both sides have modified the same value differently
+    BothModified,
+
+    /// Both sides have added a value at the same location with different contents
+    BothAdded,
+
+    /// A side have modified a value that the other side have removed
+    ModifiedAndRemoved,
+}
+
+/// A conflict that prevents a three way merge to complete
+#[derive(Debug, Clone)]
+pub struct MergeConflict {
+    /// The location of the conflict
+    pub path: SnapshotPath,
+
+    /// The kind of conflict
+    pub kind: MergeConflictKind,
+
+    /// The value in the common ancestor
+    pub base: Option<Serialized>,
+
+    /// The value in our side
+    pub ours: Option<Serialized>,
+
+    /// The value in their side
+    pub theirs: Option<Serialized>,
+}
+
+impl EntityServiceSnapshot {
+    /// Compute the changes that turn this snapshot into an other one
+    ///
+    /// # Arguments
+    /// * `other` - The snapshot after the changes
+    ///
+    pub fn diff(&self, other: &EntityServiceSnapshot) -> SnapshotDiff {
+        let old_entities = parse_snapshot(self);
+        let new_entities = parse_snapshot(other);
+
+        let mut result = SnapshotDiff::default();
+        for entity_key in union_keys(&old_entities, &new_entities) {
+            match (old_entities.get(&entity_key), new_entities.get(&entity_key)) {
+                (None, Some(new_entity)) => result.added_entities.push(new_entity.entity_id),
+                (Some(old_entity), None) => result.removed_entities.push(old_entity.entity_id),
+                (Some(old_entity), Some(new_entity)) => {
+                    let entity_diff = diff_entity(old_entity, new_entity);
+
+                    if !entity_diff.properties.is_empty()
+                        || !entity_diff.added_components.is_empty()
+                        || !entity_diff.removed_components.is_empty()
+                        || !entity_diff.modified_components.is_empty()
+                    {
+                        result.modified_entities.push(entity_diff);
+                    }
+                }
+                (None, None) => (),
+            }
+        }
+
+        result
+    }
+
+    /// Merge the changes of two snapshots that share a common ancestor
+    ///
+    /// Entities, components and fields are merged separately, so both sides can modify a same
+    /// entity without conflicting as long as they don't touch the same values
+    /// The entities are matched by guid, so both sides can add entities that got the same id,
+    /// the ids that are used twice in the result are replaced
+    ///
+    /// # Arguments
+    /// * `base` - The common ancestor
+    /// * `ours` - Our side
+    /// * `theirs` - Their side
+    ///
+    pub fn merge(
+        base: &EntityServiceSnapshot,
+        ours: &EntityServiceSnapshot,
+        theirs: &EntityServiceSnapshot,
+    ) -> Result<EntityServiceSnapshot, Vec<MergeConflict>> {
+        let base = parse_snapshot(base);
+        let ours = parse_snapshot(ours);
+        let theirs = parse_snapshot(theirs);
+
+        let mut conflicts = Vec::new();
+        let entities = merge_maps(
+            &base,
+            &ours,
+            &theirs,
+            &|entity_key| {
+                let entity = ours
+                    .get(entity_key)
+                    .or_else(|| theirs.get(entity_key))
+                    .or_else(|| base.get(entity_key));
+
+                SnapshotPath {
+                    entity_id: entity.map(|entity| entity.entity_id).unwrap_or_default(),
+                    guid: entity.and_then(|entity| entity.guid),
+                    component: None,
+                    field: None,
+                }
+            },
+            &mut conflicts,
+        );
+
+        if conflicts.is_empty() {
+            Ok(build_snapshot(&entities))
+        } else {
+            Err(conflicts)
+        }
+    }
+}
+
+// The entities are identified by their guid, the runtime id is only used for the entities that
+// have been saved without guid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum EntityKey {
+    Guid(EntityGuid),
+    Id(EntityId),
+}
+
+#[derive(Clone)]
+struct EntityModel {
+    entity_id: EntityId,
+    guid: Option<EntityGuid>,
+    properties: BTreeMap<String, Serialized>,
+    components: BTreeMap<ComponentKey, ComponentModel>,
+}
+
+#[derive(Clone)]
+struct ComponentModel {
+    class_name: String,
+    // The position of the component in the entity, the components are written back in this order
+    position: usize,
+    fields: BTreeMap<String, Serialized>,
+}
+
+impl EntityModel {
+    fn key(&self) -> EntityKey {
+        match self.guid {
+            Some(guid) => EntityKey::Guid(guid),
+            None => EntityKey::Id(self.entity_id),
+        }
+    }
+}
+
+fn parse_snapshot(snapshot: &EntityServiceSnapshot) -> BTreeMap<EntityKey, EntityModel> {
+    let mut entities = BTreeMap::new();
+
+    if let Serialized::Array(serialized_entities) = &snapshot.0 {
+        for serialized_entity in serialized_entities {
+            if let Some(entity) = parse_entity(serialized_entity) {
+                entities.insert(entity.key(), entity);
+            }
+        }
+    }
+
+    entities
+}
+
+fn parse_entity(serialized_entity: &Serialized) -> Option<EntityModel> {
+    let fields = if let Serialized::SerializedObject { fields, .. } = serialized_entity {
+        fields
+    } else {
+        return None;
+    };
+
+    let entity_id = EntityId::fruity_try_from(fields.get("entity_id")?.clone()).ok()?;
+    let guid = fields
+        .get("guid")
+        .and_then(|guid| EntityGuid::fruity_try_from(guid.clone()).ok())
+        .filter(|guid| !guid.is_nil());
+
+    let mut components = BTreeMap::new();
+    if let Some(Serialized::Array(serialized_components)) = fields.get("components") {
+        for (position, serialized_component) in serialized_components.iter().enumerate() {
+            if let Serialized::SerializedObject { class_name, fields } = serialized_component {
+                let index = components
+                    .keys()
+                    .filter(|key: &&ComponentKey| &key.class_name == class_name)
+                    .count();
+
+                components.insert(
+                    ComponentKey {
+                        class_name: class_name.clone(),
+                        index,
+                    },
+                    ComponentModel {
+                        class_name: class_name.clone(),
+                        position,
+                        fields: parse_fields(fields),
+                    },
+                );
+            }
+        }
+    }
+
+    let mut properties = parse_fields(fields);
+    properties.remove("entity_id");
+    properties.remove("guid");
+    properties.remove("components");
+
+    Some(EntityModel {
+        entity_id,
+        guid,
+        properties,
+        components,
+    })
+}
+
+// Null fields are ignored, the yaml format don't keep them
+fn parse_fields(fields: &HashMap<String, Serialized>) -> BTreeMap<String, Serialized> {
+    fields
+        .iter()
+        .filter(|(_, value)| !matches!(value, Serialized::Null))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+fn build_snapshot(entities: &BTreeMap<EntityKey, EntityModel>) -> EntityServiceSnapshot {
+    let mut entities = entities.values().cloned().collect::<Vec<_>>();
+    entities.sort_by_key(|entity| (entity.entity_id, entity.key()));
+
+    // Entities added on both sides can share an id, the entity references are stored with the
+    // guids so the ids can be replaced
+    let mut next_entity_id = entities
+        .iter()
+        .map(|entity| entity.entity_id + 1)
+        .max()
+        .unwrap_or_default();
+    let mut used_entity_ids = BTreeSet::new();
+    for entity in entities.iter_mut() {
+        if !used_entity_ids.insert(entity.entity_id) {
+            entity.entity_id = next_entity_id;
+            next_entity_id += 1;
+        }
+    }
+
+    EntityServiceSnapshot(Serialized::Array(
+        entities.iter().map(|entity| entity.serialize()).collect(),
+    ))
+}
+
+fn union_keys<K: Ord + Clone, A, B>(first: &BTreeMap<K, A>, second: &BTreeMap<K, B>) -> Vec<K> {
+    first
+        .keys()
+        .chain(second.keys())
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn diff_fields(
+    old_fields: &BTreeMap<String, Serialized>,
+    new_fields: &BTreeMap<String, Serialized>,
+) -> Vec<FieldDiff> {
+    union_keys(old_fields, new_fields)
+        .into_iter()
+        .filter_map(|name| {
+            let old_value = old_fields.get(&name);
+            let new_value = new_fields.get(&name);
+
+            let unchanged = match (old_value, new_value) {
+                (Some(old_value), Some(new_value)) => old_value.equals(new_value),
+                _ => false,
+            };
+
+            if unchanged {
+                None
+            } else {
+                Some(FieldDiff {
+                    name,
+                    old_value: old_value.cloned(),
+                    new_value: new_value.cloned(),
+                })
+            }
+        })
+        .collect()
+}
+
+fn diff_entity(old_entity: &EntityModel, new_entity: &EntityModel) -> EntityDiff {
+    let mut result = EntityDiff {
+        entity_id: new_entity.entity_id,
+        guid: new_entity.guid,
+        properties: diff_fields(&old_entity.properties, &new_entity.properties),
+        added_components: Vec::new(),
+        removed_components: Vec::new(),
+        modified_components: Vec::new(),
+    };
+
+    for component in union_keys(&old_entity.components, &new_entity.components) {
+        match (
+            old_entity.components.get(&component),
+            new_entity.components.get(&component),
+        ) {
+            (None, Some(_)) => result.added_components.push(component),
+            (Some(_), None) => result.removed_components.push(component),
+            (Some(old_component), Some(new_component)) => {
+                let fields = diff_fields(&old_component.fields, &new_component.fields);
+
+                if !fields.is_empty() {
+                    result
+                        .modified_components
+                        .push(ComponentDiff { component, fields });
+                }
+            }
+            (None, None) => (),
+        }
+    }
+
+    result
+}
+
+trait Mergeable: Clone {
+    fn equals(&self, other: &Self) -> bool;
+
+    fn serialize(&self) -> Serialized;
+
+    // Called when both sides have changed the value differently
+    fn merge(
+        base: Option<&Self>,
+        ours: &Self,
+        theirs: &Self,
+        path: &SnapshotPath,
+        conflicts: &mut Vec<MergeConflict>,
+    ) -> Self;
+}
+
+fn merge_maps<K: Ord + Clone, V: Mergeable>(
+    base: &BTreeMap<K, V>,
+    ours: &BTreeMap<K, V>,
+    theirs: &BTreeMap<K, V>,
+    path: &dyn Fn(&K) -> SnapshotPath,
+    conflicts: &mut Vec<MergeConflict>,
+) -> BTreeMap<K, V> {
+    let mut keys = union_keys(ours, theirs);
+    keys.extend(base.keys().cloned());
+    keys.sort();
+    keys.dedup();
+
+    let mut result = BTreeMap::new();
+    for key in keys {
+        let base_value = base.get(&key);
+        let merged = match (base_value, ours.get(&key), theirs.get(&key)) {
+            (_, None, None) => None,
+            (None, Some(value), None) | (None, None, Some(value)) => Some(value.clone()),
+            (Some(base_value), Some(value), None) | (Some(base_value), None, Some(value)) => {
+                if !value.equals(base_value) {
+                    conflicts.push(MergeConflict {
+                        path: path(&key),
+                        kind: MergeConflictKind::ModifiedAndRemoved,
+                        base: Some(base_value.serialize()),
+                        ours: ours.get(&key).map(|value| value.serialize()),
+                        theirs: theirs.get(&key).map(|value| value.serialize()),
+                    });
+                }
+
+                None
+            }
+            (base_value, Some(our_value), Some(their_value)) => {
+                if our_value.equals(their_value) {
+                    Some(our_value.clone())
+                } else if matches!(base_value, Some(base_value) if our_value.equals(base_value)) {
+                    Some(their_value.clone())
+                } else if matches!(base_value, Some(base_value) if their_value.equals(base_value)) {
+                    Some(our_value.clone())
+                } else {
+                    Some(V::merge(
+                        base_value,
+                        our_value,
+                        their_value,
+                        &path(&key),
+                        conflicts,
+                    ))
+                }
+            }
+        };
+
+        if let Some(merged) = merged {
+            result.insert(key, merged);
+        }
+    }
+
+    result
+}
+
+impl Mergeable for Serialized {
+    fn equals(&self, other: &Self) -> bool {
+        serialized_equals(self, other)
+    }
+
+    fn serialize(&self) -> Serialized {
+        self.clone()
+    }
+
+    fn merge(
+        base: Option<&Self>,
+        ours: &Self,
+        theirs: &Self,
+        path: &SnapshotPath,
+        conflicts: &mut Vec<MergeConflict>,
+    ) -> Self {
+        conflicts.push(MergeConflict {
+            path: path.clone(),
+            kind: if base.is_some() {
+                MergeConflictKind::BothModified
+            } else {
+                MergeConflictKind::BothAdded
+            },
+            base: base.cloned(),
+            ours: Some(ours.clone()),
+            theirs: Some(theirs.clone()),
+        });
+
+        ours.clone()
+    }
+}
+
+impl Mergeable for ComponentModel {
+    fn equals(&self, other: &Self) -> bool {
+        diff_fields(&self.fields, &other.fields).is_empty()
+    }
+
+    fn serialize(&self) -> Serialized {
+        Serialized::SerializedObject {
+            class_name: self.class_name.clone(),
+            fields: self
+                .fields
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        }
+    }
+
+    fn merge(
+        base: Option<&Self>,
+        ours: &Self,
+        theirs: &Self,
+        path: &SnapshotPath,
+        conflicts: &mut Vec<MergeConflict>,
+    ) -> Self {
+        let empty = BTreeMap::new();
+
+        ComponentModel {
+            class_name: ours.class_name.clone(),
+            position: ours.position,
+            fields: merge_maps(
+                base.map_or(&empty, |base| &base.fields),
+                &ours.fields,
+                &theirs.fields,
+                &|field| SnapshotPath {
+                    field: Some(field.clone()),
+                    ..path.clone()
+                },
+                conflicts,
+            ),
+        }
+    }
+}
+
+impl Mergeable for EntityModel {
+    fn equals(&self, other: &Self) -> bool {
+        let diff = diff_entity(self, other);
+
+        diff.properties.is_empty()
+            && diff.added_components.is_empty()
+            && diff.removed_components.is_empty()
+            && diff.modified_components.is_empty()
+    }
+
+    fn serialize(&self) -> Serialized {
+        let mut fields = self
+            .properties
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<HashMap<_, _>>();
+
+        // The components keep their original order, the ones added by a merge may share a
+        // position with an other component so the key order decides between them
+        let mut components = self.components.iter().collect::<Vec<_>>();
+        components.sort_by_key(|(key, component)| (component.position, *key));
+
+        fields.insert("entity_id".to_string(), Serialized::U64(self.entity_id));
+        if let Some(guid) = self.guid {
+            fields.insert("guid".to_string(), guid.fruity_into());
+        }
+        fields.insert(
+            "components".to_string(),
+            Serialized::Array(
+                components
+                    .into_iter()
+                    .map(|(_, component)| component.serialize())
+                    .collect(),
+            ),
+        );
+
+        Serialized::SerializedObject {
+            class_name: "Entity".to_string(),
+            fields,
+        }
+    }
+
+    fn merge(
+        base: Option<&Self>,
+        ours: &Self,
+        theirs: &Self,
+        path: &SnapshotPath,
+        conflicts: &mut Vec<MergeConflict>,
+    ) -> Self {
+        let empty_properties = BTreeMap::new();
+        let empty_components = BTreeMap::new();
+
+        EntityModel {
+            entity_id: ours.entity_id,
+            guid: ours.guid,
+            properties: merge_maps(
+                base.map_or(&empty_properties, |base| &base.properties),
+                &ours.properties,
+                &theirs.properties,
+                &|field| SnapshotPath {
+                    field: Some(field.clone()),
+                    ..path.clone()
+                },
+                conflicts,
+            ),
+            components: merge_maps(
+                base.map_or(&empty_components, |base| &base.components),
+                &ours.components,
+                &theirs.components,
+                &|component| SnapshotPath {
+                    component: Some(component.clone()),
+                    ..path.clone()
+                },
+                conflicts,
+            ),
+        }
+    }
+}
+
+/// Compare two serialized values
+///
+/// Numbers are compared by value and null fields are ignored, so that a value still equals
+/// itself after a trip through a yaml file
+///
+/// # Arguments
+/// * `first` - The first value
+/// * `second` - The second value
+///
+pub fn serialized_equals(first: &Serialized, second: &Serialized) -> bool {
+    match (first, second) {
+        (Serialized::Bool(first), Serialized::Bool(second)) => first == second,
+        (Serialized::String(first), Serialized::String(second)) => first == second,
+        (Serialized::Null, Serialized::Null) => true,
+        (Serialized::Array(first), Serialized::Array(second)) => {
+            first.len() == second.len()
+                && first
+                    .iter()
+                    .zip(second.iter())
+                    .all(|(first, second)| serialized_equals(first, second))
+        }
+        (
+            Serialized::SerializedObject {
+                class_name: first_class_name,
+                fields: first_fields,
+            },
+            Serialized::SerializedObject {
+                class_name: second_class_name,
+                fields: second_fields,
+            },
+        ) => {
+            first_class_name == second_class_name
+                && diff_fields(&parse_fields(first_fields), &parse_fields(second_fields)).is_empty()
+        }
+        (first, second) => match (as_integer(first), as_integer(second)) {
+            (Some(first), Some(second)) => first == second,
+            _ => match (as_float(first), as_float(second)) {
+                (Some(first), Some(second)) => first == second,
+                _ => false,
+            },
+        },
+    }
+}
+
+fn as_integer(value: &Serialized) -> Option<i128> {
+    match value {
+        Serialized::I8(value) => Some(*value as i128),
+        Serialized::I16(value) => Some(*value as i128),
+        Serialized::I32(value) => Some(*value as i128),
+        Serialized::I64(value) => Some(*value as i128),
+        Serialized::ISize(value) => Some(*value as i128),
+        Serialized::U8(value) => Some(*value as i128),
+        Serialized::U16(value) => Some(*value as i128),
+        Serialized::U32(value) => Some(*value as i128),
+        Serialized::U64(value) => Some(*value as i128),
+        Serialized::USize(value) => Some(*value as i128),
+        _ => None,
+    }
+}
+
+fn as_float(value: &Serialized) -> Option<f64> {
+    match value {
+        // Go through the decimal representation like the yaml writer does
+        Serialized::F32(value) => value.to_string().parse::<f64>().ok(),
+        Serialized::F64(value) => Some(*value),
+        value => as_integer(value).map(|value| value as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::entity::EntityGuid;
+    use crate::entity::entity_service::EntityServiceSnapshot;
+    use crate::entity::snapshot_diff::ComponentKey;
+    use crate::entity::snapshot_diff::MergeConflictKind;
+    use fruity_core::convert::FruityInto;
+    use fruity_core::serialize::serialized::Serialized;
+    use maplit::hashmap;
+
+    fn entity(entity_id: u64, name: &str, x: f32) -> Serialized {
+        Serialized::SerializedObject {
+            class_name: "Entity".to_string(),
+            fields: hashmap! {
+                "entity_id".to_string() => Serialized::U64(entity_id),
+                "name".to_string() => Serialized::String(name.to_string()),
+                "enabled".to_string() => Serialized::Bool(true),
+                "components".to_string() => Serialized::Array(vec![Serialized::SerializedObject {
+                    class_name: "Position".to_string(),
+                    fields: hashmap! {
+                        "x".to_string() => Serialized::F32(x),
+                        "y".to_string() => Serialized::F32(0.0),
+                    },
+                }]),
+            },
+        }
+    }
+
+    fn with_guid(entity: Serialized, guid: u128) -> Serialized {
+        match entity {
+            Serialized::SerializedObject {
+                class_name,
+                mut fields,
+            } => {
+                fields.insert("guid".to_string(), EntityGuid(guid).fruity_into());
+                Serialized::SerializedObject { class_name, fields }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn snapshot(entities: Vec<Serialized>) -> EntityServiceSnapshot {
+        EntityServiceSnapshot(Serialized::Array(entities))
+    }
+
+    #[test]
+    fn snapshot_diff_test() {
+        let old = snapshot(vec![entity(1, "a", 0.0), entity(2, "b", 0.0)]);
+        let new = snapshot(vec![entity(1, "a", 1.5), entity(3, "c", 0.0)]);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added_entities, vec![3]);
+        assert_eq!(diff.removed_entities, vec![2]);
+        assert_eq!(diff.modified_entities.len(), 1);
+
+        let entity_diff = &diff.modified_entities[0];
+        assert_eq!(entity_diff.entity_id, 1);
+        assert!(entity_diff.properties.is_empty());
+        assert_eq!(
+            entity_diff.modified_components[0].component,
+            ComponentKey {
+                class_name: "Position".to_string(),
+                index: 0
+            }
+        );
+        assert_eq!(entity_diff.modified_components[0].fields[0].name, "x");
+
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn snapshot_merge_test() {
+        let base = snapshot(vec![entity(1, "a", 0.0), entity(2, "b", 0.0)]);
+        let ours = snapshot(vec![entity(1, "renamed", 0.0), entity(2, "b", 0.0)]);
+        let theirs = snapshot(vec![entity(1, "a", 2.0)]);
+
+        let merged = EntityServiceSnapshot::merge(&base, &ours, &theirs).unwrap();
+        let expected = snapshot(vec![entity(1, "renamed", 2.0)]);
+        assert!(merged.diff(&expected).is_empty());
+    }
+
+    #[test]
+    fn snapshot_merge_conflict_test() {
+        let base = snapshot(vec![entity(1, "a", 0.0), entity(2, "b", 0.0)]);
+        let ours = snapshot(vec![entity(1, "a", 1.0)]);
+        let theirs = snapshot(vec![entity(1, "a", 2.0), entity(2, "b", 3.0)]);
+
+        let conflicts = EntityServiceSnapshot::merge(&base, &ours, &theirs).unwrap_err();
+        assert_eq!(conflicts.len(), 2);
+
+        assert_eq!(conflicts[0].path.entity_id, 1);
+        assert_eq!(conflicts[0].path.field, Some("x".to_string()));
+        assert_eq!(conflicts[0].kind, MergeConflictKind::BothModified);
+
+        assert_eq!(conflicts[1].path.entity_id, 2);
+        assert_eq!(conflicts[1].path.component, None);
+        assert_eq!(conflicts[1].kind, MergeConflictKind::ModifiedAndRemoved);
+    }
+
+    #[test]
+    fn snapshot_merge_entities_added_on_both_sides_test() {
+        let base = snapshot(vec![with_guid(entity(1, "a", 0.0), 1)]);
+        let ours = snapshot(vec![
+            with_guid(entity(1, "a", 0.0), 1),
+            with_guid(entity(2, "b", 0.0), 2),
+        ]);
+        let theirs = snapshot(vec![
+            with_guid(entity(1, "a", 0.0), 1),
+            with_guid(entity(2, "c", 1.0), 3),
+        ]);
+
+        // The entities are matched by guid, the new ones are not compared to each other
+        let diff = ours.diff(&theirs);
+        assert_eq!(diff.added_entities, vec![2]);
+        assert_eq!(diff.removed_entities, vec![2]);
+        assert!(diff.modified_entities.is_empty());
+
+        let merged = EntityServiceSnapshot::merge(&base, &ours, &theirs).unwrap();
+        let expected = snapshot(vec![
+            with_guid(entity(1, "a", 0.0), 1),
+            with_guid(entity(2, "b", 0.0), 2),
+            with_guid(entity(3, "c", 1.0), 3),
+        ]);
+        assert!(merged.diff(&expected).is_empty());
+
+        // Both added entities had the same id, the one of their side got a new id
+        let entity_ids = match &merged.0 {
+            Serialized::Array(entities) => entities
+                .iter()
+                .map(|entity| match entity {
+                    Serialized::SerializedObject { fields, .. } => fields["entity_id"].clone(),
+                    _ => unreachable!(),
+                })
+                .map(|entity_id| match entity_id {
+                    Serialized::U64(entity_id) => entity_id,
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>(),
+            _ => unreachable!(),
+        };
+        assert_eq!(entity_ids, vec![1, 2, 3]);
+
+        // The guid is not a property, an entity that got an other id is not modified
+        let reloaded = snapshot(vec![
+            with_guid(entity(5, "a", 0.0), 1),
+            with_guid(entity(6, "b", 0.0), 2),
+        ]);
+        assert!(ours.diff(&reloaded).is_empty());
+    }
+
+    #[test]
+    fn snapshot_merge_keeps_the_component_order_test() {
+        let component = |class_name: &str| Serialized::SerializedObject {
+            class_name: class_name.to_string(),
+            fields: hashmap! {
+                "value".to_string() => Serialized::U32(0),
+            },
+        };
+        let entity = Serialized::SerializedObject {
+            class_name: "Entity".to_string(),
+            fields: hashmap! {
+                "entity_id".to_string() => Serialized::U64(1),
+                "components".to_string() => Serialized::Array(vec![
+                    component("Transform2d"),
+                    component("Sprite"),
+                    component("Camera"),
+                ]),
+            },
+        };
+
+        let base = snapshot(vec![entity]);
+        let merged = EntityServiceSnapshot::merge(&base, &base, &base).unwrap();
+
+        let components = match &merged.0 {
+            Serialized::Array(entities) => match &entities[0] {
+                Serialized::SerializedObject { fields, .. } => fields["components"].clone(),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        let class_names = match components {
+            Serialized::Array(components) => components
+                .into_iter()
+                .map(|component| match component {
+                    Serialized::SerializedObject { class_name, .. } => class_name,
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>(),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(class_names, vec!["Transform2d", "Sprite", "Camera"]);
+    }
+}
@@ -0,0 +1,291 @@
+use crate::system::system_service::get_running_system_identifier;
+use crate::system::system_service::SystemParams;
+use crate::system::system_service::SystemService;
+use fruity_any::*;
+use fruity_core::convert::FruityInto;
+use fruity_core::convert::FruityTryFrom;
+use fruity_core::inject::Const;
+use fruity_core::inject::Inject1;
+use fruity_core::inject::InjectAccess;
+use fruity_core::inject::Injectable;
+use fruity_core::inject::Mut;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodCaller;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::resource::resource::Resource;
+use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::serialize::serialized::Serialized;
+use fruity_core::utils::introspect::cast_introspect_mut;
+use fruity_core::utils::introspect::cast_introspect_ref;
+use fruity_core::utils::introspect::ArgumentCaster;
+use fruity_core::Mutex;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// The reader used when events are read outside of a system
+pub const DEFAULT_EVENT_READER: &str = "default";
+
+/// A type that can be sent through an events queue, it should be convertible from and to
+/// [‘Serialized‘] to be sent and read by the scripts
+pub trait Event:
+    FruityInto<Serialized> + FruityTryFrom<Serialized> + Clone + Send + Sync + 'static
+{
+}
+
+impl<T: FruityInto<Serialized> + FruityTryFrom<Serialized> + Clone + Send + Sync + 'static> Event
+    for T
+{
+}
+
+/// A queue of typed events, used to communicate between systems and scripts
+///
+/// The queue is double buffered, an event is kept during the frame it has been sent and the
+/// following one, so every system that runs once per frame can read it whatever the order of the
+/// systems is
+///
+/// Every reader has its own cursor, so an event is read only once by a reader but can be read by
+/// many readers
+///
+#[derive(FruityAny)]
+pub struct Events<T: Send + Sync + 'static> {
+    previous_events: Vec<(usize, T)>,
+    current_events: Vec<(usize, T)>,
+    event_count: usize,
+    cursors: Mutex<HashMap<String, usize>>,
+}
+
+impl<T: Send + Sync> Debug for Events<T> {
+    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        Ok(())
+    }
+}
+
+impl<T: Send + Sync> Default for Events<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Send + Sync> Events<T> {
+    /// Returns an empty Events queue
+    pub fn new() -> Events<T> {
+        Events {
+            previous_events: Vec::new(),
+            current_events: Vec::new(),
+            event_count: 0,
+            cursors: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Send an event
+    ///
+    /// # Arguments
+    /// * `event` - The event
+    ///
+    pub fn send(&mut self, event: T) {
+        self.current_events.push((self.event_count, event));
+        self.event_count += 1;
+    }
+
+    /// Swap the event buffers, the events sent before the previous update are dropped
+    /// This is called once per frame by the system added with [‘add_events‘]
+    pub fn update(&mut self) {
+        self.previous_events = std::mem::take(&mut self.current_events);
+    }
+
+    /// Drop all the stored events
+    pub fn clear(&mut self) {
+        self.previous_events.clear();
+        self.current_events.clear();
+    }
+
+    /// Get the number of stored events
+    pub fn len(&self) -> usize {
+        self.previous_events.len() + self.current_events.len()
+    }
+
+    /// Is there no stored event
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over all the stored events, whithout moving any reader cursor
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.previous_events
+            .iter()
+            .chain(self.current_events.iter())
+            .map(|(_, event)| event)
+    }
+
+    /// Iterate over the events that have not been read yet by a reader and move its cursor
+    /// A new reader reads all the stored events
+    ///
+    /// # Arguments
+    /// * `reader` - The reader identifier
+    ///
+    pub fn read(&self, reader: &str) -> impl Iterator<Item = &T> {
+        let cursor = {
+            let mut cursors = self.cursors.lock();
+            let cursor = cursors.entry(reader.to_string()).or_insert(0);
+            std::mem::replace(cursor, self.event_count)
+        };
+
+        self.previous_events
+            .iter()
+            .chain(self.current_events.iter())
+            .filter(move |(event_id, _)| *event_id >= cursor)
+            .map(|(_, event)| event)
+    }
+}
+
+impl<T: Event> IntrospectObject for Events<T> {
+    fn get_class_name(&self) -> String {
+        "Events".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![
+            MethodInfo {
+                name: "send".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<Events<T>>(this);
+
+                    let mut caster = ArgumentCaster::new("send", args);
+                    let arg1 = caster.cast_next::<T>()?;
+
+                    this.send(arg1);
+
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "read".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<Events<T>>(this);
+
+                    let mut caster = ArgumentCaster::new("read", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    let result = this.read(&arg1).cloned().collect::<Vec<_>>();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "len".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<Events<T>>(this);
+                    let result = this.len();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "clear".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, _args| {
+                    let this = cast_introspect_mut::<Events<T>>(this);
+                    this.clear();
+
+                    Ok(None)
+                })),
+            },
+        ]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
+
+impl<T: Event> Resource for Events<T> {}
+
+/// An injectable reader over an events queue
+/// The cursor of the reader is owned by the system that is running, so every system reads
+/// every event once
+pub struct EventReader<T: Event> {
+    events: Const<Events<T>>,
+    reader: String,
+}
+
+impl<T: Event> EventReader<T> {
+    /// Iterate over the events that have not been read yet by the running system
+    pub fn read(&self) -> impl Iterator<Item = &T> {
+        self.events.read(&self.reader)
+    }
+}
+
+impl<T: Event> Injectable for EventReader<T> {
+    fn from_resource_container(resource_container: &ResourceContainer) -> Self {
+        EventReader {
+            events: Const::<Events<T>>::from_resource_container(resource_container),
+            reader: get_running_system_identifier()
+                .unwrap_or_else(|| DEFAULT_EVENT_READER.to_string()),
+        }
+    }
+
+    fn get_access() -> InjectAccess {
+        Const::<Events<T>>::get_access()
+    }
+}
+
+/// Add an events queue into the resource container and the system that updates it every frame
+///
+/// # Arguments
+/// * `resource_container` - The resource container
+/// * `identifier` - The events resource identifier, used to access it from the scripts
+/// * `origin` - The module that adds the events, used to unload the update system
+///
+/// # Generic Arguments
+/// * `T` - The event type
+///
+pub fn add_events<T: Event>(resource_container: ResourceContainer, identifier: &str, origin: &str) {
+    resource_container.add::<Events<T>>(identifier, Box::new(Events::<T>::new()));
+
+    let system_service = resource_container.require::<SystemService>();
+    let mut system_service = system_service.write();
+
+    system_service.add_system(
+        &format!("update_{}", identifier),
+        origin,
+        Inject1::new(|mut events: Mut<Events<T>>| events.update()),
+        SystemParams::default(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readers_have_their_own_cursor() {
+        let mut events = Events::<u32>::new();
+        events.send(1);
+        events.send(2);
+
+        assert_eq!(events.read("a").cloned().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(events.read("a").count(), 0);
+
+        events.send(3);
+        assert_eq!(events.read("a").cloned().collect::<Vec<_>>(), vec![3]);
+        assert_eq!(events.read("b").cloned().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn events_are_kept_for_two_updates() {
+        let mut events = Events::<u32>::new();
+        events.send(1);
+        events.update();
+        events.send(2);
+
+        assert_eq!(events.read("a").cloned().collect::<Vec<_>>(), vec![1, 2]);
+
+        events.update();
+        assert_eq!(events.iter().cloned().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(events.read("b").cloned().collect::<Vec<_>>(), vec![2]);
+
+        events.update();
+        assert!(events.is_empty());
+    }
+}
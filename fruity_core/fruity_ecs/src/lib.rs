@@ -10,6 +10,7 @@
 //! - Entities represent any object stored in the ecs, entities are composed of components, in a game engine, a game object for example
 //! - Components are structure where the datas are stored
 
+use crate::component::component_schema_service::ComponentSchemaService;
 use crate::entity::entity_service::EntityService;
 use crate::extension_component_service::ExtensionComponentService;
 use crate::system::system_service::StartupSystemParams;
@@ -23,6 +24,10 @@ pub use fruity_ecs_derive::InstantiableObject;
 pub use fruity_ecs_derive::IntrospectObject;
 pub use fruity_ecs_derive::SerializableObject;
 
+// The derive macros refer to this crate as fruity_ecs, the tests use them too
+#[cfg(test)]
+extern crate self as fruity_ecs;
+
 /// All related with components
 pub mod component;
 
@@ -32,6 +37,9 @@ pub mod entity;
 /// Provides collection for systems
 pub mod system;
 
+/// Provides typed event queues to communicate between the systems and the scripts
+pub mod events;
+
 /// A service to store components extensions
 pub mod extension_component_service;
 
@@ -57,6 +65,12 @@ pub fn initialize(resource_container: ResourceContainer) {
         Box::new(extension_component_service),
     );
 
+    let component_schema_service = ComponentSchemaService::new(resource_container.clone());
+    resource_container.add::<ComponentSchemaService>(
+        "component_schema_service",
+        Box::new(component_schema_service),
+    );
+
     let entity_service = EntityService::new(resource_container.clone());
 
     resource_container.add::<SystemService>("system_service", Box::new(system_service));
@@ -1,2 +1,5 @@
 /// Provides container for systems
 pub mod system_service;
+
+/// Provides the ordering of the systems from their stages and their before/after constraints
+pub mod system_schedule;
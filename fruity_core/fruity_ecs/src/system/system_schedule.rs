@@ -0,0 +1,625 @@
+use fruity_core::inject::InjectAccess;
+use fruity_core::inject::EXCLUSIVE_ACCESS_IDENTIFIER;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::collections::BinaryHeap;
+use std::fmt::Display;
+
+/// An error that occurs when the systems can't be ordered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// The before/after constraints form a cycle, contains the identifiers of the systems and
+    /// stages that are part of the cycle, in order
+    Cycle(Vec<String>),
+    /// A system is added into a stage that don't exists
+    UnknownStage {
+        /// The system identifier
+        system: String,
+        /// The stage identifier
+        stage: String,
+    },
+}
+
+impl Display for ScheduleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScheduleError::Cycle(nodes) => {
+                write!(f, "Systems order contains a cycle: {}", nodes.join(" -> "))
+            }
+            ScheduleError::UnknownStage { system, stage } => {
+                write!(
+                    f,
+                    "System {} is added to an unknown stage {}",
+                    system, stage
+                )
+            }
+        }
+    }
+}
+
+/// A description of the computed schedule, used to understand why systems are not run in parallel
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScheduleReport {
+    /// The batches, in the order they are run
+    pub batches: Vec<ScheduleReportBatch>,
+}
+
+/// A group of systems that are run in parallel
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScheduleReportBatch {
+    /// The stage identifier
+    pub stage: String,
+
+    /// The system identifiers
+    pub systems: Vec<String>,
+
+    /// The conflicts that prevented systems of this batch to be run in a previous batch
+    pub conflicts: Vec<ScheduleReportConflict>,
+}
+
+/// Two systems that are not run in parallel cause they access the same datas
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScheduleReportConflict {
+    /// The system that is delayed
+    pub system: String,
+
+    /// The system it conflicts with
+    pub other_system: String,
+
+    /// The identifiers of the datas that are accessed by both systems
+    pub identifiers: Vec<String>,
+}
+
+impl Display for ScheduleReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, batch) in self.batches.iter().enumerate() {
+            writeln!(
+                f,
+                "{} [{}] {}",
+                index,
+                batch.stage,
+                batch.systems.join(", ")
+            )?;
+
+            for conflict in batch.conflicts.iter() {
+                if conflict.identifiers == [EXCLUSIVE_ACCESS_IDENTIFIER] {
+                    writeln!(
+                        f,
+                        "    {} waits for {}, one of them has an exclusive access",
+                        conflict.system, conflict.other_system,
+                    )?;
+                } else {
+                    writeln!(
+                        f,
+                        "    {} waits for {}, both access {}",
+                        conflict.system,
+                        conflict.other_system,
+                        conflict.identifiers.join(", ")
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A stage or a system as seen by the scheduler
+pub(crate) struct ScheduleEntry<'a> {
+    pub identifier: &'a str,
+    pub stage: Option<&'a str>,
+    pub before: &'a [String],
+    pub after: &'a [String],
+    pub access: Option<&'a InjectAccess>,
+}
+
+/// A group of systems of the same stage that can be run in parallel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScheduleBatch {
+    /// The stage index
+    pub stage: usize,
+    /// The system indexes
+    pub systems: Vec<usize>,
+    /// The conflicts that prevented systems of this batch to be run in a previous batch, as
+    /// (system index, other system index, data identifiers)
+    pub conflicts: Vec<(usize, usize, Vec<String>)>,
+}
+
+/// Order the systems from their stages and their before/after constraints
+///
+/// Every stage is represented in the graph by a start and an end node, the systems of a stage
+/// are placed between them, a constraint that targets a stage targets all its systems
+/// Constraints that target an unknown identifier are ignored
+///
+/// The stages are run one after the other, in an order that follows the constraints, the
+/// stages that are not ordered by a constraint are run in the order they are added
+///
+/// The systems that could be run together are then splitted in batches where no system writes
+/// a data that is accessed by an other one
+///
+/// # Arguments
+/// * `stages` - The stages
+/// * `systems` - The systems, each one should provide its stage
+///
+pub(crate) fn build_schedule(
+    stages: &[ScheduleEntry],
+    systems: &[ScheduleEntry],
+) -> Result<Vec<ScheduleBatch>, ScheduleError> {
+    let system_offset = stages.len() * 2;
+    let node_count = system_offset + systems.len();
+
+    // Get the first and the last node of an identifier, a system identifier is prefered over
+    // a stage identifier
+    let resolve = |identifier: &str| -> Vec<(usize, usize)> {
+        let resolved_systems = systems
+            .iter()
+            .enumerate()
+            .filter(|(_, system)| system.identifier == identifier)
+            .map(|(index, _)| (system_offset + index, system_offset + index))
+            .collect::<Vec<_>>();
+
+        if !resolved_systems.is_empty() {
+            resolved_systems
+        } else {
+            stages
+                .iter()
+                .position(|stage| stage.identifier == identifier)
+                .map(|index| vec![(index * 2, index * 2 + 1)])
+                .unwrap_or_default()
+        }
+    };
+
+    // Build the graph edges
+    let mut edges = vec![Vec::<usize>::new(); node_count];
+    let mut add_constraints = |entry: &ScheduleEntry, first: usize, last: usize| {
+        entry.after.iter().for_each(|after| {
+            resolve(after)
+                .into_iter()
+                .for_each(|(_, after_last)| edges[after_last].push(first));
+        });
+
+        entry.before.iter().for_each(|before| {
+            resolve(before)
+                .into_iter()
+                .for_each(|(before_first, _)| edges[last].push(before_first));
+        });
+    };
+
+    for (index, stage) in stages.iter().enumerate() {
+        add_constraints(stage, index * 2, index * 2 + 1);
+    }
+
+    let mut system_stages = Vec::with_capacity(systems.len());
+    for (index, system) in systems.iter().enumerate() {
+        let node = system_offset + index;
+        let stage_identifier = system.stage.unwrap_or_default();
+        let stage = stages
+            .iter()
+            .position(|stage| stage.identifier == stage_identifier)
+            .ok_or_else(|| ScheduleError::UnknownStage {
+                system: system.identifier.to_string(),
+                stage: stage_identifier.to_string(),
+            })?;
+
+        add_constraints(system, node, node);
+        system_stages.push(stage);
+    }
+
+    for (index, stage) in system_stages.iter().enumerate() {
+        edges[stage * 2].push(system_offset + index);
+        edges[system_offset + index].push(stage * 2 + 1);
+    }
+
+    for index in 0..stages.len() {
+        edges[index * 2].push(index * 2 + 1);
+    }
+
+    // Sort the graph, each node is placed one level after its last dependency
+    let mut in_degrees = vec![0; node_count];
+    edges
+        .iter()
+        .flatten()
+        .for_each(|target| in_degrees[*target] += 1);
+
+    let mut levels = vec![0; node_count];
+    let mut queue = (0..node_count)
+        .filter(|node| in_degrees[*node] == 0)
+        .collect::<Vec<_>>();
+    let mut sorted_count = 0;
+
+    while let Some(node) = queue.pop() {
+        sorted_count += 1;
+
+        for target in edges[node].iter() {
+            levels[*target] = usize::max(levels[*target], levels[node] + 1);
+            in_degrees[*target] -= 1;
+
+            if in_degrees[*target] == 0 {
+                queue.push(*target);
+            }
+        }
+    }
+
+    if sorted_count < node_count {
+        let node_name = |node: usize| {
+            if node >= system_offset {
+                systems[node - system_offset].identifier.to_string()
+            } else {
+                stages[node / 2].identifier.to_string()
+            }
+        };
+
+        // The start and the end nodes of a stage have the same name, it's kept once
+        let mut cycle = find_cycle(&edges, &in_degrees)
+            .into_iter()
+            .map(node_name)
+            .collect::<Vec<_>>();
+        cycle.dedup();
+
+        return Err(ScheduleError::Cycle(cycle));
+    }
+
+    // Order the stages, the systems of a stage are all run before the ones of the next stage,
+    // that way a stage never splits the consecutive fixed stages
+    let node_stage = |node: usize| {
+        if node >= system_offset {
+            system_stages[node - system_offset]
+        } else {
+            node / 2
+        }
+    };
+
+    let mut stage_edges = vec![Vec::<usize>::new(); stages.len()];
+    for (source, targets) in edges.iter().enumerate() {
+        for target in targets.iter() {
+            let (source_stage, target_stage) = (node_stage(source), node_stage(*target));
+            if source_stage != target_stage && !stage_edges[source_stage].contains(&target_stage) {
+                stage_edges[source_stage].push(target_stage);
+            }
+        }
+    }
+
+    let stage_positions = sort_stages(&stage_edges).map_err(|cycle| {
+        ScheduleError::Cycle(
+            cycle
+                .into_iter()
+                .map(|stage| stages[stage].identifier.to_string())
+                .collect(),
+        )
+    })?;
+
+    // Group the systems that have the same stage and level
+    let mut batches = BTreeMap::<(usize, usize, usize), Vec<usize>>::new();
+    for (index, stage) in system_stages.iter().enumerate() {
+        batches
+            .entry((
+                stage_positions[*stage],
+                levels[system_offset + index],
+                *stage,
+            ))
+            .or_default()
+            .push(index);
+    }
+
+    Ok(batches
+        .into_iter()
+        .map(|((_, _, stage), group)| {
+            split_conflicting_systems(systems, group).into_iter().map(
+                move |(systems, conflicts)| ScheduleBatch {
+                    stage,
+                    systems,
+                    conflicts,
+                },
+            )
+        })
+        .flatten()
+        .collect())
+}
+
+// Get the position of every stage, the first added stage is taken when many stages can be run,
+// returns the cycle if the stages can't be ordered, the systems of two stages can require each
+// stage to be run before the other one
+fn sort_stages(stage_edges: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let mut in_degrees = vec![0; stage_edges.len()];
+    stage_edges
+        .iter()
+        .flatten()
+        .for_each(|target| in_degrees[*target] += 1);
+
+    let mut queue = (0..stage_edges.len())
+        .filter(|stage| in_degrees[*stage] == 0)
+        .map(Reverse)
+        .collect::<BinaryHeap<_>>();
+
+    let mut positions = vec![0; stage_edges.len()];
+    let mut sorted_count = 0;
+    while let Some(Reverse(stage)) = queue.pop() {
+        positions[stage] = sorted_count;
+        sorted_count += 1;
+
+        for target in stage_edges[stage].iter() {
+            in_degrees[*target] -= 1;
+
+            if in_degrees[*target] == 0 {
+                queue.push(Reverse(*target));
+            }
+        }
+    }
+
+    if sorted_count < stage_edges.len() {
+        Err(find_cycle(stage_edges, &in_degrees))
+    } else {
+        Ok(positions)
+    }
+}
+
+// Place every system in the first batch where it don't conflict with an other system
+fn split_conflicting_systems(
+    systems: &[ScheduleEntry],
+    group: Vec<usize>,
+) -> Vec<(Vec<usize>, Vec<(usize, usize, Vec<String>)>)> {
+    let default_access = InjectAccess::default();
+    let get_access = |index: usize| systems[index].access.unwrap_or(&default_access);
+
+    let mut batches = Vec::<(Vec<usize>, Vec<(usize, usize, Vec<String>)>)>::new();
+    for system in group {
+        let mut conflicts = Vec::new();
+        let mut placed = false;
+
+        for (batch_systems, batch_conflicts) in batches.iter_mut() {
+            let new_conflicts = batch_systems
+                .iter()
+                .map(|other| {
+                    (
+                        system,
+                        *other,
+                        get_access(system).get_conflicts(get_access(*other)),
+                    )
+                })
+                .filter(|(_, _, identifiers)| !identifiers.is_empty())
+                .collect::<Vec<_>>();
+
+            if new_conflicts.is_empty() {
+                batch_systems.push(system);
+                batch_conflicts.append(&mut conflicts);
+                placed = true;
+                break;
+            }
+
+            conflicts.extend(new_conflicts);
+        }
+
+        if !placed {
+            batches.push((vec![system], conflicts));
+        }
+    }
+
+    batches
+}
+
+// Find a cycle among the nodes that couldn't be sorted, the first node is repeated at the end
+fn find_cycle(edges: &[Vec<usize>], in_degrees: &[usize]) -> Vec<usize> {
+    // Every unsorted node has an unsorted predecessor, so walking backward from one of them
+    // always ends up on a node that is already visited
+    let mut predecessors = vec![None; edges.len()];
+    edges.iter().enumerate().for_each(|(source, targets)| {
+        if in_degrees[source] > 0 {
+            targets
+                .iter()
+                .filter(|target| in_degrees[**target] > 0)
+                .for_each(|target| predecessors[*target] = Some(source));
+        }
+    });
+
+    let start = match in_degrees.iter().position(|in_degree| *in_degree > 0) {
+        Some(start) => start,
+        None => return vec![],
+    };
+
+    let mut path = vec![start];
+    let mut current = start;
+    while let Some(predecessor) = predecessors[current] {
+        if let Some(position) = path.iter().position(|node| *node == predecessor) {
+            let mut cycle = path[position..].to_vec();
+            cycle.reverse();
+            cycle.push(cycle[0]);
+            return cycle;
+        }
+
+        path.push(predecessor);
+        current = predecessor;
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry<'a>(
+        identifier: &'a str,
+        stage: Option<&'a str>,
+        before: &'a [String],
+        after: &'a [String],
+    ) -> ScheduleEntry<'a> {
+        ScheduleEntry {
+            identifier,
+            stage,
+            before,
+            after,
+            access: None,
+        }
+    }
+
+    #[test]
+    fn systems_are_ordered_by_stages_and_constraints() {
+        let update = vec!["update".to_string()];
+        let after_a = vec!["a".to_string()];
+        let stages = vec![
+            entry("update", None, &[], &[]),
+            entry("draw", None, &[], &update),
+        ];
+        let systems = vec![
+            entry("draw", Some("draw"), &[], &[]),
+            entry("b", Some("update"), &[], &after_a),
+            entry("a", Some("update"), &[], &[]),
+            entry("c", Some("update"), &[], &[]),
+        ];
+
+        let schedule = build_schedule(&stages, &systems).unwrap();
+
+        assert_eq!(
+            schedule,
+            vec![
+                ScheduleBatch {
+                    stage: 0,
+                    systems: vec![2, 3],
+                    conflicts: vec![],
+                },
+                ScheduleBatch {
+                    stage: 0,
+                    systems: vec![1],
+                    conflicts: vec![],
+                },
+                ScheduleBatch {
+                    stage: 1,
+                    systems: vec![0],
+                    conflicts: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn conflicting_systems_are_splitted() {
+        let read_a = InjectAccess::read("A");
+        let write_a = InjectAccess::write("A");
+        let write_b = InjectAccess::write("B");
+        let stages = vec![entry("update", None, &[], &[])];
+        let mut systems = vec![
+            entry("read_a", Some("update"), &[], &[]),
+            entry("write_a", Some("update"), &[], &[]),
+            entry("write_b", Some("update"), &[], &[]),
+            entry("read_a_2", Some("update"), &[], &[]),
+        ];
+        systems[0].access = Some(&read_a);
+        systems[1].access = Some(&write_a);
+        systems[2].access = Some(&write_b);
+        systems[3].access = Some(&read_a);
+
+        let schedule = build_schedule(&stages, &systems).unwrap();
+
+        assert_eq!(
+            schedule,
+            vec![
+                ScheduleBatch {
+                    stage: 0,
+                    systems: vec![0, 2, 3],
+                    conflicts: vec![],
+                },
+                ScheduleBatch {
+                    stage: 0,
+                    systems: vec![1],
+                    conflicts: vec![(1, 0, vec!["A".to_string()])],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn exclusive_systems_are_splitted() {
+        let read_a = InjectAccess::read("A");
+        let write_b = InjectAccess::write("B");
+        let exclusive = InjectAccess::exclusive();
+        let stages = vec![entry("update", None, &[], &[])];
+        let mut systems = vec![
+            entry("read_a", Some("update"), &[], &[]),
+            entry("script", Some("update"), &[], &[]),
+            entry("write_b", Some("update"), &[], &[]),
+        ];
+        systems[0].access = Some(&read_a);
+        systems[1].access = Some(&exclusive);
+        systems[2].access = Some(&write_b);
+
+        let schedule = build_schedule(&stages, &systems).unwrap();
+
+        assert_eq!(
+            schedule,
+            vec![
+                ScheduleBatch {
+                    stage: 0,
+                    systems: vec![0, 2],
+                    conflicts: vec![],
+                },
+                ScheduleBatch {
+                    stage: 0,
+                    systems: vec![1],
+                    conflicts: vec![(1, 0, vec![EXCLUSIVE_ACCESS_IDENTIFIER.to_string()])],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unconstrained_stage_dont_split_the_other_stages() {
+        let after_fixed_a = vec!["fixed_a".to_string()];
+        let stages = vec![
+            entry("fixed_a", None, &[], &[]),
+            entry("fixed_b", None, &[], &after_fixed_a),
+            entry("custom", None, &[], &[]),
+        ];
+        let systems = vec![
+            entry("custom_system", Some("custom"), &[], &[]),
+            entry("fixed_b_system", Some("fixed_b"), &[], &[]),
+            entry("fixed_a_system", Some("fixed_a"), &[], &[]),
+        ];
+
+        let schedule = build_schedule(&stages, &systems).unwrap();
+
+        assert_eq!(
+            schedule
+                .iter()
+                .map(|batch| (batch.stage, batch.systems.clone()))
+                .collect::<Vec<_>>(),
+            vec![(0, vec![2]), (1, vec![1]), (2, vec![0])]
+        );
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let after_a = vec!["a".to_string()];
+        let after_b = vec!["b".to_string()];
+        let stages = vec![entry("update", None, &[], &[])];
+        let systems = vec![
+            entry("a", Some("update"), &[], &after_b),
+            entry("b", Some("update"), &[], &after_a),
+        ];
+
+        let error = build_schedule(&stages, &systems).unwrap_err();
+
+        match error {
+            ScheduleError::Cycle(cycle) => {
+                assert_eq!(cycle.len(), 3);
+                assert_eq!(cycle.first(), cycle.last());
+                assert!(cycle.contains(&"a".to_string()));
+                assert!(cycle.contains(&"b".to_string()));
+            }
+            _ => panic!("A cycle error was expected"),
+        }
+    }
+
+    #[test]
+    fn unknown_stages_are_rejected() {
+        let stages = vec![entry("update", None, &[], &[])];
+        let systems = vec![entry("a", Some("physics"), &[], &[])];
+
+        assert_eq!(
+            build_schedule(&stages, &systems),
+            Err(ScheduleError::UnknownStage {
+                system: "a".to_string(),
+                stage: "physics".to_string(),
+            })
+        );
+    }
+}
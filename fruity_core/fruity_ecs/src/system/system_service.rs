@@ -1,8 +1,17 @@
+use crate::system::system_schedule::build_schedule;
+use crate::system::system_schedule::ScheduleBatch;
+use crate::system::system_schedule::ScheduleEntry;
+use crate::system::system_schedule::ScheduleError;
+use crate::system::system_schedule::ScheduleReport;
+use crate::system::system_schedule::ScheduleReportBatch;
+use crate::system::system_schedule::ScheduleReportConflict;
+use crate::EntityService;
 use crate::ResourceContainer;
 use fruity_any::*;
 use fruity_core::convert::FruityInto;
 use fruity_core::inject::Inject;
 use fruity_core::inject::Inject0;
+use fruity_core::inject::InjectAccess;
 use fruity_core::introspect::log_introspect_error;
 use fruity_core::introspect::FieldInfo;
 use fruity_core::introspect::IntrospectObject;
@@ -17,13 +26,44 @@ use fruity_core::utils::introspect::cast_introspect_ref;
 use fruity_core::utils::introspect::ArgumentCaster;
 use fruity_core::Mutex;
 use fruity_ecs_derive::*;
+use itertools::Itertools;
 use rayon::prelude::*;
-use std::collections::BTreeMap;
+use std::cell::Cell;
+use std::cell::RefCell;
 use std::fmt::Debug;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+thread_local! {
+    // The change tick of the last run of the system running on this thread
+    static RUNNING_SYSTEM_LAST_RUN_TICK: Cell<Option<u64>> = Cell::new(None);
+
+    // The identifier of the system running on this thread
+    static RUNNING_SYSTEM_IDENTIFIER: RefCell<Option<String>> = RefCell::new(None);
+}
+
+// The scripting languages run on their own thread, the scripted systems are run one by one so
+// the running one can share its last run tick with the queries created by the script
+static SCRIPT_SYSTEM_LOCK: Mutex<()> = fruity_core::const_mutex(());
+static SCRIPT_SYSTEM_LAST_RUN_TICK: AtomicU64 = AtomicU64::new(0);
+
+/// Get the change tick of the last run of the system that is currently running
+/// Returns 0 if no system is running, so every component is seen as changed
+pub(crate) fn get_system_last_run_tick() -> u64 {
+    RUNNING_SYSTEM_LAST_RUN_TICK
+        .with(|last_run_tick| last_run_tick.get())
+        .unwrap_or_else(|| SCRIPT_SYSTEM_LAST_RUN_TICK.load(Ordering::Relaxed))
+}
+
+/// Get the identifier of the system that is currently running on this thread
+/// Returns None if no system is running or if it's a scripted one
+pub(crate) fn get_running_system_identifier() -> Option<String> {
+    RUNNING_SYSTEM_IDENTIFIER.with(|identifier| identifier.borrow().clone())
+}
+
 /// A callback for a system called every frame
 pub type SystemCallback = dyn Fn(ResourceContainer) + Sync + Send + 'static;
 
@@ -34,11 +74,31 @@ pub type StartupDisposeSystemCallback = Option<Box<dyn FnOnce() + Sync + Send +
 pub type StartupSystemCallback =
     dyn Fn(ResourceContainer) -> StartupDisposeSystemCallback + Sync + Send + 'static;
 
+/// The stages created by default, they are run in this order
+pub const DEFAULT_STAGES: [&str; 7] = [
+    "update",
+    "pre_fixed_update",
+    "fixed_update",
+    "physics",
+    "transform",
+    "draw",
+    "camera",
+];
+
+/// The default stages that are run with a fixed timestep
+pub const DEFAULT_FIXED_STAGES: [&str; 3] = ["pre_fixed_update", "fixed_update", "physics"];
+
 /// Params for a system
 #[derive(Debug, Clone, FruityAny, SerializableObject, IntrospectObject, InstantiableObject)]
 pub struct SystemParams {
-    /// The pool index
-    pub pool_index: usize,
+    /// The stage of the system
+    pub stage: String,
+
+    /// Identifiers of systems or stages that should be run after this system
+    pub before: Vec<String>,
+
+    /// Identifiers of systems or stages that should be run before this system
+    pub after: Vec<String>,
 
     /// If true, the system is still running while pause
     pub ignore_pause: bool,
@@ -47,12 +107,27 @@ pub struct SystemParams {
 impl Default for SystemParams {
     fn default() -> Self {
         Self {
-            pool_index: 50,
+            stage: "update".to_string(),
+            before: Vec::new(),
+            after: Vec::new(),
             ignore_pause: false,
         }
     }
 }
 
+/// Params for a stage
+#[derive(Debug, Clone, Default)]
+pub struct StageParams {
+    /// Identifiers of systems or stages that should be run after this stage
+    pub before: Vec<String>,
+
+    /// Identifiers of systems or stages that should be run before this stage
+    pub after: Vec<String>,
+
+    /// If true, the stage is run with a fixed timestep, see [‘SystemService::set_fixed_step_count‘]
+    pub fixed: bool,
+}
+
 /// Params for a system
 #[derive(Debug, Clone, FruityAny, SerializableObject, IntrospectObject, InstantiableObject)]
 pub struct StartupSystemParams {
@@ -86,19 +161,70 @@ struct StartupDisposeSystem {
 struct FrameSystem {
     identifier: String,
     origin: String,
+    stage: String,
+    before: Vec<String>,
+    after: Vec<String>,
+    access: InjectAccess,
     callback: Arc<SystemCallback>,
     ignore_pause: bool,
+    last_run_tick: Arc<AtomicU64>,
 }
 
-/// A system pool, see [‘SystemService‘] for more informations
-pub struct SystemPool<T> {
-    /// Systems of the pool
-    systems: Vec<T>,
+impl FrameSystem {
+    fn run(&self, resource_container: ResourceContainer, change_tick: u64) {
+        // The changes made by the system are dated with the change tick, so the system
+        // doesn't see its own changes on the next run
+        let last_run_tick = self.last_run_tick.swap(change_tick, Ordering::Relaxed);
+
+        RUNNING_SYSTEM_IDENTIFIER.with(|identifier| {
+            identifier.replace(Some(self.identifier.clone()));
+        });
+
+        RUNNING_SYSTEM_LAST_RUN_TICK.with(|running_last_run_tick| {
+            running_last_run_tick.set(Some(last_run_tick));
+            (self.callback)(resource_container);
+            running_last_run_tick.set(None);
+        });
+
+        RUNNING_SYSTEM_IDENTIFIER.with(|identifier| {
+            identifier.replace(None);
+        });
+    }
+}
 
-    /// Is the pool enabled
+impl FrameSystem {
+    fn get_schedule_entry(&self) -> ScheduleEntry<'_> {
+        ScheduleEntry {
+            identifier: &self.identifier,
+            stage: Some(&self.stage),
+            before: &self.before,
+            after: &self.after,
+            access: Some(&self.access),
+        }
+    }
+}
+
+/// A stage, see [‘SystemService‘] for more informations
+struct SystemStage {
+    identifier: String,
+    before: Vec<String>,
+    after: Vec<String>,
+    fixed: bool,
     enabled: bool,
 }
 
+impl SystemStage {
+    fn get_schedule_entry(&self) -> ScheduleEntry<'_> {
+        ScheduleEntry {
+            identifier: &self.identifier,
+            stage: None,
+            before: &self.before,
+            after: &self.after,
+            access: None,
+        }
+    }
+}
+
 /// A systems collection
 ///
 /// There is three type of systems:
@@ -106,16 +232,24 @@ pub struct SystemPool<T> {
 /// - end systems is called before closing the software
 /// - systems are called every frame
 ///
-/// There is a pool system, when you add a system, you can provide a pool, every systems of the same pool will be executed in parallel
-/// Try to use it realy rarely, cause parallel execution is realy usefull
-/// Pools from 0 to 10 and from 90 to 100 are reservec by the engine, you should avoid to create pool outside this range
-/// Pool 98 is for drawing
-/// Pool 99 is for camera
+/// Every system belongs to a stage and can declare before/after constraints over other systems or stages,
+/// the systems are ordered as a graph and the systems that don't depend on each other are executed in parallel
+/// Systems that write a component or a resource accessed by an other system are never executed in parallel with it,
+/// the accesses are deduced from the injected arguments, see [‘SystemService::get_schedule_report‘] to see the result
+/// The default stages are run in this order: update, pre_fixed_update, fixed_update, physics, transform, draw and camera
+/// Systems are added to the update stage if no stage is provided
+///
+/// The fixed stages (pre_fixed_update, fixed_update and physics by default) are run as many times as
+/// the number of fixed steps of the frame, so the gameplay and the physics don't depend on the frame rate
 ///
 #[derive(FruityAny)]
 pub struct SystemService {
     pause: AtomicBool,
-    system_pools: BTreeMap<usize, SystemPool<FrameSystem>>,
+    step_requested: AtomicBool,
+    fixed_step_count: AtomicUsize,
+    stages: Vec<SystemStage>,
+    systems: Vec<FrameSystem>,
+    schedule: Vec<ScheduleBatch>,
     startup_systems: Vec<StartupSystem>,
     startup_dispose_callbacks: Mutex<Vec<StartupDisposeSystem>>,
     startup_pause_dispose_callbacks: Mutex<Vec<StartupDisposeSystem>>,
@@ -133,7 +267,24 @@ impl<'s> SystemService {
     pub fn new(resource_container: ResourceContainer) -> SystemService {
         SystemService {
             pause: AtomicBool::new(true),
-            system_pools: BTreeMap::new(),
+            step_requested: AtomicBool::new(false),
+            fixed_step_count: AtomicUsize::new(1),
+            stages: DEFAULT_STAGES
+                .iter()
+                .enumerate()
+                .map(|(index, identifier)| SystemStage {
+                    identifier: identifier.to_string(),
+                    before: Vec::new(),
+                    after: match index {
+                        0 => Vec::new(),
+                        index => vec![DEFAULT_STAGES[index - 1].to_string()],
+                    },
+                    fixed: DEFAULT_FIXED_STAGES.contains(identifier),
+                    enabled: true,
+                })
+                .collect(),
+            systems: Vec::new(),
+            schedule: Vec::new(),
             startup_systems: Vec::new(),
             startup_dispose_callbacks: Mutex::new(Vec::new()),
             startup_pause_dispose_callbacks: Mutex::new(Vec::new()),
@@ -141,12 +292,14 @@ impl<'s> SystemService {
         }
     }
 
-    /// Add a system to the collection
+    /// Add a system to the collection, the system is not added and an error is logged if it
+    /// can't be ordered, see [‘SystemService::try_add_system‘]
     ///
     /// # Arguments
+    /// * `identifier` - The system identifier, can be used by other systems constraints
     /// * `origin` - An identifier for the origin of the system, used for hot reload
     /// * `system` - A function that will compute the world
-    /// * `pool_index` - A pool identifier, all the systems of the same pool will be processed together in parallel
+    /// * `params` - The system params, contains the stage and the ordering constraints
     ///
     pub fn add_system<T: Inject>(
         &mut self,
@@ -155,26 +308,92 @@ impl<'s> SystemService {
         callback: T,
         params: SystemParams,
     ) {
-        let system = FrameSystem {
+        if let Err(err) = self.try_add_system(identifier, origin, callback, params) {
+            log::error!("{}", err);
+        }
+    }
+
+    /// Add a system to the collection
+    ///
+    /// # Arguments
+    /// * `identifier` - The system identifier, can be used by other systems constraints
+    /// * `origin` - An identifier for the origin of the system, used for hot reload
+    /// * `system` - A function that will compute the world
+    /// * `params` - The system params, contains the stage and the ordering constraints
+    ///
+    pub fn try_add_system<T: Inject>(
+        &mut self,
+        identifier: &str,
+        origin: &str,
+        callback: T,
+        params: SystemParams,
+    ) -> Result<(), ScheduleError> {
+        let access = callback.get_access();
+        self.systems.push(FrameSystem {
             identifier: identifier.to_string(),
             origin: origin.to_string(),
+            stage: params.stage,
+            before: params.before,
+            after: params.after,
+            access,
             callback: callback.inject().into(),
             ignore_pause: params.ignore_pause,
-        };
+            last_run_tick: Arc::new(AtomicU64::new(0)),
+        });
 
-        if let Some(pool) = self.system_pools.get_mut(&params.pool_index) {
-            pool.systems.push(system)
-        } else {
-            // If the pool not exists, we create it
-            let systems = vec![system];
-            self.system_pools.insert(
-                params.pool_index,
-                SystemPool {
-                    systems,
-                    enabled: true,
-                },
-            );
-        };
+        // If the system can't be ordered, it's removed so the previous schedule stay valid
+        if let Err(err) = self.update_schedule() {
+            self.systems.pop();
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Add a stage, stages are used to group systems
+    ///
+    /// # Arguments
+    /// * `identifier` - The stage identifier
+    /// * `params` - The ordering constraints of the stage
+    ///
+    pub fn add_stage(
+        &mut self,
+        identifier: &str,
+        params: StageParams,
+    ) -> Result<(), ScheduleError> {
+        self.stages.push(SystemStage {
+            identifier: identifier.to_string(),
+            before: params.before,
+            after: params.after,
+            fixed: params.fixed,
+            enabled: true,
+        });
+
+        if let Err(err) = self.update_schedule() {
+            self.stages.pop();
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    fn update_schedule(&mut self) -> Result<(), ScheduleError> {
+        let stages = self
+            .stages
+            .iter()
+            .map(|stage| stage.get_schedule_entry())
+            .collect::<Vec<_>>();
+
+        let systems = self
+            .systems
+            .iter()
+            .map(|system| system.get_schedule_entry())
+            .collect::<Vec<_>>();
+
+        let schedule = build_schedule(&stages, &systems)?;
+        self.schedule = schedule;
+
+        Ok(())
     }
 
     /// Add a startup system
@@ -182,7 +401,6 @@ impl<'s> SystemService {
     /// # Arguments
     /// * `origin` - An identifier for the origin of the system, used for hot reload
     /// * `system` - A function that will compute the world
-    /// * `pool_index` - A pool identifier, all the systems of the same pool will be processed together in parallel
     ///
     pub fn add_startup_system<T: Inject<StartupDisposeSystemCallback>>(
         &mut self,
@@ -207,9 +425,10 @@ impl<'s> SystemService {
     /// * `origin` - An identifier for the origin of the system, used for hot reload
     ///
     pub fn unload_origin(&mut self, origin: &str) {
-        self.system_pools.values_mut().for_each(|pool| {
-            drain_filter(&mut pool.systems, |system| system.origin == origin);
-        });
+        drain_filter(&mut self.systems, |system| system.origin == origin);
+
+        // Removing systems can't break the order, so the schedule is always valid
+        self.update_schedule().ok();
 
         {
             let mut startup_dispose_callbacks = self.startup_pause_dispose_callbacks.lock();
@@ -232,32 +451,103 @@ impl<'s> SystemService {
         }
     }
 
-    /// Iter over all the systems pools
-    fn iter_system_pools(&self) -> impl Iterator<Item = &SystemPool<FrameSystem>> {
-        self.system_pools.iter().map(|pool| pool.1)
+    /// Get a description of the order the systems are run in and of the reasons why systems
+    /// are not run in parallel
+    pub fn get_schedule_report(&self) -> ScheduleReport {
+        ScheduleReport {
+            batches: self
+                .schedule
+                .iter()
+                .map(|batch| ScheduleReportBatch {
+                    stage: self.stages[batch.stage].identifier.clone(),
+                    systems: batch
+                        .systems
+                        .iter()
+                        .map(|system| self.systems[*system].identifier.clone())
+                        .collect(),
+                    conflicts: batch
+                        .conflicts
+                        .iter()
+                        .map(
+                            |(system, other_system, identifiers)| ScheduleReportConflict {
+                                system: self.systems[*system].identifier.clone(),
+                                other_system: self.systems[*other_system].identifier.clone(),
+                                identifiers: identifiers.clone(),
+                            },
+                        )
+                        .collect(),
+                })
+                .collect(),
+        }
     }
 
     /// Run all the stored systems
     pub fn run(&self) {
         let resource_container = self.resource_container.clone();
-        let is_paused = self.is_paused();
-
-        self.iter_system_pools().for_each(|pool| {
-            if pool.enabled {
-                pool.systems.iter().par_bridge().for_each(|system| {
-                    if !is_paused || system.ignore_pause {
-                        let _profiler_scope = if puffin::are_scopes_on() {
-                            // Safe cause identifier don't need to be static (from the doc)
-                            let identifier = unsafe { &*(&system.identifier as *const _) } as &str;
-                            Some(puffin::ProfilerScope::new(identifier, "system", ""))
-                        } else {
-                            None
-                        };
-                        (system.callback)(resource_container.clone());
-                    }
-                });
-            }
-        });
+
+        // The resources loaded in background are notified on the main thread, before the systems
+        resource_container.notify_load_handles();
+
+        let is_stepping = self.step_requested.swap(false, Ordering::Relaxed);
+        let is_paused = self.is_paused() && !is_stepping;
+        let entity_service = self.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        let run_batch = |batch: &ScheduleBatch| {
+            // The systems of a batch don't access the same components, so they share a tick
+            let change_tick = entity_service.increment_change_tick();
+            batch.systems.par_iter().for_each(|system_index| {
+                let system = &self.systems[*system_index];
+
+                if !is_paused || system.ignore_pause {
+                    let _profiler_scope = if puffin::are_scopes_on() {
+                        // Safe cause identifier don't need to be static (from the doc)
+                        let identifier = unsafe { &*(&system.identifier as *const _) } as &str;
+                        Some(puffin::ProfilerScope::new(identifier, "system", ""))
+                    } else {
+                        None
+                    };
+                    system.run(resource_container.clone(), change_tick);
+                }
+            });
+
+            // The changes made after the batch, even outside of the systems, are seen as newer
+            entity_service.increment_change_tick();
+        };
+
+        // The consecutive batches of fixed stages are run together, once per fixed step
+        let fixed_step_count = self.get_fixed_step_count();
+        self.schedule
+            .iter()
+            .filter(|batch| self.stages[batch.stage].enabled)
+            .group_by(|batch| self.stages[batch.stage].fixed)
+            .into_iter()
+            .for_each(|(fixed, batches)| {
+                if fixed {
+                    let batches = batches.collect::<Vec<_>>();
+                    (0..fixed_step_count)
+                        .for_each(|_| batches.iter().for_each(|batch| run_batch(batch)));
+                } else {
+                    batches.for_each(run_batch);
+                }
+            });
+    }
+
+    /// Get the number of fixed steps that are run by the next calls of [‘SystemService::run‘]
+    pub fn get_fixed_step_count(&self) -> usize {
+        self.fixed_step_count.load(Ordering::Relaxed)
+    }
+
+    /// Set the number of fixed steps that are run by the next calls of [‘SystemService::run‘],
+    /// all the fixed stages are run this number of times, it's intended to be set every frame by
+    /// the service that measures the time
+    ///
+    /// # Arguments
+    /// * `fixed_step_count` - The number of fixed steps
+    ///
+    pub fn set_fixed_step_count(&self, fixed_step_count: usize) {
+        self.fixed_step_count
+            .store(fixed_step_count, Ordering::Relaxed);
     }
 
     /// Run all the startup systems
@@ -354,28 +644,50 @@ impl<'s> SystemService {
         });
     }
 
-    /// Run all the systems contained in a pool
-    pub fn run_pool(&self, index: &usize) {
-        if let Some(pool) = self.system_pools.get(index) {
-            pool.systems
-                .iter()
-                .par_bridge()
-                .for_each(|system| (system.callback)(self.resource_container.clone()));
-        }
+    /// Run all the systems contained in a stage, even if the stage is disabled
+    ///
+    /// # Arguments
+    /// * `identifier` - The stage identifier
+    ///
+    pub fn run_stage(&self, identifier: &str) {
+        let entity_service = self.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        self.schedule
+            .iter()
+            .filter(|batch| self.stages[batch.stage].identifier == identifier)
+            .for_each(|batch| {
+                let change_tick = entity_service.increment_change_tick();
+                batch.systems.par_iter().for_each(|system_index| {
+                    self.systems[*system_index].run(self.resource_container.clone(), change_tick);
+                });
+
+                entity_service.increment_change_tick();
+            });
     }
 
-    /// Enable a pool
-    pub fn enable_pool(&mut self, index: &usize) {
-        if let Some(pool) = self.system_pools.get_mut(index) {
-            pool.enabled = true;
-        }
+    /// Enable a stage
+    ///
+    /// # Arguments
+    /// * `identifier` - The stage identifier
+    ///
+    pub fn enable_stage(&mut self, identifier: &str) {
+        self.stages
+            .iter_mut()
+            .filter(|stage| stage.identifier == identifier)
+            .for_each(|stage| stage.enabled = true);
     }
 
-    /// Disable a pool
-    pub fn disable_pool(&mut self, index: &usize) {
-        if let Some(pool) = self.system_pools.get_mut(index) {
-            pool.enabled = false;
-        }
+    /// Disable a stage, the systems of a disabled stage are not run
+    ///
+    /// # Arguments
+    /// * `identifier` - The stage identifier
+    ///
+    pub fn disable_stage(&mut self, identifier: &str) {
+        self.stages
+            .iter_mut()
+            .filter(|stage| stage.identifier == identifier)
+            .for_each(|stage| stage.enabled = false);
     }
 
     /// Is systems paused
@@ -399,6 +711,17 @@ impl<'s> SystemService {
 
         self.pause.store(paused, Ordering::Relaxed);
     }
+
+    /// Request the paused systems to be run for exactly one frame, with a single fixed step
+    /// The step is consumed by the next call of [‘SystemService::run‘]
+    pub fn step_frame(&self) {
+        self.step_requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Is a frame step requested for the next call of [‘SystemService::run‘]
+    pub fn is_step_requested(&self) -> bool {
+        self.step_requested.load(Ordering::Relaxed)
+    }
 }
 
 impl IntrospectObject for SystemService {
@@ -418,15 +741,23 @@ impl IntrospectObject for SystemService {
                     let arg2 = caster.cast_next::<Callback>()?;
                     let arg3 = caster.cast_next_optional::<SystemParams>();
 
+                    // The script systems don't declare the datas they access, so they are never
+                    // run in parallel with an other system
                     let callback = arg2.callback;
                     this.add_system(
                         &arg1,
                         &arg2.origin,
                         Inject0::new(move || {
+                            let _script_system_guard = SCRIPT_SYSTEM_LOCK.lock();
+                            SCRIPT_SYSTEM_LAST_RUN_TICK
+                                .store(get_system_last_run_tick(), Ordering::Relaxed);
+
                             match callback(vec![]) {
                                 Ok(_) => (),
                                 Err(err) => log_introspect_error(&err),
                             };
+
+                            SCRIPT_SYSTEM_LAST_RUN_TICK.store(0, Ordering::Relaxed);
                         }),
                         arg3.unwrap_or_default(),
                     );
@@ -471,6 +802,16 @@ impl IntrospectObject for SystemService {
                     Ok(None)
                 })),
             },
+            MethodInfo {
+                name: "get_schedule_report".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<SystemService>(this);
+
+                    let result = this.get_schedule_report().to_string();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
             MethodInfo {
                 name: "is_paused".to_string(),
                 call: MethodCaller::Const(Arc::new(|this, _args| {
@@ -491,6 +832,16 @@ impl IntrospectObject for SystemService {
 
                     this.set_paused(arg1);
 
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "step_frame".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<SystemService>(this);
+
+                    this.step_frame();
+
                     Ok(None)
                 })),
             },
@@ -503,3 +854,138 @@ impl IntrospectObject for SystemService {
 }
 
 impl Resource for SystemService {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::component::AnyComponent;
+    use crate::entity::entity_query::changed::Added;
+    use crate::entity::entity_query::changed::Changed;
+    use crate::entity::entity_query::with::With;
+    use crate::entity::entity_query::with::WithMut;
+    use crate::entity::entity_query::Query;
+    use crate::Component;
+    use fruity_core::inject::Inject1;
+    use fruity_core::inject::Inject2;
+    use fruity_core::world::World;
+    use std::sync::Mutex as StdMutex;
+
+    #[derive(Debug, Clone, Default, Component, FruityAny)]
+    struct Counter {
+        value: i32,
+    }
+
+    type ChangedQuery = Query<(With<Counter>, Changed<Counter>)>;
+
+    // Count the changed counters on every run
+    fn count_changes(changes: &Arc<StdMutex<Vec<usize>>>, query: &ChangedQuery) {
+        let count = AtomicUsize::new(0);
+        query.for_each(|_| {
+            count.fetch_add(1, Ordering::Relaxed);
+        });
+
+        changes.lock().unwrap().push(count.into_inner());
+    }
+
+    #[test]
+    fn a_change_is_seen_once_by_the_other_systems_and_never_by_the_writer_test() {
+        let world = World::new();
+        crate::initialize(world.resource_container.clone());
+
+        let writer_changes = Arc::new(StdMutex::new(Vec::new()));
+        let before_changes = Arc::new(StdMutex::new(Vec::new()));
+        let after_changes = Arc::new(StdMutex::new(Vec::new()));
+        let added = Arc::new(StdMutex::new(Vec::new()));
+
+        {
+            let system_service = world.resource_container.require::<SystemService>();
+            let mut system_service = system_service.write();
+
+            // The writer changes the counter on its first run only
+            let has_written = Arc::new(AtomicBool::new(false));
+            let changes = writer_changes.clone();
+            system_service.add_system(
+                "writer",
+                "test",
+                Inject2::new(
+                    move |query: Query<WithMut<Counter>>, changed_query: ChangedQuery| {
+                        count_changes(&changes, &changed_query);
+
+                        if !has_written.swap(true, Ordering::Relaxed) {
+                            query.for_each(|mut counter| counter.value += 1);
+                        }
+                    },
+                ),
+                SystemParams::default(),
+            );
+
+            let changes = before_changes.clone();
+            system_service.add_system(
+                "before_writer",
+                "test",
+                Inject1::new(move |query: ChangedQuery| count_changes(&changes, &query)),
+                SystemParams {
+                    before: vec!["writer".to_string()],
+                    ..Default::default()
+                },
+            );
+
+            let changes = after_changes.clone();
+            system_service.add_system(
+                "after_writer",
+                "test",
+                Inject1::new(move |query: ChangedQuery| count_changes(&changes, &query)),
+                SystemParams {
+                    after: vec!["writer".to_string()],
+                    ..Default::default()
+                },
+            );
+
+            let added = added.clone();
+            system_service.add_system(
+                "added",
+                "test",
+                Inject1::new(move |query: Query<(With<Counter>, Added<Counter>)>| {
+                    let count = AtomicUsize::new(0);
+                    query.for_each(|_| {
+                        count.fetch_add(1, Ordering::Relaxed);
+                    });
+
+                    added.lock().unwrap().push(count.into_inner());
+                }),
+                SystemParams::default(),
+            );
+        }
+
+        let entity_service = world.resource_container.require::<EntityService>();
+        entity_service
+            .read()
+            .create("Counter", true, vec![AnyComponent::new(Counter::default())]);
+
+        let system_service = world.resource_container.require::<SystemService>();
+        let system_service = system_service.read();
+        system_service.set_paused(false);
+        (0..3).for_each(|_| system_service.run());
+
+        // The first run sees the creation of the counter
+        assert_eq!(*writer_changes.lock().unwrap(), vec![1, 0, 0]);
+        assert_eq!(*before_changes.lock().unwrap(), vec![1, 1, 0]);
+        assert_eq!(*after_changes.lock().unwrap(), vec![1, 0, 0]);
+        assert_eq!(*added.lock().unwrap(), vec![1, 0, 0]);
+
+        // A change made outside of the systems is seen by every system on the next run
+        {
+            let entity_service = entity_service.read();
+            entity_service
+                .query::<WithMut<Counter>>()
+                .for_each(|mut counter| {
+                    counter.value += 1;
+                });
+        }
+
+        system_service.run();
+        assert_eq!(*writer_changes.lock().unwrap(), vec![1, 0, 0, 1]);
+        assert_eq!(*before_changes.lock().unwrap(), vec![1, 1, 0, 1]);
+        assert_eq!(*after_changes.lock().unwrap(), vec![1, 0, 0, 1]);
+    }
+}
@@ -6,6 +6,7 @@ use crate::resources::mesh_resource::MeshResource;
 use crate::resources::mesh_resource::MeshResourceSettings;
 use crate::resources::shader_resource::ShaderResource;
 use crate::resources::shader_resource::ShaderResourceSettings;
+use crate::resources::texture_resource::TextureData;
 use crate::resources::texture_resource::TextureResource;
 use crate::resources::texture_resource::TextureResourceSettings;
 use crate::Vector2d;
@@ -57,10 +58,11 @@ pub trait GraphicService: Resource {
         contents: String,
         params: ShaderResourceSettings,
     ) -> Result<Box<dyn ShaderResource>, String>;
+    fn decode_texture(&self, contents: &[u8]) -> Result<TextureData, String>;
     fn create_texture_resource(
         &self,
         identifier: &str,
-        contents: &[u8],
+        data: TextureData,
         params: TextureResourceSettings,
     ) -> Result<Box<dyn TextureResource>, String>;
     fn create_material_resource(
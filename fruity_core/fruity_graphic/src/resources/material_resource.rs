@@ -5,6 +5,7 @@ use fruity_any::*;
 use fruity_core::introspect::FieldInfo;
 use fruity_core::introspect::IntrospectObject;
 use fruity_core::introspect::MethodInfo;
+use fruity_core::resource::error::LoadResourceError;
 use fruity_core::resource::resource::Resource;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::resource::resource_reference::ResourceReference;
@@ -107,41 +108,90 @@ pub fn load_material(
     reader: &mut dyn Read,
     _settings: Settings,
     resource_container: ResourceContainer,
-) {
+) -> Result<(), LoadResourceError> {
     // Get the graphic service state
     let graphic_service = resource_container.require::<dyn GraphicService>();
     let graphic_service = graphic_service.read();
 
     // read the whole file
     let mut buffer = String::new();
-    if let Err(err) = reader.read_to_string(&mut buffer) {
-        log::error!("{}", err.to_string());
-        return;
-    }
+    reader.read_to_string(&mut buffer).map_err(|err| {
+        LoadResourceError::FileNotReadable(identifier.to_string(), err.to_string())
+    })?;
+
+    let docs = YamlLoader::load_from_str(&buffer)
+        .map_err(|err| LoadResourceError::DecodeError(identifier.to_string(), err.to_string()))?;
+    let settings = docs
+        .get(0)
+        .and_then(build_settings_from_yaml)
+        .ok_or_else(|| {
+            LoadResourceError::DecodeError(
+                identifier.to_string(),
+                "The material should be a yaml object".to_string(),
+            )
+        })?;
 
-    let docs = YamlLoader::load_from_str(&buffer).unwrap();
-    let root = &docs[0];
-    let settings = if let Some(settings) = build_settings_from_yaml(root) {
-        settings
-    } else {
-        return;
-    };
+    // The shader and the textures should be loaded before the material
+    if let Some(dependency) = find_missing_material_dependency(&settings, &resource_container) {
+        return Err(LoadResourceError::MissingDependency(
+            identifier.to_string(),
+            dependency,
+        ));
+    }
 
     // Parse settings
     let settings = read_material_settings(&settings, resource_container.clone());
+    let dependencies = get_material_dependencies(&settings);
 
     // Build the resource
     let result = graphic_service.create_material_resource(identifier, settings);
 
     // Store the resource
-    match result {
-        Ok(resource) => {
-            resource_container.add::<dyn MaterialResource>(identifier, resource);
-        }
-        Err(err) => {
-            log::error!("{}", err);
-        }
-    }
+    let resource =
+        result.map_err(|err| LoadResourceError::DecodeError(identifier.to_string(), err))?;
+    resource_container.add::<dyn MaterialResource>(identifier, resource);
+
+    // The material cannot live without its shader and textures
+    dependencies.iter().for_each(|dependency| {
+        resource_container.add_dependency(identifier, dependency);
+    });
+
+    Ok(())
+}
+
+fn find_missing_material_dependency(
+    settings: &Settings,
+    resource_container: &ResourceContainer,
+) -> Option<String> {
+    let shader_identifier = settings.get::<String>("shader", String::default());
+    let texture_identifiers = settings
+        .get::<Vec<Settings>>("bindings", Vec::new())
+        .iter()
+        .filter(|params| params.get::<String>("type", String::default()) == "texture")
+        .map(|params| params.get::<String>("value", String::default()))
+        .collect::<Vec<_>>();
+
+    std::iter::once(shader_identifier)
+        .chain(texture_identifiers.into_iter())
+        .filter(|identifier| !identifier.is_empty())
+        .find(|identifier| !resource_container.contains(identifier))
+}
+
+pub fn get_material_dependencies(settings: &MaterialResourceSettings) -> Vec<String> {
+    settings
+        .shader
+        .iter()
+        .map(|shader| shader.get_name())
+        .chain(
+            settings
+                .bindings
+                .iter()
+                .filter_map(|binding| match binding {
+                    MaterialSettingsBinding::Texture { value, .. } => Some(value.get_name()),
+                    _ => None,
+                }),
+        )
+        .collect::<Vec<_>>()
 }
 
 pub fn read_material_settings(
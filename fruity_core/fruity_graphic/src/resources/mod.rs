@@ -1,5 +1,6 @@
 pub mod default_resources;
 pub mod material_resource;
 pub mod mesh_resource;
+pub mod shader_reflection;
 pub mod shader_resource;
 pub mod texture_resource;
@@ -0,0 +1,505 @@
+use crate::resources::shader_resource::ShaderBindingType;
+use crate::resources::shader_resource::ShaderResourceSettings;
+use std::convert::TryInto;
+
+/// The kind of the shader artifacts into the import cache
+pub const SHADER_ARTIFACT_KIND: &str = "shader";
+
+/// The name of the entry points that are used by the render pipelines
+pub const SHADER_ENTRY_POINT: &str = "main";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+    Compute,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderEntryPoint {
+    pub stage: ShaderStage,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderReflectionBinding {
+    pub group: u32,
+    pub binding: u32,
+    pub name: String,
+    /// The binding type, None if the type cannot be declared into the settings, like the storage
+    /// buffers and the storage textures
+    pub ty: Option<ShaderBindingType>,
+}
+
+/// What a WGSL shader declares, it's used to check that the shader settings match the shader
+/// before the render pipeline is built
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShaderReflection {
+    pub entry_points: Vec<ShaderEntryPoint>,
+    pub bindings: Vec<ShaderReflectionBinding>,
+    pub locations: Vec<u32>,
+}
+
+impl ShaderReflection {
+    /// Read the entry points, the bindings and the locations declared into a WGSL shader
+    ///
+    /// # Arguments
+    /// * `source` - The shader source
+    ///
+    pub fn parse(source: &str) -> Result<ShaderReflection, String> {
+        let source = remove_comments(source);
+        let mut reflection = ShaderReflection::default();
+
+        let mut rest = source.as_str();
+        while let Some(start) = rest.find("[[") {
+            let end = rest[start..]
+                .find("]]")
+                .map(|end| start + end)
+                .ok_or_else(|| "An attribute is not closed".to_string())?;
+
+            let attributes = parse_attributes(&rest[start + 2..end]);
+            rest = &rest[end + 2..];
+
+            let get_attribute = |name: &str| {
+                attributes
+                    .iter()
+                    .find(|(attribute, _)| attribute == name)
+                    .map(|(_, value)| value.as_str())
+            };
+
+            if let Some(location) = get_attribute("location") {
+                reflection.locations.push(parse_index(location)?);
+            }
+
+            if let Some(stage) = get_attribute("stage") {
+                let stage = match stage {
+                    "vertex" => ShaderStage::Vertex,
+                    "fragment" => ShaderStage::Fragment,
+                    "compute" => ShaderStage::Compute,
+                    _ => return Err(format!("The stage {} is not known", stage)),
+                };
+
+                let name = rest
+                    .trim_start()
+                    .strip_prefix("fn")
+                    .and_then(|rest| rest.split('(').next())
+                    .map(|name| name.trim().to_string())
+                    .ok_or("A stage attribute should be followed by a function")?;
+
+                reflection
+                    .entry_points
+                    .push(ShaderEntryPoint { stage, name });
+            }
+
+            if let (Some(group), Some(binding)) = (get_attribute("group"), get_attribute("binding"))
+            {
+                let declaration = rest
+                    .split(';')
+                    .next()
+                    .and_then(|declaration| declaration.trim().strip_prefix("var"))
+                    .ok_or("A binding should be followed by a variable")?;
+
+                let (name, ty) = declaration.split_once(':').ok_or_else(|| {
+                    format!("The variable {} should have a type", declaration.trim())
+                })?;
+                let (qualifier, name) = match name.split_once('>') {
+                    Some((qualifier, name)) => (qualifier, name),
+                    None => ("", name),
+                };
+
+                let ty = ty.trim();
+                let ty = if qualifier.contains("uniform") {
+                    Some(ShaderBindingType::Uniform)
+                } else if ty.starts_with("texture_storage") {
+                    None
+                } else if ty.starts_with("texture") {
+                    Some(ShaderBindingType::Texture)
+                } else if ty.starts_with("sampler") {
+                    Some(ShaderBindingType::Sampler)
+                } else {
+                    None
+                };
+
+                reflection.bindings.push(ShaderReflectionBinding {
+                    group: parse_index(group)?,
+                    binding: parse_index(binding)?,
+                    name: name.trim().to_string(),
+                    ty,
+                });
+            }
+        }
+
+        Ok(reflection)
+    }
+
+    /// Check that the settings match the shader, every binding of the shader should be declared
+    /// into the binding groups with the same type and every instance attribute should be read by
+    /// the shader
+    ///
+    /// Returns an error if the shader has no entry point for the render pipeline, the other
+    /// mismatches are returned as warnings cause the graphic backend knows better if the shader
+    /// can be used, the bindings that cannot be declared into the settings are not checked
+    ///
+    /// # Arguments
+    /// * `settings` - The shader settings
+    ///
+    pub fn validate(&self, settings: &ShaderResourceSettings) -> Result<Vec<String>, String> {
+        [ShaderStage::Vertex, ShaderStage::Fragment]
+            .iter()
+            .try_for_each(|stage| {
+                if self.entry_points.iter().any(|entry_point| {
+                    &entry_point.stage == stage && entry_point.name == SHADER_ENTRY_POINT
+                }) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "The shader has no {:?} entry point named {}",
+                        stage, SHADER_ENTRY_POINT
+                    ))
+                }
+            })?;
+
+        let binding_warnings = self.bindings.iter().filter_map(|binding| {
+            let declared_binding = settings
+                .binding_groups
+                .get(binding.group as usize)
+                .and_then(|binding_group| binding_group.bindings.get(binding.binding as usize));
+
+            match (declared_binding, &binding.ty) {
+                (Some(declared_binding), Some(ty)) if &declared_binding.ty != ty => Some(format!(
+                    "The binding {} of the group {} ({}) is a {:?} but it is declared as a {:?} into the settings",
+                    binding.binding, binding.group, binding.name, ty, declared_binding.ty
+                )),
+                (Some(_), _) => None,
+                (None, Some(_)) => Some(format!(
+                    "The binding {} of the group {} ({}) is not declared into the settings",
+                    binding.binding, binding.group, binding.name
+                )),
+                (None, None) => None,
+            }
+        });
+
+        let instance_attribute_warnings =
+            settings
+                .instance_attributes
+                .iter()
+                .filter_map(|instance_attribute| {
+                    if self.locations.contains(&instance_attribute.location) {
+                        None
+                    } else {
+                        Some(format!(
+                            "The instance attribute location {} is not read by the shader",
+                            instance_attribute.location
+                        ))
+                    }
+                });
+
+        Ok(binding_warnings
+            .chain(instance_attribute_warnings)
+            .collect::<Vec<_>>())
+    }
+
+    /// Write the reflection as an artifact for the import cache
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let write_u32 =
+            |bytes: &mut Vec<u8>, value: u32| bytes.extend_from_slice(&value.to_le_bytes());
+        let write_string = |bytes: &mut Vec<u8>, value: &str| {
+            bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(value.as_bytes());
+        };
+
+        write_u32(&mut bytes, self.entry_points.len() as u32);
+        self.entry_points.iter().for_each(|entry_point| {
+            bytes.push(match entry_point.stage {
+                ShaderStage::Vertex => 0,
+                ShaderStage::Fragment => 1,
+                ShaderStage::Compute => 2,
+            });
+            write_string(&mut bytes, &entry_point.name);
+        });
+
+        write_u32(&mut bytes, self.bindings.len() as u32);
+        self.bindings.iter().for_each(|binding| {
+            write_u32(&mut bytes, binding.group);
+            write_u32(&mut bytes, binding.binding);
+            write_string(&mut bytes, &binding.name);
+            bytes.push(match binding.ty {
+                Some(ShaderBindingType::Texture) => 0,
+                Some(ShaderBindingType::Sampler) => 1,
+                Some(ShaderBindingType::Uniform) => 2,
+                None => 255,
+            });
+        });
+
+        write_u32(&mut bytes, self.locations.len() as u32);
+        self.locations
+            .iter()
+            .for_each(|location| write_u32(&mut bytes, *location));
+
+        bytes
+    }
+
+    /// Read the reflection from an artifact of the import cache, returns None if the artifact
+    /// is corrupted
+    ///
+    /// # Arguments
+    /// * `bytes` - The artifact content
+    ///
+    pub fn from_bytes(bytes: &[u8]) -> Option<ShaderReflection> {
+        let mut reader = ArtifactReader { bytes, offset: 0 };
+        let mut reflection = ShaderReflection::default();
+
+        for _ in 0..reader.read_u32()? {
+            let stage = match reader.read_u8()? {
+                0 => ShaderStage::Vertex,
+                1 => ShaderStage::Fragment,
+                2 => ShaderStage::Compute,
+                _ => return None,
+            };
+            let name = reader.read_string()?;
+
+            reflection
+                .entry_points
+                .push(ShaderEntryPoint { stage, name });
+        }
+
+        for _ in 0..reader.read_u32()? {
+            let group = reader.read_u32()?;
+            let binding = reader.read_u32()?;
+            let name = reader.read_string()?;
+            let ty = match reader.read_u8()? {
+                0 => Some(ShaderBindingType::Texture),
+                1 => Some(ShaderBindingType::Sampler),
+                2 => Some(ShaderBindingType::Uniform),
+                255 => None,
+                _ => return None,
+            };
+
+            reflection.bindings.push(ShaderReflectionBinding {
+                group,
+                binding,
+                name,
+                ty,
+            });
+        }
+
+        for _ in 0..reader.read_u32()? {
+            reflection.locations.push(reader.read_u32()?);
+        }
+
+        if reader.offset == bytes.len() {
+            Some(reflection)
+        } else {
+            None
+        }
+    }
+}
+
+struct ArtifactReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ArtifactReader<'a> {
+    fn read(&mut self, size: usize) -> Option<&'a [u8]> {
+        let result = self.bytes.get(self.offset..self.offset + size)?;
+        self.offset += size;
+
+        Some(result)
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        Some(self.read(1)?[0])
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.read(4)?.try_into().ok()?))
+    }
+
+    fn read_string(&mut self) -> Option<String> {
+        let size = self.read_u32()? as usize;
+        String::from_utf8(self.read(size)?.to_vec()).ok()
+    }
+}
+
+fn remove_comments(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+
+    loop {
+        let line_comment = rest.find("//");
+        let block_comment = rest.find("/*");
+
+        match (line_comment, block_comment) {
+            (Some(line), Some(block)) if line < block => {
+                result.push_str(&rest[..line]);
+                rest = skip_line_comment(&rest[line..]);
+            }
+            (Some(line), None) => {
+                result.push_str(&rest[..line]);
+                rest = skip_line_comment(&rest[line..]);
+            }
+            (_, Some(block)) => {
+                result.push_str(&rest[..block]);
+                result.push(' ');
+                rest = match rest[block..].find("*/") {
+                    Some(end) => &rest[block + end + 2..],
+                    None => "",
+                };
+            }
+            (None, None) => {
+                result.push_str(rest);
+                return result;
+            }
+        }
+    }
+}
+
+fn skip_line_comment(comment: &str) -> &str {
+    match comment.find('\n') {
+        Some(end) => &comment[end..],
+        None => "",
+    }
+}
+
+// Parse attributes like "group(0), binding(1)" into a list of names and values
+fn parse_attributes(attributes: &str) -> Vec<(String, String)> {
+    attributes
+        .split(',')
+        .map(|attribute| match attribute.split_once('(') {
+            Some((name, value)) => (
+                name.trim().to_string(),
+                value
+                    .trim_end_matches(|char: char| char == ')' || char.is_whitespace())
+                    .trim()
+                    .to_string(),
+            ),
+            None => (attribute.trim().to_string(), String::new()),
+        })
+        .collect()
+}
+
+fn parse_index(value: &str) -> Result<u32, String> {
+    value
+        .parse::<u32>()
+        .map_err(|_| format!("{} is not a valid index", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::shader_resource::ShaderBinding;
+    use crate::resources::shader_resource::ShaderBindingGroup;
+    use crate::resources::shader_resource::ShaderInstanceAttribute;
+
+    const SHADER: &str = "
+        struct InstanceInput {
+            [[location(5)]] model_matrix_0: vec4<f32>;
+            // [[location(9)]] tex_area_top_left: vec2<f32>;
+        };
+
+        [[group(1), binding(0)]]
+        var<uniform> camera: CameraUniform;
+
+        [[stage(vertex)]]
+        fn main(instance: InstanceInput) -> VertexOutput {}
+
+        /* [[group(0), binding(2)]]
+        var t_normal: texture_2d<f32>; */
+        [[group(0), binding(0)]]
+        var t_diffuse: texture_2d<f32>;
+        [[group(0), binding(1)]]
+        var s_diffuse: sampler;
+        [[group(2), binding(0)]]
+        var<storage, read> lights: Lights;
+        [[group(2), binding(1)]]
+        var t_output: texture_storage_2d<rgba8unorm, write>;
+
+        [[stage(fragment)]]
+        fn main(in: VertexOutput) -> [[location(0)]] vec4<f32> {}
+    ";
+
+    fn binding(ty: ShaderBindingType) -> ShaderBinding {
+        ShaderBinding {
+            ty,
+            ..Default::default()
+        }
+    }
+
+    fn build_settings() -> ShaderResourceSettings {
+        ShaderResourceSettings {
+            binding_groups: vec![
+                ShaderBindingGroup {
+                    bindings: vec![
+                        binding(ShaderBindingType::Texture),
+                        binding(ShaderBindingType::Sampler),
+                    ],
+                },
+                ShaderBindingGroup {
+                    bindings: vec![binding(ShaderBindingType::Uniform)],
+                },
+            ],
+            instance_attributes: vec![ShaderInstanceAttribute {
+                location: 5,
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn parse_reads_the_declarations_outside_the_comments() {
+        let reflection = ShaderReflection::parse(SHADER).unwrap();
+
+        assert_eq!(reflection.entry_points.len(), 2);
+        assert_eq!(reflection.locations, vec![5, 0]);
+        assert_eq!(
+            reflection
+                .bindings
+                .iter()
+                .map(|binding| (
+                    binding.group,
+                    binding.binding,
+                    binding.name.as_str(),
+                    binding.ty.clone()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, 0, "camera", Some(ShaderBindingType::Uniform)),
+                (0, 0, "t_diffuse", Some(ShaderBindingType::Texture)),
+                (0, 1, "s_diffuse", Some(ShaderBindingType::Sampler)),
+                (2, 0, "lights", None),
+                (2, 1, "t_output", None),
+            ]
+        );
+        assert_eq!(
+            ShaderReflection::from_bytes(&reflection.to_bytes()),
+            Some(reflection)
+        );
+    }
+
+    #[test]
+    fn validate_reports_the_settings_that_mismatch_the_shader() {
+        let reflection = ShaderReflection::parse(SHADER).unwrap();
+        assert_eq!(reflection.validate(&build_settings()), Ok(vec![]));
+
+        let mut settings = build_settings();
+        settings.binding_groups.truncate(1);
+        assert_eq!(reflection.validate(&settings).unwrap().len(), 1);
+
+        let mut settings = build_settings();
+        settings.binding_groups[0].bindings.swap(0, 1);
+        assert_eq!(reflection.validate(&settings).unwrap().len(), 2);
+
+        let mut settings = build_settings();
+        settings.instance_attributes[0].location = 9;
+        assert_eq!(reflection.validate(&settings).unwrap().len(), 1);
+
+        // The render pipeline cannot be built without the entry points
+        let mut reflection = reflection;
+        reflection
+            .entry_points
+            .retain(|entry_point| entry_point.stage != ShaderStage::Fragment);
+        assert!(reflection.validate(&build_settings()).is_err());
+    }
+}
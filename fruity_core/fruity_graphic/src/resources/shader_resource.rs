@@ -1,7 +1,12 @@
 use crate::graphic_service::GraphicService;
+use crate::resources::shader_reflection::ShaderReflection;
+use crate::resources::shader_reflection::SHADER_ARTIFACT_KIND;
 use fruity_any::*;
 use fruity_core::convert::FruityInto;
 use fruity_core::convert::FruityTryFrom;
+use fruity_core::resource::error::LoadResourceError;
+use fruity_core::resource::import::hash_source;
+use fruity_core::resource::import::ImportCache;
 use fruity_core::resource::resource::Resource;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::serialize::serialized::Serialized;
@@ -80,7 +85,7 @@ impl FruityInto<Serialized> for ShaderBindingVisibility {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ShaderBindingType {
     Texture,
     Sampler,
@@ -191,33 +196,66 @@ pub fn load_shader(
     reader: &mut dyn Read,
     settings: Settings,
     resource_container: ResourceContainer,
-) {
+) -> Result<(), LoadResourceError> {
     // Get the graphic service state
     let graphic_service = resource_container.require::<dyn GraphicService>();
     let graphic_service = graphic_service.read();
 
     // read the whole file
     let mut buffer = String::new();
-    if let Err(err) = reader.read_to_string(&mut buffer) {
-        log::error!("{}", err.to_string());
-        return;
-    }
+    reader.read_to_string(&mut buffer).map_err(|err| {
+        LoadResourceError::FileNotReadable(identifier.to_string(), err.to_string())
+    })?;
 
     // Parse settings
+    let source_hash = hash_source(buffer.as_bytes(), &settings);
     let settings = read_shader_settings(&settings, resource_container.clone());
 
+    // Check the shader against the settings, the reflection is read from the import cache and the
+    // shader is parsed again only if it changed
+    let import_cache = ImportCache::new(resource_container.get_file_system());
+    let reflection = match import_cache
+        .get(identifier, SHADER_ARTIFACT_KIND, source_hash)
+        .and_then(|artifact| ShaderReflection::from_bytes(&artifact))
+    {
+        Some(reflection) => Some(reflection),
+        None => match ShaderReflection::parse(&buffer) {
+            Ok(reflection) => {
+                if let Err(err) = import_cache.store(
+                    identifier,
+                    SHADER_ARTIFACT_KIND,
+                    source_hash,
+                    &reflection.to_bytes(),
+                ) {
+                    log::error!("{}", err.to_string());
+                }
+
+                Some(reflection)
+            }
+            Err(err) => {
+                log::warn!("{}: the shader is not checked, {}", identifier, err);
+                None
+            }
+        },
+    };
+
+    if let Some(reflection) = reflection {
+        reflection
+            .validate(&settings)
+            .map_err(|err| LoadResourceError::DecodeError(identifier.to_string(), err))?
+            .iter()
+            .for_each(|warning| log::warn!("{}: {}", identifier, warning));
+    }
+
     // Build the resource
     let result = graphic_service.create_shader_resource(identifier, buffer, settings);
 
     // Store the resource
-    match result {
-        Ok(resource) => {
-            resource_container.add::<dyn ShaderResource>(identifier, resource);
-        }
-        Err(err) => {
-            log::error!("{}", err);
-        }
-    }
+    let resource =
+        result.map_err(|err| LoadResourceError::DecodeError(identifier.to_string(), err))?;
+    resource_container.add::<dyn ShaderResource>(identifier, resource);
+
+    Ok(())
 }
 
 pub fn read_shader_settings(
@@ -1,10 +1,182 @@
 use crate::graphic_service::GraphicService;
+use fruity_core::resource::error::LoadResourceError;
+use fruity_core::resource::import::hash_source;
+use fruity_core::resource::import::ImportCache;
 use fruity_core::resource::resource::Resource;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::settings::Settings;
+use std::convert::TryInto;
 use std::io::Read;
 
-pub struct TextureResourceSettings {}
+/// The kind of the texture artifacts into the import cache
+pub const TEXTURE_ARTIFACT_KIND: &str = "texture";
+
+#[derive(Debug, Clone)]
+pub struct TextureResourceSettings {
+    pub mag_filter: TextureFilter,
+    pub min_filter: TextureFilter,
+    pub wrap_mode: TextureWrapMode,
+    pub srgb: bool,
+    pub mipmaps: bool,
+}
+
+impl Default for TextureResourceSettings {
+    fn default() -> Self {
+        TextureResourceSettings {
+            mag_filter: TextureFilter::Linear,
+            min_filter: TextureFilter::Nearest,
+            wrap_mode: TextureWrapMode::Clamp,
+            srgb: true,
+            mipmaps: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextureFilter {
+    Nearest,
+    Linear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextureWrapMode {
+    Clamp,
+    Repeat,
+    Mirror,
+}
+
+/// The decoded pixels of a texture, stored as RGBA with one byte per channel
+/// The first level is the full size image, the next ones are the mip levels, each one is half
+/// the size of the previous one
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextureData {
+    pub width: u32,
+    pub height: u32,
+    pub levels: Vec<Vec<u8>>,
+}
+
+impl TextureData {
+    /// Returns a TextureData without mip levels
+    ///
+    /// # Arguments
+    /// * `width` - The image width
+    /// * `height` - The image height
+    /// * `rgba` - The image pixels
+    ///
+    pub fn new(width: u32, height: u32, rgba: Vec<u8>) -> TextureData {
+        TextureData {
+            width,
+            height,
+            levels: vec![rgba],
+        }
+    }
+
+    /// Get the size of a level
+    ///
+    /// # Arguments
+    /// * `level` - The level, 0 is the full size image
+    ///
+    pub fn get_level_size(&self, level: usize) -> (u32, u32) {
+        ((self.width >> level).max(1), (self.height >> level).max(1))
+    }
+
+    /// Compute all the mip levels down to a single pixel, each pixel of a level is the average
+    /// of four pixels of the previous one
+    pub fn generate_mips(&mut self) {
+        self.levels.truncate(1);
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+
+        let mut level = 0;
+        while self.get_level_size(level) != (1, 1) {
+            let (width, height) = self.get_level_size(level);
+            let (next_width, next_height) = self.get_level_size(level + 1);
+            let pixels = &self.levels[level];
+
+            let mut next_pixels = Vec::with_capacity((next_width * next_height * 4) as usize);
+            for y in 0..next_height {
+                for x in 0..next_width {
+                    // The last row or column of an odd size is clamped
+                    let x0 = (x * 2).min(width - 1);
+                    let x1 = (x * 2 + 1).min(width - 1);
+                    let y0 = (y * 2).min(height - 1);
+                    let y1 = (y * 2 + 1).min(height - 1);
+
+                    for channel in 0..4 {
+                        let get = |x: u32, y: u32| pixels[((y * width + x) * 4 + channel) as usize];
+                        let sum = get(x0, y0) as u32
+                            + get(x1, y0) as u32
+                            + get(x0, y1) as u32
+                            + get(x1, y1) as u32;
+
+                        next_pixels.push(((sum + 2) / 4) as u8);
+                    }
+                }
+            }
+
+            self.levels.push(next_pixels);
+            level += 1;
+        }
+    }
+
+    /// Write the texture data as an artifact for the import cache
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.width.to_le_bytes());
+        bytes.extend_from_slice(&self.height.to_le_bytes());
+        bytes.extend_from_slice(&(self.levels.len() as u32).to_le_bytes());
+        self.levels
+            .iter()
+            .for_each(|level| bytes.extend_from_slice(level));
+
+        bytes
+    }
+
+    /// Read the texture data from an artifact of the import cache, returns None if the artifact
+    /// is corrupted
+    ///
+    /// # Arguments
+    /// * `bytes` - The artifact content
+    ///
+    pub fn from_bytes(bytes: &[u8]) -> Option<TextureData> {
+        let read_u32 = |offset: usize| -> Option<u32> {
+            Some(u32::from_le_bytes(
+                bytes.get(offset..offset + 4)?.try_into().ok()?,
+            ))
+        };
+
+        let mut data = TextureData {
+            width: read_u32(0)?,
+            height: read_u32(4)?,
+            levels: Vec::new(),
+        };
+
+        let level_count = read_u32(8)? as usize;
+        if level_count > 32 {
+            return None;
+        }
+
+        // The sizes are read from the artifact, so they are checked before being used
+        let mut offset = 12usize;
+        for level in 0..level_count {
+            let (width, height) = data.get_level_size(level);
+            let size = (width as usize)
+                .checked_mul(height as usize)?
+                .checked_mul(4)?;
+            let end = offset.checked_add(size)?;
+
+            data.levels.push(bytes.get(offset..end)?.to_vec());
+            offset = end;
+        }
+
+        if offset == bytes.len() {
+            Some(data)
+        } else {
+            None
+        }
+    }
+}
 
 pub trait TextureResource: Resource {
     fn get_size(&self) -> (u32, u32);
@@ -15,35 +187,145 @@ pub fn load_texture(
     reader: &mut dyn Read,
     settings: Settings,
     resource_container: ResourceContainer,
-) {
+) -> Result<(), LoadResourceError> {
     // Get the graphic service state
     let graphic_service = resource_container.require::<dyn GraphicService>();
     let graphic_service = graphic_service.read();
 
     // read the whole file
     let mut buffer = Vec::new();
-    if let Err(err) = reader.read_to_end(&mut buffer) {
-        log::error!("{}", err.to_string());
-        return;
-    }
+    reader.read_to_end(&mut buffer).map_err(|err| {
+        LoadResourceError::FileNotReadable(identifier.to_string(), err.to_string())
+    })?;
 
     // Parse settings
-    let settings = read_texture_settings(&settings);
+    let source_hash = hash_source(&buffer, &settings);
+    let settings = read_texture_settings(&settings)?;
+
+    // Get the decoded pixels from the import cache, the image is decoded only if it changed
+    let import_cache = ImportCache::new(resource_container.get_file_system());
+    let cached_data = import_cache
+        .get(identifier, TEXTURE_ARTIFACT_KIND, source_hash)
+        .and_then(|artifact| TextureData::from_bytes(&artifact));
+
+    let data = match cached_data {
+        Some(data) => data,
+        None => {
+            let mut data = graphic_service
+                .decode_texture(&buffer)
+                .map_err(|err| LoadResourceError::DecodeError(identifier.to_string(), err))?;
+
+            if settings.mipmaps {
+                data.generate_mips();
+            }
+
+            if let Err(err) = import_cache.store(
+                identifier,
+                TEXTURE_ARTIFACT_KIND,
+                source_hash,
+                &data.to_bytes(),
+            ) {
+                log::error!("{}", err.to_string());
+            }
+
+            data
+        }
+    };
 
     // Build the resource
-    let result = graphic_service.create_texture_resource(identifier, &buffer, settings);
+    let result = graphic_service.create_texture_resource(identifier, data, settings);
 
     // Store the resource
-    match result {
-        Ok(resource) => {
-            resource_container.add::<dyn TextureResource>(identifier, resource);
-        }
-        Err(err) => {
-            log::error!("{}", err);
-        }
+    let resource =
+        result.map_err(|err| LoadResourceError::DecodeError(identifier.to_string(), err))?;
+    resource_container.add::<dyn TextureResource>(identifier, resource);
+
+    Ok(())
+}
+
+pub fn read_texture_settings(
+    settings: &Settings,
+) -> Result<TextureResourceSettings, LoadResourceError> {
+    let default = TextureResourceSettings::default();
+
+    Ok(TextureResourceSettings {
+        mag_filter: read_texture_filter(settings, "mag_filter", default.mag_filter)?,
+        min_filter: read_texture_filter(settings, "min_filter", default.min_filter)?,
+        wrap_mode: match settings.get::<Option<String>>("wrap_mode", None).as_deref() {
+            Some("clamp") => TextureWrapMode::Clamp,
+            Some("repeat") => TextureWrapMode::Repeat,
+            Some("mirror") => TextureWrapMode::Mirror,
+            Some(value) => {
+                return Err(LoadResourceError::InvalidSettingsField(
+                    "wrap_mode".to_string(),
+                    format!(
+                        "\"{}\" is not a wrap mode, expected clamp, repeat or mirror",
+                        value
+                    ),
+                ))
+            }
+            None => default.wrap_mode,
+        },
+        srgb: settings.get::<bool>("srgb", default.srgb),
+        mipmaps: settings.get::<bool>("mipmaps", default.mipmaps),
+    })
+}
+
+fn read_texture_filter(
+    settings: &Settings,
+    field: &str,
+    default: TextureFilter,
+) -> Result<TextureFilter, LoadResourceError> {
+    match settings.get::<Option<String>>(field, None).as_deref() {
+        Some("nearest") => Ok(TextureFilter::Nearest),
+        Some("linear") => Ok(TextureFilter::Linear),
+        Some(value) => Err(LoadResourceError::InvalidSettingsField(
+            field.to_string(),
+            format!("\"{}\" is not a filter, expected nearest or linear", value),
+        )),
+        None => Ok(default),
     }
 }
 
-pub fn read_texture_settings(_settings: &Settings) -> TextureResourceSettings {
-    TextureResourceSettings {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_mips_averages_down_to_a_single_pixel() {
+        let mut data = TextureData::new(
+            3,
+            2,
+            vec![
+                0, 0, 0, 255, 100, 100, 100, 255, 40, 40, 40, 255, //
+                20, 20, 20, 255, 80, 80, 80, 255, 40, 40, 40, 255,
+            ],
+        );
+        data.generate_mips();
+
+        assert_eq!(data.levels.len(), 2);
+        assert_eq!(data.get_level_size(1), (1, 1));
+        assert_eq!(data.levels[1], vec![50, 50, 50, 255]);
+    }
+
+    #[test]
+    fn texture_data_round_trips_through_bytes() {
+        let mut data = TextureData::new(4, 4, (0..64).collect::<Vec<u8>>());
+        data.generate_mips();
+
+        let bytes = data.to_bytes();
+        assert_eq!(TextureData::from_bytes(&bytes), Some(data));
+        assert_eq!(TextureData::from_bytes(&bytes[..bytes.len() - 1]), None);
+    }
+
+    #[test]
+    fn texture_data_with_a_huge_size_is_rejected() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&[0, 0, 0, 255]);
+
+        assert_eq!(TextureData::from_bytes(&bytes), None);
+    }
 }
@@ -0,0 +1,12 @@
+use fruity_any::*;
+use fruity_ecs::*;
+use fruity_graphic::math::vector2d::Vector2d;
+
+/// Smooth the transform of an entity that is moved in the fixed stages, like the physic bodies
+///
+/// The transform is interpolated between the translation and the rotation of the two last fixed steps
+#[derive(Debug, Clone, Component, Default, FruityAny)]
+pub struct Interpolate2d {
+    pub previous_vec: Vector2d,
+    pub previous_angle: f32,
+}
@@ -1,4 +1,5 @@
 pub mod camera;
+pub mod interpolate_2d;
 pub mod rotate_2d;
 pub mod scale_2d;
 pub mod sprite;
@@ -1,4 +1,5 @@
 use crate::components::camera::Camera;
+use crate::components::interpolate_2d::Interpolate2d;
 use crate::components::rotate_2d::Rotate2d;
 use crate::components::scale_2d::Scale2d;
 use crate::components::sprite::Sprite;
@@ -7,12 +8,16 @@ use crate::components::translate_2d::Translate2d;
 use crate::graphic_2d_service::Graphic2dService;
 use crate::systems::draw_camera::draw_camera;
 use crate::systems::draw_sprite::draw_sprite;
+use crate::systems::initialize_interpolate_2d::initialize_interpolate_2d;
+use crate::systems::store_interpolate_2d::store_interpolate_2d;
 use crate::systems::update_transform_2d::update_transform_2d;
 use fruity_core::inject::Inject1;
 use fruity_core::inject::Inject2;
+use fruity_core::inject::Inject3;
 use fruity_core::object_factory_service::ObjectFactoryService;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::settings::Settings;
+use fruity_ecs::system::system_service::StartupSystemParams;
 use fruity_ecs::system::system_service::SystemParams;
 use fruity_ecs::system::system_service::SystemService;
 
@@ -38,17 +43,37 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
     object_factory_service.register::<Scale2d>("Scale2d");
     object_factory_service.register::<Sprite>("Sprite");
     object_factory_service.register::<Camera>("Camera");
+    object_factory_service.register::<Interpolate2d>("Interpolate2d");
 
     let system_service = resource_container.require::<SystemService>();
     let mut system_service = system_service.write();
 
+    system_service.add_startup_system(
+        "initialize_interpolate_2d",
+        MODULE_NAME,
+        Inject1::new(initialize_interpolate_2d),
+        StartupSystemParams { ignore_pause: true },
+    );
+
+    system_service.add_system(
+        "store_interpolate_2d",
+        MODULE_NAME,
+        Inject1::new(store_interpolate_2d),
+        SystemParams {
+            stage: "pre_fixed_update".to_string(),
+            ignore_pause: true,
+            ..Default::default()
+        },
+    );
+
     system_service.add_system(
         "update_transform_2d",
         MODULE_NAME,
-        Inject1::new(update_transform_2d),
+        Inject3::new(update_transform_2d),
         SystemParams {
-            pool_index: 95,
+            stage: "transform".to_string(),
             ignore_pause: true,
+            ..Default::default()
         },
     );
 
@@ -57,8 +82,9 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         MODULE_NAME,
         Inject2::new(draw_sprite),
         SystemParams {
-            pool_index: 98,
+            stage: "draw".to_string(),
             ignore_pause: true,
+            ..Default::default()
         },
     );
 
@@ -67,8 +93,9 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         MODULE_NAME,
         Inject2::new(draw_camera),
         SystemParams {
-            pool_index: 99,
+            stage: "camera".to_string(),
             ignore_pause: true,
+            ..Default::default()
         },
     );
 
@@ -0,0 +1,31 @@
+use crate::Interpolate2d;
+use crate::Rotate2d;
+use crate::Translate2d;
+use fruity_ecs::entity::entity_query::with::WithMut;
+use fruity_ecs::entity::entity_query::with::WithOptional;
+use fruity_ecs::entity::entity_query::Query;
+use fruity_ecs::system::system_service::StartupDisposeSystemCallback;
+
+pub fn initialize_interpolate_2d(
+    query: Query<(
+        WithMut<Interpolate2d>,
+        WithOptional<Translate2d>,
+        WithOptional<Rotate2d>,
+    )>,
+) -> StartupDisposeSystemCallback {
+    let handle = query.on_created(move |(mut interpolate_2d, translate_2d, rotate_2d)| {
+        if let Some(translate_2d) = translate_2d {
+            interpolate_2d.previous_vec = translate_2d.vec;
+        }
+
+        if let Some(rotate_2d) = rotate_2d {
+            interpolate_2d.previous_angle = rotate_2d.angle;
+        }
+
+        None
+    });
+
+    Some(Box::new(move || {
+        handle.dispose_by_ref();
+    }))
+}
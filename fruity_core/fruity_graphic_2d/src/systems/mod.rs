@@ -1,3 +1,5 @@
 pub mod draw_camera;
 pub mod draw_sprite;
+pub mod initialize_interpolate_2d;
+pub mod store_interpolate_2d;
 pub mod update_transform_2d;
@@ -1,33 +1,120 @@
+use crate::Interpolate2d;
 use crate::Rotate2d;
 use crate::Scale2d;
 use crate::Transform2d;
 use crate::Translate2d;
+use fruity_core::inject::Const;
+use fruity_ecs::entity::entity_query::changed::Changed;
+use fruity_ecs::entity::entity_query::changed::Or;
+use fruity_ecs::entity::entity_query::with::With;
 use fruity_ecs::entity::entity_query::with::WithMut;
 use fruity_ecs::entity::entity_query::with::WithOptional;
+use fruity_ecs::entity::entity_query::without::Without;
 use fruity_ecs::entity::entity_query::Query;
 use fruity_graphic::math::matrix3::Matrix3;
+use fruity_graphic::math::vector2d::Vector2d;
+use fruity_windows::frame_service::FrameService;
+use std::f32::consts::PI;
+use std::f32::consts::TAU;
+
+type InterpolatedTransformQuery = Query<(
+    WithMut<Transform2d>,
+    WithOptional<Translate2d>,
+    WithOptional<Rotate2d>,
+    WithOptional<Scale2d>,
+    With<Interpolate2d>,
+)>;
+
+type TransformChanged =
+    Or<Changed<Transform2d>, Or<Changed<Translate2d>, Or<Changed<Rotate2d>, Changed<Scale2d>>>>;
+
+type ChangedTransformQuery = Query<(
+    WithMut<Transform2d>,
+    WithOptional<Translate2d>,
+    WithOptional<Rotate2d>,
+    WithOptional<Scale2d>,
+    Without<Interpolate2d>,
+    TransformChanged,
+)>;
 
 pub fn update_transform_2d(
-    query: Query<(
-        WithMut<Transform2d>,
-        WithOptional<Translate2d>,
-        WithOptional<Rotate2d>,
-        WithOptional<Scale2d>,
-    )>,
+    frame_service: Const<FrameService>,
+    interpolated_query: InterpolatedTransformQuery,
+    changed_query: ChangedTransformQuery,
 ) {
-    query.for_each(|(mut transform, translate_2d, rotate_2d, scale_2d)| {
-        transform.transform = Matrix3::new_identity();
+    // The interpolated transforms move every frame, the other ones are rebuilt when a component
+    // is changed, a transform changed by an other system, like the hierarchy cascade that
+    // applies the parent transform, is also rebuilt
+    let alpha = frame_service.get_interpolation_alpha();
+    interpolated_query.for_each(
+        |(mut transform, translate_2d, rotate_2d, scale_2d, interpolate_2d)| {
+            let vec = translate_2d
+                .map(|translate_2d| interpolate_2d.previous_vec.lerp(translate_2d.vec, alpha));
+            let angle = rotate_2d
+                .map(|rotate_2d| lerp_angle(interpolate_2d.previous_angle, rotate_2d.angle, alpha));
+
+            transform.transform =
+                build_transform(vec, angle, scale_2d.map(|scale_2d| scale_2d.vec));
+        },
+    );
+
+    changed_query.for_each(|(mut transform, translate_2d, rotate_2d, scale_2d, _, _)| {
+        transform.transform = build_transform(
+            translate_2d.map(|translate_2d| translate_2d.vec),
+            rotate_2d.map(|rotate_2d| rotate_2d.angle),
+            scale_2d.map(|scale_2d| scale_2d.vec),
+        );
+    });
+}
+
+// Interpolate between two angles through the shortest way, so a rotation that crosses π don't
+// spin the long way around
+fn lerp_angle(previous: f32, angle: f32, alpha: f32) -> f32 {
+    let delta = (angle - previous).rem_euclid(TAU);
+    let delta = if delta > PI { delta - TAU } else { delta };
+
+    previous + delta * alpha
+}
+
+fn build_transform(vec: Option<Vector2d>, angle: Option<f32>, scale: Option<Vector2d>) -> Matrix3 {
+    let mut transform = Matrix3::new_identity();
+
+    if let Some(vec) = vec {
+        transform = transform * Matrix3::new_translation(vec);
+    }
+
+    if let Some(angle) = angle {
+        transform = transform * Matrix3::new_rotation(angle);
+    }
+
+    if let Some(scale) = scale {
+        transform = transform * Matrix3::new_scaling(scale);
+    }
+
+    transform
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if let Some(translate_2d) = translate_2d {
-            transform.transform = transform.transform * Matrix3::new_translation(translate_2d.vec);
-        }
+    fn assert_angle_eq(result: f32, expected: f32) {
+        assert!(
+            (result - expected).abs() < 0.0001,
+            "{} is not {}",
+            result,
+            expected
+        );
+    }
 
-        if let Some(rotate_2d) = rotate_2d {
-            transform.transform = transform.transform * Matrix3::new_rotation(rotate_2d.angle);
-        }
+    #[test]
+    fn lerp_angle_takes_the_shortest_way_test() {
+        assert_angle_eq(lerp_angle(0.0, 1.0, 0.5), 0.5);
+        assert_angle_eq(lerp_angle(1.0, -1.0, 0.25), 0.5);
 
-        if let Some(scale_2d) = scale_2d {
-            transform.transform = transform.transform * Matrix3::new_scaling(scale_2d.vec);
-        }
-    })
+        // From about π to about -π, the angle crosses π instead of going through 0
+        assert_angle_eq(lerp_angle(PI - 0.1, -PI + 0.1, 0.5), PI);
+        assert_angle_eq(lerp_angle(-PI + 0.1, PI - 0.1, 0.5), -PI);
+        assert_angle_eq(lerp_angle(PI - 0.1, -PI + 0.1, 1.0), PI + 0.1);
+    }
 }
@@ -1,10 +1,201 @@
 use fruity_any::*;
+use fruity_core::convert::FruityInto;
+use fruity_core::convert::FruityTryFrom;
+use fruity_core::serialize::serialized::Serialized;
 use fruity_core::signal::SignalProperty;
+use fruity_ecs::entity::entity::EntityGuid;
 use fruity_ecs::entity::entity::EntityId;
+use fruity_ecs::entity::entity_ref::EntityRef;
 use fruity_ecs::*;
+use std::collections::HashMap;
 
+/// Attach an entity to a parent entity
+///
+/// The version 1 stored the parent as an entity id, the version 2 stores an [’EntityRef’] so
+/// the parent is found back from its guid when a scene is loaded
 #[derive(Debug, Clone, Default, Component, FruityAny)]
+#[schema_version(2)]
 pub struct Parent {
-    pub parent_id: SignalProperty<Option<EntityId>>,
+    pub parent_id: SignalProperty<Option<EntityRef>>,
     pub nested_level: usize,
 }
+
+/// Upgrade a Parent component saved with the version 1, the entity id becomes an entity
+/// reference that is remapped like the other references when the scene is restored
+///
+/// # Arguments
+/// * `fields` - The fields of the serialized component
+///
+pub fn migrate_parent_from_v1(
+    mut fields: HashMap<String, Serialized>,
+) -> HashMap<String, Serialized> {
+    let parent_id = fields
+        .get("parent_id")
+        .filter(|parent_id| !matches!(parent_id, Serialized::SerializedObject { .. }))
+        .and_then(|parent_id| EntityId::fruity_try_from(parent_id.clone()).ok());
+
+    if let Some(parent_id) = parent_id {
+        fields.insert(
+            "parent_id".to_string(),
+            EntityRef::new(parent_id, EntityGuid::default()).fruity_into(),
+        );
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fruity_core::serialize::yaml::deserialize_yaml;
+    use fruity_core::settings::Settings;
+    use fruity_core::world::World;
+    use fruity_ecs::component::component::AnyComponent;
+    use fruity_ecs::entity::entity_service::EntityService;
+    use fruity_ecs::entity::entity_service::EntityServiceSnapshot;
+    use std::ops::Deref;
+
+    fn world() -> World {
+        let world = World::new();
+        fruity_ecs::initialize(world.resource_container.clone());
+        crate::initialize(
+            world.resource_container.clone(),
+            &Settings::Object(HashMap::new()),
+        );
+
+        world
+    }
+
+    fn find_entity(
+        entity_service: &EntityService,
+        entity_ids: &[EntityId],
+        name: &str,
+    ) -> EntityRef {
+        entity_ids
+            .iter()
+            .find_map(|entity_id| {
+                let entity = entity_service.get_entity(*entity_id)?;
+                let entity = entity.read();
+
+                if entity.get_name() == name {
+                    Some(EntityRef::new(entity.get_entity_id(), entity.get_guid()))
+                } else {
+                    None
+                }
+            })
+            .unwrap()
+    }
+
+    fn read_parent(entity_service: &EntityService, entity_id: EntityId) -> Option<EntityRef> {
+        let entity = entity_service.get_entity(entity_id).unwrap();
+        let entity = entity.read();
+        let parent = entity.read_single_component::<Parent>().unwrap();
+
+        *parent.parent_id.deref()
+    }
+
+    fn create_child(entity_service: &EntityService, name: &str, parent: EntityId) -> EntityId {
+        let parent_ref = entity_service.get_entity_ref(parent);
+        entity_service.create(
+            name,
+            true,
+            vec![AnyComponent::new(Parent {
+                parent_id: SignalProperty::new(parent_ref),
+                nested_level: 1,
+            })],
+        )
+    }
+
+    #[test]
+    fn parent_saved_as_an_entity_id_is_remapped_on_additive_load_test() {
+        let world = world();
+        let entity_service = world.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        // Those entities use the ids stored in the scene
+        let existing_ids = (0..3)
+            .map(|_| entity_service.create("Existing", true, vec![]))
+            .collect::<Vec<_>>();
+
+        let scene = deserialize_yaml(
+            &mut "
+- class_name: Entity
+  fields:
+    entity_id: 1
+    name: Root
+    enabled: true
+    components: []
+- class_name: Entity
+  fields:
+    entity_id: 2
+    name: Child
+    enabled: true
+    components:
+    - class_name: Parent
+      fields:
+        parent_id: 1
+        nested_level: 1
+"
+            .as_bytes(),
+        )
+        .unwrap();
+
+        let scene = entity_service.restore_additive(&EntityServiceSnapshot(scene));
+        let scene_entities = entity_service.get_scene_entities(scene);
+        let root = find_entity(&entity_service, &scene_entities, "Root");
+        let child = find_entity(&entity_service, &scene_entities, "Child");
+
+        assert!(!existing_ids.contains(&root.entity_id));
+        assert_eq!(read_parent(&entity_service, child.entity_id), Some(root));
+    }
+
+    #[test]
+    fn parent_is_kept_on_snapshot_restore_test() {
+        let world = world();
+        let entity_service = world.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        let root_id = entity_service.create("Root", true, vec![]);
+        let child_id = create_child(&entity_service, "Child", root_id);
+        let root_guid = entity_service.get_entity_ref(root_id).unwrap().guid;
+
+        let snapshot = entity_service.snapshot();
+        entity_service.restore(&snapshot);
+
+        let root = entity_service.get_entity_by_guid(root_guid).unwrap();
+        let root = root.read();
+        assert_eq!(
+            read_parent(&entity_service, child_id),
+            Some(EntityRef::new(root.get_entity_id(), root_guid))
+        );
+    }
+
+    #[test]
+    fn duplicate_remaps_the_parents_inside_the_copy_test() {
+        let world = world();
+        let entity_service = world.resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+
+        let root_id = entity_service.create("Root", true, vec![]);
+        let child_id = create_child(&entity_service, "Child", root_id);
+
+        // The copied child points to the copied root
+        let copy_ids = entity_service.duplicate(&[root_id, child_id]);
+        assert_eq!(copy_ids.len(), 2);
+        assert!(!copy_ids.contains(&root_id) && !copy_ids.contains(&child_id));
+
+        let root_copy = entity_service.get_entity_ref(copy_ids[0]).unwrap();
+        assert_ne!(
+            root_copy.guid,
+            entity_service.get_entity_ref(root_id).unwrap().guid
+        );
+        assert_eq!(read_parent(&entity_service, copy_ids[1]), Some(root_copy));
+
+        // A child copied alone keeps its parent
+        let copy_ids = entity_service.duplicate(&[child_id]);
+        assert_eq!(
+            read_parent(&entity_service, copy_ids[0]),
+            entity_service.get_entity_ref(root_id)
+        );
+    }
+}
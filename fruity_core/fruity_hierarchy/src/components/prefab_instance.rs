@@ -0,0 +1,47 @@
+use fruity_any::*;
+use fruity_ecs::entity::entity::EntityId;
+use fruity_ecs::*;
+
+/// Mark an entity as instantiated from a prefab
+#[derive(Debug, Clone, Default, Component, FruityAny)]
+pub struct PrefabInstance {
+    /// The prefab resource identifier
+    pub prefab: String,
+
+    /// The id of the entity inside the prefab
+    pub prefab_entity_id: EntityId,
+
+    /// The fields that are not updated anymore when the prefab changes, formatted as
+    /// "Component.field" for the first component of a class and "Component[index].field" for
+    /// the next ones
+    pub overrides: Vec<String>,
+}
+
+impl PrefabInstance {
+    /// Get the override key of a component field
+    ///
+    /// # Arguments
+    /// * `component` - The component class name
+    /// * `index` - The position of the component between the components of the same class
+    /// * `field` - The field name
+    ///
+    pub fn override_key(component: &str, index: usize, field: &str) -> String {
+        if index == 0 {
+            format!("{}.{}", component, field)
+        } else {
+            format!("{}[{}].{}", component, index, field)
+        }
+    }
+
+    /// Is a component field overridden by the instance
+    ///
+    /// # Arguments
+    /// * `component` - The component class name
+    /// * `index` - The position of the component between the components of the same class
+    /// * `field` - The field name
+    ///
+    pub fn is_overridden(&self, component: &str, index: usize, field: &str) -> bool {
+        self.overrides
+            .contains(&Self::override_key(component, index, field))
+    }
+}
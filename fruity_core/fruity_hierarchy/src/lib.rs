@@ -1,14 +1,23 @@
+use crate::components::parent::migrate_parent_from_v1;
 use crate::components::parent::Parent;
+use crate::components::prefab_instance::PrefabInstance;
+use crate::prefab_service::PrefabService;
+use crate::resources::prefab_resource::load_prefab;
 use crate::systems::delete_cascade::delete_cascade;
+use crate::systems::update_hierarchy_index::update_hierarchy_index;
 use crate::systems::update_nested_level::update_nested_level;
+use fruity_core::inject::Inject1;
 use fruity_core::inject::Inject2;
 use fruity_core::object_factory_service::ObjectFactoryService;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::settings::Settings;
+use fruity_ecs::component::component_schema_service::ComponentSchemaService;
 use fruity_ecs::system::system_service::StartupSystemParams;
 use fruity_ecs::system::system_service::SystemService;
 
 pub mod components;
+pub mod prefab_service;
+pub mod resources;
 pub mod systems;
 
 /// The module name
@@ -16,10 +25,20 @@ pub static MODULE_NAME: &str = "fruity_hierarchy";
 
 // #[no_mangle]
 pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
+    let prefab_service = PrefabService::new(resource_container.clone());
+    resource_container.add::<PrefabService>("prefab_service", Box::new(prefab_service));
+
+    resource_container.add_resource_loader("frpf", load_prefab);
+
     let object_factory_service = resource_container.require::<ObjectFactoryService>();
     let mut object_factory_service = object_factory_service.write();
 
     object_factory_service.register::<Parent>("Parent");
+    object_factory_service.register::<PrefabInstance>("PrefabInstance");
+
+    let component_schema_service = resource_container.require::<ComponentSchemaService>();
+    let mut component_schema_service = component_schema_service.write();
+    component_schema_service.register_migration("Parent", 1, migrate_parent_from_v1);
 
     let system_service = resource_container.require::<SystemService>();
     let mut system_service = system_service.write();
@@ -27,7 +46,13 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
     system_service.add_startup_system(
         "delete_cascade",
         MODULE_NAME,
-        Inject2::new(delete_cascade),
+        Inject1::new(delete_cascade),
+        StartupSystemParams { ignore_pause: true },
+    );
+    system_service.add_startup_system(
+        "update_hierarchy_index",
+        MODULE_NAME,
+        Inject2::new(update_hierarchy_index),
         StartupSystemParams { ignore_pause: true },
     );
     system_service.add_startup_system(
@@ -0,0 +1,689 @@
+use crate::components::prefab_instance::PrefabInstance;
+use crate::resources::prefab_resource::PrefabEntity;
+use crate::resources::prefab_resource::PrefabResource;
+use fruity_any::*;
+use fruity_core::convert::FruityInto;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodCaller;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::introspect::SetterCaller;
+use fruity_core::object_factory_service::ObjectFactoryService;
+use fruity_core::resource::resource::Resource;
+use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::resource::resource_reference::ResourceReference;
+use fruity_core::serialize::serialized::Serialized;
+use fruity_core::serialize::Deserialize;
+use fruity_core::utils::introspect::cast_introspect_ref;
+use fruity_core::utils::introspect::ArgumentCaster;
+use fruity_ecs::component::component::AnyComponent;
+use fruity_ecs::component::component::Component;
+use fruity_ecs::entity::entity::EntityId;
+use fruity_ecs::entity::entity_ref::EntityRef;
+use fruity_ecs::entity::entity_reference::EntityReference;
+use fruity_ecs::entity::entity_service::EntityService;
+use fruity_ecs::entity::snapshot_diff::serialized_equals;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A value that replaces a prefab field when instantiating a prefab
+#[derive(Debug, Clone)]
+pub struct PrefabOverride {
+    /// The id of the entity inside the prefab
+    pub prefab_entity_id: EntityId,
+
+    /// The component class name
+    pub component: String,
+
+    /// The position of the component between the components of the same class
+    pub index: usize,
+
+    /// The field name
+    pub field: String,
+
+    /// The value that replaces the prefab one
+    pub value: Serialized,
+}
+
+/// A service to instantiate the prefabs and to keep the instances up to date
+#[derive(FruityAny)]
+pub struct PrefabService {
+    resource_container: ResourceContainer,
+    entity_service: ResourceReference<EntityService>,
+    object_factory_service: ResourceReference<ObjectFactoryService>,
+}
+
+impl Debug for PrefabService {
+    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        Ok(())
+    }
+}
+
+impl PrefabService {
+    pub fn new(resource_container: ResourceContainer) -> PrefabService {
+        PrefabService {
+            resource_container: resource_container.clone(),
+            entity_service: resource_container.require::<EntityService>(),
+            object_factory_service: resource_container.require::<ObjectFactoryService>(),
+        }
+    }
+
+    /// Create the entities of a prefab
+    /// Returns the id of the first root entity
+    ///
+    /// # Arguments
+    /// * `identifier` - The prefab resource identifier
+    /// * `parent_id` - The entity that will be the parent of the prefab roots
+    /// * `overrides` - The values that replace the prefab ones
+    ///
+    pub fn instantiate(
+        &self,
+        identifier: &str,
+        parent_id: Option<EntityId>,
+        overrides: Vec<PrefabOverride>,
+    ) -> Option<EntityId> {
+        let prefab = if let Some(prefab) = self.resource_container.get::<PrefabResource>(identifier)
+        {
+            prefab
+        } else {
+            log::error!("Failed to instantiate the unknown prefab {}", identifier);
+            return None;
+        };
+
+        let prefab = prefab.read();
+        let entity_service = self.entity_service.read();
+        let object_factory_service = self.object_factory_service.read();
+
+        // Parents are created first so the children can reference their new ids
+        let mut id_map = HashMap::<EntityId, EntityId>::new();
+        let mut root_id = None;
+        for prefab_entity in prefab.get_ordered_entities() {
+            let entity_parent_id = match prefab.get_parent_id(prefab_entity) {
+                Some(prefab_parent_id) if id_map.contains_key(&prefab_parent_id) => {
+                    id_map.get(&prefab_parent_id).cloned()
+                }
+                _ => parent_id,
+            };
+
+            let entity_overrides = overrides
+                .iter()
+                .filter(|prefab_override| {
+                    prefab_override.prefab_entity_id == prefab_entity.entity_id
+                })
+                .collect::<Vec<_>>();
+
+            let entity_parent_ref = entity_parent_id
+                .and_then(|entity_parent_id| entity_service.get_entity_ref(entity_parent_id));
+
+            let mut components =
+                build_instance_components(prefab_entity, entity_parent_ref, &entity_overrides)
+                    .iter()
+                    .filter_map(|component| {
+                        AnyComponent::deserialize(component, &object_factory_service)
+                    })
+                    .collect::<Vec<_>>();
+
+            components.push(AnyComponent::new(PrefabInstance {
+                prefab: identifier.to_string(),
+                prefab_entity_id: prefab_entity.entity_id,
+                overrides: entity_overrides
+                    .iter()
+                    .map(|prefab_override| {
+                        PrefabInstance::override_key(
+                            &prefab_override.component,
+                            prefab_override.index,
+                            &prefab_override.field,
+                        )
+                    })
+                    .collect(),
+            }));
+
+            let entity_id =
+                entity_service.create(&prefab_entity.name, prefab_entity.enabled, components);
+            id_map.insert(prefab_entity.entity_id, entity_id);
+
+            if root_id.is_none() {
+                root_id = Some(entity_id);
+            }
+        }
+
+        root_id
+    }
+
+    /// Set a field of a prefab instance, the field will not follow the prefab changes anymore
+    ///
+    /// # Arguments
+    /// * `entity_id` - The instance entity id
+    /// * `component` - The component class name
+    /// * `index` - The position of the component between the components of the same class
+    /// * `field` - The field name
+    /// * `value` - The new value
+    ///
+    pub fn set_override(
+        &self,
+        entity_id: EntityId,
+        component: &str,
+        index: usize,
+        field: &str,
+        value: Serialized,
+    ) {
+        let entity_reference = if let Some(entity_reference) = self.get_entity(entity_id) {
+            entity_reference
+        } else {
+            return;
+        };
+
+        let object_factory_service = self.object_factory_service.read();
+        let value = value
+            .serialize_native_objects()
+            .deserialize_native_objects(&object_factory_service);
+
+        let entity = entity_reference.write();
+        if let Some(mut component) = entity
+            .iter_components_from_type_identifier_mut(component)
+            .nth(index)
+        {
+            set_component_field(&mut *component, field, value);
+        } else {
+            return;
+        }
+
+        if let Some(mut prefab_instance) = entity.write_single_component::<PrefabInstance>() {
+            if !prefab_instance.is_overridden(component, index, field) {
+                prefab_instance
+                    .overrides
+                    .push(PrefabInstance::override_key(component, index, field));
+            }
+        };
+    }
+
+    /// Remove an override of a prefab instance, the field takes back the prefab value
+    ///
+    /// # Arguments
+    /// * `entity_id` - The instance entity id
+    /// * `component` - The component class name
+    /// * `index` - The position of the component between the components of the same class
+    /// * `field` - The field name
+    ///
+    pub fn revert_override(&self, entity_id: EntityId, component: &str, index: usize, field: &str) {
+        let entity_reference = if let Some(entity_reference) = self.get_entity(entity_id) {
+            entity_reference
+        } else {
+            return;
+        };
+
+        let entity = entity_reference.write();
+        let prefab_instance =
+            if let Some(mut prefab_instance) = entity.write_single_component::<PrefabInstance>() {
+                let key = PrefabInstance::override_key(component, index, field);
+                prefab_instance
+                    .overrides
+                    .retain(|override_key| *override_key != key);
+
+                prefab_instance.deref().clone()
+            } else {
+                return;
+            };
+
+        let prefab = if let Some(prefab) = self
+            .resource_container
+            .get::<PrefabResource>(&prefab_instance.prefab)
+        {
+            prefab
+        } else {
+            return;
+        };
+
+        let prefab = prefab.read();
+        let object_factory_service = self.object_factory_service.read();
+        let value = prefab
+            .get_entity(prefab_instance.prefab_entity_id)
+            .and_then(|prefab_entity| get_prefab_field(prefab_entity, component, index, field));
+
+        if let Some(value) = value {
+            if let Some(mut instance_component) = entity
+                .iter_components_from_type_identifier_mut(component)
+                .nth(index)
+            {
+                set_component_field(
+                    &mut *instance_component,
+                    field,
+                    value.deserialize_native_objects(&object_factory_service),
+                );
+            }
+        }
+    }
+
+    /// Replace the content of a prefab and update all the instances
+    ///
+    /// The fields that are overridden by an instance are kept, a field that has been changed on
+    /// the instance since the previous prefab version is considered as overridden
+    ///
+    /// # Arguments
+    /// * `identifier` - The prefab resource identifier
+    /// * `prefab` - The new prefab content
+    ///
+    pub fn update_prefab(&self, identifier: &str, prefab: PrefabResource) {
+        let prefab_reference = if let Some(prefab_reference) =
+            self.resource_container.get::<PrefabResource>(identifier)
+        {
+            prefab_reference
+        } else {
+            self.resource_container
+                .add::<PrefabResource>(identifier, Box::new(prefab));
+            return;
+        };
+
+        let old_prefab = {
+            let mut prefab_writer = prefab_reference.write();
+            std::mem::replace(&mut *prefab_writer, prefab)
+        };
+
+        let prefab = prefab_reference.read();
+        let entity_service = self.entity_service.read();
+        let object_factory_service = self.object_factory_service.read();
+
+        let instances = entity_service
+            .iter_all_entities()
+            .filter_map(|entity| {
+                let prefab_instance = entity
+                    .read()
+                    .read_single_component::<PrefabInstance>()
+                    .map(|prefab_instance| prefab_instance.deref().clone());
+
+                match prefab_instance {
+                    Some(prefab_instance) if prefab_instance.prefab == identifier => {
+                        Some((entity, prefab_instance))
+                    }
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for (entity, prefab_instance) in instances {
+            let prefab_entity =
+                if let Some(prefab_entity) = prefab.get_entity(prefab_instance.prefab_entity_id) {
+                    prefab_entity
+                } else {
+                    continue;
+                };
+
+            let old_prefab_entity = old_prefab.get_entity(prefab_instance.prefab_entity_id);
+            let missing_components = update_instance(
+                &entity,
+                &prefab_instance,
+                prefab_entity,
+                old_prefab_entity,
+                &object_factory_service,
+            );
+
+            if !missing_components.is_empty() {
+                let entity_id = entity.read().get_entity_id();
+                entity_service
+                    .add_component(entity_id, missing_components)
+                    .ok();
+            }
+        }
+    }
+
+    fn get_entity(&self, entity_id: EntityId) -> Option<EntityReference> {
+        let entity_service = self.entity_service.read();
+        entity_service.get_entity(entity_id)
+    }
+}
+
+// Update the fields of an instance that are not overridden, returns the components that the
+// instance don't have yet
+fn update_instance(
+    entity: &EntityReference,
+    prefab_instance: &PrefabInstance,
+    prefab_entity: &PrefabEntity,
+    old_prefab_entity: Option<&PrefabEntity>,
+    object_factory_service: &ObjectFactoryService,
+) -> Vec<AnyComponent> {
+    let entity = entity.write();
+    let mut missing_components = Vec::new();
+    let mut new_overrides = Vec::new();
+    let mut indexes = HashMap::<String, usize>::new();
+
+    for prefab_component in prefab_entity.components.iter() {
+        let (class_name, fields) =
+            if let Serialized::SerializedObject { class_name, fields } = prefab_component {
+                (class_name, fields)
+            } else {
+                continue;
+            };
+
+        // The hierarchy of the instance is managed by the scene
+        if class_name == "Parent" {
+            continue;
+        }
+
+        let index = indexes.entry(class_name.clone()).or_insert(0);
+        let component_index = *index;
+        *index += 1;
+
+        let mut component = if let Some(component) = entity
+            .iter_components_from_type_identifier_mut(class_name)
+            .nth(component_index)
+        {
+            component
+        } else {
+            if let Some(component) =
+                AnyComponent::deserialize(prefab_component, object_factory_service)
+            {
+                missing_components.push(component);
+            }
+
+            continue;
+        };
+
+        let field_infos = component.get_field_infos();
+        for (field, value) in fields.iter() {
+            if prefab_instance.is_overridden(class_name, component_index, field) {
+                continue;
+            }
+
+            // A field that has been modified on the instance becomes an override
+            let old_value = old_prefab_entity.and_then(|old_prefab_entity| {
+                get_prefab_field(old_prefab_entity, class_name, component_index, field)
+            });
+
+            if let Some(old_value) = old_value {
+                let current_value = field_infos
+                    .iter()
+                    .find(|field_info| field_info.name == *field)
+                    .map(|field_info| {
+                        (field_info.getter)(component.as_any_ref()).serialize_native_objects()
+                    });
+
+                if let Some(current_value) = current_value {
+                    if !serialized_equals(&current_value, &old_value) {
+                        new_overrides.push(PrefabInstance::override_key(
+                            class_name,
+                            component_index,
+                            field,
+                        ));
+                        continue;
+                    }
+                }
+            }
+
+            set_component_field(
+                &mut *component,
+                field,
+                value.deserialize_native_objects(object_factory_service),
+            );
+        }
+    }
+
+    if !new_overrides.is_empty() {
+        if let Some(mut prefab_instance) = entity.write_single_component::<PrefabInstance>() {
+            prefab_instance.overrides.append(&mut new_overrides);
+        }
+    }
+
+    missing_components
+}
+
+// Build the components of an instance entity, the parent is remapped to the instantiated entities
+fn build_instance_components(
+    prefab_entity: &PrefabEntity,
+    parent_ref: Option<EntityRef>,
+    overrides: &[&PrefabOverride],
+) -> Vec<Serialized> {
+    let parent_id = parent_ref.fruity_into();
+
+    let mut has_parent = false;
+    let mut indexes = HashMap::<String, usize>::new();
+    let mut components = prefab_entity
+        .components
+        .iter()
+        .map(|component| {
+            let mut component = component.clone();
+
+            if let Serialized::SerializedObject { class_name, fields } = &mut component {
+                if class_name == "Parent" {
+                    has_parent = true;
+                    fields.insert("parent_id".to_string(), parent_id.clone());
+                }
+
+                let index = indexes.entry(class_name.clone()).or_insert(0);
+                let component_index = *index;
+                *index += 1;
+
+                overrides
+                    .iter()
+                    .filter(|prefab_override| {
+                        prefab_override.component == *class_name
+                            && prefab_override.index == component_index
+                    })
+                    .for_each(|prefab_override| {
+                        fields.insert(
+                            prefab_override.field.clone(),
+                            prefab_override.value.serialize_native_objects(),
+                        );
+                    });
+            }
+
+            component
+        })
+        .collect::<Vec<_>>();
+
+    if !has_parent && parent_ref.is_some() {
+        let mut fields = HashMap::new();
+        fields.insert("parent_id".to_string(), parent_id);
+
+        components.push(Serialized::SerializedObject {
+            class_name: "Parent".to_string(),
+            fields,
+        });
+    }
+
+    components
+}
+
+fn get_prefab_field(
+    prefab_entity: &PrefabEntity,
+    class_name: &str,
+    index: usize,
+    field: &str,
+) -> Option<Serialized> {
+    prefab_entity
+        .components
+        .iter()
+        .filter_map(|component| match component {
+            Serialized::SerializedObject {
+                class_name: component_class_name,
+                fields,
+            } if component_class_name == class_name => Some(fields),
+            _ => None,
+        })
+        .nth(index)
+        .and_then(|fields| fields.get(field).cloned())
+}
+
+fn set_component_field(component: &mut dyn Component, field: &str, value: Serialized) {
+    let field_info = component
+        .get_field_infos()
+        .into_iter()
+        .find(|field_info| field_info.name == field);
+
+    if let Some(field_info) = field_info {
+        match field_info.setter {
+            SetterCaller::Const(call) => call(component.as_any_ref(), value),
+            SetterCaller::Mut(call) => call(component.as_any_mut(), value),
+            SetterCaller::None => (),
+        }
+    }
+}
+
+impl IntrospectObject for PrefabService {
+    fn get_class_name(&self) -> String {
+        "PrefabService".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![
+            MethodInfo {
+                name: "instantiate".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<PrefabService>(this);
+
+                    let mut caster = ArgumentCaster::new("instantiate", args);
+                    let arg1 = caster.cast_next::<String>()?;
+                    let arg2 = caster.cast_next_optional::<EntityId>();
+
+                    let result = this.instantiate(&arg1, arg2, Vec::new());
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "set_override".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<PrefabService>(this);
+
+                    let mut caster = ArgumentCaster::new("set_override", args);
+                    let arg1 = caster.cast_next::<EntityId>()?;
+                    let arg2 = caster.cast_next::<String>()?;
+                    let arg3 = caster.cast_next::<String>()?;
+                    let arg4 = caster.next()?;
+                    let arg5 = caster.cast_next_optional::<usize>().unwrap_or(0);
+
+                    this.set_override(arg1, &arg2, arg5, &arg3, arg4);
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "revert_override".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<PrefabService>(this);
+
+                    let mut caster = ArgumentCaster::new("revert_override", args);
+                    let arg1 = caster.cast_next::<EntityId>()?;
+                    let arg2 = caster.cast_next::<String>()?;
+                    let arg3 = caster.cast_next::<String>()?;
+                    let arg4 = caster.cast_next_optional::<usize>().unwrap_or(0);
+
+                    this.revert_override(arg1, &arg2, arg4, &arg3);
+                    Ok(None)
+                })),
+            },
+        ]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
+
+impl Resource for PrefabService {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fruity_core::settings::Settings;
+    use fruity_core::world::World;
+    use fruity_ecs::entity::entity::EntityGuid;
+    use fruity_ecs::*;
+
+    #[derive(Debug, Clone, Default, Component, FruityAny)]
+    struct Stat {
+        value: i32,
+    }
+
+    fn stat(value: i32) -> Serialized {
+        let mut fields = HashMap::new();
+        fields.insert("value".to_string(), Serialized::I32(value));
+
+        Serialized::SerializedObject {
+            class_name: "Stat".to_string(),
+            fields,
+        }
+    }
+
+    fn prefab(first: i32, second: i32) -> PrefabResource {
+        PrefabResource {
+            entities: vec![PrefabEntity {
+                entity_id: 1,
+                guid: EntityGuid(1),
+                name: "Unit".to_string(),
+                enabled: true,
+                components: vec![stat(first), stat(second)],
+            }],
+        }
+    }
+
+    fn read_stats(entity_service: &EntityService, entity_id: EntityId) -> Vec<i32> {
+        let entity = entity_service.get_entity(entity_id).unwrap();
+        let entity = entity.read();
+
+        entity
+            .iter_components_from_type_identifier("Stat")
+            .map(|component| component.as_any_ref().downcast_ref::<Stat>().unwrap().value)
+            .collect()
+    }
+
+    #[test]
+    fn prefab_overrides_are_applied_and_reverted_per_component_test() {
+        let world = World::new();
+        let resource_container = world.resource_container.clone();
+        fruity_ecs::initialize(resource_container.clone());
+        crate::initialize(
+            resource_container.clone(),
+            &Settings::Object(HashMap::new()),
+        );
+        resource_container
+            .require::<ObjectFactoryService>()
+            .write()
+            .register::<Stat>("Stat");
+
+        let prefab_service = resource_container.require::<PrefabService>();
+        let prefab_service = prefab_service.read();
+        prefab_service.update_prefab("unit.frpf", prefab(1, 2));
+
+        let entity_id = prefab_service
+            .instantiate(
+                "unit.frpf",
+                None,
+                vec![PrefabOverride {
+                    prefab_entity_id: 1,
+                    component: "Stat".to_string(),
+                    index: 1,
+                    field: "value".to_string(),
+                    value: Serialized::I32(20),
+                }],
+            )
+            .unwrap();
+
+        let entity_service = resource_container.require::<EntityService>();
+        assert_eq!(read_stats(&entity_service.read(), entity_id), vec![1, 20]);
+
+        prefab_service.set_override(entity_id, "Stat", 0, "value", Serialized::I32(10));
+        assert_eq!(read_stats(&entity_service.read(), entity_id), vec![10, 20]);
+
+        {
+            let entity_service = entity_service.read();
+            let entity = entity_service.get_entity(entity_id).unwrap();
+            let entity = entity.read();
+            let prefab_instance = entity.read_single_component::<PrefabInstance>().unwrap();
+            assert_eq!(
+                prefab_instance.overrides,
+                vec!["Stat[1].value".to_string(), "Stat.value".to_string()]
+            );
+        }
+
+        // The overridden fields keep their values when the prefab changes
+        prefab_service.update_prefab("unit.frpf", prefab(3, 4));
+        assert_eq!(read_stats(&entity_service.read(), entity_id), vec![10, 20]);
+
+        // Reverting the second component does not touch the first one
+        prefab_service.revert_override(entity_id, "Stat", 1, "value");
+        assert_eq!(read_stats(&entity_service.read(), entity_id), vec![10, 4]);
+
+        prefab_service.update_prefab("unit.frpf", prefab(5, 6));
+        assert_eq!(read_stats(&entity_service.read(), entity_id), vec![10, 6]);
+    }
+}
@@ -0,0 +1 @@
+pub mod prefab_resource;
@@ -0,0 +1,247 @@
+use crate::prefab_service::PrefabService;
+use fruity_any::*;
+use fruity_core::convert::FruityTryFrom;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::object_factory_service::ObjectFactoryService;
+use fruity_core::resource::error::LoadResourceError;
+use fruity_core::resource::resource::Resource;
+use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::serialize::binary::deserialize_binary;
+use fruity_core::serialize::binary::BINARY_MAGIC;
+use fruity_core::serialize::serialized::Serialized;
+use fruity_core::serialize::yaml::deserialize_yaml;
+use fruity_core::settings::Settings;
+use fruity_ecs::component::component_schema_service::ComponentSchemaService;
+use fruity_ecs::entity::entity::EntityGuid;
+use fruity_ecs::entity::entity::EntityId;
+use fruity_ecs::entity::entity_ref::EntityRef;
+use std::io::Read;
+
+/// An entity stored into a prefab
+#[derive(Debug, Clone)]
+pub struct PrefabEntity {
+    /// The entity id, only meaningful inside the prefab
+    pub entity_id: EntityId,
+
+    /// The entity guid, only meaningful inside the prefab
+    pub guid: EntityGuid,
+
+    /// The entity name
+    pub name: String,
+
+    /// Is the entity enabled
+    pub enabled: bool,
+
+    /// The serialized components
+    pub components: Vec<Serialized>,
+}
+
+impl PrefabEntity {
+    /// Get the reference to the parent of the entity, read from the Parent component
+    pub fn get_parent_ref(&self) -> Option<EntityRef> {
+        self.components.iter().find_map(|component| {
+            if let Serialized::SerializedObject { class_name, fields } = component {
+                if class_name == "Parent" {
+                    return fields.get("parent_id").and_then(|parent_id| {
+                        Option::<EntityRef>::fruity_try_from(parent_id.clone())
+                            .ok()
+                            .flatten()
+                    });
+                }
+            }
+
+            None
+        })
+    }
+}
+
+/// A reusable tree of entities
+#[derive(Debug, Clone, Default, FruityAny)]
+pub struct PrefabResource {
+    /// The entities, a prefab uses the same layout than a scene file
+    pub entities: Vec<PrefabEntity>,
+}
+
+impl PrefabResource {
+    /// Build a prefab from a serialized scene
+    ///
+    /// # Arguments
+    /// * `serialized` - The serialized entities, as produced by [’EntityService::snapshot’]
+    ///
+    pub fn from_serialized(serialized: &Serialized) -> PrefabResource {
+        let entities = if let Serialized::Array(entities) = serialized {
+            entities
+                .iter()
+                .filter_map(read_prefab_entity)
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
+        PrefabResource { entities }
+    }
+
+    /// Get an entity of the prefab
+    pub fn get_entity(&self, entity_id: EntityId) -> Option<&PrefabEntity> {
+        self.entities
+            .iter()
+            .find(|entity| entity.entity_id == entity_id)
+    }
+
+    /// Get the parent of an entity inside the prefab
+    /// The parent is found by guid, the entity id is used for the files that don't store guids
+    ///
+    /// # Arguments
+    /// * `entity` - The prefab entity
+    ///
+    pub fn get_parent_id(&self, entity: &PrefabEntity) -> Option<EntityId> {
+        let parent_ref = entity.get_parent_ref()?;
+
+        self.entities
+            .iter()
+            .find(|other| {
+                if parent_ref.guid.is_nil() {
+                    other.entity_id == parent_ref.entity_id
+                } else {
+                    other.guid == parent_ref.guid
+                }
+            })
+            .map(|parent| parent.entity_id)
+    }
+
+    /// Get the entities that don't have a parent inside the prefab
+    pub fn get_roots(&self) -> Vec<&PrefabEntity> {
+        self.entities
+            .iter()
+            .filter(|entity| self.get_parent_id(entity).is_none())
+            .collect()
+    }
+
+    /// Get the entities ordered so that a parent always comes before its children
+    pub fn get_ordered_entities(&self) -> Vec<&PrefabEntity> {
+        let mut result = self.get_roots();
+        let mut index = 0;
+
+        while index < result.len() {
+            let parent_id = result[index].entity_id;
+            self.entities
+                .iter()
+                .filter(|entity| self.get_parent_id(entity) == Some(parent_id))
+                .for_each(|entity| {
+                    if !result
+                        .iter()
+                        .any(|other| other.entity_id == entity.entity_id)
+                    {
+                        result.push(entity);
+                    }
+                });
+
+            index += 1;
+        }
+
+        result
+    }
+}
+
+fn read_prefab_entity(serialized: &Serialized) -> Option<PrefabEntity> {
+    let fields = if let Serialized::SerializedObject { fields, .. } = serialized {
+        fields
+    } else {
+        return None;
+    };
+
+    let entity_id = EntityId::fruity_try_from(fields.get("entity_id")?.clone()).ok()?;
+    let guid = fields
+        .get("guid")
+        .and_then(|guid| EntityGuid::fruity_try_from(guid.clone()).ok())
+        .unwrap_or_default();
+    let name = String::fruity_try_from(fields.get("name")?.clone()).unwrap_or_default();
+    let enabled = fields
+        .get("enabled")
+        .and_then(|enabled| bool::fruity_try_from(enabled.clone()).ok())
+        .unwrap_or(true);
+
+    let components = if let Some(Serialized::Array(components)) = fields.get("components") {
+        components
+            .iter()
+            .map(|component| component.serialize_native_objects())
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+
+    Some(PrefabEntity {
+        entity_id,
+        guid,
+        name,
+        enabled,
+        components,
+    })
+}
+
+impl IntrospectObject for PrefabResource {
+    fn get_class_name(&self) -> String {
+        "PrefabResource".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
+
+impl Resource for PrefabResource {}
+
+pub fn load_prefab(
+    identifier: &str,
+    reader: &mut dyn Read,
+    _settings: Settings,
+    resource_container: ResourceContainer,
+) -> Result<(), LoadResourceError> {
+    // read the whole file
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer).map_err(|err| {
+        LoadResourceError::FileNotReadable(identifier.to_string(), err.to_string())
+    })?;
+
+    // A prefab can be stored with any of the scene formats
+    let serialized = if buffer.starts_with(BINARY_MAGIC) {
+        deserialize_binary(&mut buffer.as_slice())
+    } else {
+        deserialize_yaml(&mut buffer.as_slice())
+    };
+
+    let mut serialized = serialized.ok_or_else(|| {
+        LoadResourceError::DecodeError(
+            identifier.to_string(),
+            "The prefab is not a valid scene".to_string(),
+        )
+    })?;
+
+    // Upgrade the components saved with a previous schema
+    if let Serialized::Array(entities) = &mut serialized {
+        let object_factory_service = resource_container.require::<ObjectFactoryService>();
+        let object_factory_service = object_factory_service.read();
+        let component_schema_service = resource_container.require::<ComponentSchemaService>();
+        let component_schema_service = component_schema_service.read();
+
+        component_schema_service
+            .migrate_entities(entities, &object_factory_service)
+            .into_iter()
+            .for_each(|diagnostic| log::warn!("{}: {}", identifier, diagnostic));
+    }
+
+    // Store the resource, an already loaded prefab is updated so the instances follow the changes
+    let prefab = PrefabResource::from_serialized(&serialized);
+    let prefab_service = resource_container.require::<PrefabService>();
+    let prefab_service = prefab_service.read();
+
+    prefab_service.update_prefab(identifier, prefab);
+
+    Ok(())
+}
@@ -1,2 +1,3 @@
 pub mod delete_cascade;
+pub mod update_hierarchy_index;
 pub mod update_nested_level;
@@ -0,0 +1,41 @@
+use crate::Parent;
+use fruity_core::inject::Ref;
+use fruity_ecs::entity::entity_query::with::With;
+use fruity_ecs::entity::entity_query::with::WithId;
+use fruity_ecs::entity::entity_query::Query;
+use fruity_ecs::entity::entity_service::EntityService;
+use fruity_ecs::system::system_service::StartupDisposeSystemCallback;
+use std::ops::Deref;
+
+pub fn update_hierarchy_index(
+    entity_service: Ref<EntityService>,
+    query: Query<(WithId, With<Parent>)>,
+) -> StartupDisposeSystemCallback {
+    let handle = query.on_created(move |(entity_id, parent)| {
+        // Register the parent into the entity service hierarchy index
+        {
+            let parent_id = parent
+                .parent_id
+                .deref()
+                .map(|parent_id| parent_id.entity_id);
+            let entity_service_reader = entity_service.read();
+            entity_service_reader.set_parent(entity_id, parent_id);
+        }
+
+        // When parent is updated, we update the index
+        let entity_service = entity_service.clone();
+        let handle = parent.parent_id.on_updated.add_observer(move |parent_id| {
+            let parent_id = parent_id.map(|parent_id| parent_id.entity_id);
+            let entity_service_reader = entity_service.read();
+            entity_service_reader.set_parent(entity_id, parent_id);
+        });
+
+        Some(Box::new(move || {
+            handle.dispose_by_ref();
+        }))
+    });
+
+    Some(Box::new(move || {
+        handle.dispose_by_ref();
+    }))
+}
@@ -15,7 +15,7 @@ pub fn update_nested_level(
         // Get the parent entity reference
         let parent_entity = if let Some(parent_id) = &parent.parent_id.deref() {
             let entity_service_reader = entity_service.read();
-            entity_service_reader.get_entity(*parent_id)
+            entity_service_reader.get_entity(parent_id.entity_id)
         } else {
             None
         };
@@ -37,7 +37,7 @@ pub fn update_nested_level(
             // Get the parent entity reference
             let parent_entity = if let Some(parent_id) = &parent_id {
                 let entity_service_reader = entity_service.read();
-                entity_service_reader.get_entity(*parent_id)
+                entity_service_reader.get_entity(parent_id.entity_id)
             } else {
                 None
             };
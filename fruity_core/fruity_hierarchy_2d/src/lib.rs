@@ -20,8 +20,10 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         MODULE_NAME,
         Inject2::new(transform_2d_cascade),
         SystemParams {
-            pool_index: 96,
+            stage: "transform".to_string(),
+            after: vec!["update_transform_2d".to_string()],
             ignore_pause: true,
+            ..Default::default()
         },
     );
 }
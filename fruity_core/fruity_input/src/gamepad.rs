@@ -0,0 +1,154 @@
+use fruity_core::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// An identifier of a connected gamepad, the sources of the gamepad 0 are named "Gamepad0/..."
+pub type GamepadId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl GamepadButton {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            GamepadButton::South => "South",
+            GamepadButton::East => "East",
+            GamepadButton::North => "North",
+            GamepadButton::West => "West",
+            GamepadButton::LeftBumper => "LeftBumper",
+            GamepadButton::RightBumper => "RightBumper",
+            GamepadButton::LeftTrigger => "LeftTrigger",
+            GamepadButton::RightTrigger => "RightTrigger",
+            GamepadButton::Select => "Select",
+            GamepadButton::Start => "Start",
+            GamepadButton::Mode => "Mode",
+            GamepadButton::LeftThumb => "LeftThumb",
+            GamepadButton::RightThumb => "RightThumb",
+            GamepadButton::DPadUp => "DPadUp",
+            GamepadButton::DPadDown => "DPadDown",
+            GamepadButton::DPadLeft => "DPadLeft",
+            GamepadButton::DPadRight => "DPadRight",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+impl GamepadAxis {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            GamepadAxis::LeftStickX => "LeftStickX",
+            GamepadAxis::LeftStickY => "LeftStickY",
+            GamepadAxis::RightStickX => "RightStickX",
+            GamepadAxis::RightStickY => "RightStickY",
+            // The triggers are named differently from the buttons, a gamepad can report both
+            GamepadAxis::LeftTrigger => "LeftTriggerAxis",
+            GamepadAxis::RightTrigger => "RightTriggerAxis",
+        }
+    }
+}
+
+/// The prefix of the gamepad sources
+pub const GAMEPAD_SOURCE_PREFIX: &str = "Gamepad";
+
+/// Get the source of a gamepad input, for example "Gamepad0/South"
+///
+/// # Arguments
+/// * `gamepad_id` - The gamepad id
+/// * `input` - The button or axis name
+///
+pub fn get_gamepad_source(gamepad_id: GamepadId, input: &str) -> String {
+    format!("{}{}/{}", GAMEPAD_SOURCE_PREFIX, gamepad_id, input)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GamepadEvent {
+    /// A gamepad is connected, the gamepad name is provided
+    Connected(GamepadId, String),
+    Disconnected(GamepadId),
+    ButtonPressed(GamepadId, GamepadButton),
+    ButtonReleased(GamepadId, GamepadButton),
+    /// An axis moved, the value is between -1 and 1 for the sticks and between 0 and 1 for
+    /// the triggers
+    AxisChanged(GamepadId, GamepadAxis, f32),
+}
+
+/// A provider of gamepad events, implemented by the platforms
+pub trait GamepadBackend: Send {
+    /// Get the events received since the last call
+    fn poll_events(&mut self) -> Vec<GamepadEvent>;
+}
+
+/// A gamepad backend where the events are pushed by hand, used to test the gamepad inputs
+/// without a device
+///
+/// The backend can be cloned, the clones share the same events so one can be given to the
+/// [’InputService’] and the other one kept to drive the fake device
+#[derive(Debug, Clone, Default)]
+pub struct FakeGamepadBackend {
+    events: Arc<Mutex<VecDeque<GamepadEvent>>>,
+}
+
+impl FakeGamepadBackend {
+    pub fn new() -> FakeGamepadBackend {
+        FakeGamepadBackend::default()
+    }
+
+    pub fn push_event(&self, event: GamepadEvent) {
+        let mut events = self.events.lock();
+        events.push_back(event);
+    }
+
+    pub fn connect(&self, gamepad_id: GamepadId, name: &str) {
+        self.push_event(GamepadEvent::Connected(gamepad_id, name.to_string()));
+    }
+
+    pub fn disconnect(&self, gamepad_id: GamepadId) {
+        self.push_event(GamepadEvent::Disconnected(gamepad_id));
+    }
+
+    pub fn press(&self, gamepad_id: GamepadId, button: GamepadButton) {
+        self.push_event(GamepadEvent::ButtonPressed(gamepad_id, button));
+    }
+
+    pub fn release(&self, gamepad_id: GamepadId, button: GamepadButton) {
+        self.push_event(GamepadEvent::ButtonReleased(gamepad_id, button));
+    }
+
+    pub fn move_axis(&self, gamepad_id: GamepadId, axis: GamepadAxis, value: f32) {
+        self.push_event(GamepadEvent::AxisChanged(gamepad_id, axis, value));
+    }
+}
+
+impl GamepadBackend for FakeGamepadBackend {
+    fn poll_events(&mut self) -> Vec<GamepadEvent> {
+        let mut events = self.events.lock();
+        events.drain(..).collect()
+    }
+}
@@ -0,0 +1,286 @@
+use crate::input_service::Modifiers;
+use fruity_core::settings::Settings;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// The duration a hold binding should be held by default, in seconds
+pub const DEFAULT_HOLD_DURATION: f32 = 0.5;
+
+/// The maximum delay between the two presses of a double tap binding by default, in seconds
+pub const DEFAULT_DOUBLE_TAP_DELAY: f32 = 0.3;
+
+/// What a binding should detect to activate its action
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputTrigger {
+    /// Active while the sources are pressed
+    Press,
+    /// Active once the sources are held for a duration, in seconds
+    Hold(f32),
+    /// Active while the sources are pressed for the second time, the second press should come
+    /// before a delay, in seconds
+    DoubleTap(f32),
+}
+
+/// The value a binding contributes to when the action is read with
+/// [’InputService::get_axis’] or [’InputService::get_vector2d’]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputComponent {
+    Axis,
+    X,
+    Y,
+}
+
+/// A way to activate an action
+///
+/// A binding is written as "Ctrl+Shift+Keyboard/S:-x", the modifiers and the sources are
+/// separated by "+", when there are several sources they should be pressed together, and the
+/// optional suffix gives the scale and the component of the binding
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputBinding {
+    pub sources: Vec<String>,
+    pub modifiers: Modifiers,
+    pub component: InputComponent,
+    pub scale: f32,
+    pub trigger: InputTrigger,
+    pub(crate) state: InputBindingState,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct InputBindingState {
+    pub(crate) down: bool,
+    pub(crate) active: bool,
+    pub(crate) held_since: Option<f64>,
+    pub(crate) last_tap: Option<f64>,
+    pub(crate) shadowed: bool,
+}
+
+impl InputBinding {
+    /// Returns an InputBinding activated by a single source
+    pub fn new(source: &str) -> InputBinding {
+        InputBinding {
+            sources: vec![source.to_string()],
+            modifiers: Modifiers::empty(),
+            component: InputComponent::Axis,
+            scale: 1.0,
+            trigger: InputTrigger::Press,
+            state: InputBindingState::default(),
+        }
+    }
+
+    /// Read a binding written as "Ctrl+Keyboard/S" or "Keyboard/Left:-x"
+    ///
+    /// # Arguments
+    /// * `binding` - The binding
+    ///
+    pub fn parse(binding: &str) -> Result<InputBinding, String> {
+        let (sources, suffix) = match binding.rsplit_once(':') {
+            Some((sources, suffix)) => (sources, Some(suffix.trim())),
+            None => (binding, None),
+        };
+
+        let mut result = InputBinding::new("");
+        result.sources.clear();
+
+        for token in sources.split('+').map(|token| token.trim()) {
+            match token {
+                "Shift" => result.modifiers |= Modifiers::SHIFT,
+                "Ctrl" => result.modifiers |= Modifiers::CTRL,
+                "Alt" => result.modifiers |= Modifiers::ALT,
+                "Logo" => result.modifiers |= Modifiers::LOGO,
+                source if source.contains('/') => result.sources.push(source.to_string()),
+                token => {
+                    return Err(format!(
+                        "\"{}\" is neither a modifier nor a source like \"Keyboard/A\"",
+                        token
+                    ))
+                }
+            }
+        }
+
+        if result.sources.is_empty() {
+            return Err(format!("\"{}\" has no source", binding));
+        }
+
+        if let Some(suffix) = suffix {
+            let (scale, component) = if let Some(scale) = suffix.strip_suffix('x') {
+                (scale, InputComponent::X)
+            } else if let Some(scale) = suffix.strip_suffix('y') {
+                (scale, InputComponent::Y)
+            } else {
+                (suffix, InputComponent::Axis)
+            };
+
+            result.component = component;
+            result.scale = match scale {
+                "" | "+" => 1.0,
+                "-" => -1.0,
+                scale => scale
+                    .parse::<f32>()
+                    .map_err(|_| format!("\"{}\" is not a valid scale", suffix))?,
+            };
+        }
+
+        Ok(result)
+    }
+
+    /// Read a binding from the settings, it can be a string or an object that provides the
+    /// trigger, for example { source: "Keyboard/E", trigger: "hold", duration: 0.5 }
+    ///
+    /// # Arguments
+    /// * `settings` - The binding settings
+    ///
+    pub fn from_settings(settings: &Settings) -> Result<InputBinding, String> {
+        match settings {
+            Settings::String(binding) => InputBinding::parse(binding),
+            Settings::Object(_) => {
+                let source = settings.get::<Option<String>>("source", None);
+                let source = source.ok_or_else(|| "The binding has no source".to_string())?;
+                let mut binding = InputBinding::parse(&source)?;
+
+                binding.trigger = match settings.get::<Option<String>>("trigger", None).as_deref() {
+                    None | Some("press") => InputTrigger::Press,
+                    Some("hold") => {
+                        InputTrigger::Hold(settings.get::<f32>("duration", DEFAULT_HOLD_DURATION))
+                    }
+                    Some("double_tap") => InputTrigger::DoubleTap(
+                        settings.get::<f32>("duration", DEFAULT_DOUBLE_TAP_DELAY),
+                    ),
+                    Some(trigger) => {
+                        return Err(format!(
+                            "\"{}\" is not a trigger, expected press, hold or double_tap",
+                            trigger
+                        ))
+                    }
+                };
+
+                Ok(binding)
+            }
+            _ => Err(format!("{:?} is not a binding", settings)),
+        }
+    }
+
+    /// Get the binding as it's written in the settings, see [’InputBinding::from_settings’]
+    pub fn to_settings(&self) -> Settings {
+        let (trigger, duration) = match self.trigger {
+            InputTrigger::Press => return Settings::String(self.to_string()),
+            InputTrigger::Hold(duration) => ("hold", duration),
+            InputTrigger::DoubleTap(delay) => ("double_tap", delay),
+        };
+
+        let mut fields = HashMap::new();
+        fields.insert("source".to_string(), Settings::String(self.to_string()));
+        fields.insert("trigger".to_string(), Settings::String(trigger.to_string()));
+        fields.insert("duration".to_string(), Settings::F64(duration as f64));
+
+        Settings::Object(fields)
+    }
+
+    /// Check if two bindings are activated by the same sources and modifiers
+    ///
+    /// # Arguments
+    /// * `other` - The other binding
+    ///
+    pub fn is_same_input(&self, other: &InputBinding) -> bool {
+        let mut sources = self.sources.clone();
+        let mut other_sources = other.sources.clone();
+        sources.sort();
+        other_sources.sort();
+
+        self.modifiers == other.modifiers && sources == other_sources
+    }
+
+    /// Is this binding read from the same sources than an other one but with more modifiers, the
+    /// less specific binding is not down while the more specific one is, see [’InputService’]
+    ///
+    /// # Arguments
+    /// * `other` - The other binding
+    ///
+    pub fn is_more_specific_than(&self, other: &InputBinding) -> bool {
+        self.modifiers != other.modifiers
+            && self.modifiers.contains(other.modifiers)
+            && self.sources.len() == other.sources.len()
+            && self
+                .sources
+                .iter()
+                .all(|source| other.sources.contains(source))
+    }
+
+    /// Update the state of the binding, returns true if the binding is active
+    ///
+    /// # Arguments
+    /// * `down` - True if the sources and the modifiers are pressed
+    /// * `time` - The current time, in seconds
+    ///
+    pub(crate) fn update(&mut self, down: bool, time: f64) -> bool {
+        let state = &mut self.state;
+        let just_pressed = down && !state.down;
+        state.down = down;
+
+        state.active = match self.trigger {
+            InputTrigger::Press => down,
+            InputTrigger::Hold(duration) => {
+                if down {
+                    let held_since = *state.held_since.get_or_insert(time);
+                    time - held_since >= duration as f64
+                } else {
+                    state.held_since = None;
+                    false
+                }
+            }
+            InputTrigger::DoubleTap(delay) => {
+                if just_pressed {
+                    match state.last_tap {
+                        Some(last_tap) if time - last_tap <= delay as f64 => {
+                            state.last_tap = None;
+                            true
+                        }
+                        _ => {
+                            state.last_tap = Some(time);
+                            false
+                        }
+                    }
+                } else {
+                    down && state.active
+                }
+            }
+        };
+
+        state.active
+    }
+}
+
+impl Display for InputBinding {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let modifiers = [
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::CTRL, "Ctrl"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::LOGO, "Logo"),
+        ];
+
+        let tokens = modifiers
+            .iter()
+            .filter(|(modifier, _)| self.modifiers.contains(*modifier))
+            .map(|(_, name)| name.to_string())
+            .chain(self.sources.iter().cloned())
+            .collect::<Vec<_>>();
+
+        write!(formatter, "{}", tokens.join("+"))?;
+
+        let component = match self.component {
+            InputComponent::Axis => "",
+            InputComponent::X => "x",
+            InputComponent::Y => "y",
+        };
+
+        if self.scale == 1.0 && component.is_empty() {
+            Ok(())
+        } else if self.scale == 1.0 {
+            write!(formatter, ":+{}", component)
+        } else if self.scale == -1.0 && !component.is_empty() {
+            write!(formatter, ":-{}", component)
+        } else {
+            write!(formatter, ":{}{}", self.scale, component)
+        }
+    }
+}
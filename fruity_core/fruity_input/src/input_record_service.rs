@@ -0,0 +1,473 @@
+use crate::input_recording::InputRecordFrame;
+use crate::input_recording::InputRecording;
+use crate::input_service::InputService;
+use fruity_any::*;
+use fruity_core::convert::FruityInto;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodCaller;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::introspect::SetterCaller;
+use fruity_core::resource::resource::Resource;
+use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::resource::resource_reference::ResourceReference;
+use fruity_core::serialize::serialized::Serialized;
+use fruity_core::settings::Settings;
+use fruity_core::signal::Signal;
+use fruity_core::utils::introspect::cast_introspect_mut;
+use fruity_core::utils::introspect::cast_introspect_ref;
+use fruity_core::utils::introspect::ArgumentCaster;
+use fruity_windows::frame_service::FrameService;
+use fruity_windows::window_service::WindowService;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// A service to record the inputs frame by frame and to replay them
+///
+/// A recording stores the events received by the [’InputService’], the cursor position and
+/// the unscaled delta of every frame. During a replay the platform events are ignored, the
+/// recorded events are injected at the start of each frame and the [’FrameService’] is forced
+/// to use the recorded deltas, so the systems see exactly the same frames.
+#[derive(FruityAny)]
+pub struct InputRecordService {
+    resource_container: ResourceContainer,
+    input_service: ResourceReference<InputService>,
+    frame_service: ResourceReference<FrameService>,
+    window_service: ResourceReference<dyn WindowService>,
+    recording: Option<InputRecording>,
+    replay: Option<(InputRecording, usize)>,
+    pub on_replay_ended: Signal<()>,
+}
+
+impl Debug for InputRecordService {
+    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        Ok(())
+    }
+}
+
+impl InputRecordService {
+    pub fn new(resource_container: ResourceContainer) -> InputRecordService {
+        InputRecordService {
+            resource_container: resource_container.clone(),
+            input_service: resource_container.require::<InputService>(),
+            frame_service: resource_container.require::<FrameService>(),
+            window_service: resource_container.require::<dyn WindowService>(),
+            recording: None,
+            replay: None,
+            on_replay_ended: Signal::new(),
+        }
+    }
+
+    /// Read the recording settings, a recording can be replayed from the start with
+    /// `input_replay: "user://replay.yaml"`
+    pub fn read_input_record_settings(&mut self, settings: &Settings) {
+        let replay_path = settings.get::<Option<String>>("input_replay", None);
+
+        if let Some(replay_path) = replay_path {
+            if let Err(err) = self.load_replay(&replay_path) {
+                log::error!("{}", err);
+            }
+        }
+    }
+
+    /// Start recording the inputs, a recording that was started before is dropped
+    ///
+    /// # Arguments
+    /// * `seed` - The seed stored with the recording, see [’InputRecording’]
+    ///
+    pub fn start_recording(&mut self, seed: u64) {
+        self.stop_replay();
+        self.recording = Some(InputRecording::new(seed));
+
+        let mut input_service = self.input_service.write();
+        input_service.set_recording(true);
+    }
+
+    /// Stop recording the inputs and returns the recording
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        let mut input_service = self.input_service.write();
+        input_service.set_recording(false);
+
+        self.recording.take()
+    }
+
+    /// Stop recording the inputs and write the recording into a file
+    ///
+    /// # Arguments
+    /// * `path` - The file path, for example "user://replay.yaml"
+    ///
+    pub fn save_recording(&mut self, path: &str) -> Result<(), String> {
+        let recording = self
+            .stop_recording()
+            .ok_or_else(|| "The inputs are not recorded".to_string())?;
+
+        let file_system = self.resource_container.get_file_system();
+        let mut writer = file_system.create(path).map_err(|err| err.to_string())?;
+
+        recording.write(&mut writer).map_err(|err| err.to_string())
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Replay a recording from the next frame, the platform inputs are ignored until the
+    /// replay ends
+    ///
+    /// # Arguments
+    /// * `recording` - The recording
+    ///
+    pub fn start_replay(&mut self, recording: InputRecording) {
+        self.stop_recording();
+
+        {
+            let mut input_service = self.input_service.write();
+            input_service.set_replaying(true);
+        }
+
+        self.replay = Some((recording, 0));
+        self.force_replay_delta();
+    }
+
+    /// Read a recording written by [’InputRecordService::save_recording’] and replay it
+    ///
+    /// # Arguments
+    /// * `path` - The file path
+    ///
+    pub fn load_replay(&mut self, path: &str) -> Result<(), String> {
+        let file_system = self.resource_container.get_file_system();
+        let mut reader = file_system.open(path).map_err(|err| err.to_string())?;
+        let recording = InputRecording::read(&mut reader)
+            .map_err(|err| format!("Couldn't read the input recording {}: {}", path, err))?;
+
+        self.start_replay(recording);
+        Ok(())
+    }
+
+    /// Stop the current replay, the platform inputs are used again
+    pub fn stop_replay(&mut self) {
+        if self.replay.take().is_none() {
+            return;
+        }
+
+        {
+            let mut input_service = self.input_service.write();
+            input_service.set_replaying(false);
+        }
+
+        let mut frame_service = self.frame_service.write();
+        frame_service.set_forced_delta(None);
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// Get the seed of the current recording or replay
+    pub fn get_seed(&self) -> Option<u64> {
+        if let Some(recording) = &self.recording {
+            Some(recording.seed)
+        } else {
+            self.replay.as_ref().map(|(recording, _)| recording.seed)
+        }
+    }
+
+    /// Inject the events of the replayed frame, should be called when a frame starts, before
+    /// the [’InputService’] is updated
+    pub fn begin_frame(&mut self) {
+        let frame = if let Some((recording, frame_index)) = &self.replay {
+            recording.frames[*frame_index].clone()
+        } else {
+            return;
+        };
+
+        let on_cursor_moved = {
+            let mut window_service = self.window_service.write();
+            if window_service.get_cursor_position() != frame.cursor_position {
+                window_service.set_cursor_position(frame.cursor_position);
+                Some(window_service.on_cursor_moved().clone())
+            } else {
+                None
+            }
+        };
+
+        // The signal is notified after the window service is released, the observers may read it
+        if let Some(on_cursor_moved) = on_cursor_moved {
+            on_cursor_moved.notify(frame.cursor_position);
+        }
+
+        let mut input_service = self.input_service.write();
+        frame
+            .events
+            .into_iter()
+            .for_each(|event| input_service.replay_event(event));
+    }
+
+    /// Store the recorded frame or move the replay to the next frame, should be called when a
+    /// frame ends
+    pub fn end_frame(&mut self) {
+        if let Some(recording) = &mut self.recording {
+            let delta = {
+                let frame_service = self.frame_service.read();
+                frame_service.get_unscaled_delta()
+            };
+
+            let cursor_position = {
+                let window_service = self.window_service.read();
+                window_service.get_cursor_position()
+            };
+
+            let events = {
+                let mut input_service = self.input_service.write();
+                input_service.take_recorded_events()
+            };
+
+            recording.frames.push(InputRecordFrame {
+                delta,
+                cursor_position,
+                events,
+            });
+        }
+
+        if let Some((_, frame_index)) = &mut self.replay {
+            *frame_index += 1;
+            self.force_replay_delta();
+        }
+    }
+
+    /// Force the delta of the next replayed frame or end the replay if all the frames are played
+    fn force_replay_delta(&mut self) {
+        let delta = match &self.replay {
+            Some((recording, frame_index)) => {
+                recording.frames.get(*frame_index).map(|frame| frame.delta)
+            }
+            None => None,
+        };
+
+        if let Some(delta) = delta {
+            let mut frame_service = self.frame_service.write();
+            frame_service.set_forced_delta(Some(delta));
+        } else {
+            self.stop_replay();
+            self.on_replay_ended.notify(());
+        }
+    }
+}
+
+impl IntrospectObject for InputRecordService {
+    fn get_class_name(&self) -> String {
+        "InputRecordService".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![
+            MethodInfo {
+                name: "start_recording".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<InputRecordService>(this);
+
+                    let mut caster = ArgumentCaster::new("start_recording", args);
+                    let arg1 = caster.cast_next::<u64>()?;
+
+                    this.start_recording(arg1);
+
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "save_recording".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<InputRecordService>(this);
+
+                    let mut caster = ArgumentCaster::new("save_recording", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    if let Err(err) = this.save_recording(&arg1) {
+                        log::error!("{}", err);
+                    }
+
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "is_recording".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<InputRecordService>(this);
+                    let result = this.is_recording();
+
+                    Ok(Some(Serialized::Bool(result)))
+                })),
+            },
+            MethodInfo {
+                name: "load_replay".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<InputRecordService>(this);
+
+                    let mut caster = ArgumentCaster::new("load_replay", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    if let Err(err) = this.load_replay(&arg1) {
+                        log::error!("{}", err);
+                    }
+
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "stop_replay".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, _args| {
+                    let this = cast_introspect_mut::<InputRecordService>(this);
+                    this.stop_replay();
+
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "is_replaying".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<InputRecordService>(this);
+                    let result = this.is_replaying();
+
+                    Ok(Some(Serialized::Bool(result)))
+                })),
+            },
+            MethodInfo {
+                name: "get_seed".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<InputRecordService>(this);
+                    let result = this.get_seed();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+        ]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![FieldInfo {
+            name: "on_replay_ended".to_string(),
+            serializable: false,
+            getter: Arc::new(|this| {
+                this.downcast_ref::<InputRecordService>()
+                    .unwrap()
+                    .on_replay_ended
+                    .clone()
+                    .fruity_into()
+            }),
+            setter: SetterCaller::None,
+        }]
+    }
+}
+
+impl Resource for InputRecordService {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fruity_core::world::World;
+    use fruity_headless_windows::window_service::HeadlessWindowService;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    fn build_resource_container() -> ResourceContainer {
+        let world = World::new();
+        let resource_container = world.resource_container.clone();
+        fruity_ecs::initialize(resource_container.clone());
+
+        resource_container.add::<dyn WindowService>(
+            "window_service",
+            Box::new(HeadlessWindowService::new("test", 640, 480)),
+        );
+        fruity_windows::initialize(resource_container.clone(), &Settings::new());
+
+        let mut input_service = InputService::new(resource_container.clone());
+        input_service.register_input("Jump", "Keyboard/Up");
+        resource_container.add::<InputService>("input_service", Box::new(input_service));
+
+        let input_record_service = InputRecordService::new(resource_container.clone());
+        resource_container
+            .add::<InputRecordService>("input_record_service", Box::new(input_record_service));
+
+        resource_container
+    }
+
+    // Run a frame like the platforms do, the given delta is ignored when a delta is forced
+    fn run_frame(resource_container: &ResourceContainer, delta: f32, pressed: Option<bool>) {
+        let input_record_service = resource_container.require::<InputRecordService>();
+        let frame_service = resource_container.require::<FrameService>();
+        let input_service = resource_container.require::<InputService>();
+
+        input_record_service.write().begin_frame();
+        frame_service.write().begin_fixed_frame(delta);
+
+        {
+            let mut input_service = input_service.write();
+            match pressed {
+                Some(true) => input_service.notify_pressed("Keyboard/Up"),
+                Some(false) => input_service.notify_released("Keyboard/Up"),
+                None => (),
+            }
+
+            input_service.update(frame_service.read().get_unscaled_delta());
+        }
+
+        input_record_service.write().end_frame();
+        input_service.write().handle_frame_end();
+    }
+
+    #[test]
+    fn a_recording_is_replayed_with_the_same_deltas_and_inputs() {
+        let resource_container = build_resource_container();
+        let input_record_service = resource_container.require::<InputRecordService>();
+        let frame_service = resource_container.require::<FrameService>();
+        let input_service = resource_container.require::<InputService>();
+
+        let frames = [(0.25, Some(true)), (0.5, None), (0.125, Some(false))];
+
+        input_record_service.write().start_recording(42);
+        frames.iter().for_each(|(delta, pressed)| {
+            run_frame(&resource_container, *delta, *pressed);
+        });
+
+        let recording = input_record_service.write().stop_recording().unwrap();
+        assert_eq!(recording.seed, 42);
+        assert_eq!(
+            recording
+                .frames
+                .iter()
+                .map(|frame| frame.delta)
+                .collect::<Vec<_>>(),
+            vec![0.25, 0.5, 0.125]
+        );
+
+        let replay_ended = Arc::new(AtomicUsize::new(0));
+        let replay_ended_2 = replay_ended.clone();
+        input_record_service
+            .read()
+            .on_replay_ended
+            .add_observer(move |_| {
+                replay_ended_2.fetch_add(1, Ordering::Relaxed);
+            });
+
+        // The platform delta and the platform inputs are ignored during the replay
+        input_record_service.write().start_replay(recording);
+        let replayed = frames
+            .iter()
+            .map(|_| {
+                run_frame(&resource_container, 1.0, Some(false));
+                let delta = frame_service.read().get_unscaled_delta();
+                let jump = input_service.read().is_pressed("Jump");
+
+                (delta, jump)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(replayed, vec![(0.25, true), (0.5, true), (0.125, false)]);
+        assert_eq!(replay_ended.load(Ordering::Relaxed), 1);
+        assert!(!input_record_service.read().is_replaying());
+
+        // The platform delta is used again once the replay ended
+        run_frame(&resource_container, 1.0, None);
+        assert_eq!(frame_service.read().get_unscaled_delta(), 1.0);
+    }
+}
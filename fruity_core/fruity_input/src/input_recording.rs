@@ -0,0 +1,275 @@
+use crate::input_service::Modifiers;
+use fruity_core::convert::FruityTryFrom;
+use fruity_core::serialize::serialized::Serialized;
+use fruity_core::serialize::yaml::deserialize_yaml;
+use fruity_core::serialize::yaml::serialize_yaml;
+use std::collections::HashMap;
+use std::io::Read;
+use std::io::Write;
+
+/// A call received by the [’InputService’] from the platform
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputRecordEvent {
+    Pressed(String),
+    Released(String),
+    Modifiers(Modifiers),
+    Axis(String, f32),
+    Text(String),
+    ImeComposition(String),
+}
+
+/// Everything the inputs received during a frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputRecordFrame {
+    /// The unscaled duration of the frame, in seconds
+    pub delta: f32,
+    pub cursor_position: (u32, u32),
+    /// The events received before the frame update, in the order they were received
+    pub events: Vec<InputRecordEvent>,
+}
+
+/// A recording of the inputs, frame by frame
+///
+/// The seed is not used by the engine, it's stored so a game that seeds its random with it
+/// gets the same random numbers during the replay
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputRecording {
+    pub seed: u64,
+    pub frames: Vec<InputRecordFrame>,
+}
+
+impl InputRecording {
+    /// Returns an empty InputRecording
+    pub fn new(seed: u64) -> InputRecording {
+        InputRecording {
+            seed,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Write the recording as a yaml file
+    ///
+    /// # Arguments
+    /// * `writer` - The write io stream
+    ///
+    pub fn write(&self, writer: &mut dyn Write) -> Result<(), std::io::Error> {
+        serialize_yaml(writer, &self.serialize())
+    }
+
+    /// Read a recording written by [’InputRecording::write’]
+    ///
+    /// # Arguments
+    /// * `reader` - The read io stream
+    ///
+    pub fn read(reader: &mut dyn Read) -> Result<InputRecording, String> {
+        let serialized = deserialize_yaml(reader)
+            .ok_or_else(|| "The input recording is not a valid yaml file".to_string())?;
+
+        InputRecording::deserialize(serialized)
+    }
+
+    fn serialize(&self) -> Serialized {
+        let frames = self
+            .frames
+            .iter()
+            .map(|frame| {
+                let events = frame.events.iter().map(serialize_event).collect::<Vec<_>>();
+
+                serialized_object(
+                    "InputRecordFrame",
+                    vec![
+                        // The delta is written as a f64 so it's read back without any rounding
+                        ("delta", Serialized::F64(frame.delta as f64)),
+                        ("cursor_x", Serialized::U32(frame.cursor_position.0)),
+                        ("cursor_y", Serialized::U32(frame.cursor_position.1)),
+                        ("events", Serialized::Array(events)),
+                    ],
+                )
+            })
+            .collect::<Vec<_>>();
+
+        serialized_object(
+            "InputRecording",
+            vec![
+                ("seed", Serialized::U64(self.seed)),
+                ("frames", Serialized::Array(frames)),
+            ],
+        )
+    }
+
+    fn deserialize(serialized: Serialized) -> Result<InputRecording, String> {
+        let mut fields = object_fields(serialized, "InputRecording")?;
+
+        let frames = match fields.remove("frames") {
+            Some(Serialized::Array(frames)) => frames,
+            _ => Vec::new(),
+        };
+
+        let frames = frames
+            .into_iter()
+            .map(|frame| {
+                let mut fields = object_fields(frame, "InputRecordFrame")?;
+
+                let events = match fields.remove("events") {
+                    Some(Serialized::Array(events)) => events,
+                    _ => Vec::new(),
+                };
+
+                Ok(InputRecordFrame {
+                    delta: get_field::<f64>(&mut fields, "delta")? as f32,
+                    cursor_position: (
+                        get_field::<u32>(&mut fields, "cursor_x")?,
+                        get_field::<u32>(&mut fields, "cursor_y")?,
+                    ),
+                    events: events
+                        .into_iter()
+                        .map(deserialize_event)
+                        .collect::<Result<Vec<_>, _>>()?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(InputRecording {
+            seed: get_field::<u64>(&mut fields, "seed")?,
+            frames,
+        })
+    }
+}
+
+fn serialize_event(event: &InputRecordEvent) -> Serialized {
+    match event {
+        InputRecordEvent::Pressed(source) => serialized_object(
+            "Pressed",
+            vec![("source", Serialized::String(source.clone()))],
+        ),
+        InputRecordEvent::Released(source) => serialized_object(
+            "Released",
+            vec![("source", Serialized::String(source.clone()))],
+        ),
+        InputRecordEvent::Modifiers(modifiers) => serialized_object(
+            "Modifiers",
+            vec![("modifiers", Serialized::U32(modifiers.bits()))],
+        ),
+        InputRecordEvent::Axis(source, value) => serialized_object(
+            "Axis",
+            vec![
+                ("source", Serialized::String(source.clone())),
+                ("value", Serialized::F64(*value as f64)),
+            ],
+        ),
+        InputRecordEvent::Text(text) => {
+            serialized_object("Text", vec![("text", Serialized::String(text.clone()))])
+        }
+        InputRecordEvent::ImeComposition(text) => serialized_object(
+            "ImeComposition",
+            vec![("text", Serialized::String(text.clone()))],
+        ),
+    }
+}
+
+fn deserialize_event(serialized: Serialized) -> Result<InputRecordEvent, String> {
+    let class_name = match &serialized {
+        Serialized::SerializedObject { class_name, .. } => class_name.clone(),
+        _ => return Err("An input event should be an object".to_string()),
+    };
+    let mut fields = object_fields(serialized, &class_name)?;
+
+    match class_name.as_str() {
+        "Pressed" => Ok(InputRecordEvent::Pressed(get_field(&mut fields, "source")?)),
+        "Released" => Ok(InputRecordEvent::Released(get_field(
+            &mut fields,
+            "source",
+        )?)),
+        "Modifiers" => Ok(InputRecordEvent::Modifiers(Modifiers::from_bits_truncate(
+            get_field(&mut fields, "modifiers")?,
+        ))),
+        "Axis" => Ok(InputRecordEvent::Axis(
+            get_field(&mut fields, "source")?,
+            get_field::<f64>(&mut fields, "value")? as f32,
+        )),
+        "Text" => Ok(InputRecordEvent::Text(get_field(&mut fields, "text")?)),
+        "ImeComposition" => Ok(InputRecordEvent::ImeComposition(get_field(
+            &mut fields,
+            "text",
+        )?)),
+        class_name => Err(format!("Unknown input event {}", class_name)),
+    }
+}
+
+fn serialized_object(class_name: &str, fields: Vec<(&str, Serialized)>) -> Serialized {
+    Serialized::SerializedObject {
+        class_name: class_name.to_string(),
+        fields: fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    }
+}
+
+fn object_fields(
+    serialized: Serialized,
+    expected_class_name: &str,
+) -> Result<HashMap<String, Serialized>, String> {
+    match serialized {
+        Serialized::SerializedObject { class_name, fields }
+            if class_name == expected_class_name =>
+        {
+            Ok(fields)
+        }
+        _ => Err(format!("Expected a {}", expected_class_name)),
+    }
+}
+
+fn get_field<T: FruityTryFrom<Serialized, Error = String>>(
+    fields: &mut HashMap<String, Serialized>,
+    name: &str,
+) -> Result<T, String> {
+    let value = fields
+        .remove(name)
+        .ok_or_else(|| format!("The field {} is missing", name))?;
+
+    T::fruity_try_from(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_recording_is_read_back_unchanged() {
+        let recording = InputRecording {
+            seed: 42,
+            frames: vec![
+                InputRecordFrame {
+                    delta: 1.0 / 60.0,
+                    cursor_position: (10, 20),
+                    events: vec![
+                        InputRecordEvent::Modifiers(Modifiers::CTRL),
+                        InputRecordEvent::Pressed("Keyboard/S".to_string()),
+                    ],
+                },
+                InputRecordFrame {
+                    delta: 0.0173,
+                    cursor_position: (11, 20),
+                    events: vec![
+                        InputRecordEvent::Released("Keyboard/S".to_string()),
+                        InputRecordEvent::Axis("Gamepad0/LeftStickX".to_string(), -0.123_456_7),
+                        InputRecordEvent::ImeComposition("にほ".to_string()),
+                        InputRecordEvent::Text("日本".to_string()),
+                    ],
+                },
+                InputRecordFrame {
+                    delta: 0.016,
+                    cursor_position: (11, 20),
+                    events: Vec::new(),
+                },
+            ],
+        };
+
+        let mut buffer = Vec::new();
+        recording.write(&mut buffer).unwrap();
+        let read_recording = InputRecording::read(&mut buffer.as_slice()).unwrap();
+
+        assert_eq!(read_recording, recording);
+    }
+}
@@ -1,3 +1,11 @@
+use crate::gamepad::get_gamepad_source;
+use crate::gamepad::GamepadBackend;
+use crate::gamepad::GamepadEvent;
+use crate::gamepad::GamepadId;
+use crate::input_binding::InputBinding;
+use crate::input_binding::InputComponent;
+use crate::input_binding::InputTrigger;
+use crate::input_recording::InputRecordEvent;
 use bitflags::bitflags;
 use fruity_any::*;
 use fruity_core::convert::FruityInto;
@@ -10,10 +18,15 @@ use fruity_core::introspect::SetterCaller;
 use fruity_core::resource::resource::Resource;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::serialize::serialized::Serialized;
+use fruity_core::settings::read_settings;
+use fruity_core::settings::write_settings;
 use fruity_core::settings::Settings;
 use fruity_core::signal::Signal;
+use fruity_core::utils::introspect::cast_introspect_mut;
 use fruity_core::utils::introspect::cast_introspect_ref;
 use fruity_core::utils::introspect::ArgumentCaster;
+use fruity_core::Mutex;
+use fruity_graphic::math::vector2d::Vector2d;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -48,9 +61,41 @@ impl Modifiers {
     }
 }
 
+/// The dead zone of the axis that don't declare one
+pub const DEFAULT_DEAD_ZONE: f32 = 0.2;
+
+/// The value an analog source should reach to press the bindings it is part of
+pub const PRESS_THRESHOLD: f32 = 0.5;
+
+/// The file where the bindings changed by the user are saved if the settings don't set one
+pub const DEFAULT_INPUT_OVERRIDES_PATH: &str = "user://input.yaml";
+
+/// The sources that are ignored while listening for an input, they are held as modifiers
+const MODIFIER_SOURCES: &[&str] = &[
+    "Keyboard/LShift",
+    "Keyboard/RShift",
+    "Keyboard/LControl",
+    "Keyboard/RControl",
+    "Keyboard/LAlt",
+    "Keyboard/RAlt",
+    "Keyboard/LWin",
+    "Keyboard/RWin",
+];
+
+/// The prefix of the keyboard sources, their bindings are suppressed while the text input is
+/// enabled
+const KEYBOARD_SOURCE_PREFIX: &str = "Keyboard/";
+
 #[derive(FruityAny)]
 pub struct InputService {
-    pub input_map: HashMap<String, String>,
+    pub actions: HashMap<String, Vec<InputBinding>>,
+    pub default_actions: HashMap<String, Vec<InputBinding>>,
+    pub overridden_actions: HashSet<String>,
+    pub overrides_path: String,
+    pub axis_values: HashMap<String, f32>,
+    pub dead_zone: f32,
+    pub dead_zones: HashMap<String, f32>,
+    pub gamepads: HashMap<GamepadId, String>,
     pub pressed_inputs: HashSet<String>,
     pub pressed_sources: HashSet<String>,
     pub pressed_modifiers: Modifiers,
@@ -58,8 +103,22 @@ pub struct InputService {
     pub pressed_this_frame_sources: HashSet<String>,
     pub released_this_frame_inputs: HashSet<String>,
     pub released_this_frame_sources: HashSet<String>,
+    pub text_input_enabled: bool,
+    pub text_this_frame: String,
+    pub ime_composition: String,
+    pub time: f64,
     pub on_pressed: Signal<String>,
     pub on_released: Signal<String>,
+    pub on_gamepad_connected: Signal<GamepadId>,
+    pub on_gamepad_disconnected: Signal<GamepadId>,
+    pub on_text_input: Signal<String>,
+    pub on_ime_composition: Signal<String>,
+    gamepad_backend: Option<Mutex<Box<dyn GamepadBackend>>>,
+    resource_container: ResourceContainer,
+    recorded_events: Option<Vec<InputRecordEvent>>,
+    replaying: bool,
+    listening: bool,
+    listened_input: Option<String>,
 }
 
 impl Debug for InputService {
@@ -69,9 +128,16 @@ impl Debug for InputService {
 }
 
 impl InputService {
-    pub fn new(_resource_container: ResourceContainer) -> InputService {
+    pub fn new(resource_container: ResourceContainer) -> InputService {
         InputService {
-            input_map: HashMap::new(),
+            actions: HashMap::new(),
+            default_actions: HashMap::new(),
+            overridden_actions: HashSet::new(),
+            overrides_path: DEFAULT_INPUT_OVERRIDES_PATH.to_string(),
+            axis_values: HashMap::new(),
+            dead_zone: DEFAULT_DEAD_ZONE,
+            dead_zones: HashMap::new(),
+            gamepads: HashMap::new(),
             pressed_inputs: HashSet::new(),
             pressed_sources: HashSet::new(),
             pressed_modifiers: Default::default(),
@@ -79,28 +145,470 @@ impl InputService {
             pressed_this_frame_sources: HashSet::new(),
             released_this_frame_inputs: HashSet::new(),
             released_this_frame_sources: HashSet::new(),
+            text_input_enabled: false,
+            text_this_frame: String::new(),
+            ime_composition: String::new(),
+            time: 0.0,
             on_pressed: Signal::new(),
             on_released: Signal::new(),
+            on_gamepad_connected: Signal::new(),
+            on_gamepad_disconnected: Signal::new(),
+            on_text_input: Signal::new(),
+            on_ime_composition: Signal::new(),
+            gamepad_backend: None,
+            resource_container,
+            recorded_events: None,
+            replaying: false,
+            listening: false,
+            listened_input: None,
         }
     }
 
+    /// Read the bindings from the settings, see [’InputBinding’] for the binding syntax
+    /// The bindings saved by the user are then read and replace the ones of the settings
     pub fn read_input_settings(&mut self, settings: &Settings) {
-        let settings = settings.get_settings("input");
-        if let Settings::Object(input_map) = settings {
-            input_map.iter().for_each(|(input, sources)| {
-                let sources = Vec::<String>::fruity_try_from(sources.clone());
+        self.dead_zone = settings.get::<f32>("input_dead_zone", DEFAULT_DEAD_ZONE);
 
-                if let Ok(sources) = sources {
-                    sources
-                        .iter()
-                        .for_each(|source| self.register_input(input, source));
+        read_bindings_settings(&settings.get_settings("input"))
+            .into_iter()
+            .for_each(|(input, bindings)| {
+                bindings
+                    .into_iter()
+                    .for_each(|binding| self.register_binding(&input, binding))
+            });
+
+        let dead_zones = settings.get_settings("input_dead_zones");
+        if let Settings::Object(dead_zones) = dead_zones {
+            dead_zones.iter().for_each(|(axis, dead_zone)| {
+                if let Ok(dead_zone) = f32::fruity_try_from(dead_zone.clone()) {
+                    self.set_dead_zone(axis, dead_zone);
                 }
             })
         }
+
+        self.overrides_path =
+            settings.get::<String>("input_overrides", DEFAULT_INPUT_OVERRIDES_PATH.to_string());
+
+        let file_system = self.resource_container.get_file_system();
+        if file_system.exists(&self.overrides_path) {
+            if let Err(err) = self.load_input_overrides() {
+                log::error!("{}", err);
+            }
+        }
     }
 
+    /// Bind a source to an input
+    ///
+    /// # Arguments
+    /// * `input` - The input name
+    /// * `source` - The binding, for example "Keyboard/A" or "Ctrl+Keyboard/S", see [’InputBinding’]
+    ///
     pub fn register_input(&mut self, input: &str, source: &str) {
-        self.input_map.insert(source.to_string(), input.to_string());
+        match InputBinding::parse(source) {
+            Ok(binding) => self.register_binding(input, binding),
+            Err(err) => log::error!("Input \"{}\": {}", input, err),
+        }
+    }
+
+    /// Add a binding to an input, an input can have many bindings and a source can be part of
+    /// the bindings of many inputs
+    ///
+    /// # Arguments
+    /// * `input` - The input name
+    /// * `binding` - The binding
+    ///
+    pub fn register_binding(&mut self, input: &str, binding: InputBinding) {
+        let default_bindings = self.default_actions.entry(input.to_string()).or_default();
+        default_bindings.push(binding.clone());
+
+        // The bindings changed by the user are kept
+        if !self.overridden_actions.contains(input) {
+            let bindings = self.actions.entry(input.to_string()).or_default();
+            bindings.push(binding);
+        }
+    }
+
+    /// Get the bindings of an input as they are written in the settings
+    ///
+    /// # Arguments
+    /// * `input` - The input name
+    ///
+    pub fn get_bindings(&self, input: &str) -> Vec<String> {
+        self.actions
+            .get(input)
+            .map(|bindings| bindings.iter().map(|binding| binding.to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Replace a binding of an input, the change is part of the user overrides
+    /// The component, the scale and the trigger of the replaced binding are kept, that way
+    /// "Keyboard/A" can replace "Keyboard/Left:-x" in a 2D vector input
+    /// Returns the other inputs that are bound to the same sources
+    ///
+    /// # Arguments
+    /// * `input` - The input name
+    /// * `index` - The index of the binding, the binding is added if it's the binding count
+    /// * `binding` - The new binding, for example "Ctrl+Keyboard/S"
+    ///
+    pub fn rebind(
+        &mut self,
+        input: &str,
+        index: usize,
+        binding: &str,
+    ) -> Result<Vec<String>, String> {
+        let new_binding = InputBinding::parse(binding)?;
+        let bindings = self.actions.entry(input.to_string()).or_default();
+
+        if index < bindings.len() {
+            let binding = &mut bindings[index];
+            binding.sources = new_binding.sources.clone();
+            binding.modifiers = new_binding.modifiers;
+            binding.state = Default::default();
+        } else if index == bindings.len() {
+            bindings.push(new_binding.clone());
+        } else {
+            return Err(format!(
+                "Input \"{}\" has {} bindings, the binding {} can't be set",
+                input,
+                bindings.len(),
+                index
+            ));
+        }
+
+        self.overridden_actions.insert(input.to_string());
+        self.update_inputs();
+
+        Ok(self.get_conflicts(input, &new_binding))
+    }
+
+    /// Remove a binding of an input, the change is part of the user overrides
+    ///
+    /// # Arguments
+    /// * `input` - The input name
+    /// * `index` - The index of the binding
+    ///
+    pub fn unbind(&mut self, input: &str, index: usize) {
+        if let Some(bindings) = self.actions.get_mut(input) {
+            if index < bindings.len() {
+                bindings.remove(index);
+                self.overridden_actions.insert(input.to_string());
+                self.update_inputs();
+            }
+        }
+    }
+
+    /// Get the inputs other than the given one that are bound to the same sources and modifiers
+    ///
+    /// # Arguments
+    /// * `input` - The input name
+    /// * `binding` - The binding
+    ///
+    pub fn get_conflicts(&self, input: &str, binding: &InputBinding) -> Vec<String> {
+        let mut conflicts = self
+            .actions
+            .iter()
+            .filter(|(other_input, other_bindings)| {
+                other_input.as_str() != input
+                    && other_bindings
+                        .iter()
+                        .any(|other_binding| other_binding.is_same_input(binding))
+            })
+            .map(|(other_input, _)| other_input.clone())
+            .collect::<Vec<_>>();
+
+        conflicts.sort();
+        conflicts
+    }
+
+    /// Restore the bindings of the settings for an input
+    ///
+    /// # Arguments
+    /// * `input` - The input name
+    ///
+    pub fn reset_bindings(&mut self, input: &str) {
+        match self.default_actions.get(input) {
+            Some(bindings) => {
+                self.actions.insert(input.to_string(), bindings.clone());
+            }
+            None => {
+                self.actions.remove(input);
+            }
+        }
+
+        self.overridden_actions.remove(input);
+        self.update_inputs();
+    }
+
+    /// Restore the bindings of the settings for all the inputs
+    pub fn reset_all_bindings(&mut self) {
+        self.actions = self.default_actions.clone();
+        self.overridden_actions.clear();
+        self.update_inputs();
+    }
+
+    /// Write the bindings changed by the user into [’InputService::overrides_path’], the file
+    /// has the same layout as the input settings
+    pub fn save_input_overrides(&self) -> Result<(), String> {
+        let overrides = self
+            .overridden_actions
+            .iter()
+            .map(|input| {
+                let bindings = self
+                    .actions
+                    .get(input)
+                    .map(|bindings| {
+                        bindings
+                            .iter()
+                            .map(|binding| binding.to_settings())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                (input.clone(), Settings::Array(bindings))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let file_system = self.resource_container.get_file_system();
+        let mut writer = file_system
+            .create(&self.overrides_path)
+            .map_err(|err| err.to_string())?;
+
+        write_settings(&mut writer, &Settings::Object(overrides)).map_err(|err| err.to_string())
+    }
+
+    /// Read the bindings saved by [’InputService::save_input_overrides’], they replace the
+    /// bindings of the settings for the inputs they are about
+    pub fn load_input_overrides(&mut self) -> Result<(), String> {
+        let file_system = self.resource_container.get_file_system();
+        let mut reader = file_system
+            .open(&self.overrides_path)
+            .map_err(|err| err.to_string())?;
+
+        read_bindings_settings(&read_settings(&mut reader))
+            .into_iter()
+            .for_each(|(input, bindings)| {
+                self.actions.insert(input.clone(), bindings);
+                self.overridden_actions.insert(input);
+            });
+
+        self.update_inputs();
+        Ok(())
+    }
+
+    /// Capture the next pressed source instead of applying it, it's then returned by
+    /// [’InputService::take_listened_input’] with the held modifiers, for example "Ctrl+Keyboard/S"
+    pub fn listen_next_input(&mut self) {
+        self.listening = true;
+        self.listened_input = None;
+    }
+
+    pub fn cancel_listening(&mut self) {
+        self.listening = false;
+    }
+
+    pub fn is_listening(&self) -> bool {
+        self.listening
+    }
+
+    /// Get the input captured since [’InputService::listen_next_input’] was called, if any
+    pub fn take_listened_input(&mut self) -> Option<String> {
+        self.listened_input.take()
+    }
+
+    /// Set the dead zone of an axis, the analog values below the dead zone are ignored
+    ///
+    /// # Arguments
+    /// * `axis` - The axis name
+    /// * `dead_zone` - The dead zone, between 0 and 1
+    ///
+    pub fn set_dead_zone(&mut self, axis: &str, dead_zone: f32) {
+        self.dead_zones
+            .insert(axis.to_string(), dead_zone.clamp(0.0, 1.0));
+    }
+
+    /// Get the value of an axis, the sum of the bindings clamped between -1 and 1
+    /// The bindings that contribute to a vector component are ignored
+    ///
+    /// # Arguments
+    /// * `axis` - The axis name
+    ///
+    pub fn get_axis(&self, axis: &str) -> f32 {
+        let value = self.get_component_value(axis, InputComponent::Axis);
+        value.clamp(-1.0, 1.0)
+    }
+
+    /// Get the value of a 2D vector input, the bindings contribute to the x and the y components
+    /// with a suffix like "Keyboard/Left:-x", the vector length is at most 1
+    ///
+    /// # Arguments
+    /// * `input` - The input name
+    ///
+    pub fn get_vector2d(&self, input: &str) -> Vector2d {
+        let vector = Vector2d::new(
+            self.get_component_value(input, InputComponent::X),
+            self.get_component_value(input, InputComponent::Y),
+        );
+
+        if vector.length() > 1.0 {
+            vector.normalise()
+        } else {
+            vector
+        }
+    }
+
+    /// Get the value of a source, the value of an analog source or 1 if a digital source is
+    /// pressed
+    ///
+    /// # Arguments
+    /// * `source` - The source
+    /// * `dead_zone` - The dead zone applied to the analog sources
+    ///
+    pub fn get_source_value(&self, source: &str, dead_zone: f32) -> f32 {
+        if let Some(value) = self.axis_values.get(source) {
+            apply_dead_zone(*value, dead_zone)
+        } else if self.pressed_sources.contains(source) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn get_dead_zone(&self, input: &str) -> f32 {
+        self.dead_zones
+            .get(input)
+            .copied()
+            .unwrap_or(self.dead_zone)
+    }
+
+    fn get_component_value(&self, input: &str, component: InputComponent) -> f32 {
+        let dead_zone = self.get_dead_zone(input);
+
+        self.actions
+            .get(input)
+            .map(|bindings| {
+                bindings
+                    .iter()
+                    .filter(|binding| binding.component == component)
+                    .map(|binding| self.get_binding_value(binding, dead_zone) * binding.scale)
+                    .sum::<f32>()
+            })
+            .unwrap_or_default()
+    }
+
+    fn get_binding_value(&self, binding: &InputBinding, dead_zone: f32) -> f32 {
+        // A single analog source keeps its value, the other bindings are either 0 or 1
+        match (binding.trigger, binding.sources.as_slice()) {
+            (InputTrigger::Press, [source]) => {
+                if self.pressed_modifiers.contains(binding.modifiers)
+                    && !binding.state.shadowed
+                    && !self.is_binding_suppressed(binding)
+                {
+                    self.get_source_value(source, dead_zone)
+                } else {
+                    0.0
+                }
+            }
+            _ => {
+                if binding.state.active {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    // The keys typed as text don't trigger the keyboard bindings, so typing a name doesn't
+    // make the character jump
+    fn is_binding_suppressed(&self, binding: &InputBinding) -> bool {
+        self.text_input_enabled
+            && binding
+                .sources
+                .iter()
+                .any(|source| source.starts_with(KEYBOARD_SOURCE_PREFIX))
+    }
+
+    fn is_binding_down(&self, binding: &InputBinding, dead_zone: f32) -> bool {
+        self.pressed_modifiers.contains(binding.modifiers)
+            && !self.is_binding_suppressed(binding)
+            && binding
+                .sources
+                .iter()
+                .all(|source| self.get_source_value(source, dead_zone).abs() >= PRESS_THRESHOLD)
+    }
+
+    /// Update the state of the inputs from the state of the sources and notify the inputs that
+    /// are pressed or released
+    fn update_inputs(&mut self) {
+        // A binding is shadowed by a more specific binding of the same sources that is down, so
+        // "Keyboard/S" is not down while "Ctrl+Keyboard/S" is
+        let this = &*self;
+        let modified_bindings = this
+            .actions
+            .iter()
+            .flat_map(|(input, bindings)| {
+                let dead_zone = this.get_dead_zone(input);
+                bindings
+                    .iter()
+                    .filter(move |binding| {
+                        !binding.modifiers.is_empty() && this.is_binding_down(binding, dead_zone)
+                    })
+                    .cloned()
+            })
+            .collect::<Vec<_>>();
+
+        let mut actions = std::mem::take(&mut self.actions);
+
+        let changes = actions
+            .iter_mut()
+            .filter_map(|(input, bindings)| {
+                let dead_zone = self.get_dead_zone(input);
+
+                let mut pressed = false;
+                bindings.iter_mut().for_each(|binding| {
+                    binding.state.shadowed = modified_bindings
+                        .iter()
+                        .any(|modified_binding| modified_binding.is_more_specific_than(binding));
+
+                    let down = !binding.state.shadowed && self.is_binding_down(binding, dead_zone);
+                    pressed |= binding.update(down, self.time);
+                });
+
+                if pressed != self.pressed_inputs.contains(input) {
+                    Some((input.clone(), pressed))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        self.actions = actions;
+
+        changes.into_iter().for_each(|(input, pressed)| {
+            if pressed {
+                self.pressed_inputs.insert(input.clone());
+                self.pressed_this_frame_inputs.insert(input.clone());
+                self.on_pressed.notify(input);
+            } else {
+                self.pressed_inputs.remove(&input);
+                self.released_this_frame_inputs.insert(input.clone());
+                self.on_released.notify(input);
+            }
+        });
+    }
+
+    /// Read the gamepads and advance the time of the inputs, should be called once per frame
+    ///
+    /// The gamepads are read before the time advances, that way they are handled like the
+    /// events received from the platform between two frames and a replay is exact
+    ///
+    /// # Arguments
+    /// * `delta` - The duration of the last frame, in seconds
+    ///
+    pub fn update(&mut self, delta: f32) {
+        self.poll_gamepads();
+        self.time += delta as f64;
+        self.update_inputs();
     }
 
     pub fn is_pressed(&self, input: &str) -> bool {
@@ -146,40 +654,367 @@ impl InputService {
     }
 
     pub fn notify_pressed(&mut self, source: &str) {
-        self.pressed_sources.insert(source.to_string());
-        self.pressed_this_frame_sources.insert(source.to_string());
+        self.handle_source_event(InputRecordEvent::Pressed(source.to_string()));
+    }
 
-        if let Some(input) = self.input_map.get(source) {
-            if !self.pressed_inputs.contains(input) {
-                self.pressed_inputs.insert(input.clone());
-                self.pressed_this_frame_inputs.insert(input.to_string());
-                self.on_pressed.notify(input.clone());
+    pub fn notify_released(&mut self, source: &str) {
+        self.handle_source_event(InputRecordEvent::Released(source.to_string()));
+    }
+
+    pub fn notify_axis(&mut self, source: &str, value: f32) {
+        self.handle_source_event(InputRecordEvent::Axis(source.to_string(), value));
+    }
+
+    pub fn notify_modifiers(&mut self, modifier: Modifiers) {
+        self.handle_source_event(InputRecordEvent::Modifiers(modifier));
+    }
+
+    /// Notify a text typed by the user or committed by the IME
+    ///
+    /// # Arguments
+    /// * `text` - The typed text
+    ///
+    pub fn notify_text(&mut self, text: &str) {
+        self.handle_source_event(InputRecordEvent::Text(text.to_string()));
+    }
+
+    /// Notify the text that is being composed with the IME, an empty text ends the composition
+    ///
+    /// The platform backends that can read the composition call it, the winit backend can't
+    /// and only notifies the committed text
+    ///
+    /// # Arguments
+    /// * `text` - The composed text
+    ///
+    pub fn notify_ime_composition(&mut self, text: &str) {
+        self.handle_source_event(InputRecordEvent::ImeComposition(text.to_string()));
+    }
+
+    /// Start or stop receiving the text typed by the user, the text is ignored when the text
+    /// input is disabled so the key presses of the game are not read as text
+    ///
+    /// While the text input is enabled, the bindings of the keyboard sources are released and
+    /// don't trigger their inputs, the keyboard sources can still be read with
+    /// [’InputService::is_source_pressed’]
+    ///
+    /// # Arguments
+    /// * `enabled` - True to receive the text
+    ///
+    pub fn set_text_input_enabled(&mut self, enabled: bool) {
+        self.text_input_enabled = enabled;
+
+        if !enabled {
+            self.text_this_frame.clear();
+            self.ime_composition.clear();
+        }
+
+        self.update_inputs();
+    }
+
+    pub fn is_text_input_enabled(&self) -> bool {
+        self.text_input_enabled
+    }
+
+    /// Get the text typed since the start of the frame
+    pub fn get_text_this_frame(&self) -> String {
+        self.text_this_frame.clone()
+    }
+
+    /// Get the text that is being composed with the IME, it's not committed yet
+    pub fn get_ime_composition(&self) -> String {
+        self.ime_composition.clone()
+    }
+
+    /// Start or stop recording the events received from the platform
+    /// The sources that are already pressed are recorded first, that way a replay starts
+    /// from the same state
+    ///
+    /// # Arguments
+    /// * `recording` - True to start recording
+    ///
+    pub fn set_recording(&mut self, recording: bool) {
+        self.recorded_events = if recording {
+            Some(self.get_source_state_events())
+        } else {
+            None
+        };
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorded_events.is_some()
+    }
+
+    /// Get the events recorded since the last call
+    pub fn take_recorded_events(&mut self) -> Vec<InputRecordEvent> {
+        self.recorded_events
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Start or stop ignoring the events received from the platform while a recording is
+    /// replayed, the sources are released in both cases so the replay starts from a clean state
+    ///
+    /// # Arguments
+    /// * `replaying` - True to start replaying
+    ///
+    pub fn set_replaying(&mut self, replaying: bool) {
+        self.replaying = replaying;
+        self.release_sources();
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replaying
+    }
+
+    /// Apply an event of a replayed recording
+    ///
+    /// # Arguments
+    /// * `event` - The recorded event
+    ///
+    pub fn replay_event(&mut self, event: InputRecordEvent) {
+        self.apply_source_event(event);
+    }
+
+    fn handle_source_event(&mut self, event: InputRecordEvent) {
+        if self.replaying {
+            return;
+        }
+
+        if let Some(recorded_events) = &mut self.recorded_events {
+            recorded_events.push(event.clone());
+        }
+
+        self.apply_source_event(event);
+    }
+
+    fn apply_source_event(&mut self, event: InputRecordEvent) {
+        if self.listening {
+            if let Some(listened_input) = self.get_listened_input(&event) {
+                self.listening = false;
+                self.listened_input = Some(listened_input);
+                return;
             }
         }
+
+        match event {
+            InputRecordEvent::Pressed(source) => {
+                self.pressed_sources.insert(source.clone());
+                self.pressed_this_frame_sources.insert(source);
+            }
+            InputRecordEvent::Released(source) => {
+                self.pressed_sources.remove(&source);
+                self.released_this_frame_sources.insert(source);
+            }
+            InputRecordEvent::Modifiers(modifiers) => {
+                self.pressed_modifiers = modifiers;
+            }
+            InputRecordEvent::Axis(source, value) => {
+                self.axis_values.insert(source, value);
+            }
+            InputRecordEvent::Text(text) => {
+                self.receive_text(&text);
+                return;
+            }
+            InputRecordEvent::ImeComposition(text) => {
+                if self.text_input_enabled && self.ime_composition != text {
+                    self.ime_composition = text.clone();
+                    self.on_ime_composition.notify(text);
+                }
+
+                return;
+            }
+        }
+
+        self.update_inputs();
     }
 
-    pub fn notify_released(&mut self, source: &str) {
-        self.pressed_sources.remove(source);
-        self.released_this_frame_sources.insert(source.to_string());
-
-        if let Some(input) = self.input_map.get(source) {
-            if self.pressed_inputs.contains(input) {
-                self.pressed_inputs.remove(input);
-                self.released_this_frame_inputs.insert(input.to_string());
-                self.on_released.notify(input.clone());
+    fn receive_text(&mut self, text: &str) {
+        if !self.text_input_enabled {
+            return;
+        }
+
+        // The control characters like backspace or return are read with their keyboard sources
+        let text = text
+            .chars()
+            .filter(|character| !character.is_control())
+            .collect::<String>();
+
+        if !text.is_empty() {
+            self.text_this_frame.push_str(&text);
+            self.on_text_input.notify(text);
+        }
+    }
+
+    fn get_listened_input(&self, event: &InputRecordEvent) -> Option<String> {
+        let source = match event {
+            InputRecordEvent::Pressed(source) if !MODIFIER_SOURCES.contains(&source.as_str()) => {
+                source
+            }
+            InputRecordEvent::Axis(source, value) if value.abs() >= PRESS_THRESHOLD => source,
+            _ => return None,
+        };
+
+        let mut binding = InputBinding::new(source);
+        binding.modifiers = self.pressed_modifiers;
+
+        Some(binding.to_string())
+    }
+
+    fn get_source_state_events(&self) -> Vec<InputRecordEvent> {
+        let mut pressed_sources = self.pressed_sources.iter().cloned().collect::<Vec<_>>();
+        pressed_sources.sort();
+
+        let mut axis_values = self
+            .axis_values
+            .iter()
+            .map(|(source, value)| (source.clone(), *value))
+            .collect::<Vec<_>>();
+        axis_values.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        std::iter::once(InputRecordEvent::Modifiers(self.pressed_modifiers))
+            .chain(pressed_sources.into_iter().map(InputRecordEvent::Pressed))
+            .chain(
+                axis_values
+                    .into_iter()
+                    .map(|(source, value)| InputRecordEvent::Axis(source, value)),
+            )
+            .collect()
+    }
+
+    fn release_sources(&mut self) {
+        let pressed_sources = std::mem::take(&mut self.pressed_sources);
+        self.released_this_frame_sources.extend(pressed_sources);
+        self.axis_values.clear();
+        self.pressed_modifiers = Modifiers::empty();
+
+        self.update_inputs();
+    }
+
+    /// Set the backend the gamepad events are read from
+    pub fn set_gamepad_backend(&mut self, gamepad_backend: Box<dyn GamepadBackend>) {
+        self.gamepad_backend = Some(Mutex::new(gamepad_backend));
+    }
+
+    /// Read the events of the gamepad backend
+    pub fn poll_gamepads(&mut self) {
+        let events = if let Some(gamepad_backend) = &self.gamepad_backend {
+            let mut gamepad_backend = gamepad_backend.lock();
+            gamepad_backend.poll_events()
+        } else {
+            return;
+        };
+
+        events
+            .into_iter()
+            .for_each(|event| self.handle_gamepad_event(event));
+    }
+
+    pub fn handle_gamepad_event(&mut self, event: GamepadEvent) {
+        match event {
+            GamepadEvent::Connected(gamepad_id, name) => {
+                self.gamepads.insert(gamepad_id, name);
+                self.on_gamepad_connected.notify(gamepad_id);
+            }
+            GamepadEvent::Disconnected(gamepad_id) => {
+                // The inputs held on the gamepad are released
+                let prefix = get_gamepad_source(gamepad_id, "");
+                let pressed_sources = self
+                    .pressed_sources
+                    .iter()
+                    .filter(|source| source.starts_with(&prefix))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                let axis_sources = self
+                    .axis_values
+                    .keys()
+                    .filter(|source| source.starts_with(&prefix))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                pressed_sources
+                    .iter()
+                    .for_each(|source| self.notify_released(source));
+
+                axis_sources
+                    .iter()
+                    .for_each(|source| self.notify_axis(source, 0.0));
+
+                if self.gamepads.remove(&gamepad_id).is_some() {
+                    self.on_gamepad_disconnected.notify(gamepad_id);
+                }
+            }
+            GamepadEvent::ButtonPressed(gamepad_id, button) => {
+                self.notify_pressed(&get_gamepad_source(gamepad_id, button.get_name()));
+            }
+            GamepadEvent::ButtonReleased(gamepad_id, button) => {
+                self.notify_released(&get_gamepad_source(gamepad_id, button.get_name()));
+            }
+            GamepadEvent::AxisChanged(gamepad_id, axis, value) => {
+                self.notify_axis(&get_gamepad_source(gamepad_id, axis.get_name()), value);
             }
         }
     }
 
+    /// Get the connected gamepads
+    pub fn get_gamepads(&self) -> Vec<GamepadId> {
+        let mut gamepads = self.gamepads.keys().copied().collect::<Vec<_>>();
+        gamepads.sort_unstable();
+        gamepads
+    }
+
     pub fn handle_frame_end(&mut self) {
         self.pressed_this_frame_sources.clear();
         self.pressed_this_frame_inputs.clear();
         self.released_this_frame_sources.clear();
         self.released_this_frame_inputs.clear();
+        self.text_this_frame.clear();
     }
+}
 
-    pub fn notify_modifiers(&mut self, modifier: Modifiers) {
-        self.pressed_modifiers = modifier;
+/// Read the bindings of an input settings block, the bindings that can't be read are logged
+///
+/// # Arguments
+/// * `settings` - The settings, an object where each input has an array of bindings
+///
+pub fn read_bindings_settings(settings: &Settings) -> HashMap<String, Vec<InputBinding>> {
+    let mut result = HashMap::new();
+
+    if let Settings::Object(input_map) = settings {
+        input_map.iter().for_each(|(input, bindings)| {
+            if let Settings::Array(bindings) = bindings {
+                let bindings = bindings
+                    .iter()
+                    .filter_map(|binding| match InputBinding::from_settings(binding) {
+                        Ok(binding) => Some(binding),
+                        Err(err) => {
+                            log::error!("Input \"{}\": {}", input, err);
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                result.insert(input.clone(), bindings);
+            }
+        })
+    }
+
+    result
+}
+
+/// Remove the dead zone from an analog value, the remaining range is scaled so the value
+/// still goes from 0 to 1
+///
+/// # Arguments
+/// * `value` - The analog value, between -1 and 1
+/// * `dead_zone` - The dead zone, between 0 and 1
+///
+pub fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value.abs() <= dead_zone || dead_zone >= 1.0 {
+        0.0
+    } else {
+        value.signum() * (value.abs() - dead_zone) / (1.0 - dead_zone)
     }
 }
 
@@ -262,6 +1097,221 @@ impl IntrospectObject for InputService {
                     Ok(Some(Serialized::Bool(result)))
                 })),
             },
+            MethodInfo {
+                name: "get_axis".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<InputService>(this);
+
+                    let mut caster = ArgumentCaster::new("get_axis", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    let result = this.get_axis(&arg1);
+                    Ok(Some(Serialized::F32(result)))
+                })),
+            },
+            MethodInfo {
+                name: "get_vector2d".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<InputService>(this);
+
+                    let mut caster = ArgumentCaster::new("get_vector2d", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    let result = this.get_vector2d(&arg1);
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "get_gamepads".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<InputService>(this);
+
+                    let result = this.get_gamepads();
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "get_bindings".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<InputService>(this);
+
+                    let mut caster = ArgumentCaster::new("get_bindings", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    let result = this.get_bindings(&arg1);
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "rebind".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<InputService>(this);
+
+                    let mut caster = ArgumentCaster::new("rebind", args);
+                    let arg1 = caster.cast_next::<String>()?;
+                    let arg2 = caster.cast_next::<usize>()?;
+                    let arg3 = caster.cast_next::<String>()?;
+
+                    match this.rebind(&arg1, arg2, &arg3) {
+                        Ok(result) => Ok(Some(result.fruity_into())),
+                        Err(err) => {
+                            log::error!("{}", err);
+                            Ok(None)
+                        }
+                    }
+                })),
+            },
+            MethodInfo {
+                name: "unbind".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<InputService>(this);
+
+                    let mut caster = ArgumentCaster::new("unbind", args);
+                    let arg1 = caster.cast_next::<String>()?;
+                    let arg2 = caster.cast_next::<usize>()?;
+
+                    this.unbind(&arg1, arg2);
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "get_conflicts".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<InputService>(this);
+
+                    let mut caster = ArgumentCaster::new("get_conflicts", args);
+                    let arg1 = caster.cast_next::<String>()?;
+                    let arg2 = caster.cast_next::<String>()?;
+
+                    match InputBinding::parse(&arg2) {
+                        Ok(binding) => Ok(Some(this.get_conflicts(&arg1, &binding).fruity_into())),
+                        Err(err) => {
+                            log::error!("{}", err);
+                            Ok(None)
+                        }
+                    }
+                })),
+            },
+            MethodInfo {
+                name: "reset_bindings".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<InputService>(this);
+
+                    let mut caster = ArgumentCaster::new("reset_bindings", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    this.reset_bindings(&arg1);
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "reset_all_bindings".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, _args| {
+                    let this = cast_introspect_mut::<InputService>(this);
+
+                    this.reset_all_bindings();
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "save_input_overrides".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<InputService>(this);
+
+                    if let Err(err) = this.save_input_overrides() {
+                        log::error!("{}", err);
+                    }
+
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "load_input_overrides".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, _args| {
+                    let this = cast_introspect_mut::<InputService>(this);
+
+                    if let Err(err) = this.load_input_overrides() {
+                        log::error!("{}", err);
+                    }
+
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "listen_next_input".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, _args| {
+                    let this = cast_introspect_mut::<InputService>(this);
+
+                    this.listen_next_input();
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "cancel_listening".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, _args| {
+                    let this = cast_introspect_mut::<InputService>(this);
+
+                    this.cancel_listening();
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "is_listening".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<InputService>(this);
+
+                    let result = this.is_listening();
+                    Ok(Some(Serialized::Bool(result)))
+                })),
+            },
+            MethodInfo {
+                name: "take_listened_input".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, _args| {
+                    let this = cast_introspect_mut::<InputService>(this);
+
+                    let result = this.take_listened_input();
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "set_text_input_enabled".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<InputService>(this);
+
+                    let mut caster = ArgumentCaster::new("set_text_input_enabled", args);
+                    let arg1 = caster.cast_next::<bool>()?;
+
+                    this.set_text_input_enabled(arg1);
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "is_text_input_enabled".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<InputService>(this);
+                    let result = this.is_text_input_enabled();
+
+                    Ok(Some(Serialized::Bool(result)))
+                })),
+            },
+            MethodInfo {
+                name: "get_text_this_frame".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<InputService>(this);
+                    let result = this.get_text_this_frame();
+
+                    Ok(Some(Serialized::String(result)))
+                })),
+            },
+            MethodInfo {
+                name: "get_ime_composition".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<InputService>(this);
+                    let result = this.get_ime_composition();
+
+                    Ok(Some(Serialized::String(result)))
+                })),
+            },
         ]
     }
 
@@ -291,8 +1341,458 @@ impl IntrospectObject for InputService {
                 }),
                 setter: SetterCaller::None,
             },
+            FieldInfo {
+                name: "on_gamepad_connected".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    this.downcast_ref::<InputService>()
+                        .unwrap()
+                        .on_gamepad_connected
+                        .clone()
+                        .fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+            FieldInfo {
+                name: "on_gamepad_disconnected".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    this.downcast_ref::<InputService>()
+                        .unwrap()
+                        .on_gamepad_disconnected
+                        .clone()
+                        .fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+            FieldInfo {
+                name: "on_text_input".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    this.downcast_ref::<InputService>()
+                        .unwrap()
+                        .on_text_input
+                        .clone()
+                        .fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+            FieldInfo {
+                name: "on_ime_composition".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    this.downcast_ref::<InputService>()
+                        .unwrap()
+                        .on_ime_composition
+                        .clone()
+                        .fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
         ]
     }
 }
 
 impl Resource for InputService {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gamepad::FakeGamepadBackend;
+    use crate::gamepad::GamepadAxis;
+    use crate::gamepad::GamepadButton;
+    use crate::input_binding::InputBinding;
+    use fruity_core::vfs::directory_backend::DirectoryBackend;
+    use fruity_core::vfs::virtual_file_system::VirtualFileSystem;
+    use fruity_core::vfs::virtual_file_system::USER_SCHEME;
+
+    fn input_service() -> (InputService, FakeGamepadBackend) {
+        let mut input_service = InputService::new(ResourceContainer::new());
+        input_service.register_input("Jump", "Gamepad0/South");
+        input_service.register_input("Move X", "Gamepad0/LeftStickX");
+        input_service.register_input("Move X", "Keyboard/Left:-1");
+        input_service.register_input("Move X", "Keyboard/Right:+1");
+
+        let gamepad_backend = FakeGamepadBackend::new();
+        input_service.set_gamepad_backend(Box::new(gamepad_backend.clone()));
+
+        (input_service, gamepad_backend)
+    }
+
+    #[test]
+    fn parse_input_binding_reads_the_modifiers_and_the_scale() {
+        let binding = InputBinding::parse("Keyboard/Left:-1").unwrap();
+        assert_eq!(binding.sources, vec!["Keyboard/Left".to_string()]);
+        assert_eq!(binding.scale, -1.0);
+        assert_eq!(binding.component, InputComponent::Axis);
+
+        let binding = InputBinding::parse("Ctrl + Shift + Keyboard/S").unwrap();
+        assert_eq!(binding.sources, vec!["Keyboard/S".to_string()]);
+        assert_eq!(binding.modifiers, Modifiers::CTRL | Modifiers::SHIFT);
+        assert_eq!(binding.scale, 1.0);
+
+        let binding = InputBinding::parse("Gamepad0/LeftBumper+Gamepad0/South:-0.5y").unwrap();
+        assert_eq!(binding.sources.len(), 2);
+        assert_eq!(binding.scale, -0.5);
+        assert_eq!(binding.component, InputComponent::Y);
+
+        assert!(InputBinding::parse("Ctrl").is_err());
+        assert!(InputBinding::parse("Keyboard/S:z").is_err());
+    }
+
+    #[test]
+    fn get_axis_applies_the_dead_zone() {
+        let (mut input_service, gamepad_backend) = input_service();
+
+        gamepad_backend.connect(0, "Fake gamepad");
+        gamepad_backend.move_axis(0, GamepadAxis::LeftStickX, 0.1);
+        input_service.update(0.0);
+        assert_eq!(input_service.get_gamepads(), vec![0]);
+        assert_eq!(input_service.get_axis("Move X"), 0.0);
+
+        gamepad_backend.move_axis(0, GamepadAxis::LeftStickX, -0.6);
+        input_service.update(0.0);
+        assert!((input_service.get_axis("Move X") + 0.5).abs() < 0.0001);
+
+        input_service.set_dead_zone("Move X", 0.0);
+        assert!((input_service.get_axis("Move X") + 0.6).abs() < 0.0001);
+
+        // The keyboard and the gamepad are summed and clamped
+        input_service.notify_pressed("Keyboard/Left");
+        assert_eq!(input_service.get_axis("Move X"), -1.0);
+        input_service.notify_pressed("Keyboard/Right");
+        assert!((input_service.get_axis("Move X") + 0.6).abs() < 0.0001);
+    }
+
+    #[test]
+    fn gamepad_disconnection_releases_the_inputs() {
+        let (mut input_service, gamepad_backend) = input_service();
+
+        gamepad_backend.connect(0, "Fake gamepad");
+        gamepad_backend.press(0, GamepadButton::South);
+        gamepad_backend.move_axis(0, GamepadAxis::LeftStickX, 1.0);
+        input_service.update(0.0);
+        assert!(input_service.is_pressed("Jump"));
+        assert!(input_service.is_source_pressed("Gamepad0/South"));
+        assert_eq!(input_service.get_axis("Move X"), 1.0);
+
+        gamepad_backend.disconnect(0);
+        input_service.update(0.0);
+        assert!(!input_service.is_pressed("Jump"));
+        assert!(input_service.is_released_this_frame("Jump"));
+        assert_eq!(input_service.get_axis("Move X"), 0.0);
+        assert_eq!(input_service.get_gamepads(), Vec::<GamepadId>::new());
+    }
+
+    #[test]
+    fn get_vector2d_composes_the_bindings() {
+        let mut input_service = InputService::new(ResourceContainer::new());
+        input_service.register_input("Move", "Keyboard/Left:-x");
+        input_service.register_input("Move", "Keyboard/Right:+x");
+        input_service.register_input("Move", "Keyboard/Up:y");
+        input_service.register_input("Move", "Keyboard/Down:-y");
+
+        input_service.notify_pressed("Keyboard/Left");
+        assert_eq!(input_service.get_vector2d("Move"), Vector2d::new(-1.0, 0.0));
+        assert!(input_service.is_pressed("Move"));
+
+        // The diagonals are not faster
+        input_service.notify_pressed("Keyboard/Up");
+        let vector = input_service.get_vector2d("Move");
+        assert!((vector.length() - 1.0).abs() < 0.0001);
+        assert!(vector.x < 0.0 && vector.y > 0.0);
+    }
+
+    #[test]
+    fn modifiers_and_chords_gate_the_bindings() {
+        let mut input_service = InputService::new(ResourceContainer::new());
+        input_service.register_input("Save", "Ctrl+Keyboard/S");
+        input_service.register_input("Down", "Keyboard/S");
+        input_service.register_input("Super", "Keyboard/Q+Keyboard/E");
+
+        input_service.notify_pressed("Keyboard/S");
+        assert!(!input_service.is_pressed("Save"));
+        assert!(input_service.is_pressed("Down"));
+
+        input_service.notify_modifiers(Modifiers::CTRL);
+        assert!(input_service.is_pressed("Save"));
+        assert!(input_service.is_pressed_this_frame("Save"));
+
+        // The binding without modifier is shadowed by the more specific one
+        assert!(!input_service.is_pressed("Down"));
+        assert_eq!(input_service.get_axis("Down"), 0.0);
+        input_service.notify_modifiers(Modifiers::empty());
+        assert!(!input_service.is_pressed("Save"));
+        assert!(input_service.is_pressed("Down"));
+
+        input_service.notify_pressed("Keyboard/Q");
+        assert!(!input_service.is_pressed("Super"));
+        input_service.notify_pressed("Keyboard/E");
+        assert!(input_service.is_pressed("Super"));
+        input_service.notify_released("Keyboard/Q");
+        assert!(!input_service.is_pressed("Super"));
+    }
+
+    #[test]
+    fn hold_and_double_tap_wait_for_the_timing() {
+        let mut input_service = InputService::new(ResourceContainer::new());
+
+        let mut charge = InputBinding::parse("Keyboard/E").unwrap();
+        charge.trigger = InputTrigger::Hold(0.5);
+        input_service.register_binding("Charge", charge);
+
+        let mut dash = InputBinding::parse("Keyboard/Right").unwrap();
+        dash.trigger = InputTrigger::DoubleTap(0.3);
+        input_service.register_binding("Dash", dash);
+
+        input_service.notify_pressed("Keyboard/E");
+        input_service.update(0.3);
+        assert!(!input_service.is_pressed("Charge"));
+        input_service.update(0.3);
+        assert!(input_service.is_pressed("Charge"));
+        input_service.notify_released("Keyboard/E");
+        assert!(!input_service.is_pressed("Charge"));
+
+        // Two taps too far apart
+        input_service.notify_pressed("Keyboard/Right");
+        input_service.notify_released("Keyboard/Right");
+        input_service.update(0.5);
+        input_service.notify_pressed("Keyboard/Right");
+        assert!(!input_service.is_pressed("Dash"));
+        input_service.notify_released("Keyboard/Right");
+
+        input_service.update(0.1);
+        input_service.notify_pressed("Keyboard/Right");
+        assert!(input_service.is_pressed("Dash"));
+        input_service.notify_released("Keyboard/Right");
+        assert!(!input_service.is_pressed("Dash"));
+    }
+
+    type RecordedFrame = Box<dyn Fn(&mut InputService)>;
+
+    #[test]
+    fn replayed_events_give_the_same_inputs() {
+        let new_input_service = || {
+            let mut input_service = InputService::new(ResourceContainer::new());
+            input_service.register_input("Jump", "Keyboard/Up");
+            input_service.register_input("Save", "Ctrl+Keyboard/S");
+
+            let mut charge = InputBinding::parse("Keyboard/E").unwrap();
+            charge.trigger = InputTrigger::Hold(0.5);
+            input_service.register_binding("Charge", charge);
+
+            input_service
+        };
+
+        // The sources held before the recording starts are recorded too
+        let mut input_service = new_input_service();
+        input_service.notify_pressed("Keyboard/E");
+        input_service.set_recording(true);
+
+        let frames: Vec<RecordedFrame> = vec![
+            Box::new(|input_service| input_service.notify_modifiers(Modifiers::CTRL)),
+            Box::new(|input_service| input_service.notify_pressed("Keyboard/S")),
+            Box::new(|input_service| {
+                input_service.notify_released("Keyboard/S");
+                input_service.notify_pressed("Keyboard/Up");
+            }),
+            Box::new(|_| ()),
+        ];
+
+        let mut recorded_frames = Vec::new();
+        let mut recorded_states = Vec::new();
+        for frame in frames.iter() {
+            frame(&mut input_service);
+            input_service.update(0.2);
+
+            recorded_frames.push(input_service.take_recorded_events());
+            recorded_states.push(input_service.pressed_inputs.clone());
+            input_service.handle_frame_end();
+        }
+
+        let mut input_service = new_input_service();
+        input_service.set_replaying(true);
+
+        for (events, recorded_state) in recorded_frames.into_iter().zip(recorded_states) {
+            // The platform events are ignored during a replay
+            input_service.notify_pressed("Keyboard/Q");
+
+            events
+                .into_iter()
+                .for_each(|event| input_service.replay_event(event));
+            input_service.update(0.2);
+
+            assert_eq!(input_service.pressed_inputs, recorded_state);
+            input_service.handle_frame_end();
+        }
+
+        assert!(input_service.is_pressed("Charge"));
+        assert!(!input_service.is_source_pressed("Keyboard/Q"));
+    }
+
+    #[test]
+    fn input_binding_is_written_as_it_is_parsed() {
+        [
+            "Keyboard/S",
+            "Shift+Ctrl+Keyboard/S",
+            "Keyboard/Left:-1",
+            "Keyboard/Right:+x",
+            "Keyboard/Down:-y",
+            "Gamepad0/LeftBumper+Gamepad0/South:0.5y",
+        ]
+        .iter()
+        .for_each(|binding| {
+            assert_eq!(InputBinding::parse(binding).unwrap().to_string(), *binding)
+        });
+    }
+
+    #[test]
+    fn rebind_keeps_the_component_and_reports_the_conflicts() {
+        let mut input_service = InputService::new(ResourceContainer::new());
+        input_service.register_input("Move", "Keyboard/Left:-x");
+        input_service.register_input("Move", "Keyboard/Right:+x");
+        input_service.register_input("Action 1", "Keyboard/A");
+
+        let conflicts = input_service.rebind("Move", 0, "Keyboard/A").unwrap();
+        assert_eq!(conflicts, vec!["Action 1".to_string()]);
+        assert_eq!(
+            input_service.get_bindings("Move"),
+            vec!["Keyboard/A:-x".to_string(), "Keyboard/Right:+x".to_string()]
+        );
+
+        input_service.notify_pressed("Keyboard/A");
+        assert_eq!(input_service.get_vector2d("Move"), Vector2d::new(-1.0, 0.0));
+        assert!(input_service.rebind("Move", 3, "Keyboard/Q").is_err());
+
+        // The bindings registered later don't replace the user ones
+        input_service.register_input("Move", "Keyboard/D:+x");
+        assert_eq!(input_service.get_bindings("Move").len(), 2);
+
+        input_service.reset_bindings("Move");
+        assert_eq!(
+            input_service.get_bindings("Move"),
+            vec![
+                "Keyboard/Left:-x".to_string(),
+                "Keyboard/Right:+x".to_string(),
+                "Keyboard/D:+x".to_string()
+            ]
+        );
+        assert_eq!(input_service.get_vector2d("Move"), Vector2d::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn listen_next_input_captures_the_source_with_the_modifiers() {
+        let mut input_service = InputService::new(ResourceContainer::new());
+        input_service.register_input("Jump", "Keyboard/S");
+
+        input_service.listen_next_input();
+        input_service.notify_pressed("Keyboard/LControl");
+        input_service.notify_modifiers(Modifiers::CTRL);
+        assert!(input_service.is_listening());
+        assert_eq!(input_service.take_listened_input(), None);
+
+        // The captured source doesn't press the inputs
+        input_service.notify_pressed("Keyboard/S");
+        assert!(!input_service.is_listening());
+        assert!(!input_service.is_pressed("Jump"));
+        assert_eq!(
+            input_service.take_listened_input(),
+            Some("Ctrl+Keyboard/S".to_string())
+        );
+        assert_eq!(input_service.take_listened_input(), None);
+    }
+
+    #[test]
+    fn input_overrides_are_saved_and_merged_over_the_settings() {
+        let user_folder = std::env::temp_dir().join("fruity_input_overrides_test");
+        std::fs::remove_dir_all(&user_folder).ok();
+
+        let resource_container = ResourceContainer::new();
+        let virtual_file_system = VirtualFileSystem::new();
+        virtual_file_system.mount(
+            USER_SCHEME,
+            Box::new(DirectoryBackend::new(user_folder.to_str().unwrap())),
+        );
+        resource_container
+            .add::<VirtualFileSystem>("virtual_file_system", Box::new(virtual_file_system));
+
+        let new_input_service = || {
+            let mut input_service = InputService::new(resource_container.clone());
+            input_service.register_input("Jump", "Keyboard/Up");
+            input_service.register_input("Down", "Keyboard/Down");
+            input_service
+        };
+
+        let mut input_service = new_input_service();
+        input_service.rebind("Jump", 0, "Keyboard/Space").unwrap();
+        let mut charge = InputBinding::parse("Keyboard/E").unwrap();
+        charge.trigger = InputTrigger::Hold(0.5);
+        input_service.register_binding("Charge", charge);
+        input_service.rebind("Charge", 0, "Keyboard/R").unwrap();
+        input_service.save_input_overrides().unwrap();
+
+        let mut input_service = new_input_service();
+        input_service.load_input_overrides().unwrap();
+        assert_eq!(
+            input_service.get_bindings("Jump"),
+            vec!["Keyboard/Space".to_string()]
+        );
+        assert_eq!(
+            input_service.get_bindings("Down"),
+            vec!["Keyboard/Down".to_string()]
+        );
+        assert_eq!(
+            input_service.actions.get("Charge").unwrap()[0].trigger,
+            InputTrigger::Hold(0.5)
+        );
+
+        std::fs::remove_dir_all(&user_folder).ok();
+    }
+
+    #[test]
+    fn text_input_is_buffered_during_the_frame() {
+        let mut input_service = InputService::new(ResourceContainer::new());
+        input_service.register_input("Action 1", "Keyboard/E");
+        input_service.register_input("Action 1", "Mouse/Left");
+        input_service.register_input("Action 2", "Keyboard/Up");
+
+        // The text is ignored until the text input is enabled
+        input_service.notify_text("a");
+        assert_eq!(input_service.get_text_this_frame(), "");
+
+        // The held keyboard bindings are released when the text input starts
+        input_service.notify_pressed("Keyboard/Up");
+        assert!(input_service.is_pressed("Action 2"));
+
+        input_service.set_text_input_enabled(true);
+        assert!(!input_service.is_pressed("Action 2"));
+        assert_eq!(input_service.get_axis("Action 2"), 0.0);
+
+        input_service.notify_pressed("Keyboard/E");
+        input_service.notify_text("e");
+        input_service.notify_text("\u{8}");
+        input_service.notify_ime_composition("にほ");
+        assert_eq!(input_service.get_ime_composition(), "にほ");
+
+        input_service.notify_ime_composition("");
+        input_service.notify_text("日本");
+        assert_eq!(input_service.get_text_this_frame(), "e日本");
+        assert_eq!(input_service.get_ime_composition(), "");
+        assert!(input_service.is_source_pressed("Keyboard/E"));
+        assert!(!input_service.is_pressed("Action 1"));
+
+        // The other sources still trigger their bindings
+        input_service.notify_pressed("Mouse/Left");
+        assert!(input_service.is_pressed("Action 1"));
+        input_service.notify_released("Mouse/Left");
+
+        input_service.handle_frame_end();
+        assert_eq!(input_service.get_text_this_frame(), "");
+
+        input_service.notify_ime_composition("か");
+        input_service.set_text_input_enabled(false);
+        assert_eq!(input_service.get_ime_composition(), "");
+        assert!(input_service.is_pressed("Action 1"));
+        assert!(input_service.is_pressed("Action 2"));
+    }
+}
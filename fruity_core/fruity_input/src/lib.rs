@@ -1,9 +1,16 @@
 use crate::drag_service::DragService;
+use crate::input_record_service::InputRecordService;
 use crate::input_service::InputService;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::settings::Settings;
+use fruity_windows::frame_service::FrameService;
+use fruity_windows::window_service::WindowService;
 
 pub mod drag_service;
+pub mod gamepad;
+pub mod input_binding;
+pub mod input_record_service;
+pub mod input_recording;
 pub mod input_service;
 
 /// The module name
@@ -17,4 +24,48 @@ pub fn initialize(resource_container: ResourceContainer, settings: &Settings) {
 
     let drag_service = DragService::new(resource_container.clone());
     resource_container.add::<DragService>("drag_service", Box::new(drag_service));
+
+    let mut input_record_service = InputRecordService::new(resource_container.clone());
+    input_record_service.read_input_record_settings(settings);
+    resource_container
+        .add::<InputRecordService>("input_record_service", Box::new(input_record_service));
+
+    // The inputs are updated here for every platform, the platforms only notify the sources
+    let window_service = resource_container.require::<dyn WindowService>();
+    let window_service = window_service.read();
+
+    let resource_container_2 = resource_container.clone();
+    window_service.on_start_update().add_observer(move |_| {
+        puffin::profile_scope!("update_inputs");
+
+        let input_record_service = resource_container_2.require::<InputRecordService>();
+        let mut input_record_service = input_record_service.write();
+        input_record_service.begin_frame();
+
+        let frame_service = resource_container_2.require::<FrameService>();
+        let frame_service = frame_service.read();
+
+        let input_service = resource_container_2.require::<InputService>();
+        let mut input_service = input_service.write();
+        input_service.update(frame_service.get_unscaled_delta());
+    });
+
+    // The end of frame observer is added when the loop starts, after the observers of all the
+    // other modules, the editor for example reads the inputs of the frame while it's drawn
+    let resource_container_2 = resource_container.clone();
+    window_service.on_enter_loop().add_observer(move |_| {
+        let window_service = resource_container_2.require::<dyn WindowService>();
+        let window_service = window_service.read();
+
+        let resource_container_3 = resource_container_2.clone();
+        window_service.on_end_update().add_observer(move |_| {
+            let input_record_service = resource_container_3.require::<InputRecordService>();
+            let mut input_record_service = input_record_service.write();
+            input_record_service.end_frame();
+
+            let input_service = resource_container_3.require::<InputService>();
+            let mut input_service = input_service.write();
+            input_service.handle_frame_end();
+        });
+    });
 }
@@ -23,7 +23,7 @@ impl JavascriptService {
     pub fn new(resource_container: ResourceContainer) -> JavascriptService {
         let single_thread_wrapper =
             SingleThreadWrapper::<JsRuntime, Option<Serialized>>::start(move || {
-                let mut runtime = JsRuntime::new();
+                let mut runtime = JsRuntime::new(resource_container.get_file_system());
                 configure_constructors(&mut runtime, resource_container.clone());
                 configure_resource_container(&mut runtime, resource_container.clone());
 
@@ -13,6 +13,8 @@ use crate::serialize::serialize::serialize_v8;
 use crate::thread_scope_stack::clear_thread_scope_stack;
 use crate::thread_scope_stack::push_thread_scope_stack;
 use fruity_core::serialize::serialized::Serialized;
+use fruity_core::vfs::virtual_file_system::split_scheme;
+use fruity_core::vfs::virtual_file_system::VirtualFileSystem;
 use rusty_v8 as v8;
 use std::cell::RefCell;
 use std::path::Path;
@@ -37,7 +39,7 @@ impl Drop for JsRuntime {
 }
 
 impl JsRuntime {
-  pub fn new() -> JsRuntime {
+  pub fn new(file_system: VirtualFileSystem) -> JsRuntime {
     // Initialize V8
     let platform = v8::new_default_platform(0, false).make_shared();
     v8::V8::initialize_platform(platform);
@@ -56,6 +58,7 @@ impl JsRuntime {
     };
 
     isolate.set_slot(Rc::new(RefCell::new(ModuleMap::new())));
+    isolate.set_slot(file_system);
 
     // Create the runtime
     let mut runtime = JsRuntime {
@@ -220,6 +223,11 @@ impl JsRuntime {
     let module_map = isolate.get_slot::<Rc<RefCell<ModuleMap>>>().unwrap();
     module_map.clone()
   }
+
+  pub fn file_system(isolate: &v8::Isolate) -> VirtualFileSystem {
+    let file_system = isolate.get_slot::<VirtualFileSystem>().unwrap();
+    file_system.clone()
+  }
 }
 
 /// Called by V8 during `JsRuntime::instantiate_module`.
@@ -263,7 +271,7 @@ pub fn compile_module<'a>(
   }
 
   // Prepare sources
-  let source_str = match std::fs::read_to_string(filepath) {
+  let source_str = match JsRuntime::file_system(scope).read_to_string(filepath) {
     Ok(code) => Ok(code),
     Err(_) => Err(JsError::FileNotFound(filepath.to_string())),
   }?;
@@ -319,15 +327,11 @@ pub fn get_referrer_directory<'a>(
   let module_map = module_map_rc.borrow();
   let referrer_global = v8::Global::new(scope, referrer);
   let module_info = module_map.get(&referrer_global).unwrap();
-  let referrer_path = PathBuf::from(&module_info.filepath);
-  let referrer_directory = referrer_path
-    .parent()
-    .unwrap()
-    .to_str()
-    .unwrap()
-    .to_string();
-
-  referrer_directory
+  let (scheme, referrer_path) = split_scheme(&module_info.filepath);
+  let referrer_path = PathBuf::from(referrer_path);
+  let referrer_directory = referrer_path.parent().unwrap().to_str().unwrap();
+
+  with_scheme(scheme, referrer_directory)
 }
 
 pub fn get_specifier_filename<'a>(
@@ -338,14 +342,26 @@ pub fn get_specifier_filename<'a>(
   let specifier = specifier.to_rust_string_lossy(scope);
 
   if specifier.starts_with("./") || specifier.starts_with("../") {
+    // The scheme of a virtual file system path is kept apart, it's not a path component
+    let (scheme, base) = split_scheme(base);
     let filepath = Path::new(base)
       .join(specifier)
       .to_str()
       .unwrap()
       .to_string();
 
-    Ok(normalize_path(filepath).to_str().unwrap().to_string())
+    Ok(with_scheme(
+      scheme,
+      normalize_path(filepath).to_str().unwrap(),
+    ))
   } else {
     Err(JsError::ImportModuleWithoutPrefix(specifier.to_string()))
   }
 }
+
+fn with_scheme(scheme: Option<&str>, path: &str) -> String {
+  match scheme {
+    Some(scheme) => format!("{}://{}", scheme, path),
+    None => path.to_string(),
+  }
+}
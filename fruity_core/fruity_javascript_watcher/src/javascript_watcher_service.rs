@@ -6,6 +6,7 @@ use fruity_core::resource::resource::Resource;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::resource::resource_reference::ResourceReference;
 use fruity_core::utils::single_thread_wrapper::SingleThreadWrapper;
+use fruity_core::vfs::virtual_file_system::VirtualFileSystem;
 use fruity_ecs::system::system_service::SystemService;
 use fruity_javascript::javascript_service::JavascriptService;
 use fruity_windows::window_service::WindowService;
@@ -41,6 +42,7 @@ impl Debug for InnerJavascriptWatcherService {
 pub struct JavascriptWatcherService {
     javascript_service: ResourceReference<JavascriptService>,
     system_service: ResourceReference<SystemService>,
+    file_system: VirtualFileSystem,
     single_thread_wrappers: Vec<SingleThreadWrapper<InnerJavascriptWatcherService>>,
 }
 
@@ -48,6 +50,7 @@ impl JavascriptWatcherService {
     pub fn new(resource_container: ResourceContainer) -> Self {
         let javascript_service = resource_container.require::<JavascriptService>();
         let system_service = resource_container.require::<SystemService>();
+        let file_system = resource_container.get_file_system();
         let window_service = resource_container.require::<dyn WindowService>();
         let window_service = window_service.read();
 
@@ -62,17 +65,24 @@ impl JavascriptWatcherService {
         Self {
             javascript_service,
             system_service,
+            file_system,
             single_thread_wrappers: Vec::new(),
         }
     }
 
     pub fn watch_module(&mut self, path: &str, folder: &str) {
         let module_path = path.to_string();
-        let folder = folder.to_string();
 
         let javascript_service = self.javascript_service.read();
         javascript_service.run_module(&module_path);
 
+        // Only the folders stored on the disk can be watched, a packed folder never changes
+        let folder = if let Some(folder) = self.file_system.get_real_path(folder) {
+            folder
+        } else {
+            return;
+        };
+
         let single_thread_wrapper =
             SingleThreadWrapper::<InnerJavascriptWatcherService>::start(move || {
                 let (tx, watch_event_receiver) = channel();
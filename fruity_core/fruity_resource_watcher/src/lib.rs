@@ -0,0 +1,18 @@
+use crate::resource_watcher_service::ResourceWatcherService;
+use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::settings::Settings;
+
+pub mod resource_watcher_service;
+
+/// The module name
+pub static MODULE_NAME: &str = "fruity_resource_watcher";
+
+// #[no_mangle]
+pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
+    let resource_watcher_service = ResourceWatcherService::new(resource_container.clone());
+
+    resource_container.add::<ResourceWatcherService>(
+        "resource_watcher_service",
+        Box::new(resource_watcher_service),
+    );
+}
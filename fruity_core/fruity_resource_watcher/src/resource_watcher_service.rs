@@ -0,0 +1,169 @@
+use fruity_any::*;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodCaller;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::resource::resource::Resource;
+use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::utils::introspect::cast_introspect_mut;
+use fruity_core::utils::introspect::ArgumentCaster;
+use fruity_core::utils::single_thread_wrapper::SingleThreadWrapper;
+use fruity_windows::window_service::WindowService;
+use notify::op::*;
+use notify::raw_watcher;
+use notify::RawEvent;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::sync::mpsc::channel;
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+
+pub struct InnerResourceWatcherService {
+    watch_event_receiver: Receiver<RawEvent>,
+    _watcher: RecommendedWatcher,
+}
+
+impl Debug for InnerResourceWatcherService {
+    fn fmt(
+        &self,
+        _formatter: &mut std::fmt::Formatter<'_>,
+    ) -> std::result::Result<(), std::fmt::Error> {
+        Ok(())
+    }
+}
+
+/// Watch the resource folders and reload the resources when their files change
+/// The resources are swapped in place and their dependents are reloaded as well, see
+/// [’ResourceContainer::reload’]
+#[derive(Debug, FruityAny)]
+pub struct ResourceWatcherService {
+    resource_container: ResourceContainer,
+    single_thread_wrappers: Vec<SingleThreadWrapper<InnerResourceWatcherService, Vec<PathBuf>>>,
+}
+
+impl ResourceWatcherService {
+    pub fn new(resource_container: ResourceContainer) -> Self {
+        let window_service = resource_container.require::<dyn WindowService>();
+        let window_service = window_service.read();
+
+        // Subscribe to windows observer to proceed the hot reload before the system run
+        let resource_container_2 = resource_container.clone();
+        window_service.on_start_update().add_observer(move |_| {
+            let watcher_service = resource_container_2.require::<ResourceWatcherService>();
+            let watcher_service = watcher_service.read();
+
+            watcher_service.update_watch();
+        });
+
+        Self {
+            resource_container,
+            single_thread_wrappers: Vec::new(),
+        }
+    }
+
+    /// Watch a folder and its sub folders, the folders that are not stored on the disk, like the
+    /// packed ones, are ignored
+    ///
+    /// # Arguments
+    /// * `folder` - The folder path
+    ///
+    pub fn watch_folder(&mut self, folder: &str) {
+        let folder = if let Some(folder) = self
+            .resource_container
+            .get_file_system()
+            .get_real_path(folder)
+        {
+            folder
+        } else {
+            return;
+        };
+
+        let single_thread_wrapper =
+            SingleThreadWrapper::<InnerResourceWatcherService, Vec<PathBuf>>::start(move || {
+                let (tx, watch_event_receiver) = channel();
+
+                let mut watcher = raw_watcher(tx).unwrap();
+                if let Err(err) = watcher.watch(&folder, RecursiveMode::Recursive) {
+                    log::error!(
+                        "Failed to watch the folder {}: {}",
+                        folder.to_string_lossy(),
+                        err.to_string()
+                    );
+                }
+
+                InnerResourceWatcherService {
+                    watch_event_receiver,
+                    _watcher: watcher,
+                }
+            });
+
+        self.single_thread_wrappers.push(single_thread_wrapper);
+    }
+
+    pub fn update_watch(&self) {
+        puffin::profile_function!();
+
+        // Collect the changed files, a save generally raises many events for the same file
+        let mut changed_paths = self
+            .single_thread_wrappers
+            .iter()
+            .flat_map(|single_thread_wrapper| {
+                single_thread_wrapper.call(|inner| {
+                    inner
+                        .watch_event_receiver
+                        .try_iter()
+                        .filter_map(|event| match event {
+                            RawEvent {
+                                path: Some(path),
+                                op: Ok(op),
+                                cookie: _,
+                            } if op.intersects(CREATE | WRITE | CLOSE_WRITE | RENAME) => Some(path),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        changed_paths.sort();
+        changed_paths.dedup();
+
+        changed_paths.iter().for_each(|path| {
+            let reloaded = self.resource_container.reload_path(&path.to_string_lossy());
+
+            if !reloaded.is_empty() {
+                log::info!("Resources reloaded: {}", reloaded.join(", "));
+            }
+        });
+    }
+}
+
+impl IntrospectObject for ResourceWatcherService {
+    fn get_class_name(&self) -> String {
+        "ResourceWatcherService".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![MethodInfo {
+            name: "watch_folder".to_string(),
+            call: MethodCaller::Mut(Arc::new(|this, args| {
+                let this = cast_introspect_mut::<ResourceWatcherService>(this);
+
+                let mut caster = ArgumentCaster::new("watch_folder", args);
+                let arg1 = caster.cast_next::<String>()?;
+                this.watch_folder(&arg1);
+
+                Ok(None)
+            })),
+        }]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
+
+impl Resource for ResourceWatcherService {}
@@ -0,0 +1,58 @@
+/// A clipboard that stores text, the window services read and write it through this trait
+/// so a platform clipboard can be plugged in
+pub trait Clipboard: Send + Sync {
+    /// Get the text stored in the clipboard, None if the clipboard is empty or doesn't hold
+    /// any text
+    fn get_text(&self) -> Option<String>;
+
+    /// Replace the content of the clipboard
+    ///
+    /// # Arguments
+    /// * `text` - The text
+    ///
+    fn set_text(&mut self, text: &str);
+}
+
+/// A clipboard that only lives in memory, it's not shared with the other applications
+///
+/// It's used when the platform clipboard is not available and in the tests
+#[derive(Debug, Default, Clone)]
+pub struct MemoryClipboard {
+    text: Option<String>,
+}
+
+impl MemoryClipboard {
+    pub fn new() -> MemoryClipboard {
+        MemoryClipboard::default()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn get_text(&self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.text = Some(text.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_clipboard_keeps_the_last_text() {
+        let mut clipboard = MemoryClipboard::new();
+        assert_eq!(clipboard.get_text(), None);
+
+        clipboard.set_text("first");
+        clipboard.set_text("second");
+        assert_eq!(clipboard.get_text(), Some("second".to_string()));
+
+        // A copy of the clipboard doesn't share its text
+        let mut copy = clipboard.clone();
+        copy.set_text("third");
+        assert_eq!(clipboard.get_text(), Some("second".to_string()));
+    }
+}
@@ -1,5 +1,6 @@
 use fruity_any::*;
 use fruity_core::convert::FruityInto;
+use fruity_core::convert::FruityTryFrom;
 use fruity_core::introspect::FieldInfo;
 use fruity_core::introspect::IntrospectObject;
 use fruity_core::introspect::MethodCaller;
@@ -7,16 +8,32 @@ use fruity_core::introspect::MethodInfo;
 use fruity_core::introspect::SetterCaller;
 use fruity_core::resource::resource::Resource;
 use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::resource::resource_reference::ResourceReference;
 use fruity_core::serialize::serialized::Serialized;
+use fruity_core::settings::Settings;
+use fruity_core::utils::introspect::cast_introspect_mut;
 use fruity_core::utils::introspect::cast_introspect_ref;
+use fruity_core::utils::introspect::ArgumentCaster;
+use fruity_ecs::system::system_service::SystemService;
 use std::fmt::Debug;
 use std::sync::Arc;
 use std::time::Instant;
 
 #[derive(FruityAny)]
 pub struct FrameService {
+    system_service: ResourceReference<SystemService>,
     last_frame_instant: Instant,
     delta: f32,
+    unscaled_delta: f32,
+    time_scale: f32,
+    elapsed: f64,
+    frame_count: u64,
+    fixed_delta: f32,
+    max_fixed_steps: usize,
+    fixed_accumulator: f32,
+    fixed_step_count: usize,
+    interpolation_alpha: f32,
+    forced_delta: Option<f32>,
 }
 
 impl Debug for FrameService {
@@ -26,23 +43,161 @@ impl Debug for FrameService {
 }
 
 impl FrameService {
-    pub fn new(_resource_container: ResourceContainer) -> FrameService {
+    pub fn new(resource_container: ResourceContainer) -> FrameService {
         FrameService {
+            system_service: resource_container.require::<SystemService>(),
             delta: 0.0,
+            unscaled_delta: 0.0,
+            time_scale: 1.0,
+            elapsed: 0.0,
+            frame_count: 0,
             last_frame_instant: Instant::now(),
+            fixed_delta: 1.0 / 60.0,
+            max_fixed_steps: 5,
+            fixed_accumulator: 0.0,
+            fixed_step_count: 0,
+            interpolation_alpha: 0.0,
+            forced_delta: None,
         }
     }
 
+    /// Read the fixed timestep settings
+    ///
+    /// # Arguments
+    /// * `settings` - The application settings
+    ///
+    pub fn read_frame_settings(&mut self, settings: &Settings) {
+        let settings = settings.get_settings("frame");
+        let fixed_tick_rate = settings.get("fixed_tick_rate", 60.0);
+
+        if fixed_tick_rate > 0.0 {
+            self.fixed_delta = (1.0 / fixed_tick_rate) as f32;
+        }
+
+        self.max_fixed_steps = settings.get("max_fixed_steps", 5);
+    }
+
+    /// Get the duration of the current frame, scaled by the time scale
     pub fn get_delta(&self) -> f32 {
         self.delta
     }
 
+    /// Get the real duration of the current frame, not affected by the time scale
+    pub fn get_unscaled_delta(&self) -> f32 {
+        self.unscaled_delta
+    }
+
+    /// Get the time scale, 1 is the normal speed and 0 freezes the time
+    pub fn get_time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Set the time scale, 1 is the normal speed and 0 freezes the time
+    ///
+    /// # Arguments
+    /// * `time_scale` - The time scale, negative values are clamped to 0
+    ///
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = f32::max(time_scale, 0.0);
+    }
+
+    /// Get the scaled time elapsed while the systems are not paused, in seconds
+    pub fn get_elapsed(&self) -> f64 {
+        self.elapsed
+    }
+
+    /// Get the number of frames run while the systems are not paused
+    pub fn get_frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Get the duration of a fixed step, it's the delta to use in the fixed stages
+    pub fn get_fixed_delta(&self) -> f32 {
+        self.fixed_delta
+    }
+
+    /// Get the number of fixed steps run during the current frame
+    pub fn get_fixed_step_count(&self) -> usize {
+        self.fixed_step_count
+    }
+
+    /// Get the progression between the last fixed step and the next one, between 0 and 1
+    /// It's used to interpolate what is computed in the fixed stages
+    pub fn get_interpolation_alpha(&self) -> f32 {
+        self.interpolation_alpha
+    }
+
+    /// Get the delta that replaces the measured frame durations, if any
+    pub fn get_forced_delta(&self) -> Option<f32> {
+        self.forced_delta
+    }
+
+    /// Force the duration of the next frames instead of measuring them, it's used to replay
+    /// an input recording with the exact recorded deltas
+    ///
+    /// # Arguments
+    /// * `forced_delta` - The unscaled frame duration, None to measure the frames again
+    ///
+    pub fn set_forced_delta(&mut self, forced_delta: Option<f32>) {
+        self.forced_delta = forced_delta;
+    }
+
     pub fn begin_frame(&mut self) {
         let now = Instant::now();
         let delta = now.duration_since(self.last_frame_instant);
 
-        self.delta = delta.as_secs_f32();
         self.last_frame_instant = now;
+        self.update_time(self.forced_delta.unwrap_or(delta.as_secs_f32()));
+    }
+
+    pub fn begin_fixed_frame(&mut self, delta: f32) {
+        self.last_frame_instant = Instant::now();
+        self.update_time(self.forced_delta.unwrap_or(delta));
+    }
+
+    fn update_time(&mut self, unscaled_delta: f32) {
+        let system_service = self.system_service.read();
+        self.unscaled_delta = unscaled_delta;
+
+        // A stepped frame advances the paused systems by exactly one fixed step, the result of
+        // the step is fully shown until the next one
+        // While paused, the fixed steps and the interpolation are frozen
+        if system_service.is_step_requested() {
+            self.delta = self.fixed_delta;
+            self.fixed_step_count = 1;
+            self.fixed_accumulator = self.fixed_delta;
+            self.interpolation_alpha = 1.0;
+        } else if system_service.is_paused() {
+            self.delta = unscaled_delta * self.time_scale;
+            self.fixed_step_count = 0;
+        } else {
+            self.delta = unscaled_delta * self.time_scale;
+            self.update_fixed_steps();
+        }
+
+        if system_service.is_step_requested() || !system_service.is_paused() {
+            self.elapsed += self.delta as f64;
+            self.frame_count += 1;
+        }
+
+        system_service.set_fixed_step_count(self.fixed_step_count);
+    }
+
+    fn update_fixed_steps(&mut self) {
+        self.fixed_accumulator += self.delta;
+
+        // If the frame is too long, the late steps are dropped so the application
+        // don't spend even more time to catch up
+        let fixed_step_count = (self.fixed_accumulator / self.fixed_delta) as usize;
+        if fixed_step_count > self.max_fixed_steps {
+            self.fixed_step_count = self.max_fixed_steps;
+            self.fixed_accumulator %= self.fixed_delta;
+        } else {
+            self.fixed_step_count = fixed_step_count;
+            self.fixed_accumulator -= fixed_step_count as f32 * self.fixed_delta;
+        }
+
+        self.interpolation_alpha = self.fixed_accumulator / self.fixed_delta;
     }
 }
 
@@ -52,29 +207,285 @@ impl IntrospectObject for FrameService {
     }
 
     fn get_method_infos(&self) -> Vec<MethodInfo> {
-        vec![MethodInfo {
-            name: "get_delta".to_string(),
-            call: MethodCaller::Const(Arc::new(|this, _args| {
-                let this = cast_introspect_ref::<FrameService>(this);
-                let result = this.get_delta();
-                Ok(Some(Serialized::F32(result)))
-            })),
-        }]
+        vec![
+            MethodInfo {
+                name: "get_delta".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<FrameService>(this);
+                    let result = this.get_delta();
+                    Ok(Some(Serialized::F32(result)))
+                })),
+            },
+            MethodInfo {
+                name: "get_unscaled_delta".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<FrameService>(this);
+                    let result = this.get_unscaled_delta();
+                    Ok(Some(Serialized::F32(result)))
+                })),
+            },
+            MethodInfo {
+                name: "get_time_scale".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<FrameService>(this);
+                    let result = this.get_time_scale();
+                    Ok(Some(Serialized::F32(result)))
+                })),
+            },
+            MethodInfo {
+                name: "set_time_scale".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<FrameService>(this);
+
+                    let mut caster = ArgumentCaster::new("set_time_scale", args);
+                    let arg1 = caster.cast_next::<f32>()?;
+
+                    this.set_time_scale(arg1);
+
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "get_elapsed".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<FrameService>(this);
+                    let result = this.get_elapsed();
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "get_frame_count".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<FrameService>(this);
+                    let result = this.get_frame_count();
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "get_fixed_delta".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<FrameService>(this);
+                    let result = this.get_fixed_delta();
+                    Ok(Some(Serialized::F32(result)))
+                })),
+            },
+            MethodInfo {
+                name: "get_interpolation_alpha".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<FrameService>(this);
+                    let result = this.get_interpolation_alpha();
+                    Ok(Some(Serialized::F32(result)))
+                })),
+            },
+        ]
     }
 
     fn get_field_infos(&self) -> Vec<FieldInfo> {
-        vec![FieldInfo {
-            name: "delta".to_string(),
-            serializable: false,
-            getter: Arc::new(|this| {
-                this.downcast_ref::<FrameService>()
-                    .unwrap()
-                    .delta
-                    .fruity_into()
-            }),
-            setter: SetterCaller::None,
-        }]
+        vec![
+            FieldInfo {
+                name: "delta".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    this.downcast_ref::<FrameService>()
+                        .unwrap()
+                        .delta
+                        .fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+            FieldInfo {
+                name: "unscaled_delta".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    this.downcast_ref::<FrameService>()
+                        .unwrap()
+                        .unscaled_delta
+                        .fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+            FieldInfo {
+                name: "time_scale".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    this.downcast_ref::<FrameService>()
+                        .unwrap()
+                        .time_scale
+                        .fruity_into()
+                }),
+                setter: SetterCaller::Mut(Arc::new(|this, value| {
+                    let this = this.downcast_mut::<FrameService>().unwrap();
+
+                    match f32::fruity_try_from(value) {
+                        Ok(value) => this.set_time_scale(value),
+                        Err(_) => {
+                            log::error!("Expected a f32 for property time_scale");
+                        }
+                    }
+                })),
+            },
+            FieldInfo {
+                name: "elapsed".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    this.downcast_ref::<FrameService>()
+                        .unwrap()
+                        .elapsed
+                        .fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+            FieldInfo {
+                name: "frame_count".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    this.downcast_ref::<FrameService>()
+                        .unwrap()
+                        .frame_count
+                        .fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+            FieldInfo {
+                name: "fixed_delta".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    this.downcast_ref::<FrameService>()
+                        .unwrap()
+                        .fixed_delta
+                        .fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+            FieldInfo {
+                name: "interpolation_alpha".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    this.downcast_ref::<FrameService>()
+                        .unwrap()
+                        .interpolation_alpha
+                        .fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+        ]
     }
 }
 
 impl Resource for FrameService {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fruity_core::settings::read_settings;
+    use fruity_core::world::World;
+
+    fn frame_service(settings: &str) -> (World, FrameService) {
+        let world = World::new();
+        fruity_ecs::initialize(world.resource_container.clone());
+
+        // The systems are paused until the application starts them
+        let system_service = world.resource_container.require::<SystemService>();
+        system_service.read().set_paused(false);
+
+        let mut frame_service = FrameService::new(world.resource_container.clone());
+        frame_service.read_frame_settings(&read_settings(&mut settings.as_bytes()));
+
+        (world, frame_service)
+    }
+
+    fn run_frame(frame_service: &mut FrameService, delta: f32) {
+        frame_service.set_forced_delta(Some(delta));
+        frame_service.begin_frame();
+    }
+
+    #[test]
+    fn fixed_steps_are_accumulated_across_the_frames_test() {
+        let (world, mut frame_service) = frame_service("frame:\n  fixed_tick_rate: 4\n");
+        let system_service = world.resource_container.require::<SystemService>();
+        assert_eq!(frame_service.get_fixed_delta(), 0.25);
+
+        // 0.375s is one step of 0.25s and the half of the next one
+        run_frame(&mut frame_service, 0.375);
+        assert_eq!(frame_service.get_fixed_step_count(), 1);
+        assert_eq!(system_service.read().get_fixed_step_count(), 1);
+        assert_eq!(frame_service.get_interpolation_alpha(), 0.5);
+
+        // The remaining half is completed by the next frame
+        run_frame(&mut frame_service, 0.375);
+        assert_eq!(frame_service.get_fixed_step_count(), 2);
+        assert_eq!(system_service.read().get_fixed_step_count(), 2);
+        assert_eq!(frame_service.get_interpolation_alpha(), 0.0);
+
+        // A short frame don't run any step
+        run_frame(&mut frame_service, 0.0625);
+        assert_eq!(frame_service.get_fixed_step_count(), 0);
+        assert_eq!(frame_service.get_interpolation_alpha(), 0.25);
+    }
+
+    #[test]
+    fn late_fixed_steps_are_dropped_test() {
+        let (_world, mut frame_service) =
+            frame_service("frame:\n  fixed_tick_rate: 4\n  max_fixed_steps: 2\n");
+
+        // 2.125s would need 8 steps, only 2 are run and the late ones are dropped
+        run_frame(&mut frame_service, 2.125);
+        assert_eq!(frame_service.get_fixed_step_count(), 2);
+        assert_eq!(frame_service.get_interpolation_alpha(), 0.5);
+
+        run_frame(&mut frame_service, 0.125);
+        assert_eq!(frame_service.get_fixed_step_count(), 1);
+        assert_eq!(frame_service.get_interpolation_alpha(), 0.0);
+    }
+
+    #[test]
+    fn time_scale_slows_the_fixed_steps_test() {
+        let (_world, mut frame_service) = frame_service("frame:\n  fixed_tick_rate: 4\n");
+
+        frame_service.set_time_scale(0.5);
+        run_frame(&mut frame_service, 0.75);
+        assert_eq!(frame_service.get_delta(), 0.375);
+        assert_eq!(frame_service.get_unscaled_delta(), 0.75);
+        assert_eq!(frame_service.get_fixed_step_count(), 1);
+        assert_eq!(frame_service.get_interpolation_alpha(), 0.5);
+
+        // A frozen time don't move the interpolation
+        frame_service.set_time_scale(0.0);
+        run_frame(&mut frame_service, 0.75);
+        assert_eq!(frame_service.get_fixed_step_count(), 0);
+        assert_eq!(frame_service.get_interpolation_alpha(), 0.5);
+    }
+
+    #[test]
+    fn paused_frames_freeze_the_interpolation_test() {
+        let (world, mut frame_service) = frame_service("frame:\n  fixed_tick_rate: 4\n");
+        let system_service = world.resource_container.require::<SystemService>();
+
+        run_frame(&mut frame_service, 0.375);
+        assert_eq!(frame_service.get_interpolation_alpha(), 0.5);
+
+        // The paused frames don't run any step and don't move the interpolation
+        system_service.read().set_paused(true);
+        run_frame(&mut frame_service, 0.375);
+        assert_eq!(frame_service.get_fixed_step_count(), 0);
+        assert_eq!(system_service.read().get_fixed_step_count(), 0);
+        assert_eq!(frame_service.get_interpolation_alpha(), 0.5);
+
+        // A stepped frame runs a single step and shows its result
+        system_service.read().step_frame();
+        run_frame(&mut frame_service, 0.0625);
+        assert_eq!(frame_service.get_fixed_step_count(), 1);
+        assert_eq!(frame_service.get_interpolation_alpha(), 1.0);
+        system_service.read().run();
+
+        run_frame(&mut frame_service, 0.375);
+        assert_eq!(frame_service.get_fixed_step_count(), 0);
+        assert_eq!(frame_service.get_interpolation_alpha(), 1.0);
+
+        // The interpolation goes on from the stepped result
+        system_service.read().set_paused(false);
+        run_frame(&mut frame_service, 0.0625);
+        assert_eq!(frame_service.get_fixed_step_count(), 1);
+        assert_eq!(frame_service.get_interpolation_alpha(), 0.25);
+    }
+}
@@ -2,14 +2,16 @@ use crate::frame_service::FrameService;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::settings::Settings;
 
+pub mod clipboard;
 pub mod frame_service;
 pub mod window_service;
 
 /// The module name
 pub static MODULE_NAME: &str = "fruity_windows";
 
-pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
-    let frame_service = FrameService::new(resource_container.clone());
+pub fn initialize(resource_container: ResourceContainer, settings: &Settings) {
+    let mut frame_service = FrameService::new(resource_container.clone());
+    frame_service.read_frame_settings(settings);
 
     resource_container.add::<FrameService>("frame_service", Box::new(frame_service));
 }
@@ -7,8 +7,15 @@ pub trait WindowService: Resource {
     fn get_windows_size(&self) -> (u32, u32);
     fn get_scale_factor(&self) -> f64;
     fn get_cursor_position(&self) -> (u32, u32);
+    /// Override the stored cursor position, the os cursor is not moved
+    /// It's used to replay the cursor of an input recording
+    fn set_cursor_position(&mut self, position: (u32, u32));
     fn set_size(&self, width: u32, height: u32);
     fn set_title(&self, title: &str);
+    fn get_clipboard_text(&self) -> Option<String>;
+    fn set_clipboard_text(&mut self, text: &str);
+    /// Move the IME candidate window next to the text that is edited
+    fn set_ime_position(&self, position: (u32, u32));
     fn on_enter_loop(&self) -> &Signal<()>;
     fn on_start_update(&self) -> &Signal<()>;
     fn on_end_update(&self) -> &Signal<()>;
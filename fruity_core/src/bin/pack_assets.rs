@@ -0,0 +1,25 @@
+//! Pack the resources folder into an archive that is mounted on res:// when the game is
+//! distributed
+//!
+//! Usage: pack_assets [folder] [archive]
+
+use fruity_core::vfs::packer::pack_folder;
+use fruity_core::RESOURCES_ARCHIVE;
+use fruity_core::RESOURCES_FOLDER;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let folder = args.next().unwrap_or_else(|| RESOURCES_FOLDER.to_string());
+    let archive_path = args.next().unwrap_or_else(|| RESOURCES_ARCHIVE.to_string());
+
+    match pack_folder(&folder, &archive_path) {
+        Ok(packed) => {
+            packed.iter().for_each(|path| println!("{}", path));
+            println!("{} files packed into {}", packed.len(), archive_path);
+        }
+        Err(err) => {
+            eprintln!("Failed to pack {}: {}", folder, err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -3,6 +3,8 @@ use crate::resource::resource_container::ResourceContainer;
 use crate::resource::resource_reference::ResourceReadGuard;
 use crate::resource::resource_reference::ResourceReference;
 use crate::resource::resource_reference::ResourceWriteGuard;
+use std::collections::BTreeSet;
+use std::iter::FromIterator;
 
 /// A reference over a resource
 pub type Ref<T> = ResourceReference<T>;
@@ -13,10 +15,114 @@ pub type Const<T> = ResourceReadGuard<T>;
 /// A write guard over a resource
 pub type Mut<T> = ResourceWriteGuard<T>;
 
+/// The identifier reported for a conflict with an exclusive access
+pub const EXCLUSIVE_ACCESS_IDENTIFIER: &str = "undeclared datas";
+
+/// The datas that are accessed by an injected function, it's used to know which functions can be
+/// run in parallel
+///
+/// Resources are identified by their type name and components by their component name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InjectAccess {
+    /// Identifiers of the datas that are read
+    pub reads: BTreeSet<String>,
+
+    /// Identifiers of the datas that are written
+    pub writes: BTreeSet<String>,
+
+    /// If true, the accessed datas are unknown, the function can't be run in parallel with
+    /// any other one
+    pub exclusive: bool,
+}
+
+impl InjectAccess {
+    /// Returns an InjectAccess that reads a data
+    ///
+    /// # Arguments
+    /// * `identifier` - The data identifier
+    ///
+    pub fn read(identifier: &str) -> InjectAccess {
+        let mut access = InjectAccess::default();
+        access.reads.insert(identifier.to_string());
+        access
+    }
+
+    /// Returns an InjectAccess that writes a data
+    ///
+    /// # Arguments
+    /// * `identifier` - The data identifier
+    ///
+    pub fn write(identifier: &str) -> InjectAccess {
+        let mut access = InjectAccess::default();
+        access.writes.insert(identifier.to_string());
+        access
+    }
+
+    /// Returns an InjectAccess that can access any data
+    pub fn exclusive() -> InjectAccess {
+        InjectAccess {
+            exclusive: true,
+            ..InjectAccess::default()
+        }
+    }
+
+    /// Add the datas accessed by an other access
+    ///
+    /// # Arguments
+    /// * `other` - The other access
+    ///
+    pub fn merge(&mut self, other: &InjectAccess) {
+        self.reads.extend(other.reads.iter().cloned());
+        self.writes.extend(other.writes.iter().cloned());
+        self.exclusive |= other.exclusive;
+    }
+
+    /// Get the identifiers of the datas that prevent two accesses to be done in parallel, a data
+    /// can be read in parallel but not written while it's read or written by the other access
+    ///
+    /// # Arguments
+    /// * `other` - The other access
+    ///
+    pub fn get_conflicts(&self, other: &InjectAccess) -> Vec<String> {
+        if self.exclusive || other.exclusive {
+            return vec![EXCLUSIVE_ACCESS_IDENTIFIER.to_string()];
+        }
+
+        self.writes
+            .iter()
+            .filter(|identifier| {
+                other.reads.contains(*identifier) || other.writes.contains(*identifier)
+            })
+            .chain(
+                self.reads
+                    .iter()
+                    .filter(|identifier| other.writes.contains(*identifier)),
+            )
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+impl FromIterator<InjectAccess> for InjectAccess {
+    fn from_iter<I: IntoIterator<Item = InjectAccess>>(iter: I) -> Self {
+        let mut result = InjectAccess::default();
+        iter.into_iter().for_each(|access| result.merge(&access));
+        result
+    }
+}
+
 /// A trait for types that can be exposed from resource container
 pub trait Injectable: 'static {
     /// Get the object
     fn from_resource_container(resource_container: &ResourceContainer) -> Self;
+
+    /// Get the datas accessed by the object, an object that don't declare its accesses is
+    /// considered to access any data
+    fn get_access() -> InjectAccess {
+        InjectAccess::exclusive()
+    }
 }
 
 impl Injectable for ResourceContainer {
@@ -29,6 +135,11 @@ impl<T: Resource + ?Sized> Injectable for Ref<T> {
     fn from_resource_container(resource_container: &ResourceContainer) -> Self {
         resource_container.require::<T>()
     }
+
+    // The reference can be locked for writing at any time by the function
+    fn get_access() -> InjectAccess {
+        InjectAccess::write(std::any::type_name::<T>())
+    }
 }
 
 impl<T: Resource + ?Sized> Injectable for Const<T> {
@@ -36,6 +147,10 @@ impl<T: Resource + ?Sized> Injectable for Const<T> {
         let reference = Ref::<T>::from_resource_container(resource_container);
         reference.read()
     }
+
+    fn get_access() -> InjectAccess {
+        InjectAccess::read(std::any::type_name::<T>())
+    }
 }
 
 impl<T: Resource + ?Sized> Injectable for Mut<T> {
@@ -43,12 +158,22 @@ impl<T: Resource + ?Sized> Injectable for Mut<T> {
         let reference = Ref::<T>::from_resource_container(resource_container);
         reference.write()
     }
+
+    fn get_access() -> InjectAccess {
+        InjectAccess::write(std::any::type_name::<T>())
+    }
 }
 
 /// A trait that is implemented by functions that supports dependency injection
 pub trait Inject<R = ()> {
     /// Get a function that proceed the injection
     fn inject(self) -> Box<dyn Fn(ResourceContainer) -> R + Send + Sync>;
+
+    /// Get the datas accessed by the injected arguments, a function that don't declare its
+    /// accesses, like a script function, is considered to access any data
+    fn get_access(&self) -> InjectAccess {
+        InjectAccess::exclusive()
+    }
 }
 
 /// A shortcut for a boxed inject function
@@ -78,6 +203,10 @@ impl<T1, R> Inject1<T1, R> {
 }
 
 impl<T1: Injectable, R: 'static> Inject<R> for Inject1<T1, R> {
+    fn get_access(&self) -> InjectAccess {
+        vec![T1::get_access()].into_iter().collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) -> R + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(T1::from_resource_container(&resource_container))
@@ -96,6 +225,12 @@ impl<T1, T2, R> Inject2<T1, T2, R> {
 }
 
 impl<T1: Injectable, T2: Injectable, R: 'static> Inject<R> for Inject2<T1, T2, R> {
+    fn get_access(&self) -> InjectAccess {
+        vec![T1::get_access(), T2::get_access()]
+            .into_iter()
+            .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) -> R + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -117,6 +252,12 @@ impl<T1, T2, T3> Inject3<T1, T2, T3> {
 }
 
 impl<T1: Injectable, T2: Injectable, T3: Injectable> Inject for Inject3<T1, T2, T3> {
+    fn get_access(&self) -> InjectAccess {
+        vec![T1::get_access(), T2::get_access(), T3::get_access()]
+            .into_iter()
+            .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -141,6 +282,17 @@ impl<T1, T2, T3, T4> Inject4<T1, T2, T3, T4> {
 impl<T1: Injectable, T2: Injectable, T3: Injectable, T4: Injectable> Inject
     for Inject4<T1, T2, T3, T4>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -166,6 +318,18 @@ impl<T1, T2, T3, T4, T5> Inject5<T1, T2, T3, T4, T5> {
 impl<T1: Injectable, T2: Injectable, T3: Injectable, T4: Injectable, T5: Injectable> Inject
     for Inject5<T1, T2, T3, T4, T5>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -198,6 +362,19 @@ impl<
         T6: Injectable,
     > Inject for Inject6<T1, T2, T3, T4, T5, T6>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -234,6 +411,20 @@ impl<
         T7: Injectable,
     > Inject for Inject7<T1, T2, T3, T4, T5, T6, T7>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -272,6 +463,21 @@ impl<
         T8: Injectable,
     > Inject for Inject8<T1, T2, T3, T4, T5, T6, T7, T8>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -312,6 +518,22 @@ impl<
         T9: Injectable,
     > Inject for Inject9<T1, T2, T3, T4, T5, T6, T7, T8, T9>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -356,6 +578,23 @@ impl<
         T10: Injectable,
     > Inject for Inject10<T1, T2, T3, T4, T5, T6, T7, T8, T9, T10>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+            T10::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -404,6 +643,24 @@ impl<
         T11: Injectable,
     > Inject for Inject11<T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+            T10::get_access(),
+            T11::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -454,6 +711,25 @@ impl<
         T12: Injectable,
     > Inject for Inject12<T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+            T10::get_access(),
+            T11::get_access(),
+            T12::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -506,6 +782,26 @@ impl<
         T13: Injectable,
     > Inject for Inject13<T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+            T10::get_access(),
+            T11::get_access(),
+            T12::get_access(),
+            T13::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -563,6 +859,27 @@ impl<
         T14: Injectable,
     > Inject for Inject14<T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+            T10::get_access(),
+            T11::get_access(),
+            T12::get_access(),
+            T13::get_access(),
+            T14::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -622,6 +939,28 @@ impl<
         T15: Injectable,
     > Inject for Inject15<T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+            T10::get_access(),
+            T11::get_access(),
+            T12::get_access(),
+            T13::get_access(),
+            T14::get_access(),
+            T15::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -685,6 +1024,29 @@ impl<
         T16: Injectable,
     > Inject for Inject16<T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+            T10::get_access(),
+            T11::get_access(),
+            T12::get_access(),
+            T13::get_access(),
+            T14::get_access(),
+            T15::get_access(),
+            T16::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -753,6 +1115,30 @@ impl<
     > Inject
     for Inject17<T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+            T10::get_access(),
+            T11::get_access(),
+            T12::get_access(),
+            T13::get_access(),
+            T14::get_access(),
+            T15::get_access(),
+            T16::get_access(),
+            T17::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -823,6 +1209,31 @@ impl<
     > Inject
     for Inject18<T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18>
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+            T10::get_access(),
+            T11::get_access(),
+            T12::get_access(),
+            T13::get_access(),
+            T14::get_access(),
+            T15::get_access(),
+            T16::get_access(),
+            T17::get_access(),
+            T18::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -935,6 +1346,32 @@ impl<
         T19,
     >
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+            T10::get_access(),
+            T11::get_access(),
+            T12::get_access(),
+            T13::get_access(),
+            T14::get_access(),
+            T15::get_access(),
+            T16::get_access(),
+            T17::get_access(),
+            T18::get_access(),
+            T19::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
@@ -1112,6 +1549,33 @@ impl<
         T20,
     >
 {
+    fn get_access(&self) -> InjectAccess {
+        vec![
+            T1::get_access(),
+            T2::get_access(),
+            T3::get_access(),
+            T4::get_access(),
+            T5::get_access(),
+            T6::get_access(),
+            T7::get_access(),
+            T8::get_access(),
+            T9::get_access(),
+            T10::get_access(),
+            T11::get_access(),
+            T12::get_access(),
+            T13::get_access(),
+            T14::get_access(),
+            T15::get_access(),
+            T16::get_access(),
+            T17::get_access(),
+            T18::get_access(),
+            T19::get_access(),
+            T20::get_access(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     fn inject(self) -> Box<dyn Fn(ResourceContainer) + Send + Sync> {
         Box::new(move |resource_container| {
             (self.0)(
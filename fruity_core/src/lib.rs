@@ -11,8 +11,16 @@
 //! - Components are structure where the datas are stored
 
 use crate::object_factory_service::ObjectFactoryService;
+use crate::resource::load_diagnostic_service::LoadDiagnosticService;
 use crate::resource::resource_container::ResourceContainer;
 use crate::resource::resource_reference::AnyResourceReference;
+use crate::vfs::archive_backend::ArchiveBackend;
+use crate::vfs::directory_backend::DirectoryBackend;
+use crate::vfs::virtual_file_system::VirtualFileSystem;
+use crate::vfs::virtual_file_system::CACHE_SCHEME;
+use crate::vfs::virtual_file_system::RESOURCES_SCHEME;
+use crate::vfs::virtual_file_system::USER_SCHEME;
+use std::path::Path;
 
 pub use parking_lot::*;
 
@@ -52,6 +60,9 @@ pub mod utils;
 /// Provides a main object for the game engine
 pub mod world;
 
+/// A virtual file system, every file of the game should be accessed through it
+pub mod vfs;
+
 /// Provides a factory for the introspect object
 /// This will be used by the scripting language to expose object creation, especialy components
 pub mod object_factory_service;
@@ -59,6 +70,18 @@ pub mod object_factory_service;
 /// The module name
 pub static MODULE_NAME: &str = "fruity_core";
 
+/// The folder mounted on res://
+pub static RESOURCES_FOLDER: &str = "./assets";
+
+/// The archive mounted on res://, it's created by packing the resources folder for the distribution
+pub static RESOURCES_ARCHIVE: &str = "./assets.frpk";
+
+/// The folder mounted on user://
+pub static USER_FOLDER: &str = "./user";
+
+/// The folder mounted on cache://, where the imported artifacts of the resources are stored
+pub static IMPORT_CACHE_FOLDER: &str = "./.import";
+
 /// Initialize this extension
 pub fn initialize(resource_container: ResourceContainer) {
     //let module_manager = ModuleManager::new(resource_container.clone());
@@ -71,4 +94,31 @@ pub fn initialize(resource_container: ResourceContainer) {
     let mut object_factory_service = object_factory_service.write();
 
     object_factory_service.register::<AnyResourceReference>("ResourceReference");
+
+    let load_diagnostic_service = LoadDiagnosticService::new(resource_container.clone());
+    resource_container
+        .add::<LoadDiagnosticService>("load_diagnostic_service", Box::new(load_diagnostic_service));
+
+    // Mount the file systems, the resources folder overrides the packed resources if both exist
+    let virtual_file_system = VirtualFileSystem::new();
+    if Path::new(RESOURCES_ARCHIVE).exists() {
+        match ArchiveBackend::open(RESOURCES_ARCHIVE) {
+            Ok(archive_backend) => {
+                virtual_file_system.mount(RESOURCES_SCHEME, Box::new(archive_backend))
+            }
+            Err(err) => log::error!("{}", err.to_string()),
+        }
+    }
+    virtual_file_system.mount(
+        RESOURCES_SCHEME,
+        Box::new(DirectoryBackend::new(RESOURCES_FOLDER)),
+    );
+    virtual_file_system.mount(USER_SCHEME, Box::new(DirectoryBackend::new(USER_FOLDER)));
+    virtual_file_system.mount(
+        CACHE_SCHEME,
+        Box::new(DirectoryBackend::new(IMPORT_CACHE_FOLDER)),
+    );
+
+    resource_container
+        .add::<VirtualFileSystem>("virtual_file_system", Box::new(virtual_file_system));
 }
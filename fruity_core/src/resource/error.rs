@@ -3,6 +3,18 @@
 pub enum LoadResourceError {
     /// The resource type is not known, a resource loader should be added to handle this type of file
     ResourceTypeNotKnown(String),
+    /// The resource file cannot be read, the path and the reason are provided
+    FileNotReadable(String, String),
+    /// The resource content cannot be decoded, the identifier and the reason are provided
+    DecodeError(String, String),
+    /// The resource needs another resource that is not loaded, the identifier and the missing
+    /// dependency are provided
+    MissingDependency(String, String),
+    /// A field of the resource settings is missing or not valid, the field and the reason are
+    /// provided
+    InvalidSettingsField(String, String),
+    /// The resource has not been loaded from a file, so it cannot be reloaded
+    ResourceSourceNotKnown(String),
 }
 
 impl ToString for LoadResourceError {
@@ -14,6 +26,53 @@ impl ToString for LoadResourceError {
                     &name
                 )
             }
+            LoadResourceError::FileNotReadable(path, reason) => {
+                format!("Resource file \"{}\" cannot be read: {}", &path, &reason)
+            }
+            LoadResourceError::DecodeError(name, reason) => {
+                format!(
+                    "Resource named \"{}\" cannot be decoded: {}",
+                    &name, &reason
+                )
+            }
+            LoadResourceError::MissingDependency(name, dependency) => {
+                format!(
+                    "Resource named \"{}\" needs \"{}\" that is not loaded, it should be declared before",
+                    &name, &dependency
+                )
+            }
+            LoadResourceError::InvalidSettingsField(field, reason) => {
+                format!(
+                    "Resource settings field \"{}\" is not valid: {}",
+                    &field, &reason
+                )
+            }
+            LoadResourceError::ResourceSourceNotKnown(name) => {
+                format!(
+                    "Resource named \"{}\" has not been loaded from a file, it cannot be reloaded",
+                    &name
+                )
+            }
+        }
+    }
+}
+
+/// A problem met while loading an entry of the resources settings
+#[derive(Debug, Clone)]
+pub struct LoadResourceDiagnostic {
+    /// The index of the entry into the resources settings
+    pub index: usize,
+    /// The resource name, if the entry provides one
+    pub name: Option<String>,
+    /// The error
+    pub error: LoadResourceError,
+}
+
+impl ToString for LoadResourceDiagnostic {
+    fn to_string(&self) -> String {
+        match &self.name {
+            Some(name) => format!("Resource \"{}\": {}", name, self.error.to_string()),
+            None => format!("Resource #{}: {}", self.index, self.error.to_string()),
         }
     }
 }
@@ -23,6 +82,10 @@ impl ToString for LoadResourceError {
 pub enum RemoveResourceError {
     /// The resource not exists
     ResourceNotFound(String),
+    /// Other resources depend on the resource, they should be removed first
+    ResourceHasDependents(String, Vec<String>),
+    /// The resource is still referenced, the name and the reference count are provided
+    ResourceIsReferenced(String, usize),
 }
 
 impl ToString for RemoveResourceError {
@@ -31,6 +94,19 @@ impl ToString for RemoveResourceError {
             RemoveResourceError::ResourceNotFound(name) => {
                 format!("Resource named \"{}\" not exists", &name)
             }
+            RemoveResourceError::ResourceHasDependents(name, dependents) => {
+                format!(
+                    "Resource named \"{}\" is still used by {}",
+                    &name,
+                    dependents.join(", ")
+                )
+            }
+            RemoveResourceError::ResourceIsReferenced(name, reference_count) => {
+                format!(
+                    "Resource named \"{}\" is still referenced {} times",
+                    &name, reference_count
+                )
+            }
         }
     }
 }
@@ -0,0 +1,366 @@
+use crate::settings::build_settings_from_yaml;
+use crate::settings::Settings;
+use crate::vfs::virtual_file_system::VirtualFileSystem;
+use crate::vfs::virtual_file_system::CACHE_SCHEME;
+use std::io::Read;
+use std::io::Write;
+use yaml_rust::YamlLoader;
+
+/// The extension of the import settings files, the import settings of "res://character.png"
+/// are stored next to it into "res://character.png.import"
+pub const IMPORT_SETTINGS_EXTENSION: &str = "import";
+
+/// The bytes an artifact starts with
+pub const ARTIFACT_MAGIC: &[u8; 4] = b"FRIM";
+
+/// The version of the artifact header, the artifacts with another version are imported again
+pub const ARTIFACT_VERSION: u32 = 1;
+
+/// Get the path of the import settings file of an asset
+///
+/// # Arguments
+/// * `path` - The asset path
+///
+pub fn get_import_settings_path(path: &str) -> String {
+    format!("{}.{}", path, IMPORT_SETTINGS_EXTENSION)
+}
+
+/// Read the import settings that are stored next to an asset, returns None if the asset has
+/// no import settings file
+///
+/// # Arguments
+/// * `file_system` - The file system the asset is read from
+/// * `path` - The asset path
+///
+pub fn read_import_settings(file_system: &VirtualFileSystem, path: &str) -> Option<Settings> {
+    let import_settings_path = get_import_settings_path(path);
+    if !file_system.exists(&import_settings_path) {
+        return None;
+    }
+
+    let buffer = match file_system.read_to_string(&import_settings_path) {
+        Ok(buffer) => buffer,
+        Err(err) => {
+            log::error!("{}", err.to_string());
+            return None;
+        }
+    };
+
+    match YamlLoader::load_from_str(&buffer) {
+        Ok(docs) => docs.first().and_then(build_settings_from_yaml),
+        Err(err) => {
+            log::error!(
+                "Import settings \"{}\" cannot be read: {}",
+                &import_settings_path,
+                err.to_string()
+            );
+            None
+        }
+    }
+}
+
+/// Merge the import settings of an asset with the settings of a resource, the fields of the
+/// resource settings are kept when both declare them
+///
+/// # Arguments
+/// * `settings` - The resource settings
+/// * `import_settings` - The import settings
+///
+pub fn merge_import_settings(settings: Settings, import_settings: Settings) -> Settings {
+    match (settings, import_settings) {
+        (Settings::Object(fields), Settings::Object(mut import_fields)) => {
+            import_fields.extend(fields);
+            Settings::Object(import_fields)
+        }
+        (settings, _) => settings,
+    }
+}
+
+/// Hash an asset source with the settings it is imported with, the hash is stable between two
+/// launches so it can be used to know if an artifact is fresh
+///
+/// # Arguments
+/// * `source` - The asset content
+/// * `settings` - The settings the asset is imported with
+///
+pub fn hash_source(source: &[u8], settings: &Settings) -> u64 {
+    let mut hasher = SourceHasher::new();
+    hasher.write(source);
+    hasher.write_settings(settings);
+    hasher.finish()
+}
+
+/// A cache that stores the processed artifacts of the assets, like the decoded textures, so the
+/// loaders don't process the same source again at every launch
+///
+/// The artifacts are stored on the cache:// scheme, each one is keyed by the hash of its source,
+/// see [’hash_source’]
+#[derive(Debug, Clone)]
+pub struct ImportCache {
+    file_system: VirtualFileSystem,
+}
+
+impl ImportCache {
+    /// Returns an ImportCache
+    ///
+    /// # Arguments
+    /// * `file_system` - The file system where the cache:// scheme is mounted
+    ///
+    pub fn new(file_system: VirtualFileSystem) -> ImportCache {
+        ImportCache { file_system }
+    }
+
+    /// Get an artifact, returns None if the artifact is missing or if it has been imported from
+    /// another source
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    /// * `kind` - The kind of artifact, for example "texture"
+    /// * `source_hash` - The hash of the source, see [’hash_source’]
+    ///
+    pub fn get(&self, identifier: &str, kind: &str, source_hash: u64) -> Option<Vec<u8>> {
+        let mut reader = self
+            .file_system
+            .open(&Self::get_artifact_path(identifier, kind))
+            .ok()?;
+
+        let mut header = [0u8; 16];
+        reader.read_exact(&mut header).ok()?;
+        if &header[0..4] != ARTIFACT_MAGIC
+            || header[4..8] != ARTIFACT_VERSION.to_le_bytes()
+            || header[8..16] != source_hash.to_le_bytes()
+        {
+            return None;
+        }
+
+        let mut artifact = Vec::new();
+        reader.read_to_end(&mut artifact).ok()?;
+
+        Some(artifact)
+    }
+
+    /// Store an artifact, the previous artifact of the resource is replaced
+    ///
+    /// The artifact is written into a temporary file that is then renamed, so an interrupted
+    /// write never leaves an artifact that looks valid
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    /// * `kind` - The kind of artifact, for example "texture"
+    /// * `source_hash` - The hash of the source, see [’hash_source’]
+    /// * `artifact` - The artifact content
+    ///
+    pub fn store(
+        &self,
+        identifier: &str,
+        kind: &str,
+        source_hash: u64,
+        artifact: &[u8],
+    ) -> std::io::Result<()> {
+        let artifact_path = Self::get_artifact_path(identifier, kind);
+        let temporary_path = format!("{}.tmp", artifact_path);
+
+        {
+            let mut writer = self.file_system.create(&temporary_path)?;
+            writer.write_all(ARTIFACT_MAGIC)?;
+            writer.write_all(&ARTIFACT_VERSION.to_le_bytes())?;
+            writer.write_all(&source_hash.to_le_bytes())?;
+            writer.write_all(artifact)?;
+            writer.flush()?;
+        }
+
+        self.file_system.rename(&temporary_path, &artifact_path)
+    }
+
+    fn get_artifact_path(identifier: &str, kind: &str) -> String {
+        // The identifier is hashed as well, two identifiers can give the same file name
+        let file_name = identifier
+            .chars()
+            .map(|char| {
+                if char.is_ascii_alphanumeric() || char == '.' || char == '-' {
+                    char
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+
+        let mut hasher = SourceHasher::new();
+        hasher.write(identifier.as_bytes());
+
+        format!(
+            "{}://{}-{:016x}.{}",
+            CACHE_SCHEME,
+            file_name,
+            hasher.finish(),
+            kind
+        )
+    }
+}
+
+// A FNV-1a hasher, the std hashers are not guaranteed to give the same hash between two builds
+struct SourceHasher {
+    hash: u64,
+}
+
+impl SourceHasher {
+    fn new() -> SourceHasher {
+        SourceHasher {
+            hash: 0xcbf29ce484222325,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        bytes.iter().for_each(|byte| {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(0x100000001b3);
+        });
+    }
+
+    fn write_settings(&mut self, settings: &Settings) {
+        match settings {
+            Settings::I64(value) => {
+                self.write(&[0]);
+                self.write(&value.to_le_bytes());
+            }
+            Settings::F64(value) => {
+                self.write(&[1]);
+                self.write(&value.to_le_bytes());
+            }
+            Settings::Bool(value) => {
+                self.write(&[2, *value as u8]);
+            }
+            Settings::String(value) => {
+                self.write(&[3]);
+                self.write(&(value.len() as u64).to_le_bytes());
+                self.write(value.as_bytes());
+            }
+            Settings::Array(values) => {
+                self.write(&[4]);
+                self.write(&(values.len() as u64).to_le_bytes());
+                values.iter().for_each(|value| self.write_settings(value));
+            }
+            Settings::Object(fields) => {
+                // The fields are sorted, the order of an hashmap changes between two launches
+                let mut keys = fields.keys().collect::<Vec<_>>();
+                keys.sort();
+
+                self.write(&[5]);
+                self.write(&(keys.len() as u64).to_le_bytes());
+                keys.into_iter().for_each(|key| {
+                    self.write(&(key.len() as u64).to_le_bytes());
+                    self.write(key.as_bytes());
+                    self.write_settings(&fields[key]);
+                });
+            }
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vfs::directory_backend::DirectoryBackend;
+    use std::collections::HashMap;
+
+    fn build_settings(fields: Vec<(&str, Settings)>) -> Settings {
+        Settings::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<HashMap<_, _>>(),
+        )
+    }
+
+    #[test]
+    fn resource_settings_override_import_settings() {
+        let settings = build_settings(vec![
+            ("name", Settings::String("character.png".to_string())),
+            ("srgb", Settings::Bool(false)),
+        ]);
+        let import_settings = build_settings(vec![
+            ("srgb", Settings::Bool(true)),
+            ("wrap_mode", Settings::String("repeat".to_string())),
+        ]);
+
+        let settings = merge_import_settings(settings, import_settings);
+        assert!(!settings.get::<bool>("srgb", true));
+        assert_eq!(
+            settings.get::<String>("wrap_mode", String::default()),
+            "repeat"
+        );
+        assert_eq!(
+            settings.get::<String>("name", String::default()),
+            "character.png"
+        );
+    }
+
+    #[test]
+    fn hash_source_depends_on_the_source_and_the_settings() {
+        let settings = build_settings(vec![
+            ("srgb", Settings::Bool(true)),
+            ("wrap_mode", Settings::String("repeat".to_string())),
+        ]);
+
+        assert_eq!(
+            hash_source(b"png", &settings),
+            hash_source(b"png", &settings)
+        );
+        assert_ne!(
+            hash_source(b"png", &settings),
+            hash_source(b"jpg", &settings)
+        );
+        assert_ne!(
+            hash_source(b"png", &settings),
+            hash_source(b"png", &Settings::new())
+        );
+    }
+
+    #[test]
+    fn import_cache_returns_only_fresh_artifacts() {
+        let cache_folder = std::env::temp_dir().join("fruity_core_import_cache_test");
+        std::fs::remove_dir_all(&cache_folder).ok();
+
+        let file_system = VirtualFileSystem::new();
+        file_system.mount(
+            CACHE_SCHEME,
+            Box::new(DirectoryBackend::new(cache_folder.to_str().unwrap())),
+        );
+        let import_cache = ImportCache::new(file_system);
+
+        assert!(import_cache
+            .get("./assets/ball.png", "texture", 1)
+            .is_none());
+
+        import_cache
+            .store("./assets/ball.png", "texture", 1, &[1, 2, 3])
+            .unwrap();
+        assert_eq!(
+            import_cache.get("./assets/ball.png", "texture", 1),
+            Some(vec![1, 2, 3])
+        );
+        assert!(import_cache
+            .get("./assets/ball.png", "texture", 2)
+            .is_none());
+        assert!(import_cache.get("./assets/ball.png", "shader", 1).is_none());
+        assert!(import_cache
+            .get("./assets/ball_png", "texture", 1)
+            .is_none());
+
+        // Only the renamed artifacts are left into the cache
+        import_cache
+            .store("./assets/ball.png", "texture", 2, &[4, 5])
+            .unwrap();
+        assert_eq!(
+            import_cache.get("./assets/ball.png", "texture", 2),
+            Some(vec![4, 5])
+        );
+        assert_eq!(std::fs::read_dir(&cache_folder).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(&cache_folder).ok();
+    }
+}
@@ -0,0 +1,93 @@
+use crate::convert::FruityInto;
+use crate::introspect::FieldInfo;
+use crate::introspect::IntrospectObject;
+use crate::introspect::MethodCaller;
+use crate::introspect::MethodInfo;
+use crate::resource::error::LoadResourceDiagnostic;
+use crate::resource::resource::Resource;
+use crate::resource::resource_container::ResourceContainer;
+use crate::utils::introspect::cast_introspect_mut;
+use crate::utils::introspect::cast_introspect_ref;
+use fruity_any::*;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Keeps the problems met while loading the resources, so the editor can show them
+#[derive(FruityAny)]
+pub struct LoadDiagnosticService {
+    diagnostics: Vec<LoadResourceDiagnostic>,
+}
+
+impl Debug for LoadDiagnosticService {
+    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        Ok(())
+    }
+}
+
+impl LoadDiagnosticService {
+    /// Returns a LoadDiagnosticService without any diagnostic
+    pub fn new(_resource_container: ResourceContainer) -> LoadDiagnosticService {
+        LoadDiagnosticService {
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Keep diagnostics, they are generaly returned by
+    /// [’ResourceContainer::load_resources_settings’]
+    ///
+    /// # Arguments
+    /// * `diagnostics` - The diagnostics
+    ///
+    pub fn add_diagnostics(&mut self, mut diagnostics: Vec<LoadResourceDiagnostic>) {
+        self.diagnostics.append(&mut diagnostics);
+    }
+
+    /// Get all the kept diagnostics, in the order they have been added
+    pub fn get_diagnostics(&self) -> &[LoadResourceDiagnostic] {
+        &self.diagnostics
+    }
+
+    /// Forget all the kept diagnostics
+    pub fn clear(&mut self) {
+        self.diagnostics.clear();
+    }
+}
+
+impl IntrospectObject for LoadDiagnosticService {
+    fn get_class_name(&self) -> String {
+        "LoadDiagnosticService".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![
+            MethodInfo {
+                name: "get_diagnostics".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<LoadDiagnosticService>(this);
+                    let result = this
+                        .get_diagnostics()
+                        .iter()
+                        .map(|diagnostic| diagnostic.to_string())
+                        .collect::<Vec<_>>();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "clear".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, _args| {
+                    let this = cast_introspect_mut::<LoadDiagnosticService>(this);
+                    this.clear();
+
+                    Ok(None)
+                })),
+            },
+        ]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
+
+impl Resource for LoadDiagnosticService {}
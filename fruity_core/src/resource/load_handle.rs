@@ -0,0 +1,371 @@
+use crate::convert::FruityInto;
+use crate::introspect::FieldInfo;
+use crate::introspect::IntrospectObject;
+use crate::introspect::MethodCaller;
+use crate::introspect::MethodInfo;
+use crate::introspect::SetterCaller;
+use crate::resource::resource::Resource;
+use crate::resource::resource_container::ResourceContainer;
+use crate::resource::resource_reference::ResourceReference;
+use crate::serialize::serialized::SerializableObject;
+use crate::serialize::serialized::Serialized;
+use crate::signal::Signal;
+use crate::utils::introspect::cast_introspect_ref;
+use crate::utils::introspect::ArgumentCaster;
+use crate::Mutex;
+use fruity_any::*;
+use parking_lot::Condvar;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// The state of a resource loading
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadState {
+    /// The resource is not loaded yet
+    Pending,
+    /// The resource is loaded and stored into the resource container
+    Loaded,
+    /// The resource loading failed, the reason is provided
+    Failed(String),
+}
+
+impl FruityInto<Serialized> for LoadState {
+    fn fruity_into(self) -> Serialized {
+        match self {
+            LoadState::Pending => Serialized::String("pending".to_string()),
+            LoadState::Loaded => Serialized::String("loaded".to_string()),
+            LoadState::Failed(_) => Serialized::String("failed".to_string()),
+        }
+    }
+}
+
+// A change of the loading that is waiting to be notified on the main thread
+enum LoadEvent {
+    ResourceLoaded(String),
+    Done,
+}
+
+struct InnerLoadHandle {
+    states: Mutex<Vec<(String, LoadState)>>,
+    events: Mutex<Vec<LoadEvent>>,
+    done: Condvar,
+}
+
+/// A handle over resources that are loaded in background, returned by
+/// [’ResourceContainer::load_resources_settings_async’]
+///
+/// It can be used to show a loading screen or to get the resources once they are ready
+///
+/// The resources are loaded by worker threads but the signals are notified on the main thread,
+/// by [’ResourceContainer::notify_load_handles’] that is called at the beginning of every frame
+#[derive(Clone, FruityAny)]
+pub struct LoadHandle {
+    inner: Arc<InnerLoadHandle>,
+    resource_container: ResourceContainer,
+
+    /// Signal notified when a resource is loaded, with the resource identifier
+    pub on_resource_loaded: Signal<String>,
+
+    /// Signal notified when all the resources are loaded or failed
+    pub on_done: Signal<()>,
+}
+
+impl LoadHandle {
+    /// Returns a LoadHandle where all the resources are pending
+    ///
+    /// # Arguments
+    /// * `resource_container` - The resource container that stores the loaded resources
+    /// * `identifiers` - The identifiers of the loaded resources
+    ///
+    pub fn new(resource_container: ResourceContainer, identifiers: Vec<String>) -> LoadHandle {
+        // A handle without any resource is done from the start
+        let events = if identifiers.is_empty() {
+            vec![LoadEvent::Done]
+        } else {
+            Vec::new()
+        };
+
+        LoadHandle {
+            inner: Arc::new(InnerLoadHandle {
+                states: Mutex::new(
+                    identifiers
+                        .into_iter()
+                        .map(|identifier| (identifier, LoadState::Pending))
+                        .collect::<Vec<_>>(),
+                ),
+                events: Mutex::new(events),
+                done: Condvar::new(),
+            }),
+            resource_container,
+            on_resource_loaded: Signal::new(),
+            on_done: Signal::new(),
+        }
+    }
+
+    /// Get the state of all the resources together
+    /// The state is pending until every resource is loaded or failed, failed if a resource failed
+    pub fn get_state(&self) -> LoadState {
+        let states = self.inner.states.lock();
+
+        if states
+            .iter()
+            .any(|(_, state)| matches!(state, LoadState::Pending))
+        {
+            return LoadState::Pending;
+        }
+
+        let errors = states
+            .iter()
+            .filter_map(|(identifier, state)| match state {
+                LoadState::Failed(err) => Some(format!("{}: {}", identifier, err)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            LoadState::Loaded
+        } else {
+            LoadState::Failed(errors.join("\n"))
+        }
+    }
+
+    /// Get the state of a single resource
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    ///
+    pub fn get_resource_state(&self, identifier: &str) -> Option<LoadState> {
+        let states = self.inner.states.lock();
+        states
+            .iter()
+            .find(|(state_identifier, _)| state_identifier == identifier)
+            .map(|(_, state)| state.clone())
+    }
+
+    /// Get the ratio of the resources that are loaded or failed, between 0 and 1
+    pub fn get_progress(&self) -> f32 {
+        let states = self.inner.states.lock();
+
+        if states.is_empty() {
+            return 1.0;
+        }
+
+        let finished = states
+            .iter()
+            .filter(|(_, state)| !matches!(state, LoadState::Pending))
+            .count();
+
+        finished as f32 / states.len() as f32
+    }
+
+    /// Are all the resources loaded or failed
+    pub fn is_done(&self) -> bool {
+        self.get_state() != LoadState::Pending
+    }
+
+    /// Block the current thread until all the resources are loaded or failed
+    pub fn wait(&self) {
+        let mut states = self.inner.states.lock();
+        while states
+            .iter()
+            .any(|(_, state)| matches!(state, LoadState::Pending))
+        {
+            self.inner.done.wait(&mut states);
+        }
+    }
+
+    /// Get a loaded resource, returns None while the resource is pending or if it failed
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    ///
+    /// # Generic Arguments
+    /// * `T` - The resource type
+    ///
+    pub fn get<T: Resource + ?Sized>(&self, identifier: &str) -> Option<ResourceReference<T>> {
+        match self.get_resource_state(identifier) {
+            Some(LoadState::Loaded) => self.resource_container.get::<T>(identifier),
+            _ => None,
+        }
+    }
+
+    /// Call a function with a resource once it is loaded, the function is called right now if the
+    /// resource is already loaded and is never called if the loading fails
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    /// * `callback` - The function called with the loaded resource
+    ///
+    /// # Generic Arguments
+    /// * `T` - The resource type
+    ///
+    pub fn get_when_loaded<T: Resource + ?Sized>(
+        &self,
+        identifier: &str,
+        callback: impl Fn(ResourceReference<T>) + Send + Sync + 'static,
+    ) {
+        // The events are only notified on the main thread, so a resource that is loaded by now
+        // but not notified yet is not missed
+        if let Some(resource) = self.get::<T>(identifier) {
+            callback(resource);
+            return;
+        }
+
+        let identifier = identifier.to_string();
+        let resource_container = self.resource_container.clone();
+        self.on_resource_loaded
+            .add_self_dispose_observer(move |loaded_identifier, handler| {
+                if loaded_identifier == &identifier {
+                    if let Some(resource) = resource_container.get::<T>(&identifier) {
+                        callback(resource);
+                    }
+
+                    handler.dispose_by_ref();
+                }
+            });
+    }
+
+    /// Notify the signals for the changes of the loading since the last call, it's intended to be
+    /// called on the main thread by [’ResourceContainer::notify_load_handles’]
+    /// Returns true once [’LoadHandle::on_done’] has been notified
+    pub(crate) fn notify_events(&self) -> bool {
+        let events = std::mem::take(&mut *self.inner.events.lock());
+
+        events
+            .into_iter()
+            .fold(false, |is_done, event| match event {
+                LoadEvent::ResourceLoaded(identifier) => {
+                    self.on_resource_loaded.notify(identifier);
+                    is_done
+                }
+                LoadEvent::Done => {
+                    self.on_done.notify(());
+                    true
+                }
+            })
+    }
+
+    /// Set the state of a resource, it's intended to be called by the loading worker
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    /// * `state` - The new state
+    ///
+    pub(crate) fn set_resource_state(&self, identifier: &str, state: LoadState) {
+        let is_loaded = state == LoadState::Loaded;
+
+        let is_done = {
+            let mut states = self.inner.states.lock();
+            states
+                .iter_mut()
+                .filter(|(state_identifier, _)| state_identifier == identifier)
+                .for_each(|(_, resource_state)| *resource_state = state.clone());
+
+            !states
+                .iter()
+                .any(|(_, state)| matches!(state, LoadState::Pending))
+        };
+
+        // The signals are notified later on the main thread
+        let mut events = self.inner.events.lock();
+        if is_loaded {
+            events.push(LoadEvent::ResourceLoaded(identifier.to_string()));
+        }
+
+        if is_done {
+            events.push(LoadEvent::Done);
+            self.inner.done.notify_all();
+        }
+    }
+}
+
+impl Debug for LoadHandle {
+    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        Ok(())
+    }
+}
+
+impl IntrospectObject for LoadHandle {
+    fn get_class_name(&self) -> String {
+        "LoadHandle".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![
+            MethodInfo {
+                name: "get_state".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<LoadHandle>(this);
+                    let result = this.get_state();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "get_resource_state".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<LoadHandle>(this);
+
+                    let mut caster = ArgumentCaster::new("get_resource_state", args);
+                    let arg1 = caster.cast_next::<String>()?;
+                    let result = this.get_resource_state(&arg1);
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "get_progress".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<LoadHandle>(this);
+                    let result = this.get_progress();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "is_done".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<LoadHandle>(this);
+                    let result = this.is_done();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+        ]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![
+            FieldInfo {
+                name: "on_resource_loaded".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    let this = this.downcast_ref::<LoadHandle>().unwrap();
+                    this.on_resource_loaded.clone().fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+            FieldInfo {
+                name: "on_done".to_string(),
+                serializable: false,
+                getter: Arc::new(|this| {
+                    let this = this.downcast_ref::<LoadHandle>().unwrap();
+                    this.on_done.clone().fruity_into()
+                }),
+                setter: SetterCaller::None,
+            },
+        ]
+    }
+}
+
+impl SerializableObject for LoadHandle {
+    fn duplicate(&self) -> Box<dyn SerializableObject> {
+        Box::new(self.clone())
+    }
+}
+
+impl FruityInto<Serialized> for LoadHandle {
+    fn fruity_into(self) -> Serialized {
+        Serialized::NativeObject(Box::new(self))
+    }
+}
@@ -10,5 +10,14 @@ pub mod resource_reference;
 /// The resource manager
 pub mod resource_container;
 
+/// A handle to follow the resources loaded in background
+pub mod load_handle;
+
+/// A service that keeps the problems met while loading the resources
+pub mod load_diagnostic_service;
+
+/// The import settings of the assets and the cache of their processed artifacts
+pub mod import;
+
 /// A wrapper for resource that come from scripting languages as serialized
 pub mod serialized_resource;
@@ -1,9 +1,16 @@
+use crate::convert::FruityInto;
 use crate::introspect::FieldInfo;
 use crate::introspect::IntrospectObject;
 use crate::introspect::MethodCaller;
 use crate::introspect::MethodInfo;
+use crate::resource::error::LoadResourceDiagnostic;
 use crate::resource::error::LoadResourceError;
 use crate::resource::error::RemoveResourceError;
+use crate::resource::import::merge_import_settings;
+use crate::resource::import::read_import_settings;
+use crate::resource::import::IMPORT_SETTINGS_EXTENSION;
+use crate::resource::load_handle::LoadHandle;
+use crate::resource::load_handle::LoadState;
 use crate::resource::resource::Resource;
 use crate::resource::resource_reference::AnyResourceReference;
 use crate::resource::resource_reference::ResourceReference;
@@ -13,18 +20,22 @@ use crate::serialize::serialized::Serialized;
 use crate::settings::Settings;
 use crate::utils::introspect::cast_introspect_ref;
 use crate::utils::introspect::ArgumentCaster;
+use crate::vfs::virtual_file_system::VirtualFileSystem;
 use crate::RwLock;
 use fruity_any::*;
+use rayon::prelude::*;
 use std::any::TypeId;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
-use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// A a function that is used to load a resource
-pub type ResourceLoader = fn(&str, &mut dyn Read, Settings, ResourceContainer);
+pub type ResourceLoader =
+    fn(&str, &mut dyn Read, Settings, ResourceContainer) -> Result<(), LoadResourceError>;
 
 /// The resource manager
 #[derive(FruityAny, Clone)]
@@ -45,6 +56,61 @@ pub(crate) struct InnerResourceContainer {
     resources: HashMap<String, Arc<dyn Resource>>,
     identifier_by_type: HashMap<TypeId, String>,
     resource_loaders: HashMap<String, ResourceLoader>,
+    dependencies: HashMap<String, Vec<String>>,
+    loaded_resources: HashSet<String>,
+    sources: HashMap<String, ResourceSource>,
+    load_handles: Vec<LoadHandle>,
+}
+
+/// An entry of the resource settings
+struct ResourceEntry {
+    name: String,
+    path: String,
+    resource_type: String,
+    fields: HashMap<String, Settings>,
+}
+
+/// The file a resource has been loaded from, used to reload it
+#[derive(Clone)]
+struct ResourceSource {
+    path: String,
+    canonical_path: PathBuf,
+    resource_type: String,
+    settings: Settings,
+}
+
+impl InnerResourceContainer {
+    fn get_reference_count(&self, identifier: &str) -> usize {
+        self.resources
+            .get(identifier)
+            .map(|resource| Arc::strong_count(resource) - 1)
+            .unwrap_or(0)
+    }
+
+    fn get_dependents(&self, identifier: &str) -> Vec<String> {
+        let mut dependents = self
+            .dependencies
+            .iter()
+            .filter(|(_, dependencies)| {
+                dependencies
+                    .iter()
+                    .any(|dependency| dependency == identifier)
+            })
+            .map(|(dependent, _)| dependent.clone())
+            .collect::<Vec<_>>();
+
+        dependents.sort();
+        dependents
+    }
+
+    fn remove_entry(&mut self, identifier: &str) {
+        self.resources.remove(identifier);
+        self.dependencies.remove(identifier);
+        self.loaded_resources.remove(identifier);
+        self.sources.remove(identifier);
+        self.identifier_by_type
+            .retain(|_, type_identifier| type_identifier != identifier);
+    }
 }
 
 impl ResourceContainer {
@@ -55,6 +121,10 @@ impl ResourceContainer {
                 resources: HashMap::new(),
                 identifier_by_type: HashMap::new(),
                 resource_loaders: HashMap::new(),
+                dependencies: HashMap::new(),
+                loaded_resources: HashSet::new(),
+                sources: HashMap::new(),
+                load_handles: Vec::new(),
             })),
         }
     }
@@ -142,12 +212,31 @@ impl ResourceContainer {
     }
 
     /// Add a resource into the collection
+    /// If a resource with the same identifier and type already exists, its content is swapped in
+    /// place, so the existing [’ResourceReference’] see the new content
     ///
     /// # Arguments
     /// * `identifier` - The resource identifier
     /// * `resource` - The resource object
     ///
     pub fn add<T: Resource + ?Sized>(&self, identifier: &str, resource: Box<T>) {
+        let existing = {
+            let inner = self.inner.read();
+            inner
+                .resources
+                .get(identifier)
+                .cloned()
+                .and_then(|existing| existing.as_any_arc().downcast::<RwLock<Box<T>>>().ok())
+        };
+
+        // The container lock is released before locking the resource, a system can hold the
+        // resource while it accesses the container
+        if let Some(existing) = existing {
+            let mut existing = existing.write();
+            *existing = resource;
+            return;
+        }
+
         let mut inner = self.inner.write();
 
         let shared = Arc::new(RwLock::new(resource));
@@ -160,6 +249,8 @@ impl ResourceContainer {
     }
 
     /// Remove a resource of the collection
+    /// The removal is refused if other resources depend on it, see [’ResourceContainer::remove_cascade’],
+    /// or if it is still referenced, see [’ResourceContainer::get_reference_count’]
     ///
     /// # Arguments
     /// * `identifier` - The resource identifier
@@ -167,15 +258,171 @@ impl ResourceContainer {
     pub fn remove(&self, identifier: &str) -> Result<(), RemoveResourceError> {
         let mut inner = self.inner.write();
 
-        if inner.resources.contains_key(identifier) {
-            inner.resources.remove(identifier);
+        if !inner.resources.contains_key(identifier) {
+            return Err(RemoveResourceError::ResourceNotFound(
+                identifier.to_string(),
+            ));
+        }
 
-            Ok(())
-        } else {
-            Err(RemoveResourceError::ResourceNotFound(
+        let dependents = inner.get_dependents(identifier);
+        if !dependents.is_empty() {
+            return Err(RemoveResourceError::ResourceHasDependents(
                 identifier.to_string(),
-            ))
+                dependents,
+            ));
         }
+
+        let reference_count = inner.get_reference_count(identifier);
+        if reference_count > 0 {
+            return Err(RemoveResourceError::ResourceIsReferenced(
+                identifier.to_string(),
+                reference_count,
+            ));
+        }
+
+        inner.remove_entry(identifier);
+        Ok(())
+    }
+
+    /// Remove a resource of the collection and all the resources that depend on it
+    /// Returns the identifiers of the removed resources
+    /// Nothing is removed if one of them is still referenced
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    ///
+    pub fn remove_cascade(&self, identifier: &str) -> Result<Vec<String>, RemoveResourceError> {
+        let mut inner = self.inner.write();
+
+        if !inner.resources.contains_key(identifier) {
+            return Err(RemoveResourceError::ResourceNotFound(
+                identifier.to_string(),
+            ));
+        }
+
+        // Collect the resources that depend directly or indirectly on the removed one
+        let mut removed = vec![identifier.to_string()];
+        let mut index = 0;
+        while index < removed.len() {
+            inner
+                .get_dependents(&removed[index])
+                .into_iter()
+                .for_each(|dependent| {
+                    if !removed.contains(&dependent) {
+                        removed.push(dependent);
+                    }
+                });
+
+            index += 1;
+        }
+
+        if let Some((identifier, reference_count)) = removed
+            .iter()
+            .map(|identifier| (identifier, inner.get_reference_count(identifier)))
+            .find(|(_, reference_count)| *reference_count > 0)
+        {
+            return Err(RemoveResourceError::ResourceIsReferenced(
+                identifier.to_string(),
+                reference_count,
+            ));
+        }
+
+        removed
+            .iter()
+            .rev()
+            .for_each(|identifier| inner.remove_entry(identifier));
+
+        Ok(removed)
+    }
+
+    /// Remove all the loaded resources that are not referenced anymore, neither by a
+    /// [’ResourceReference’] nor by an other resource, this is intended to be used between levels
+    /// Returns the identifiers of the removed resources
+    ///
+    /// The resources that are added directly with [’ResourceContainer::add’], like the services,
+    /// are never unloaded
+    pub fn unload_unused(&self) -> Vec<String> {
+        let mut inner = self.inner.write();
+        let mut unloaded = Vec::new();
+
+        // Removing a resource can release the references it holds, so we loop until nothing
+        // more is removed
+        loop {
+            let mut unused = inner
+                .loaded_resources
+                .iter()
+                .filter(|identifier| {
+                    inner.get_reference_count(identifier) == 0
+                        && inner.get_dependents(identifier).is_empty()
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if unused.is_empty() {
+                break;
+            }
+
+            unused.sort();
+            unused
+                .iter()
+                .for_each(|identifier| inner.remove_entry(identifier));
+            unloaded.append(&mut unused);
+        }
+
+        unloaded
+    }
+
+    /// Get the number of live references over a resource, the guards are counted as well
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    ///
+    pub fn get_reference_count(&self, identifier: &str) -> usize {
+        let inner = self.inner.read();
+        inner.get_reference_count(identifier)
+    }
+
+    /// Declare that a resource depends on an other one, it's intended to be called by the resource
+    /// loaders, a resource cannot be removed while a resource that depends on it exists
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    /// * `dependency` - The identifier of the resource it depends on
+    ///
+    pub fn add_dependency(&self, identifier: &str, dependency: &str) {
+        let mut inner = self.inner.write();
+        let dependencies = inner
+            .dependencies
+            .entry(identifier.to_string())
+            .or_default();
+
+        if !dependencies.iter().any(|existing| existing == dependency) {
+            dependencies.push(dependency.to_string());
+        }
+    }
+
+    /// Get the resources a resource depends on
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    ///
+    pub fn get_dependencies(&self, identifier: &str) -> Vec<String> {
+        let inner = self.inner.read();
+        inner
+            .dependencies
+            .get(identifier)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Get the resources that depend on a resource
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    ///
+    pub fn get_dependents(&self, identifier: &str) -> Vec<String> {
+        let inner = self.inner.read();
+        inner.get_dependents(identifier)
     }
 
     /// Add a resource loader that will be used to load resources
@@ -202,12 +449,42 @@ impl ResourceContainer {
         path: &str,
         resource_type: &str,
     ) -> Result<(), LoadResourceError> {
-        let mut file = File::open(path).unwrap();
-        Self::load_resource(self, path, resource_type, &mut file, Settings::new())?;
+        let mut file = self
+            .get_file_system()
+            .open(path)
+            .map_err(|err| LoadResourceError::FileNotReadable(path.to_string(), err.to_string()))?;
+        let settings = self.with_import_settings(path, Settings::new());
+        Self::load_resource(self, path, resource_type, &mut file, settings)?;
+        self.set_source(path, path, resource_type, Settings::new());
 
         Ok(())
     }
 
+    /// Load an any resource file in background, the file is read and decoded by a worker thread
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file
+    /// * `resource_type` - The resource type
+    ///
+    pub fn load_resource_file_async(&self, path: &str, resource_type: &str) -> LoadHandle {
+        let handle = self.add_load_handle(vec![path.to_string()]);
+
+        let resource_container = self.clone();
+        let path = path.to_string();
+        let resource_type = resource_type.to_string();
+        let task_handle = handle.clone();
+        rayon::spawn(move || {
+            let state = match resource_container.load_resource_file(&path, &resource_type) {
+                Ok(()) => resource_container.get_load_state(&path),
+                Err(err) => LoadState::Failed(err.to_string()),
+            };
+
+            task_handle.set_resource_state(&path, state);
+        });
+
+        handle
+    }
+
     /// Load and add a resource into the collection
     ///
     /// # Arguments
@@ -234,19 +511,101 @@ impl ResourceContainer {
             }?
         };
 
-        resource_loader(identifier, reader, settings, self.clone());
+        resource_loader(identifier, reader, settings, self.clone())?;
+
+        // The resources created by a loader can be unloaded when they are not used anymore
+        {
+            let mut inner = self.inner.write();
+            if inner.resources.contains_key(identifier) {
+                inner.loaded_resources.insert(identifier.to_string());
+            }
+        }
+
         Ok(())
     }
 
-    /// Load many resources for settings
+    /// Load many resources for settings, returns the problems met for each entry that cannot
+    /// be loaded
     ///
     /// # Arguments
     /// * `settings` - The settings of resources
     ///
-    pub fn load_resources_settings(&self, settings: Vec<Settings>) {
-        settings.into_iter().for_each(|settings| {
-            Self::load_resource_settings(self, settings);
-        })
+    pub fn load_resources_settings(&self, settings: Vec<Settings>) -> Vec<LoadResourceDiagnostic> {
+        settings
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, settings)| {
+                let name = settings.get::<Option<String>>("name", None);
+
+                Self::load_resource_settings(self, settings)
+                    .err()
+                    .map(|error| LoadResourceDiagnostic { index, name, error })
+            })
+            .collect()
+    }
+
+    /// Load many resources for settings in background, the files are read in parallel by the
+    /// worker threads
+    ///
+    /// The resources are decoded in the order of the settings, so a resource can use the
+    /// resources that are declared before it
+    ///
+    /// # Arguments
+    /// * `settings` - The settings of resources
+    ///
+    pub fn load_resources_settings_async(&self, settings: Vec<Settings>) -> LoadHandle {
+        let identifiers = settings
+            .iter()
+            .filter_map(|settings| settings.get::<Option<String>>("name", None))
+            .collect::<Vec<_>>();
+        let handle = self.add_load_handle(identifiers);
+
+        let resource_container = self.clone();
+        let task_handle = handle.clone();
+        rayon::spawn(move || {
+            let entries = settings
+                .into_par_iter()
+                .map(|settings| {
+                    let name = settings.get::<Option<String>>("name", None);
+                    (name, resource_container.read_resource_settings(settings))
+                })
+                .collect::<Vec<_>>();
+
+            entries.into_iter().for_each(|(name, entry)| {
+                let result = entry.and_then(|(entry, content)| {
+                    resource_container.load_resource_entry(entry, &mut content.as_slice())
+                });
+
+                if let Some(name) = name {
+                    let state = match result {
+                        Ok(()) => resource_container.get_load_state(&name),
+                        Err(err) => LoadState::Failed(err.to_string()),
+                    };
+
+                    task_handle.set_resource_state(&name, state);
+                }
+            });
+        });
+
+        handle
+    }
+
+    /// Notify the signals of the background loadings, see [’LoadHandle’]
+    /// It's called on the main thread at the beginning of every frame
+    pub fn notify_load_handles(&self) {
+        // The handles are notified without the lock, so the observers can use the container
+        let load_handles = {
+            let mut inner = self.inner.write();
+            std::mem::take(&mut inner.load_handles)
+        };
+
+        let mut pending_handles = load_handles
+            .into_iter()
+            .filter(|handle| !handle.notify_events())
+            .collect::<Vec<_>>();
+
+        let mut inner = self.inner.write();
+        inner.load_handles.append(&mut pending_handles);
     }
 
     /// Load resources for settings
@@ -254,48 +613,278 @@ impl ResourceContainer {
     /// # Arguments
     /// * `settings` - The settings of resources
     ///
-    pub fn load_resource_settings(&self, settings: Settings) -> Option<()> {
+    pub fn load_resource_settings(&self, settings: Settings) -> Result<(), LoadResourceError> {
+        let entry = Self::parse_resource_settings(settings)?;
+        let mut resource_file = self.get_file_system().open(&entry.path).map_err(|err| {
+            LoadResourceError::FileNotReadable(entry.path.clone(), err.to_string())
+        })?;
+
+        self.load_resource_entry(entry, &mut resource_file)
+    }
+
+    /// Load again a resource from the file it has been loaded from, the resources that depend on
+    /// it are loaded again as well, so a material follows the changes of its shader
+    /// The resources are swapped in place, so the existing [’ResourceReference’] see the changes
+    /// Returns the identifiers of the reloaded resources
+    ///
+    /// # Arguments
+    /// * `identifier` - The resource identifier
+    ///
+    pub fn reload(&self, identifier: &str) -> Result<Vec<String>, LoadResourceError> {
+        self.reload_source(identifier)?;
+
+        // Collect the resources that depend directly or indirectly on the reloaded one
+        let mut dependents = vec![identifier.to_string()];
+        let mut index = 0;
+        while index < dependents.len() {
+            self.get_dependents(&dependents[index])
+                .into_iter()
+                .for_each(|dependent| {
+                    if !dependents.contains(&dependent) {
+                        dependents.push(dependent);
+                    }
+                });
+
+            index += 1;
+        }
+
+        let mut reloaded = vec![identifier.to_string()];
+        dependents
+            .into_iter()
+            .skip(1)
+            .for_each(|dependent| match self.reload_source(&dependent) {
+                Ok(()) => reloaded.push(dependent),
+                Err(err) => log::error!("{}", err.to_string()),
+            });
+
+        Ok(reloaded)
+    }
+
+    /// Reload all the resources that have been loaded from a file, see [’ResourceContainer::reload’]
+    /// Returns the identifiers of the reloaded resources
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file
+    ///
+    pub fn reload_path(&self, path: &str) -> Vec<String> {
+        // A change of the import settings reloads the asset they are stored next to
+        let path = path
+            .strip_suffix(&format!(".{}", IMPORT_SETTINGS_EXTENSION))
+            .unwrap_or(path);
+
+        self.get_identifiers_by_path(path)
+            .iter()
+            .flat_map(|identifier| match self.reload(identifier) {
+                Ok(reloaded) => reloaded,
+                Err(err) => {
+                    log::error!("{}", err.to_string());
+                    Vec::new()
+                }
+            })
+            .collect()
+    }
+
+    /// Get the identifiers of the resources that have been loaded from a file
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file
+    ///
+    pub fn get_identifiers_by_path(&self, path: &str) -> Vec<String> {
+        let canonical_path = self.canonicalize_path(path);
+        let inner = self.inner.read();
+
+        let mut identifiers = inner
+            .sources
+            .iter()
+            .filter(|(_, source)| source.canonical_path == canonical_path)
+            .map(|(identifier, _)| identifier.clone())
+            .collect::<Vec<_>>();
+
+        identifiers.sort();
+        identifiers
+    }
+
+    /// Get the virtual file system the resources are loaded from, a file system without any
+    /// mount is returned if none has been added to the container
+    pub fn get_file_system(&self) -> VirtualFileSystem {
+        let file_system = {
+            let inner = self.inner.read();
+            inner
+                .identifier_by_type
+                .get(&TypeId::of::<VirtualFileSystem>())
+                .and_then(|identifier| inner.resources.get(identifier))
+                .cloned()
+        };
+
+        match file_system.and_then(|resource| {
+            resource
+                .as_any_arc()
+                .downcast::<RwLock<Box<VirtualFileSystem>>>()
+                .ok()
+        }) {
+            Some(file_system) => {
+                let file_system = file_system.read();
+                file_system.as_ref().clone()
+            }
+            None => VirtualFileSystem::new(),
+        }
+    }
+
+    fn set_source(&self, identifier: &str, path: &str, resource_type: &str, settings: Settings) {
+        let canonical_path = self.canonicalize_path(path);
+
+        let mut inner = self.inner.write();
+        inner.sources.insert(
+            identifier.to_string(),
+            ResourceSource {
+                path: path.to_string(),
+                canonical_path,
+                resource_type: resource_type.to_string(),
+                settings,
+            },
+        );
+    }
+
+    fn canonicalize_path(&self, path: &str) -> PathBuf {
+        let real_path = self
+            .get_file_system()
+            .get_real_path(path)
+            .unwrap_or_else(|| PathBuf::from(path));
+
+        std::fs::canonicalize(&real_path).unwrap_or(real_path)
+    }
+
+    fn reload_source(&self, identifier: &str) -> Result<(), LoadResourceError> {
+        let source = {
+            let inner = self.inner.read();
+            inner.sources.get(identifier).cloned()
+        }
+        .ok_or_else(|| LoadResourceError::ResourceSourceNotKnown(identifier.to_string()))?;
+
+        let mut file = self.get_file_system().open(&source.path).map_err(|err| {
+            LoadResourceError::FileNotReadable(source.path.clone(), err.to_string())
+        })?;
+
+        let settings = self.with_import_settings(&source.path, source.settings);
+        Self::load_resource(self, identifier, &source.resource_type, &mut file, settings)
+    }
+
+    fn with_import_settings(&self, path: &str, settings: Settings) -> Settings {
+        match read_import_settings(&self.get_file_system(), path) {
+            Some(import_settings) => merge_import_settings(settings, import_settings),
+            None => settings,
+        }
+    }
+
+    fn parse_resource_settings(settings: Settings) -> Result<ResourceEntry, LoadResourceError> {
         // Parse settings
         let fields = if let Settings::Object(fields) = settings {
             fields
         } else {
-            return None;
+            return Err(LoadResourceError::InvalidSettingsField(
+                "".to_string(),
+                "The resource settings should be an object".to_string(),
+            ));
         };
 
         // Get the resource name
-        let name = {
-            if let Settings::String(name) = fields.get("name")? {
-                name.clone()
-            } else {
-                return None;
-            }
+        let name = if let Some(Settings::String(name)) = fields.get("name") {
+            name.clone()
+        } else {
+            return Err(LoadResourceError::InvalidSettingsField(
+                "name".to_string(),
+                "A string is expected".to_string(),
+            ));
         };
 
         // Get the resource path
-        let path = {
-            if let Settings::String(path) = fields.get("path")? {
-                path.clone()
-            } else {
-                return None;
-            }
+        let path = if let Some(Settings::String(path)) = fields.get("path") {
+            path.clone()
+        } else {
+            return Err(LoadResourceError::InvalidSettingsField(
+                "path".to_string(),
+                "A string is expected".to_string(),
+            ));
         };
 
         // Deduce informations about the resource from the path
-        let resource_type = Path::new(&path).extension()?;
-        let resource_type = resource_type.to_str()?;
-        let mut resource_file = File::open(&path).ok()?;
+        let resource_type = Path::new(&path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .ok_or_else(|| {
+                LoadResourceError::InvalidSettingsField(
+                    "path".to_string(),
+                    format!("\"{}\" has no extension to deduce the resource type", &path),
+                )
+            })?
+            .to_string();
 
-        // Load the resource
+        Ok(ResourceEntry {
+            name,
+            path,
+            resource_type,
+            fields,
+        })
+    }
+
+    // Parse the settings and read the whole file, it's intended to be run by a worker thread
+    fn read_resource_settings(
+        &self,
+        settings: Settings,
+    ) -> Result<(ResourceEntry, Vec<u8>), LoadResourceError> {
+        let entry = Self::parse_resource_settings(settings)?;
+
+        let mut content = Vec::new();
+        self.get_file_system()
+            .open(&entry.path)
+            .and_then(|mut resource_file| resource_file.read_to_end(&mut content))
+            .map_err(|err| {
+                LoadResourceError::FileNotReadable(entry.path.clone(), err.to_string())
+            })?;
+
+        Ok((entry, content))
+    }
+
+    fn load_resource_entry(
+        &self,
+        entry: ResourceEntry,
+        reader: &mut dyn Read,
+    ) -> Result<(), LoadResourceError> {
+        // Load the resource, the import settings stored next to the file are merged
+        let resource_settings =
+            self.with_import_settings(&entry.path, Settings::Object(entry.fields.clone()));
         Self::load_resource(
             self,
-            &name,
-            resource_type,
-            &mut resource_file,
-            Settings::Object(fields.clone()),
-        )
-        .ok()?;
+            &entry.name,
+            &entry.resource_type,
+            reader,
+            resource_settings,
+        )?;
+        self.set_source(
+            &entry.name,
+            &entry.path,
+            &entry.resource_type,
+            Settings::Object(entry.fields),
+        );
 
-        Some(())
+        Ok(())
+    }
+
+    fn add_load_handle(&self, identifiers: Vec<String>) -> LoadHandle {
+        let handle = LoadHandle::new(self.clone(), identifiers);
+
+        let mut inner = self.inner.write();
+        inner.load_handles.push(handle.clone());
+
+        handle
+    }
+
+    fn get_load_state(&self, identifier: &str) -> LoadState {
+        if self.contains(identifier) {
+            LoadState::Loaded
+        } else {
+            LoadState::Failed("The resource loader has not created the resource".to_string())
+        }
     }
 }
 
@@ -341,11 +930,81 @@ impl IntrospectObject for ResourceContainer {
                     let mut caster = ArgumentCaster::new("remove", args);
                     let arg1 = caster.cast_next::<String>()?;
 
-                    this.remove(&arg1).unwrap();
+                    if let Err(err) = this.remove(&arg1) {
+                        log::error!("{}", err.to_string());
+                    }
 
                     Ok(None)
                 })),
             },
+            MethodInfo {
+                name: "remove_cascade".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<ResourceContainer>(this);
+
+                    let mut caster = ArgumentCaster::new("remove_cascade", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    match this.remove_cascade(&arg1) {
+                        Ok(removed) => Ok(Some(removed.fruity_into())),
+                        Err(err) => {
+                            log::error!("{}", err.to_string());
+                            Ok(None)
+                        }
+                    }
+                })),
+            },
+            MethodInfo {
+                name: "unload_unused".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<ResourceContainer>(this);
+                    let result = this.unload_unused();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "load_resource_file_async".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<ResourceContainer>(this);
+
+                    let mut caster = ArgumentCaster::new("load_resource_file_async", args);
+                    let arg1 = caster.cast_next::<String>()?;
+                    let arg2 = caster.cast_next::<String>()?;
+                    let result = this.load_resource_file_async(&arg1, &arg2);
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "reload".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<ResourceContainer>(this);
+
+                    let mut caster = ArgumentCaster::new("reload", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    match this.reload(&arg1) {
+                        Ok(reloaded) => Ok(Some(reloaded.fruity_into())),
+                        Err(err) => {
+                            log::error!("{}", err.to_string());
+                            Ok(None)
+                        }
+                    }
+                })),
+            },
+            MethodInfo {
+                name: "get_reference_count".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<ResourceContainer>(this);
+
+                    let mut caster = ArgumentCaster::new("get_reference_count", args);
+                    let arg1 = caster.cast_next::<String>()?;
+                    let result = this.get_reference_count(&arg1);
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
         ]
     }
 
@@ -359,3 +1018,269 @@ impl SerializableObject for ResourceContainer {
         Box::new(self.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mutex;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    fn load_serialized(
+        identifier: &str,
+        _reader: &mut dyn Read,
+        _settings: Settings,
+        resource_container: ResourceContainer,
+    ) -> Result<(), LoadResourceError> {
+        resource_container.add::<SerializedResource>(
+            identifier,
+            Box::new(SerializedResource::new(Serialized::Null)),
+        );
+
+        Ok(())
+    }
+
+    fn load(resource_container: &ResourceContainer, identifier: &str) {
+        resource_container
+            .load_resource(identifier, "test", &mut std::io::empty(), Settings::new())
+            .ok();
+    }
+
+    fn build_resource_container() -> ResourceContainer {
+        let resource_container = ResourceContainer::new();
+        resource_container.add_resource_loader("test", load_serialized);
+
+        load(&resource_container, "shader.wgsl");
+        load(&resource_container, "character.png");
+        load(&resource_container, "character.material");
+        resource_container.add_dependency("character.material", "shader.wgsl");
+        resource_container.add_dependency("character.material", "character.png");
+
+        resource_container
+    }
+
+    #[test]
+    fn remove_is_refused_for_a_dependency() {
+        let resource_container = build_resource_container();
+
+        assert!(matches!(
+            resource_container.remove("shader.wgsl"),
+            Err(RemoveResourceError::ResourceHasDependents(_, _))
+        ));
+
+        let removed = resource_container
+            .remove_cascade("shader.wgsl")
+            .ok()
+            .unwrap();
+        assert_eq!(removed, vec!["shader.wgsl", "character.material"]);
+        assert!(resource_container.contains("character.png"));
+    }
+
+    #[test]
+    fn remove_is_refused_for_a_referenced_resource() {
+        let resource_container = build_resource_container();
+
+        let material = resource_container.get::<SerializedResource>("character.material");
+        assert!(matches!(
+            resource_container.remove("character.material"),
+            Err(RemoveResourceError::ResourceIsReferenced(_, 1))
+        ));
+
+        // The referenced resource is a dependent, so nothing is removed
+        assert!(matches!(
+            resource_container.remove_cascade("shader.wgsl"),
+            Err(RemoveResourceError::ResourceIsReferenced(_, 1))
+        ));
+        assert!(resource_container.contains("shader.wgsl"));
+        assert!(resource_container.contains("character.material"));
+
+        std::mem::drop(material);
+        assert!(resource_container.remove("character.material").is_ok());
+        assert!(!resource_container.contains("character.material"));
+    }
+
+    #[test]
+    fn unload_unused_keeps_the_referenced_resources() {
+        let resource_container = build_resource_container();
+        resource_container.add::<SerializedResource>(
+            "service",
+            Box::new(SerializedResource::new(Serialized::Null)),
+        );
+
+        let material = resource_container.get::<SerializedResource>("character.material");
+        assert_eq!(
+            resource_container.get_reference_count("character.material"),
+            1
+        );
+        assert!(resource_container.unload_unused().is_empty());
+
+        std::mem::drop(material);
+        assert_eq!(
+            resource_container.unload_unused(),
+            vec!["character.material", "character.png", "shader.wgsl"]
+        );
+        assert!(resource_container.contains("service"));
+    }
+
+    #[test]
+    fn load_handle_reports_the_loading_state() {
+        let resource_container = ResourceContainer::new();
+        resource_container.add_resource_loader("test", load_serialized);
+
+        let handle = resource_container.load_resource_file_async("Cargo.toml", "test");
+        handle.wait();
+        assert_eq!(handle.get_state(), LoadState::Loaded);
+        assert_eq!(handle.get_progress(), 1.0);
+        assert!(handle.get::<SerializedResource>("Cargo.toml").is_some());
+
+        let handle = resource_container.load_resource_file_async("missing.test", "test");
+        handle.wait();
+        assert!(matches!(handle.get_state(), LoadState::Failed(_)));
+        assert!(handle.get::<SerializedResource>("missing.test").is_none());
+    }
+
+    #[test]
+    fn an_empty_load_handle_is_done_at_the_next_notification() {
+        let resource_container = ResourceContainer::new();
+        let handle = resource_container.load_resources_settings_async(Vec::new());
+        assert!(handle.is_done());
+
+        let done = Arc::new(AtomicUsize::new(0));
+        let done_2 = done.clone();
+        handle.on_done.add_observer(move |_| {
+            done_2.fetch_add(1, Ordering::Relaxed);
+        });
+
+        resource_container.notify_load_handles();
+        resource_container.notify_load_handles();
+        assert_eq!(done.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn load_handle_resolves_the_reference_once_the_resource_is_ready() {
+        let resource_container = ResourceContainer::new();
+        resource_container.add_resource_loader("toml", load_serialized);
+
+        let mut cargo = HashMap::new();
+        cargo.insert("name".to_string(), Settings::String("cargo".to_string()));
+        cargo.insert(
+            "path".to_string(),
+            Settings::String("Cargo.toml".to_string()),
+        );
+
+        let handle =
+            resource_container.load_resources_settings_async(vec![Settings::Object(cargo)]);
+
+        let resolved = Arc::new(Mutex::new(None));
+        let resolved_2 = resolved.clone();
+        handle.get_when_loaded::<SerializedResource>("cargo", move |resource| {
+            *resolved_2.lock() = Some(resource);
+        });
+
+        let done = Arc::new(AtomicUsize::new(0));
+        let done_2 = done.clone();
+        handle.on_done.add_observer(move |_| {
+            done_2.fetch_add(1, Ordering::Relaxed);
+        });
+
+        // The signals wait for the main thread to be notified
+        handle.wait();
+        assert_eq!(done.load(Ordering::Relaxed), 0);
+
+        resource_container.notify_load_handles();
+        assert_eq!(done.load(Ordering::Relaxed), 1);
+        assert!(resolved.lock().is_some());
+
+        // A resource that is already loaded is resolved right away
+        let resolved = Arc::new(Mutex::new(None));
+        let resolved_2 = resolved.clone();
+        handle.get_when_loaded::<SerializedResource>("cargo", move |resource| {
+            *resolved_2.lock() = Some(resource);
+        });
+        assert!(resolved.lock().is_some());
+    }
+
+    #[test]
+    fn load_resources_settings_reports_each_failed_entry() {
+        let resource_container = ResourceContainer::new();
+        resource_container.add_resource_loader("toml", load_serialized);
+
+        let mut valid = HashMap::new();
+        valid.insert("name".to_string(), Settings::String("cargo".to_string()));
+        valid.insert(
+            "path".to_string(),
+            Settings::String("Cargo.toml".to_string()),
+        );
+
+        let mut missing_file = HashMap::new();
+        missing_file.insert("name".to_string(), Settings::String("missing".to_string()));
+        missing_file.insert(
+            "path".to_string(),
+            Settings::String("missing.toml".to_string()),
+        );
+
+        let diagnostics = resource_container.load_resources_settings(vec![
+            Settings::Object(valid),
+            Settings::Object(HashMap::new()),
+            Settings::Object(missing_file),
+        ]);
+
+        assert!(resource_container.contains("cargo"));
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].index, 1);
+        assert!(matches!(
+            diagnostics[0].error,
+            LoadResourceError::InvalidSettingsField(_, _)
+        ));
+        assert_eq!(diagnostics[1].name, Some("missing".to_string()));
+        assert!(matches!(
+            diagnostics[1].error,
+            LoadResourceError::FileNotReadable(_, _)
+        ));
+    }
+
+    #[test]
+    fn reload_swaps_in_place_and_cascades_to_dependents() {
+        let resource_container = ResourceContainer::new();
+        resource_container.add_resource_loader("toml", load_serialized);
+        resource_container.add_resource_loader("rs", load_serialized);
+
+        let mut cargo = HashMap::new();
+        cargo.insert("name".to_string(), Settings::String("cargo".to_string()));
+        cargo.insert(
+            "path".to_string(),
+            Settings::String("Cargo.toml".to_string()),
+        );
+
+        let mut lib = HashMap::new();
+        lib.insert("name".to_string(), Settings::String("lib".to_string()));
+        lib.insert(
+            "path".to_string(),
+            Settings::String("src/lib.rs".to_string()),
+        );
+
+        resource_container
+            .load_resources_settings(vec![Settings::Object(cargo), Settings::Object(lib)]);
+        resource_container.add_dependency("lib", "cargo");
+
+        let _cargo = resource_container.get::<SerializedResource>("cargo");
+        assert_eq!(
+            resource_container.get_identifiers_by_path("./Cargo.toml"),
+            vec!["cargo"]
+        );
+        assert_eq!(
+            resource_container.reload_path("./Cargo.toml"),
+            vec!["cargo", "lib"]
+        );
+        assert_eq!(resource_container.get_reference_count("cargo"), 1);
+
+        resource_container.add::<SerializedResource>(
+            "service",
+            Box::new(SerializedResource::new(Serialized::Null)),
+        );
+        assert!(matches!(
+            resource_container.reload("service"),
+            Err(LoadResourceError::ResourceSourceNotKnown(_))
+        ));
+    }
+}
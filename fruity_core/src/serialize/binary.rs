@@ -0,0 +1,485 @@
+use crate::serialize::Serialized;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::io::Read;
+use std::io::Write;
+
+/// The bytes that starts every binary serialized file
+pub const BINARY_MAGIC: &[u8; 4] = b"FRSB";
+
+/// The current version of the binary format
+pub const BINARY_VERSION: u8 = 1;
+
+const TAG_NULL: u8 = 0;
+const TAG_I8: u8 = 1;
+const TAG_I16: u8 = 2;
+const TAG_I32: u8 = 3;
+const TAG_I64: u8 = 4;
+const TAG_ISIZE: u8 = 5;
+const TAG_U8: u8 = 6;
+const TAG_U16: u8 = 7;
+const TAG_U32: u8 = 8;
+const TAG_U64: u8 = 9;
+const TAG_USIZE: u8 = 10;
+const TAG_F32: u8 = 11;
+const TAG_F64: u8 = 12;
+const TAG_BOOL: u8 = 13;
+const TAG_STRING: u8 = 14;
+const TAG_ARRAY: u8 = 15;
+const TAG_OBJECT: u8 = 16;
+
+/// The maximum nesting of the arrays and objects that is read, a deeper file is rejected
+/// instead of overflowing the stack
+pub const MAX_BINARY_DEPTH: usize = 256;
+
+/// Serialize a [’Serialized’] with a compact binary encoding
+///
+/// The file starts with [’BINARY_MAGIC’] and [’BINARY_VERSION’], followed by a table that stores
+/// once every class name and field key, the values then reference this table by index.
+/// Values that can't be stored (iterators, callbacks and native objects) make the serialization fail.
+///
+/// # Arguments
+/// * `writer` - The write io stream
+/// * `serialized` - The serialized value
+///
+pub fn serialize_binary(
+    writer: &mut dyn Write,
+    serialized: &Serialized,
+) -> Result<(), std::io::Error> {
+    let mut string_table = StringTable::default();
+    let mut body = Vec::new();
+    intern_serialize_binary(&mut body, &mut string_table, serialized)?;
+
+    let mut buffer = Vec::with_capacity(body.len() + 64);
+    buffer.extend_from_slice(BINARY_MAGIC);
+    buffer.push(BINARY_VERSION);
+    write_u32(&mut buffer, string_table.strings.len() as u32);
+    string_table
+        .strings
+        .iter()
+        .for_each(|string| write_string(&mut buffer, string));
+    buffer.append(&mut body);
+
+    writer.write_all(&buffer)?;
+    Ok(())
+}
+
+/// Deserialize a [’Serialized’] from a binary file written by [’serialize_binary’]
+///
+/// # Arguments
+/// * `reader` - The read io stream
+///
+pub fn deserialize_binary(reader: &mut dyn Read) -> Option<Serialized> {
+    let mut buffer = Vec::new();
+    if let Err(err) = reader.read_to_end(&mut buffer) {
+        log::error!("{}", err);
+        return None;
+    }
+
+    let mut cursor = Cursor {
+        buffer: &buffer,
+        position: 0,
+    };
+
+    if cursor.read_bytes(BINARY_MAGIC.len())? != BINARY_MAGIC {
+        log::error!("Failed to read a binary file, the file header is not recognized");
+        return None;
+    }
+
+    let version = cursor.read_u8()?;
+    if version > BINARY_VERSION {
+        log::error!(
+            "Failed to read a binary file, version {} is not supported, the last known version is {}",
+            version,
+            BINARY_VERSION
+        );
+        return None;
+    }
+
+    let string_count = cursor.read_u32()?;
+    let strings = (0..string_count)
+        .map(|_| cursor.read_string())
+        .collect::<Option<Vec<_>>>()?;
+
+    let result = intern_deserialize_binary(&mut cursor, &strings, 0);
+    if result.is_none() {
+        log::error!("Failed to read a binary file, the content is corrupted");
+    }
+
+    result
+}
+
+#[derive(Default)]
+struct StringTable {
+    strings: Vec<String>,
+    indexes: HashMap<String, u32>,
+}
+
+impl StringTable {
+    fn intern(&mut self, string: &str) -> u32 {
+        if let Some(index) = self.indexes.get(string) {
+            *index
+        } else {
+            let index = self.strings.len() as u32;
+            self.strings.push(string.to_string());
+            self.indexes.insert(string.to_string(), index);
+            index
+        }
+    }
+}
+
+fn write_u32(buffer: &mut Vec<u8>, value: u32) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_string(buffer: &mut Vec<u8>, value: &str) {
+    write_u32(buffer, value.len() as u32);
+    buffer.extend_from_slice(value.as_bytes());
+}
+
+fn intern_serialize_binary(
+    buffer: &mut Vec<u8>,
+    string_table: &mut StringTable,
+    serialized: &Serialized,
+) -> Result<(), std::io::Error> {
+    match serialized {
+        Serialized::I8(value) => {
+            buffer.push(TAG_I8);
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        Serialized::I16(value) => {
+            buffer.push(TAG_I16);
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        Serialized::I32(value) => {
+            buffer.push(TAG_I32);
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        Serialized::I64(value) => {
+            buffer.push(TAG_I64);
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        Serialized::ISize(value) => {
+            buffer.push(TAG_ISIZE);
+            buffer.extend_from_slice(&(*value as i64).to_le_bytes());
+        }
+        Serialized::U8(value) => {
+            buffer.push(TAG_U8);
+            buffer.push(*value);
+        }
+        Serialized::U16(value) => {
+            buffer.push(TAG_U16);
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        Serialized::U32(value) => {
+            buffer.push(TAG_U32);
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        Serialized::U64(value) => {
+            buffer.push(TAG_U64);
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        Serialized::USize(value) => {
+            buffer.push(TAG_USIZE);
+            buffer.extend_from_slice(&(*value as u64).to_le_bytes());
+        }
+        Serialized::F32(value) => {
+            buffer.push(TAG_F32);
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        Serialized::F64(value) => {
+            buffer.push(TAG_F64);
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        Serialized::Bool(value) => {
+            buffer.push(TAG_BOOL);
+            buffer.push(*value as u8);
+        }
+        Serialized::String(value) => {
+            buffer.push(TAG_STRING);
+            write_string(buffer, value);
+        }
+        Serialized::Array(value) => {
+            buffer.push(TAG_ARRAY);
+            write_u32(buffer, value.len() as u32);
+            value
+                .iter()
+                .try_for_each(|elem| intern_serialize_binary(buffer, string_table, elem))?;
+        }
+        Serialized::SerializedObject { class_name, fields } => {
+            buffer.push(TAG_OBJECT);
+            write_u32(buffer, string_table.intern(class_name));
+            write_u32(buffer, fields.len() as u32);
+
+            // Fields are sorted so a same object always produces the same bytes
+            let mut fields = fields.iter().collect::<Vec<_>>();
+            fields.sort_by_key(|(key, _)| *key);
+            fields.into_iter().try_for_each(|(key, value)| {
+                write_u32(buffer, string_table.intern(key));
+                intern_serialize_binary(buffer, string_table, value)
+            })?;
+        }
+        Serialized::Null => buffer.push(TAG_NULL),
+        Serialized::Iterator(_) => return Err(unsupported_value_error("an iterator")),
+        Serialized::Callback(_) => return Err(unsupported_value_error("a callback")),
+        Serialized::NativeObject(_) => return Err(unsupported_value_error("a native object")),
+    }
+
+    Ok(())
+}
+
+fn unsupported_value_error(value: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("Failed to write a binary file, {} can't be stored", value),
+    )
+}
+
+struct Cursor<'a> {
+    buffer: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(len)?;
+        let bytes = self.buffer.get(self.position..end)?;
+        self.position = end;
+
+        Some(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.read_bytes(N)?.try_into().ok()
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        Some(self.read_array::<1>()?[0])
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_string(&mut self) -> Option<String> {
+        let len = self.read_u32()? as usize;
+        let bytes = self.read_bytes(len)?;
+
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+fn intern_deserialize_binary(
+    cursor: &mut Cursor,
+    strings: &Vec<String>,
+    depth: usize,
+) -> Option<Serialized> {
+    if depth > MAX_BINARY_DEPTH {
+        return None;
+    }
+
+    let result = match cursor.read_u8()? {
+        TAG_NULL => Serialized::Null,
+        TAG_I8 => Serialized::I8(i8::from_le_bytes(cursor.read_array()?)),
+        TAG_I16 => Serialized::I16(i16::from_le_bytes(cursor.read_array()?)),
+        TAG_I32 => Serialized::I32(i32::from_le_bytes(cursor.read_array()?)),
+        TAG_I64 => Serialized::I64(i64::from_le_bytes(cursor.read_array()?)),
+        TAG_ISIZE => Serialized::ISize(i64::from_le_bytes(cursor.read_array()?) as isize),
+        TAG_U8 => Serialized::U8(cursor.read_u8()?),
+        TAG_U16 => Serialized::U16(u16::from_le_bytes(cursor.read_array()?)),
+        TAG_U32 => Serialized::U32(cursor.read_u32()?),
+        TAG_U64 => Serialized::U64(u64::from_le_bytes(cursor.read_array()?)),
+        TAG_USIZE => Serialized::USize(u64::from_le_bytes(cursor.read_array()?) as usize),
+        TAG_F32 => Serialized::F32(f32::from_le_bytes(cursor.read_array()?)),
+        TAG_F64 => Serialized::F64(f64::from_le_bytes(cursor.read_array()?)),
+        TAG_BOOL => Serialized::Bool(cursor.read_u8()? != 0),
+        TAG_STRING => Serialized::String(cursor.read_string()?),
+        TAG_ARRAY => {
+            let len = cursor.read_u32()?;
+            let elements = (0..len)
+                .map(|_| intern_deserialize_binary(cursor, strings, depth + 1))
+                .collect::<Option<Vec<_>>>()?;
+
+            Serialized::Array(elements)
+        }
+        TAG_OBJECT => {
+            let class_name = strings.get(cursor.read_u32()? as usize)?.clone();
+            let len = cursor.read_u32()?;
+
+            let mut fields = HashMap::new();
+            for _ in 0..len {
+                let key = strings.get(cursor.read_u32()? as usize)?.clone();
+                let value = intern_deserialize_binary(cursor, strings, depth + 1)?;
+                fields.insert(key, value);
+            }
+
+            Serialized::SerializedObject { class_name, fields }
+        }
+        _ => return None,
+    };
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::serialize::binary::deserialize_binary;
+    use crate::serialize::binary::serialize_binary;
+    use crate::serialize::binary::MAX_BINARY_DEPTH;
+    use crate::serialize::serialized::Callback;
+    use crate::serialize::serialized::Serialized;
+    use crate::serialize::yaml::deserialize_yaml;
+    use crate::serialize::yaml::serialize_yaml;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn to_binary(serialized: &Serialized) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        serialize_binary(&mut buffer, serialized).unwrap();
+        buffer
+    }
+
+    // Serialized doesn't implement PartialEq, the values supported by the binary format are
+    // compared field by field
+    fn assert_same_value(left: &Serialized, right: &Serialized) {
+        match (left, right) {
+            (Serialized::Array(left), Serialized::Array(right)) => {
+                assert_eq!(left.len(), right.len());
+                left.iter()
+                    .zip(right.iter())
+                    .for_each(|(left, right)| assert_same_value(left, right));
+            }
+            (
+                Serialized::SerializedObject {
+                    class_name: left_class_name,
+                    fields: left_fields,
+                },
+                Serialized::SerializedObject {
+                    class_name: right_class_name,
+                    fields: right_fields,
+                },
+            ) => {
+                assert_eq!(left_class_name, right_class_name);
+                assert_eq!(left_fields.len(), right_fields.len());
+                left_fields.iter().for_each(|(name, left)| {
+                    assert_same_value(left, right_fields.get(name).unwrap())
+                });
+            }
+            (Serialized::I8(left), Serialized::I8(right)) => assert_eq!(left, right),
+            (Serialized::I16(left), Serialized::I16(right)) => assert_eq!(left, right),
+            (Serialized::I32(left), Serialized::I32(right)) => assert_eq!(left, right),
+            (Serialized::I64(left), Serialized::I64(right)) => assert_eq!(left, right),
+            (Serialized::ISize(left), Serialized::ISize(right)) => assert_eq!(left, right),
+            (Serialized::U8(left), Serialized::U8(right)) => assert_eq!(left, right),
+            (Serialized::U16(left), Serialized::U16(right)) => assert_eq!(left, right),
+            (Serialized::U32(left), Serialized::U32(right)) => assert_eq!(left, right),
+            (Serialized::U64(left), Serialized::U64(right)) => assert_eq!(left, right),
+            (Serialized::USize(left), Serialized::USize(right)) => assert_eq!(left, right),
+            (Serialized::F32(left), Serialized::F32(right)) => assert_eq!(left, right),
+            (Serialized::F64(left), Serialized::F64(right)) => assert_eq!(left, right),
+            (Serialized::Bool(left), Serialized::Bool(right)) => assert_eq!(left, right),
+            (Serialized::String(left), Serialized::String(right)) => assert_eq!(left, right),
+            (Serialized::Null, Serialized::Null) => (),
+            _ => panic!("The serialized values have different types"),
+        }
+    }
+
+    fn to_yaml(serialized: &Serialized) -> String {
+        let mut buffer = Vec::new();
+        serialize_yaml(&mut buffer, serialized).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn binary_round_trip_test() {
+        let mut fields = HashMap::new();
+        fields.insert("name".to_string(), Serialized::String("Player".to_string()));
+        fields.insert("enabled".to_string(), Serialized::Bool(true));
+        fields.insert("entity_id".to_string(), Serialized::U64(u64::MAX));
+        fields.insert("speed".to_string(), Serialized::F32(1.5));
+        fields.insert("parent".to_string(), Serialized::Null);
+        fields.insert(
+            "components".to_string(),
+            Serialized::Array(vec![
+                Serialized::I8(-4),
+                Serialized::I64(i64::MIN),
+                Serialized::USize(12),
+            ]),
+        );
+
+        let serialized = Serialized::Array(vec![
+            Serialized::SerializedObject {
+                class_name: "Entity".to_string(),
+                fields: fields.clone(),
+            },
+            Serialized::SerializedObject {
+                class_name: "Entity".to_string(),
+                fields,
+            },
+        ]);
+
+        let binary = to_binary(&serialized);
+        let restored = deserialize_binary(&mut binary.as_slice()).unwrap();
+
+        assert_same_value(&restored, &serialized);
+        assert_eq!(to_binary(&restored), binary);
+    }
+
+    #[test]
+    fn binary_matches_yaml_test() {
+        let yaml = "---\n- class_name: Transform2d\n  fields:\n    angle: 0.5\n    size: 3\n    name: test\n";
+        let from_yaml = deserialize_yaml(&mut yaml.as_bytes()).unwrap();
+
+        let binary = to_binary(&from_yaml);
+        let from_binary = deserialize_binary(&mut binary.as_slice()).unwrap();
+        assert_same_value(&from_binary, &from_yaml);
+        assert_eq!(to_binary(&from_binary), binary);
+
+        let yaml = to_yaml(&from_binary);
+        let from_yaml = deserialize_yaml(&mut yaml.as_bytes()).unwrap();
+        assert_eq!(to_binary(&from_yaml), binary);
+    }
+
+    #[test]
+    fn binary_rejects_corrupted_file_test() {
+        let binary = to_binary(&Serialized::Array(vec![Serialized::String(
+            "truncated".to_string(),
+        )]));
+
+        assert!(deserialize_binary(&mut &binary[..binary.len() - 2]).is_none());
+        assert!(deserialize_binary(&mut &b"FRSC\x01"[..]).is_none());
+    }
+
+    #[test]
+    fn binary_rejects_unsupported_values_test() {
+        let serialized = Serialized::Array(vec![Serialized::Callback(Callback {
+            origin: "test".to_string(),
+            callback: Arc::new(|_| Ok(None)),
+        })]);
+
+        let mut buffer = Vec::new();
+        assert!(serialize_binary(&mut buffer, &serialized).is_err());
+    }
+
+    #[test]
+    fn binary_rejects_too_deep_file_test() {
+        let mut serialized = Serialized::Null;
+        (0..MAX_BINARY_DEPTH)
+            .for_each(|_| serialized = Serialized::Array(vec![serialized.clone()]));
+        let binary = to_binary(&serialized);
+        assert_same_value(
+            &deserialize_binary(&mut binary.as_slice()).unwrap(),
+            &serialized,
+        );
+
+        // A crafted file that only opens arrays
+        let mut binary = to_binary(&Serialized::Null);
+        binary.pop();
+        (0..100_000).for_each(|_| {
+            binary.push(15);
+            binary.extend_from_slice(&1u32.to_le_bytes());
+        });
+        binary.push(0);
+        assert!(deserialize_binary(&mut binary.as_slice()).is_none());
+    }
+}
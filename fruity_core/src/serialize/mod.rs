@@ -1,6 +1,13 @@
+use crate::serialize::binary::deserialize_binary;
+use crate::serialize::binary::serialize_binary;
 use crate::serialize::serialized::Serialized;
+use crate::serialize::yaml::deserialize_yaml;
+use crate::serialize::yaml::serialize_yaml;
+use crate::utils::string::get_file_type_from_path;
+use crate::vfs::virtual_file_system::VirtualFileSystem;
 use crate::ObjectFactoryService;
 use std::collections::HashMap;
+use std::io::Write;
 
 /// A structure used to serialize datas
 pub mod serialized;
@@ -8,6 +15,67 @@ pub mod serialized;
 /// Provides functions to serialize and deserialize a serialized value to yaml
 pub mod yaml;
 
+/// Provides functions to serialize and deserialize a serialized value to a compact binary format
+pub mod binary;
+
+/// The file extension of the scenes stored with the binary format
+pub const BINARY_SCENE_EXTENSION: &str = "frsb";
+
+/// Serialize a [’Serialized’] into a file
+///
+/// The binary format is used if the file extension is [’BINARY_SCENE_EXTENSION’], yaml otherwise
+///
+/// # Arguments
+/// * `file_system` - The file system the file is written into
+/// * `filepath` - The file path
+/// * `serialized` - The serialized value
+///
+pub fn serialize_file(
+    file_system: &VirtualFileSystem,
+    filepath: &str,
+    serialized: &Serialized,
+) -> Result<(), std::io::Error> {
+    // The value is serialized before the file is created, that way a value that can't be
+    // serialized doesn't wipe the existing file
+    let mut buffer = Vec::new();
+    if is_binary_file(filepath) {
+        serialize_binary(&mut buffer, serialized)?;
+    } else {
+        serialize_yaml(&mut buffer, serialized)?;
+    }
+
+    let mut writer = file_system.create(filepath)?;
+    writer.write_all(&buffer)
+}
+
+/// Deserialize a [’Serialized’] from a file
+///
+/// The binary format is used if the file extension is [’BINARY_SCENE_EXTENSION’], yaml otherwise
+///
+/// # Arguments
+/// * `file_system` - The file system the file is read from
+/// * `filepath` - The file path
+///
+pub fn deserialize_file(file_system: &VirtualFileSystem, filepath: &str) -> Option<Serialized> {
+    let mut reader = match file_system.open(filepath) {
+        Ok(reader) => reader,
+        Err(err) => {
+            log::error!("{}", err.to_string());
+            return None;
+        }
+    };
+
+    if is_binary_file(filepath) {
+        deserialize_binary(&mut reader)
+    } else {
+        deserialize_yaml(&mut reader)
+    }
+}
+
+fn is_binary_file(filepath: &str) -> bool {
+    get_file_type_from_path(filepath).as_deref() == Some(BINARY_SCENE_EXTENSION)
+}
+
 /// A trait that implements a function to serialize an object to a [’Serialized’]
 pub trait Serialize {
     /// A function to serialize an object to a [’Serialized’]
@@ -127,3 +195,38 @@ impl Serialized {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialize::serialized::Callback;
+    use crate::vfs::directory_backend::DirectoryBackend;
+    use crate::vfs::virtual_file_system::USER_SCHEME;
+    use std::sync::Arc;
+
+    #[test]
+    fn failed_save_keeps_the_existing_file() {
+        let folder = std::env::temp_dir().join("fruity_core_serialize_file_test");
+        std::fs::create_dir_all(&folder).unwrap();
+
+        let file_system = VirtualFileSystem::new();
+        file_system.mount(
+            USER_SCHEME,
+            Box::new(DirectoryBackend::new(folder.to_str().unwrap())),
+        );
+
+        let saved = Serialized::Array(vec![Serialized::String("saved".to_string())]);
+        serialize_file(&file_system, "user://scene.frsb", &saved).unwrap();
+        let content = std::fs::read(folder.join("scene.frsb")).unwrap();
+
+        // The binary writer rejects the callbacks
+        let unsupported = Serialized::Array(vec![Serialized::Callback(Callback {
+            origin: "test".to_string(),
+            callback: Arc::new(|_| Ok(None)),
+        })]);
+        assert!(serialize_file(&file_system, "user://scene.frsb", &unsupported).is_err());
+        assert_eq!(std::fs::read(folder.join("scene.frsb")).unwrap(), content);
+
+        std::fs::remove_dir_all(&folder).ok();
+    }
+}
@@ -76,7 +76,11 @@ fn intern_serialize_yaml(serialized: &Serialized) -> Yaml {
             let field_hashmap = {
                 let mut hashmap = LinkedHashMap::<Yaml, Yaml>::new();
 
-                fields.iter().for_each(|(key, value)| {
+                // Fields are sorted to keep a stable output, that way the files are easier to diff
+                let mut fields = fields.iter().collect::<Vec<_>>();
+                fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+                fields.into_iter().for_each(|(key, value)| {
                     let key = Yaml::String(key.clone());
                     hashmap.insert(key, intern_serialize_yaml(value));
                 });
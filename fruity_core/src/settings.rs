@@ -6,7 +6,10 @@ use crate::resource::resource::Resource;
 use fruity_any::*;
 use std::collections::HashMap;
 use std::io::Read;
+use std::io::Write;
+use yaml_rust::yaml::Hash;
 use yaml_rust::Yaml;
+use yaml_rust::YamlEmitter;
 use yaml_rust::YamlLoader;
 
 /// Settings collection
@@ -143,6 +146,45 @@ pub fn build_settings_from_yaml(yaml: &Yaml) -> Option<Settings> {
     }
 }
 
+/// Write a Settings as a yaml document, it can be read back with [’read_settings’]
+///
+/// # Arguments
+/// * `writer` - The write io stream
+/// * `settings` - The settings
+///
+pub fn write_settings(writer: &mut dyn Write, settings: &Settings) -> Result<(), std::io::Error> {
+    let yaml = build_yaml_from_settings(settings);
+
+    let mut write_buf = String::new();
+    let mut emitter = YamlEmitter::new(&mut write_buf);
+    emitter.dump(&yaml).unwrap();
+
+    writer.write_all(write_buf.as_bytes())
+}
+
+/// Build a yaml document from a Settings
+pub fn build_yaml_from_settings(settings: &Settings) -> Yaml {
+    match settings {
+        Settings::I64(value) => Yaml::Integer(*value),
+        Settings::F64(value) => Yaml::Real(value.to_string()),
+        Settings::Bool(value) => Yaml::Boolean(*value),
+        Settings::String(value) => Yaml::String(value.clone()),
+        Settings::Array(array) => Yaml::Array(array.iter().map(build_yaml_from_settings).collect()),
+        Settings::Object(fields) => {
+            // Fields are sorted to keep a stable output, that way the files are easier to diff
+            let mut fields = fields.iter().collect::<Vec<_>>();
+            fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            let mut hashmap = Hash::new();
+            fields.into_iter().for_each(|(key, value)| {
+                hashmap.insert(Yaml::String(key.clone()), build_yaml_from_settings(value));
+            });
+
+            Yaml::Hash(hashmap)
+        }
+    }
+}
+
 macro_rules! impl_numeric_from_settings {
     ( $type:ident ) => {
         impl FruityTryFrom<Settings> for $type {
@@ -0,0 +1,177 @@
+use crate::vfs::file_system_backend::FileReader;
+use crate::vfs::file_system_backend::FileSystemBackend;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+
+/// The bytes an archive starts with
+pub const ARCHIVE_MAGIC: &[u8; 4] = b"FRPK";
+
+/// The version of the archive format
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// The file extension of the archives
+pub const ARCHIVE_EXTENSION: &str = "frpk";
+
+/// The position of a file into an archive
+#[derive(Debug, Clone, Copy)]
+pub struct ArchiveEntry {
+    /// The position of the first byte of the file, from the start of the archive
+    pub offset: u64,
+    /// The size of the file
+    pub size: u64,
+}
+
+/// A backend that reads the files packed into a single archive, see [’pack_folder’]
+///
+/// The archive starts with [’ARCHIVE_MAGIC’], the version and the index of the files, then the
+/// content of the files follows, all the numbers are stored as little endian
+#[derive(Debug, Clone)]
+pub struct ArchiveBackend {
+    archive_path: PathBuf,
+    entries: BTreeMap<String, ArchiveEntry>,
+}
+
+impl ArchiveBackend {
+    /// Open an archive and read its index
+    ///
+    /// # Arguments
+    /// * `archive_path` - The archive path on the disk
+    ///
+    pub fn open(archive_path: &str) -> std::io::Result<ArchiveBackend> {
+        let file = File::open(archive_path)?;
+        let archive_size = file.metadata()?.len();
+        let mut file = BufReader::new(file);
+
+        let mut magic = [0u8; 4];
+        file.read_exact(&mut magic)?;
+        if &magic != ARCHIVE_MAGIC {
+            return Err(invalid_data(&format!("{} is not an archive", archive_path)));
+        }
+
+        let version = read_u32(&mut file)?;
+        if version != ARCHIVE_VERSION {
+            return Err(invalid_data(&format!(
+                "{} has the version {}, only the version {} is supported",
+                archive_path, version, ARCHIVE_VERSION
+            )));
+        }
+
+        let entry_count = read_u32(&mut file)?;
+        let mut entries = BTreeMap::new();
+        for _ in 0..entry_count {
+            // The length is checked before the path is allocated, a corrupted archive could
+            // declare a huge one
+            let path_length = read_u32(&mut file)?;
+            if path_length as u64 > archive_size {
+                return Err(invalid_data(&format!(
+                    "{} has an invalid path",
+                    archive_path
+                )));
+            }
+
+            let mut path = vec![0u8; path_length as usize];
+            file.read_exact(&mut path)?;
+            let path = String::from_utf8(path)
+                .map_err(|_| invalid_data(&format!("{} has an invalid path", archive_path)))?;
+
+            let offset = read_u64(&mut file)?;
+            let size = read_u64(&mut file)?;
+            entries.insert(path, ArchiveEntry { offset, size });
+        }
+
+        Ok(ArchiveBackend {
+            archive_path: PathBuf::from(archive_path),
+            entries,
+        })
+    }
+
+    /// Get the paths of all the packed files
+    pub fn get_paths(&self) -> Vec<String> {
+        self.entries.keys().cloned().collect()
+    }
+
+    fn normalize(path: &str) -> &str {
+        path.trim_start_matches("./").trim_matches('/')
+    }
+}
+
+impl FileSystemBackend for ArchiveBackend {
+    fn open(&self, path: &str) -> std::io::Result<FileReader> {
+        let entry = self.entries.get(Self::normalize(path)).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{} is not packed into the archive", path),
+            )
+        })?;
+
+        let mut file = File::open(&self.archive_path)?;
+        file.seek(SeekFrom::Start(entry.offset))?;
+
+        Ok(Box::new(file.take(entry.size)))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        let path = Self::normalize(path);
+        let folder = format!("{}/", path);
+
+        path.is_empty()
+            || self.entries.contains_key(path)
+            || self.entries.keys().any(|entry| entry.starts_with(&folder))
+    }
+
+    fn is_dir(&self, path: &str) -> bool {
+        let path = Self::normalize(path);
+        let folder = format!("{}/", path);
+
+        path.is_empty() || self.entries.keys().any(|entry| entry.starts_with(&folder))
+    }
+
+    fn read_dir(&self, path: &str) -> std::io::Result<Vec<String>> {
+        let path = Self::normalize(path);
+        let folder = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", path)
+        };
+
+        let mut names = self
+            .entries
+            .keys()
+            .filter_map(|entry| entry.strip_prefix(&folder))
+            .filter_map(|entry| entry.split('/').next())
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+
+        if names.is_empty() && !self.exists(path) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{} is not packed into the archive", path),
+            ));
+        }
+
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+}
+
+fn read_u32(reader: &mut dyn Read) -> std::io::Result<u32> {
+    let mut buffer = [0u8; 4];
+    reader.read_exact(&mut buffer)?;
+    Ok(u32::from_le_bytes(buffer))
+}
+
+fn read_u64(reader: &mut dyn Read) -> std::io::Result<u64> {
+    let mut buffer = [0u8; 8];
+    reader.read_exact(&mut buffer)?;
+    Ok(u64::from_le_bytes(buffer))
+}
+
+fn invalid_data(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.to_string())
+}
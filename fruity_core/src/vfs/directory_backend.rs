@@ -0,0 +1,109 @@
+use crate::vfs::file_system_backend::FileReader;
+use crate::vfs::file_system_backend::FileSystemBackend;
+use crate::vfs::file_system_backend::FileWriter;
+use std::fs::File;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// A backend that reads and writes the files of a folder on the disk
+///
+/// The paths can't go out of the folder, the absolute paths and the paths that contain ".." are
+/// rejected
+#[derive(Debug, Clone)]
+pub struct DirectoryBackend {
+    root: PathBuf,
+    confined: bool,
+}
+
+impl DirectoryBackend {
+    /// Returns a DirectoryBackend
+    ///
+    /// # Arguments
+    /// * `root` - The folder path, the paths are resolved from the current directory if empty
+    ///
+    pub fn new(root: &str) -> DirectoryBackend {
+        DirectoryBackend {
+            root: PathBuf::from(root),
+            confined: true,
+        }
+    }
+
+    /// Returns a DirectoryBackend that accepts any path, even the ones that go out of the folder
+    /// It's used for the paths without scheme, that are resolved from the current directory
+    ///
+    /// # Arguments
+    /// * `root` - The folder path, the paths are resolved from the current directory if empty
+    ///
+    pub fn new_unconfined(root: &str) -> DirectoryBackend {
+        DirectoryBackend {
+            root: PathBuf::from(root),
+            confined: false,
+        }
+    }
+
+    fn resolve(&self, path: &str) -> std::io::Result<PathBuf> {
+        let is_inside_root = Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+
+        if self.confined && !is_inside_root {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!(
+                    "Cannot access {}, the path goes out of the mounted folder",
+                    path
+                ),
+            ));
+        }
+
+        Ok(self.root.join(path))
+    }
+}
+
+impl FileSystemBackend for DirectoryBackend {
+    fn open(&self, path: &str) -> std::io::Result<FileReader> {
+        let file = File::open(self.resolve(path)?)?;
+        Ok(Box::new(file))
+    }
+
+    fn create(&self, path: &str) -> std::io::Result<FileWriter> {
+        let path = self.resolve(path)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = File::create(path)?;
+        Ok(Box::new(file))
+    }
+
+    fn rename(&self, from: &str, to: &str) -> std::io::Result<()> {
+        std::fs::rename(self.resolve(from)?, self.resolve(to)?)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.resolve(path)
+            .map(|path| path.exists())
+            .unwrap_or(false)
+    }
+
+    fn is_dir(&self, path: &str) -> bool {
+        self.resolve(path)
+            .map(|path| path.is_dir())
+            .unwrap_or(false)
+    }
+
+    fn read_dir(&self, path: &str) -> std::io::Result<Vec<String>> {
+        let mut names = std::fs::read_dir(self.resolve(path)?)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+
+        names.sort();
+        Ok(names)
+    }
+
+    fn get_real_path(&self, path: &str) -> Option<PathBuf> {
+        self.resolve(path).ok()
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt::Debug;
+use std::io::Read;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// A file opened for reading from a file system backend
+pub type FileReader = Box<dyn Read + Send>;
+
+/// A file opened for writing from a file system backend
+pub type FileWriter = Box<dyn Write + Send>;
+
+/// A storage that can be mounted into the [’VirtualFileSystem’]
+///
+/// The paths are relative to the mount point and use "/" as separator
+pub trait FileSystemBackend: Debug + Send + Sync {
+    /// Open a file to read it
+    ///
+    /// # Arguments
+    /// * `path` - The file path
+    ///
+    fn open(&self, path: &str) -> std::io::Result<FileReader>;
+
+    /// Create or truncate a file to write it, the read only backends return an error
+    ///
+    /// # Arguments
+    /// * `path` - The file path
+    ///
+    fn create(&self, path: &str) -> std::io::Result<FileWriter> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("Cannot write {}, the file system is read only", path),
+        ))
+    }
+
+    /// Rename a file, the destination is replaced if it exists, the read only backends return
+    /// an error
+    ///
+    /// # Arguments
+    /// * `from` - The file path
+    /// * `to` - The new file path
+    ///
+    fn rename(&self, from: &str, _to: &str) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("Cannot rename {}, the file system is read only", from),
+        ))
+    }
+
+    /// Check if a file or a folder exists
+    ///
+    /// # Arguments
+    /// * `path` - The file path
+    ///
+    fn exists(&self, path: &str) -> bool;
+
+    /// Check if a path is a folder
+    ///
+    /// # Arguments
+    /// * `path` - The folder path
+    ///
+    fn is_dir(&self, path: &str) -> bool;
+
+    /// Get the names of the files and folders that are into a folder, sorted by name
+    ///
+    /// # Arguments
+    /// * `path` - The folder path
+    ///
+    fn read_dir(&self, path: &str) -> std::io::Result<Vec<String>>;
+
+    /// Get the path of a file on the disk, returns None if the backend is not stored as files on
+    /// the disk
+    ///
+    /// # Arguments
+    /// * `path` - The file path
+    ///
+    fn get_real_path(&self, _path: &str) -> Option<PathBuf> {
+        None
+    }
+}
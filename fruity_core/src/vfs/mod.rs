@@ -0,0 +1,14 @@
+/// The trait implemented by the storages that can be mounted into the virtual file system
+pub mod file_system_backend;
+
+/// A backend that reads and writes the files of a folder on the disk
+pub mod directory_backend;
+
+/// A backend that reads the files packed into a single archive
+pub mod archive_backend;
+
+/// Provides a tool to pack a folder into an archive
+pub mod packer;
+
+/// The virtual file system, where the backends are mounted on schemes like res:// or user://
+pub mod virtual_file_system;
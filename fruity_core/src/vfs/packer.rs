@@ -0,0 +1,84 @@
+use crate::vfs::archive_backend::ARCHIVE_MAGIC;
+use crate::vfs::archive_backend::ARCHIVE_VERSION;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+/// Pack all the files of a folder and its sub folders into an archive that can be mounted with
+/// an [’ArchiveBackend’], this is intended to bundle the assets for the distribution
+/// Returns the packed paths
+///
+/// # Arguments
+/// * `folder` - The folder path
+/// * `archive_path` - The path of the created archive
+///
+pub fn pack_folder(folder: &str, archive_path: &str) -> std::io::Result<Vec<String>> {
+    // The archive can be created into the packed folder, it should not pack itself
+    let archive_canonical_path = std::fs::canonicalize(archive_path).ok();
+
+    let mut files = Vec::new();
+    collect_files(Path::new(folder), "", &mut files)?;
+    files.retain(|(_, real_path, _)| {
+        archive_canonical_path.is_none()
+            || std::fs::canonicalize(real_path).ok() != archive_canonical_path
+    });
+    files.sort_by(|(path1, _, _), (path2, _, _)| path1.cmp(path2));
+
+    // The index is written first, so we compute its size to know where the files start
+    let index_size = 4
+        + 4
+        + 4
+        + files
+            .iter()
+            .map(|(path, _, _)| 4 + path.len() as u64 + 8 + 8)
+            .sum::<u64>();
+
+    let mut writer = BufWriter::new(File::create(archive_path)?);
+    writer.write_all(ARCHIVE_MAGIC)?;
+    writer.write_all(&ARCHIVE_VERSION.to_le_bytes())?;
+    writer.write_all(&(files.len() as u32).to_le_bytes())?;
+
+    let mut offset = index_size;
+    for (path, _, size) in files.iter() {
+        writer.write_all(&(path.len() as u32).to_le_bytes())?;
+        writer.write_all(path.as_bytes())?;
+        writer.write_all(&offset.to_le_bytes())?;
+        writer.write_all(&size.to_le_bytes())?;
+        offset += size;
+    }
+
+    for (_, real_path, _) in files.iter() {
+        let mut file = File::open(real_path)?;
+        std::io::copy(&mut file, &mut writer)?;
+    }
+
+    writer.flush()?;
+
+    Ok(files.into_iter().map(|(path, _, _)| path).collect())
+}
+
+fn collect_files(
+    folder: &Path,
+    relative_folder: &str,
+    files: &mut Vec<(String, std::path::PathBuf, u64)>,
+) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(folder)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let relative_path = if relative_folder.is_empty() {
+            name
+        } else {
+            format!("{}/{}", relative_folder, name)
+        };
+
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            collect_files(&entry.path(), &relative_path, files)?;
+        } else {
+            files.push((relative_path, entry.path(), metadata.len()));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,450 @@
+use crate::convert::FruityInto;
+use crate::introspect::FieldInfo;
+use crate::introspect::IntrospectObject;
+use crate::introspect::MethodCaller;
+use crate::introspect::MethodInfo;
+use crate::resource::resource::Resource;
+use crate::utils::introspect::cast_introspect_ref;
+use crate::utils::introspect::ArgumentCaster;
+use crate::vfs::directory_backend::DirectoryBackend;
+use crate::vfs::file_system_backend::FileReader;
+use crate::vfs::file_system_backend::FileSystemBackend;
+use crate::vfs::file_system_backend::FileWriter;
+use crate::RwLock;
+use fruity_any::*;
+use std::fmt::Debug;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// The scheme where the game resources are mounted
+pub const RESOURCES_SCHEME: &str = "res";
+
+/// The scheme where the user datas are mounted, like the saves or the settings
+pub const USER_SCHEME: &str = "user";
+
+/// The scheme where the imported artifacts of the resources are cached
+pub const CACHE_SCHEME: &str = "cache";
+
+#[derive(Debug)]
+struct Mount {
+    scheme: String,
+    backend: Arc<dyn FileSystemBackend>,
+}
+
+/// The virtual file system, the backends are mounted on schemes and the files are accessed with
+/// paths like "res://textures/character.png"
+///
+/// Many backends can be mounted on the same scheme, the last mounted one is looked first, so a
+/// folder can override the files of an archive
+///
+/// The paths without scheme are resolved from the current directory
+///
+#[derive(Debug, Clone, FruityAny)]
+pub struct VirtualFileSystem {
+    mounts: Arc<RwLock<Vec<Mount>>>,
+    default_backend: Arc<DirectoryBackend>,
+}
+
+impl Default for VirtualFileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VirtualFileSystem {
+    /// Returns a VirtualFileSystem without any mount
+    pub fn new() -> VirtualFileSystem {
+        VirtualFileSystem {
+            mounts: Arc::new(RwLock::new(Vec::new())),
+            default_backend: Arc::new(DirectoryBackend::new_unconfined("")),
+        }
+    }
+
+    /// Mount a backend on a scheme
+    ///
+    /// # Arguments
+    /// * `scheme` - The scheme, for example "res" to access the files with "res://"
+    /// * `backend` - The backend
+    ///
+    pub fn mount(&self, scheme: &str, backend: Box<dyn FileSystemBackend>) {
+        let mut mounts = self.mounts.write();
+        mounts.push(Mount {
+            scheme: scheme.to_string(),
+            backend: Arc::from(backend),
+        });
+    }
+
+    /// Unmount all the backends of a scheme
+    ///
+    /// # Arguments
+    /// * `scheme` - The scheme
+    ///
+    pub fn unmount(&self, scheme: &str) {
+        let mut mounts = self.mounts.write();
+        mounts.retain(|mount| mount.scheme != scheme);
+    }
+
+    /// Open a file to read it
+    ///
+    /// # Arguments
+    /// * `path` - The file path
+    ///
+    pub fn open(&self, path: &str) -> std::io::Result<FileReader> {
+        self.find(path, |backend, path| backend.open(path))
+    }
+
+    /// Create or truncate a file to write it
+    ///
+    /// # Arguments
+    /// * `path` - The file path
+    ///
+    pub fn create(&self, path: &str) -> std::io::Result<FileWriter> {
+        self.find(path, |backend, path| backend.create(path))
+    }
+
+    /// Rename a file, the destination is replaced if it exists
+    /// Both paths should be on the same scheme
+    ///
+    /// # Arguments
+    /// * `from` - The file path
+    /// * `to` - The new file path
+    ///
+    pub fn rename(&self, from: &str, to: &str) -> std::io::Result<()> {
+        let (from_scheme, _) = split_scheme(from);
+        let (to_scheme, relative_to) = split_scheme(to);
+        if from_scheme != to_scheme {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Cannot rename {} to {}, the schemes are different",
+                    from, to
+                ),
+            ));
+        }
+
+        self.find(from, |backend, path| backend.rename(path, relative_to))
+    }
+
+    /// Check if a file or a folder exists
+    ///
+    /// # Arguments
+    /// * `path` - The file path
+    ///
+    pub fn exists(&self, path: &str) -> bool {
+        self.find(path, |backend, path| {
+            if backend.exists(path) {
+                Ok(())
+            } else {
+                Err(not_found(path))
+            }
+        })
+        .is_ok()
+    }
+
+    /// Check if a path is a folder into one of the backends mounted on its scheme
+    ///
+    /// # Arguments
+    /// * `path` - The folder path
+    ///
+    pub fn is_dir(&self, path: &str) -> bool {
+        let (_, relative_path) = split_scheme(path);
+
+        match self.get_backends(path) {
+            Ok(backends) => backends.iter().any(|backend| backend.is_dir(relative_path)),
+            Err(_) => false,
+        }
+    }
+
+    /// Get the names of the files and folders that are into a folder, the names of all the
+    /// backends mounted on the scheme are merged
+    ///
+    /// # Arguments
+    /// * `path` - The folder path
+    ///
+    pub fn read_dir(&self, path: &str) -> std::io::Result<Vec<String>> {
+        let (_, relative_path) = split_scheme(path);
+        let backends = self.get_backends(path)?;
+
+        let results = backends
+            .iter()
+            .map(|backend| backend.read_dir(relative_path))
+            .collect::<Vec<_>>();
+
+        if results.iter().all(|result| result.is_err()) {
+            return Err(not_found(path));
+        }
+
+        let mut names = results
+            .into_iter()
+            .filter_map(|result| result.ok())
+            .flatten()
+            .collect::<Vec<_>>();
+
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    /// Read a whole file as a string
+    ///
+    /// # Arguments
+    /// * `path` - The file path
+    ///
+    pub fn read_to_string(&self, path: &str) -> std::io::Result<String> {
+        let mut reader = self.open(path)?;
+        let mut buffer = String::new();
+        reader.read_to_string(&mut buffer)?;
+
+        Ok(buffer)
+    }
+
+    /// Get the path of a file on the disk, returns None if the file is not stored on the disk,
+    /// for example if it is packed into an archive
+    ///
+    /// # Arguments
+    /// * `path` - The file path
+    ///
+    pub fn get_real_path(&self, path: &str) -> Option<PathBuf> {
+        let (_, relative_path) = split_scheme(path);
+        let backends = self.get_backends(path).ok()?;
+
+        // A file that exists is looked first, the first backend is used for a new file
+        backends
+            .iter()
+            .filter(|backend| backend.exists(relative_path))
+            .chain(backends.iter())
+            .find_map(|backend| backend.get_real_path(relative_path))
+    }
+
+    fn get_backends(&self, path: &str) -> std::io::Result<Vec<Arc<dyn FileSystemBackend>>> {
+        match split_scheme(path) {
+            (Some(scheme), _) => {
+                let mounts = self.mounts.read();
+                let backends = mounts
+                    .iter()
+                    .rev()
+                    .filter(|mount| mount.scheme == scheme)
+                    .map(|mount| mount.backend.clone())
+                    .collect::<Vec<_>>();
+
+                if backends.is_empty() {
+                    Err(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("Nothing is mounted on {}://", scheme),
+                    ))
+                } else {
+                    Ok(backends)
+                }
+            }
+            (None, _) => Ok(vec![self.default_backend.clone()]),
+        }
+    }
+
+    fn find<T>(
+        &self,
+        path: &str,
+        callback: impl Fn(&dyn FileSystemBackend, &str) -> std::io::Result<T>,
+    ) -> std::io::Result<T> {
+        let (_, relative_path) = split_scheme(path);
+        let backends = self.get_backends(path)?;
+
+        let mut first_error = None;
+        for backend in backends.iter() {
+            match callback(backend.as_ref(), relative_path) {
+                Ok(result) => return Ok(result),
+                Err(err) => {
+                    if first_error.is_none() {
+                        first_error = Some(err);
+                    }
+                }
+            }
+        }
+
+        Err(first_error.unwrap_or_else(|| not_found(path)))
+    }
+}
+
+/// Split a path into its scheme and the path relative to the mount point
+///
+/// # Arguments
+/// * `path` - The path, for example "res://textures/character.png"
+///
+pub fn split_scheme(path: &str) -> (Option<&str>, &str) {
+    match path.find("://") {
+        Some(index) => (Some(&path[..index]), &path[index + 3..]),
+        None => (None, path),
+    }
+}
+
+fn not_found(path: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("{} is not found", path),
+    )
+}
+
+impl IntrospectObject for VirtualFileSystem {
+    fn get_class_name(&self) -> String {
+        "VirtualFileSystem".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![
+            MethodInfo {
+                name: "exists".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<VirtualFileSystem>(this);
+
+                    let mut caster = ArgumentCaster::new("exists", args);
+                    let arg1 = caster.cast_next::<String>()?;
+                    let result = this.exists(&arg1);
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "read_to_string".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<VirtualFileSystem>(this);
+
+                    let mut caster = ArgumentCaster::new("read_to_string", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    match this.read_to_string(&arg1) {
+                        Ok(result) => Ok(Some(result.fruity_into())),
+                        Err(err) => {
+                            log::error!("{}", err.to_string());
+                            Ok(None)
+                        }
+                    }
+                })),
+            },
+            MethodInfo {
+                name: "read_dir".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<VirtualFileSystem>(this);
+
+                    let mut caster = ArgumentCaster::new("read_dir", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    match this.read_dir(&arg1) {
+                        Ok(result) => Ok(Some(result.fruity_into())),
+                        Err(err) => {
+                            log::error!("{}", err.to_string());
+                            Ok(None)
+                        }
+                    }
+                })),
+            },
+        ]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
+
+impl Resource for VirtualFileSystem {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vfs::archive_backend::ArchiveBackend;
+    use crate::vfs::archive_backend::ARCHIVE_MAGIC;
+    use crate::vfs::archive_backend::ARCHIVE_VERSION;
+    use crate::vfs::packer::pack_folder;
+
+    #[test]
+    fn split_scheme_test() {
+        assert_eq!(
+            split_scheme("res://textures/character.png"),
+            (Some("res"), "textures/character.png")
+        );
+        assert_eq!(
+            split_scheme("./assets/scene.frsc"),
+            (None, "./assets/scene.frsc")
+        );
+    }
+
+    #[test]
+    fn packed_folder_is_read_through_the_mount() {
+        let archive_path = std::env::temp_dir().join("fruity_core_vfs_test.frpk");
+        let archive_path = archive_path.to_str().unwrap();
+
+        let packed = pack_folder("src/vfs", archive_path).unwrap();
+        assert!(packed.contains(&"packer.rs".to_string()));
+
+        let virtual_file_system = VirtualFileSystem::new();
+        virtual_file_system.mount(
+            RESOURCES_SCHEME,
+            Box::new(ArchiveBackend::open(archive_path).unwrap()),
+        );
+
+        assert_eq!(
+            virtual_file_system
+                .read_to_string("res://packer.rs")
+                .unwrap(),
+            std::fs::read_to_string("src/vfs/packer.rs").unwrap()
+        );
+        assert!(virtual_file_system.exists("res://mod.rs"));
+        assert!(!virtual_file_system.exists("res://missing.rs"));
+        assert!(virtual_file_system.get_real_path("res://mod.rs").is_none());
+        assert!(virtual_file_system.is_dir("res://"));
+        assert!(!virtual_file_system.is_dir("res://mod.rs"));
+        assert!(virtual_file_system.open("user://save.yaml").is_err());
+
+        // A folder mounted after the archive is looked first
+        virtual_file_system.mount(RESOURCES_SCHEME, Box::new(DirectoryBackend::new("src")));
+        assert!(virtual_file_system.get_real_path("res://lib.rs").is_some());
+        assert!(virtual_file_system.exists("res://packer.rs"));
+        assert!(virtual_file_system
+            .read_dir("res://")
+            .unwrap()
+            .contains(&"vfs".to_string()));
+        assert!(virtual_file_system.is_dir("res://vfs"));
+        assert!(!virtual_file_system.is_dir("res://lib.rs"));
+
+        std::fs::remove_file(archive_path).ok();
+    }
+
+    #[test]
+    fn paths_cannot_go_out_of_a_mounted_folder() {
+        let virtual_file_system = VirtualFileSystem::new();
+        virtual_file_system.mount(RESOURCES_SCHEME, Box::new(DirectoryBackend::new("src/vfs")));
+
+        assert!(virtual_file_system.exists("res://packer.rs"));
+        assert!(virtual_file_system.exists("res://./packer.rs"));
+        assert!(!virtual_file_system.exists("res://../lib.rs"));
+        assert!(virtual_file_system.open("res://../lib.rs").is_err());
+        assert!(virtual_file_system.open("res://vfs/../../lib.rs").is_err());
+        assert!(virtual_file_system.create("res://../escaped.rs").is_err());
+        assert!(virtual_file_system
+            .read_to_string("res:///etc/passwd")
+            .is_err());
+        assert!(virtual_file_system
+            .get_real_path("res://../lib.rs")
+            .is_none());
+
+        // The paths without scheme are resolved from the current directory
+        assert!(virtual_file_system.exists("src/../Cargo.toml"));
+    }
+
+    #[test]
+    fn corrupted_archive_is_rejected() {
+        let archive_path = std::env::temp_dir().join("fruity_core_corrupted_archive_test.frpk");
+        let archive_path = archive_path.to_str().unwrap();
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(ARCHIVE_MAGIC);
+        bytes.extend_from_slice(&ARCHIVE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(archive_path, bytes).unwrap();
+
+        let error = ArchiveBackend::open(archive_path).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+        std::fs::remove_file(archive_path).ok();
+    }
+}
@@ -30,8 +30,9 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         MODULE_NAME,
         Inject4::new(draw_gizmos_2d),
         SystemParams {
-            pool_index: 98,
+            stage: "draw".to_string(),
             ignore_pause: true,
+            ..Default::default()
         },
     );
 
@@ -40,8 +41,9 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         MODULE_NAME,
         Inject3::new(display_grid),
         SystemParams {
-            pool_index: 98,
+            stage: "draw".to_string(),
             ignore_pause: true,
+            ..Default::default()
         },
     );
 
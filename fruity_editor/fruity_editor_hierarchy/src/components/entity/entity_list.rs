@@ -13,39 +13,22 @@ use fruity_editor::ui::elements::UIWidget;
 use fruity_editor::ui::hooks::use_read_service;
 use fruity_editor::ui::hooks::use_service;
 use fruity_editor::ui::hooks::use_write_service;
-use fruity_hierarchy::components::parent::Parent;
 use std::sync::Arc;
 
 pub fn entity_list_component(ctx: &mut UIContext) -> UIElement {
     let entity_service = use_service::<EntityService>(ctx);
     let entity_service_reader = entity_service.read();
 
-    let all_entities = entity_service_reader
+    let root_entities = entity_service_reader
         .iter_all_entities()
-        .collect::<Vec<_>>();
-
-    let root_entities = all_entities
-        .iter()
-        .filter(|entity| {
-            if let Some(parent) = entity.read().read_single_component::<Parent>() {
-                if let Some(_) = *parent.parent_id {
-                    false
-                } else {
-                    true
-                }
-            } else {
-                true
-            }
-        })
+        .filter(|entity| entity.get_parent().is_none())
         .collect::<Vec<_>>();
 
     Scroll {
         child: Column {
             children: root_entities
                 .iter()
-                .map(|child| {
-                    draw_entity_line(ctx, (*child).clone(), &all_entities, entity_service.clone())
-                })
+                .map(|child| draw_entity_line(ctx, child.clone(), entity_service.clone()))
                 .collect::<Vec<_>>(),
             ..Default::default()
         }
@@ -58,29 +41,14 @@ pub fn entity_list_component(ctx: &mut UIContext) -> UIElement {
 pub fn draw_entity_line(
     ctx: &mut UIContext,
     entity: EntityReference,
-    all_entities: &Vec<EntityReference>,
     entity_service: ResourceReference<EntityService>,
 ) -> UIElement {
     let entity_2 = entity.clone();
     let entity_3 = entity.clone();
+    let children = entity.get_children();
     let entity_reader = entity.read();
     let entity_id = entity_reader.get_entity_id();
 
-    let children = all_entities
-        .iter()
-        .filter(|entity| {
-            if let Some(parent) = entity.read().read_single_component::<Parent>() {
-                if let Some(parent_id) = *parent.parent_id {
-                    parent_id == entity_id
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        })
-        .collect::<Vec<_>>();
-
     if children.len() > 0 {
         let entity_service_2 = entity_service.clone();
         Collapsible {
@@ -93,14 +61,7 @@ pub fn draw_entity_line(
             child: Column {
                 children: children
                     .iter()
-                    .map(|child| {
-                        draw_entity_line(
-                            ctx,
-                            (*child).clone(),
-                            all_entities,
-                            entity_service_2.clone(),
-                        )
-                    })
+                    .map(|child| draw_entity_line(ctx, child.clone(), entity_service_2.clone()))
                     .collect::<Vec<_>>(),
                 ..Default::default()
             }
@@ -10,7 +10,17 @@ pub fn get_thumbnail_js(
     resource_container.get::<dyn TextureResource>("Editor/Icons/js")
 }
 
-pub fn on_selected_js(_ctx: &UIContext, file_path: &str) {
+pub fn on_selected_js(ctx: &UIContext, file_path: &str) {
+    // The script is edited on the disk, a packed script cannot be edited
+    let resource_container = ctx.resource_container();
+    let real_path = match resource_container
+        .get_file_system()
+        .get_real_path(file_path)
+    {
+        Some(real_path) => real_path,
+        None => return,
+    };
+
     // TODO: Display an error popup if failed
-    edit::edit_file(file_path).unwrap();
+    edit::edit_file(real_path).unwrap();
 }
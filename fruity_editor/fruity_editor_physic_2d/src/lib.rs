@@ -35,8 +35,9 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         MODULE_NAME,
         Inject6::new(draw_circle_collider_2d_gizmos),
         SystemParams {
-            pool_index: 98,
+            stage: "draw".to_string(),
             ignore_pause: true,
+            ..Default::default()
         },
     );
 
@@ -45,8 +46,9 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         MODULE_NAME,
         Inject5::new(draw_rectangle_collider_2d_gizmos),
         SystemParams {
-            pool_index: 98,
+            stage: "draw".to_string(),
             ignore_pause: true,
+            ..Default::default()
         },
     );
 
@@ -13,16 +13,16 @@ use crate::Arc;
 use crate::FileExplorerService;
 use fruity_any::FruityAny;
 use fruity_core::utils::string::get_file_type_from_path;
-use std::path::PathBuf;
 
-pub fn file_item_component(ctx: &mut UIContext, path: PathBuf) -> UIElement {
+pub fn file_item_component(ctx: &mut UIContext, path: String, is_dir: bool) -> UIElement {
     let resource_container = ctx.resource_container();
     let file_explorer_service = use_service::<FileExplorerService>(ctx);
     let file_explorer_service_reader = file_explorer_service.read();
 
     let file_explorer_service_2 = file_explorer_service.clone();
-    let path_string = path.to_str().unwrap().to_string();
-    if !path.is_dir() {
+    let path_string = path.clone();
+    let file_name = path.rsplit('/').next().unwrap_or(&path).to_string();
+    if !is_dir {
         if let Some(extension) = get_file_type_from_path(&path_string) {
             if !resource_container.contains(&path_string) {
                 resource_container
@@ -49,7 +49,7 @@ pub fn file_item_component(ctx: &mut UIContext, path: PathBuf) -> UIElement {
                     }
                     .elem(),
                     Text {
-                        text: path.file_name().unwrap().to_string_lossy().to_string(),
+                        text: file_name,
                         ..Default::default()
                     }
                     .elem(),
@@ -73,7 +73,7 @@ pub fn file_item_component(ctx: &mut UIContext, path: PathBuf) -> UIElement {
                     height: 64.0,
                     on_click: Arc::new(move |ctx| {
                         let mut file_explorer_state = use_write_service::<FileExplorerState>(ctx);
-                        file_explorer_state.open_dir(&path_2.to_path_buf().to_string_lossy());
+                        file_explorer_state.open_dir(&path_2);
                     }),
                     drag_item: None,
                     accept_drag: None,
@@ -81,7 +81,7 @@ pub fn file_item_component(ctx: &mut UIContext, path: PathBuf) -> UIElement {
                 }
                 .elem(),
                 Text {
-                    text: path.file_name().unwrap().to_string_lossy().to_string(),
+                    text: file_name,
                     ..Default::default()
                 }
                 .elem(),
@@ -13,16 +13,25 @@ use fruity_graphic::resources::texture_resource::TextureResource;
 pub mod file_item;
 
 pub fn file_explorer_component(ctx: &mut UIContext) -> UIElement {
-    let file_explorer_state = use_read_service::<FileExplorerState>(ctx);
-    let files = file_explorer_state.get_files();
+    let files = {
+        let file_explorer_state = use_read_service::<FileExplorerState>(ctx);
+        file_explorer_state
+            .get_files()
+            .into_iter()
+            .map(|path| {
+                let is_dir = file_explorer_state.is_dir(&path);
+                (path, is_dir)
+            })
+            .collect::<Vec<_>>()
+    };
 
     Scroll {
         child: Row {
             children: files
                 .into_iter()
-                .map(|file| RowItem {
+                .map(|(path, is_dir)| RowItem {
                     size: UISize::Units(64.0),
-                    child: file_item_component(ctx, file),
+                    child: file_item_component(ctx, path, is_dir),
                 })
                 .collect::<Vec<_>>(),
             ..Default::default()
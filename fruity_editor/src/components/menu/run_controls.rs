@@ -32,6 +32,16 @@ pub fn run_controls_component(ctx: &mut UIContext) -> Vec<UIElement> {
             }
             .elem()
         },
+        Button {
+            label: "⏭".to_string(),
+            on_click: Arc::new(move |ctx| {
+                let mut scene_state = use_write_service::<SceneState>(&ctx);
+                scene_state.step();
+            }),
+            enabled: !scene_state.is_running(),
+            ..Default::default()
+        }
+        .elem(),
         Button {
             label: "◼".to_string(),
             on_click: Arc::new(move |ctx| {
@@ -3,5 +3,6 @@ pub mod file_explorer;
 pub mod inspector;
 pub mod menu;
 pub mod panes;
+pub mod problems;
 pub mod root;
 pub mod scene;
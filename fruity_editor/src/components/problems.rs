@@ -0,0 +1,40 @@
+use crate::ui::context::UIContext;
+use crate::ui::elements::display::Text;
+use crate::ui::elements::layout::Column;
+use crate::ui::elements::layout::Scroll;
+use crate::ui::elements::UIElement;
+use crate::ui::elements::UIWidget;
+use crate::ui::hooks::use_read_service;
+use fruity_core::resource::load_diagnostic_service::LoadDiagnosticService;
+
+pub fn problems_component(ctx: &mut UIContext) -> UIElement {
+    let load_diagnostic_service = use_read_service::<LoadDiagnosticService>(ctx);
+    let diagnostics = load_diagnostic_service.get_diagnostics();
+
+    let children = if diagnostics.is_empty() {
+        vec![Text {
+            text: "No problem".to_string(),
+        }
+        .elem()]
+    } else {
+        diagnostics
+            .iter()
+            .map(|diagnostic| {
+                Text {
+                    text: diagnostic.to_string(),
+                }
+                .elem()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    Scroll {
+        child: Column {
+            children,
+            ..Default::default()
+        }
+        .elem(),
+        ..Default::default()
+    }
+    .elem()
+}
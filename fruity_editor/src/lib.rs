@@ -1,5 +1,6 @@
 use crate::components::file_explorer::file_explorer_component;
 use crate::components::inspector::inspector_component;
+use crate::components::problems::problems_component;
 use crate::components::scene::scene_component;
 use crate::editor_component_service::EditorComponentService;
 use crate::editor_menu_service::EditorMenuService;
@@ -94,7 +95,7 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
     );
     resource_container.add::<FileExplorerState>(
         "file_explorer_state",
-        Box::new(FileExplorerState::default()),
+        Box::new(FileExplorerState::new(resource_container.clone())),
     );
 
     let system_service = resource_container.require::<SystemService>();
@@ -106,7 +107,7 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         Inject1::new(pause_at_startup),
         Default::default(),
     );
-    system_service.disable_pool(&99);
+    system_service.disable_stage("camera");
 
     let inspector_service = resource_container.require::<InspectorService>();
     let mut inspector_service = inspector_service.write();
@@ -174,6 +175,7 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
     editor_panels_service.add_panel("Inspector", UIPaneSide::Right, inspector_component);
     editor_panels_service.add_panel("Profiling", UIPaneSide::Right, |_ctx| Profiling {}.elem());
     editor_panels_service.add_panel("File explorer", UIPaneSide::Bottom, file_explorer_component);
+    editor_panels_service.add_panel("Problems", UIPaneSide::Bottom, problems_component);
 
     load_default_resources(resource_container);
 }
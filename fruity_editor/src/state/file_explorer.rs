@@ -3,23 +3,23 @@ use fruity_core::introspect::FieldInfo;
 use fruity_core::introspect::IntrospectObject;
 use fruity_core::introspect::MethodInfo;
 use fruity_core::resource::resource::Resource;
-use std::fs;
-use std::path::PathBuf;
+use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::vfs::virtual_file_system::VirtualFileSystem;
 
 #[derive(Debug, FruityAny)]
 pub struct FileExplorerState {
+    file_system: VirtualFileSystem,
     current_dir: String,
 }
 
-impl Default for FileExplorerState {
-    fn default() -> Self {
+impl FileExplorerState {
+    pub fn new(resource_container: ResourceContainer) -> Self {
         FileExplorerState {
-            current_dir: "./assets".to_string(),
+            file_system: resource_container.get_file_system(),
+            current_dir: "res://".to_string(),
         }
     }
-}
 
-impl FileExplorerState {
     pub fn get_current_dir(&self) -> String {
         self.current_dir.clone()
     }
@@ -28,15 +28,25 @@ impl FileExplorerState {
         self.current_dir = path.to_string();
     }
 
-    pub fn get_files(&self) -> Vec<PathBuf> {
-        match fs::read_dir(&self.current_dir) {
-            Ok(dir) => dir
-                .filter_map(|file| file.ok())
-                .map(|file| file.path())
+    pub fn get_files(&self) -> Vec<String> {
+        match self.file_system.read_dir(&self.current_dir) {
+            Ok(names) => names
+                .into_iter()
+                .map(|name| {
+                    if self.current_dir.ends_with('/') {
+                        format!("{}{}", self.current_dir, name)
+                    } else {
+                        format!("{}/{}", self.current_dir, name)
+                    }
+                })
                 .collect::<Vec<_>>(),
             Err(_) => Vec::new(),
         }
     }
+
+    pub fn is_dir(&self, path: &str) -> bool {
+        self.file_system.is_dir(path)
+    }
 }
 
 // TODO
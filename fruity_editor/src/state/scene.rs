@@ -7,11 +7,9 @@ use fruity_core::introspect::MethodInfo;
 use fruity_core::resource::resource::Resource;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::resource::resource_reference::ResourceReference;
-use fruity_core::serialize::yaml::serialize_yaml;
 use fruity_ecs::entity::entity_service::EntityService;
 use fruity_ecs::entity::entity_service::EntityServiceSnapshot;
 use fruity_ecs::system::system_service::SystemService;
-use std::fs::File;
 
 #[derive(Debug, FruityAny)]
 pub struct SceneState {
@@ -48,6 +46,19 @@ impl SceneState {
         system_service.set_paused(false);
     }
 
+    pub fn step(&mut self) {
+        if self.snapshot.is_none() {
+            let mut inspector_state = self.inspector_state.write();
+
+            let entity_service = self.entity_service.read();
+            self.snapshot = Some(entity_service.snapshot());
+            inspector_state.unselect();
+        }
+
+        let system_service = self.system_service.read();
+        system_service.step_frame();
+    }
+
     pub fn pause(&mut self) {
         let system_service = self.system_service.read();
         system_service.set_paused(true);
@@ -82,7 +93,13 @@ impl SceneState {
             let entity_service = self.entity_service.read();
             let system_service = self.system_service.read();
 
-            entity_service.restore_from_file(&filepath);
+            // The components that cannot be read are reported, they are not silently dropped
+            entity_service
+                .restore_from_file(&filepath)
+                .into_iter()
+                .for_each(|diagnostic| {
+                    log::warn!("{}: {}", &filepath, diagnostic);
+                });
             system_service.set_paused(true);
             inspector_state.unselect();
             self.current_filepath = Some(filepath);
@@ -91,14 +108,11 @@ impl SceneState {
 
     pub fn save(&mut self) {
         if let Some(filepath) = &self.current_filepath {
-            if let Ok(mut writer) = File::create(&filepath) {
-                let entity_service = self.entity_service.read();
-                let snapshot = entity_service.snapshot();
-
-                if let Ok(_) = serialize_yaml(&mut writer, &snapshot.0) {
-                    self.current_filepath = Some(filepath.clone());
-                } else {
-                }
+            let entity_service = self.entity_service.read();
+
+            if let Ok(_) = entity_service.save_to_file(&filepath) {
+                self.current_filepath = Some(filepath.clone());
+            } else {
             }
         } else {
             self.save_as()
@@ -117,15 +131,12 @@ impl SceneState {
         let dialog_service = self.resource_container.require::<dyn DialogService>();
         let dialog_service = dialog_service.read();
 
-        if let Some(filepath) = dialog_service.save("scene.frsc", &["frsc"]) {
-            if let Ok(mut writer) = File::create(&filepath) {
-                let entity_service = self.entity_service.read();
-                let snapshot = entity_service.snapshot();
+        if let Some(filepath) = dialog_service.save("scene.frsc", &["frsc", "frsb"]) {
+            let entity_service = self.entity_service.read();
 
-                if let Ok(_) = serialize_yaml(&mut writer, &snapshot.0) {
-                    self.current_filepath = Some(filepath);
-                } else {
-                }
+            if let Ok(_) = entity_service.save_to_file(&filepath) {
+                self.current_filepath = Some(filepath);
+            } else {
             }
         }
     }
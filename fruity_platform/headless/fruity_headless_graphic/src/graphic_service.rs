@@ -0,0 +1,259 @@
+use crate::resources::material_resource::HeadlessMaterialResource;
+use crate::resources::mesh_resource::HeadlessMeshResource;
+use crate::resources::shader_resource::HeadlessShaderResource;
+use crate::resources::texture_resource::HeadlessTextureResource;
+use fruity_any::*;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::resource::resource::Resource;
+use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::resource::resource_reference::ResourceReference;
+use fruity_core::signal::Signal;
+use fruity_core::RwLock;
+use fruity_graphic::graphic_service::GraphicService;
+use fruity_graphic::graphic_service::MaterialParam;
+use fruity_graphic::math::matrix4::Matrix4;
+use fruity_graphic::math::vector2d::Vector2d;
+use fruity_graphic::math::Color;
+use fruity_graphic::resources::material_resource::MaterialResource;
+use fruity_graphic::resources::material_resource::MaterialResourceSettings;
+use fruity_graphic::resources::mesh_resource::MeshResource;
+use fruity_graphic::resources::mesh_resource::MeshResourceSettings;
+use fruity_graphic::resources::shader_resource::ShaderResource;
+use fruity_graphic::resources::shader_resource::ShaderResourceSettings;
+use fruity_graphic::resources::texture_resource::TextureData;
+use fruity_graphic::resources::texture_resource::TextureResource;
+use fruity_graphic::resources::texture_resource::TextureResourceSettings;
+use fruity_windows::window_service::WindowService;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+/// A graphic service that draws nothing
+///
+/// Resources are created without any GPU allocation and draw calls are ignored, the camera
+/// and viewport are still tracked so that the coordinate conversions keep working
+#[derive(Debug, FruityAny)]
+pub struct HeadlessGraphicService {
+    window_service: ResourceReference<dyn WindowService>,
+    camera_transform: RwLock<Matrix4>,
+    viewport_offset: RwLock<(u32, u32)>,
+    viewport_size: RwLock<(u32, u32)>,
+    pub on_before_draw_end: Signal<()>,
+    pub on_after_draw_end: Signal<()>,
+}
+
+impl HeadlessGraphicService {
+    pub fn new(resource_container: ResourceContainer) -> HeadlessGraphicService {
+        let window_service = resource_container.require::<dyn WindowService>();
+
+        let viewport_size = {
+            let window_service = window_service.read();
+
+            // Keep the draw end signals, some systems are relying on them
+            let resource_container_2 = resource_container.clone();
+            window_service.on_end_update().add_observer(move |_| {
+                let graphic_service = resource_container_2.require::<dyn GraphicService>();
+                let graphic_service = graphic_service.read();
+
+                graphic_service.on_before_draw_end().notify(());
+                graphic_service.on_after_draw_end().notify(());
+            });
+
+            window_service.get_windows_size()
+        };
+
+        HeadlessGraphicService {
+            window_service,
+            camera_transform: RwLock::new(Matrix4::identity()),
+            viewport_offset: Default::default(),
+            viewport_size: RwLock::new(viewport_size),
+            on_before_draw_end: Signal::new(),
+            on_after_draw_end: Signal::new(),
+        }
+    }
+}
+
+impl GraphicService for HeadlessGraphicService {
+    fn start_draw(&mut self) {}
+
+    fn end_draw(&mut self) {}
+
+    fn render_scene(
+        &self,
+        view_proj: Matrix4,
+        _background_color: Color,
+        _target: Option<ResourceReference<dyn TextureResource>>,
+    ) {
+        let mut camera_transform = self.camera_transform.write();
+        *camera_transform = view_proj;
+    }
+
+    fn get_camera_transform(&self) -> Matrix4 {
+        let camera_transform = self.camera_transform.read();
+        camera_transform.clone()
+    }
+
+    fn resize(&mut self, _width: u32, _height: u32) {}
+
+    fn on_before_draw_end(&self) -> &Signal<()> {
+        &self.on_before_draw_end
+    }
+
+    fn on_after_draw_end(&self) -> &Signal<()> {
+        &self.on_after_draw_end
+    }
+
+    fn draw_mesh(
+        &self,
+        _identifier: u64,
+        _mesh: ResourceReference<dyn MeshResource>,
+        _material: ResourceReference<dyn MaterialResource>,
+        _params: HashMap<String, MaterialParam>,
+        _z_index: i32,
+    ) {
+    }
+
+    fn create_mesh_resource(
+        &self,
+        _identifier: &str,
+        params: MeshResourceSettings,
+    ) -> Result<Box<dyn MeshResource>, String> {
+        Ok(Box::new(HeadlessMeshResource::new(params)))
+    }
+
+    fn create_shader_resource(
+        &self,
+        _identifier: &str,
+        _contents: String,
+        params: ShaderResourceSettings,
+    ) -> Result<Box<dyn ShaderResource>, String> {
+        Ok(Box::new(HeadlessShaderResource::new(params)))
+    }
+
+    fn decode_texture(&self, contents: &[u8]) -> Result<TextureData, String> {
+        // Only the size is read, the pixels are left blank since nothing is drawn
+        let (width, height) = read_png_size(contents).unwrap_or_default();
+
+        Ok(TextureData::new(
+            width,
+            height,
+            vec![0; (width * height * 4) as usize],
+        ))
+    }
+
+    fn create_texture_resource(
+        &self,
+        _identifier: &str,
+        data: TextureData,
+        _params: TextureResourceSettings,
+    ) -> Result<Box<dyn TextureResource>, String> {
+        Ok(Box::new(HeadlessTextureResource::new((
+            data.width,
+            data.height,
+        ))))
+    }
+
+    fn create_material_resource(
+        &self,
+        _identifier: &str,
+        params: MaterialResourceSettings,
+    ) -> Result<Box<dyn MaterialResource>, String> {
+        Ok(Box::new(HeadlessMaterialResource::new(params)))
+    }
+
+    /// Convert a position in 2d world to a position in viewport
+    fn world_position_to_viewport_position(&self, pos: Vector2d) -> (u32, u32) {
+        let viewport_offset = self.get_viewport_offset();
+        let viewport_size = self.get_viewport_size();
+        let camera_transform = self.get_camera_transform().clone();
+
+        let viewport_pos = camera_transform * pos;
+
+        (
+            ((viewport_pos.x + 1.0) / 2.0 * viewport_size.0 as f32 + viewport_offset.0 as f32)
+                as u32,
+            ((viewport_pos.y - 1.0) / -2.0 * viewport_size.1 as f32 + viewport_offset.1 as f32)
+                as u32,
+        )
+    }
+
+    /// Convert a position in the viewport to a position in 2d world
+    fn viewport_position_to_world_position(&self, x: u32, y: u32) -> Vector2d {
+        let viewport_offset = self.get_viewport_offset();
+        let viewport_size = self.get_viewport_size();
+        let camera_transform = self.get_camera_transform().clone();
+
+        let cursor_pos = Vector2d::new(
+            ((x as f32 - viewport_offset.0 as f32) / viewport_size.0 as f32) * 2.0 - 1.0,
+            ((y as f32 - viewport_offset.1 as f32) / viewport_size.1 as f32) * -2.0 + 1.0,
+        );
+
+        camera_transform.invert() * cursor_pos
+    }
+
+    /// Get the cursor position in the viewport, take in care the camera transform
+    fn get_cursor_position(&self) -> Vector2d {
+        let cursor_position = {
+            let window_service = self.window_service.read();
+            window_service.get_cursor_position()
+        };
+
+        self.viewport_position_to_world_position(cursor_position.0, cursor_position.1)
+    }
+
+    /// There is no cursor in a headless application
+    fn is_cursor_hover_scene(&self) -> bool {
+        false
+    }
+
+    fn get_viewport_offset(&self) -> (u32, u32) {
+        let viewport_offset = self.viewport_offset.read();
+        viewport_offset.clone()
+    }
+
+    fn set_viewport_offset(&self, x: u32, y: u32) {
+        let mut viewport_offset = self.viewport_offset.write();
+        *viewport_offset = (x, y);
+    }
+
+    fn get_viewport_size(&self) -> (u32, u32) {
+        let viewport_size = self.viewport_size.read();
+        viewport_size.clone()
+    }
+
+    fn set_viewport_size(&self, x: u32, y: u32) {
+        let mut viewport_size = self.viewport_size.write();
+        *viewport_size = (x, y);
+    }
+}
+
+impl IntrospectObject for HeadlessGraphicService {
+    fn get_class_name(&self) -> String {
+        "GraphicService".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
+
+impl Resource for HeadlessGraphicService {}
+
+/// Read the image size from the IHDR chunk of a png file, without decoding it
+fn read_png_size(contents: &[u8]) -> Option<(u32, u32)> {
+    const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+    if contents.len() < 24 || contents[0..8] != PNG_SIGNATURE || &contents[12..16] != b"IHDR" {
+        return None;
+    }
+
+    let width = u32::from_be_bytes([contents[16], contents[17], contents[18], contents[19]]);
+    let height = u32::from_be_bytes([contents[20], contents[21], contents[22], contents[23]]);
+
+    Some((width, height))
+}
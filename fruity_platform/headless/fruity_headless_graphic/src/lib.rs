@@ -0,0 +1,16 @@
+use crate::graphic_service::HeadlessGraphicService;
+use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::settings::Settings;
+use fruity_graphic::graphic_service::GraphicService;
+
+pub mod graphic_service;
+pub mod resources;
+
+/// The module name
+pub static MODULE_NAME: &str = "fruity_headless_graphic";
+
+pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
+    let graphic_service = HeadlessGraphicService::new(resource_container.clone());
+
+    resource_container.add::<dyn GraphicService>("graphic_service", Box::new(graphic_service));
+}
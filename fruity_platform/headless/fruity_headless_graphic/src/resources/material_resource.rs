@@ -0,0 +1,42 @@
+use fruity_any::*;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::resource::resource::Resource;
+use fruity_core::resource::resource_reference::ResourceReference;
+use fruity_graphic::resources::material_resource::MaterialResource;
+use fruity_graphic::resources::material_resource::MaterialResourceSettings;
+use fruity_graphic::resources::shader_resource::ShaderResource;
+
+#[derive(Debug, FruityAny)]
+pub struct HeadlessMaterialResource {
+    pub params: MaterialResourceSettings,
+}
+
+impl HeadlessMaterialResource {
+    pub fn new(params: MaterialResourceSettings) -> HeadlessMaterialResource {
+        HeadlessMaterialResource { params }
+    }
+}
+
+impl MaterialResource for HeadlessMaterialResource {
+    fn get_shader(&self) -> Option<ResourceReference<dyn ShaderResource>> {
+        self.params.shader.clone()
+    }
+}
+
+impl Resource for HeadlessMaterialResource {}
+
+impl IntrospectObject for HeadlessMaterialResource {
+    fn get_class_name(&self) -> String {
+        "MaterialResource".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
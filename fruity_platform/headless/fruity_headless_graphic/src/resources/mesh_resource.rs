@@ -0,0 +1,36 @@
+use fruity_any::*;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::resource::resource::Resource;
+use fruity_graphic::resources::mesh_resource::MeshResource;
+use fruity_graphic::resources::mesh_resource::MeshResourceSettings;
+
+#[derive(Debug, FruityAny)]
+pub struct HeadlessMeshResource {
+    pub params: MeshResourceSettings,
+}
+
+impl HeadlessMeshResource {
+    pub fn new(params: MeshResourceSettings) -> HeadlessMeshResource {
+        HeadlessMeshResource { params }
+    }
+}
+
+impl MeshResource for HeadlessMeshResource {}
+
+impl Resource for HeadlessMeshResource {}
+
+impl IntrospectObject for HeadlessMeshResource {
+    fn get_class_name(&self) -> String {
+        "MeshResource".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
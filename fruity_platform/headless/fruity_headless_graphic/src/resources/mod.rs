@@ -0,0 +1,4 @@
+pub mod material_resource;
+pub mod mesh_resource;
+pub mod shader_resource;
+pub mod texture_resource;
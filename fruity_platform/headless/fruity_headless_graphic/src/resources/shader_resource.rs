@@ -0,0 +1,36 @@
+use fruity_any::*;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::resource::resource::Resource;
+use fruity_graphic::resources::shader_resource::ShaderResource;
+use fruity_graphic::resources::shader_resource::ShaderResourceSettings;
+
+#[derive(Debug, FruityAny)]
+pub struct HeadlessShaderResource {
+    pub params: ShaderResourceSettings,
+}
+
+impl HeadlessShaderResource {
+    pub fn new(params: ShaderResourceSettings) -> HeadlessShaderResource {
+        HeadlessShaderResource { params }
+    }
+}
+
+impl ShaderResource for HeadlessShaderResource {}
+
+impl Resource for HeadlessShaderResource {}
+
+impl IntrospectObject for HeadlessShaderResource {
+    fn get_class_name(&self) -> String {
+        "ShaderResource".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
@@ -0,0 +1,40 @@
+use fruity_any::*;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::resource::resource::Resource;
+use fruity_graphic::resources::texture_resource::TextureResource;
+
+/// A texture that is never decoded, only the size of png images is known
+#[derive(Debug, FruityAny)]
+pub struct HeadlessTextureResource {
+    pub size: (u32, u32),
+}
+
+impl HeadlessTextureResource {
+    pub fn new(size: (u32, u32)) -> HeadlessTextureResource {
+        HeadlessTextureResource { size }
+    }
+}
+
+impl TextureResource for HeadlessTextureResource {
+    fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+}
+
+impl Resource for HeadlessTextureResource {}
+
+impl IntrospectObject for HeadlessTextureResource {
+    fn get_class_name(&self) -> String {
+        "TextureResource".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
@@ -0,0 +1,231 @@
+use crate::window_service::HeadlessWindowService;
+use fruity_core::platform::Initializer;
+use fruity_core::resource::resource_container::ResourceContainer;
+use fruity_core::settings::Settings;
+use fruity_ecs::system::system_service::SystemService;
+use fruity_windows::frame_service::FrameService;
+use fruity_windows::window_service::WindowService;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+pub mod window_service;
+
+struct WindowSettings {
+    title: String,
+    width: usize,
+    height: usize,
+}
+
+struct HeadlessSettings {
+    /// The number of frames simulated per second
+    frame_rate: f64,
+
+    /// The number of frames to run before exiting, 0 means that the loop runs until the window is closed
+    frame_count: u64,
+
+    /// If true, the loop waits between frames to match the frame rate, otherwise frames are chained as fast as possible
+    realtime: bool,
+
+    /// If true, the systems stay paused like in the editor, only the systems that ignore the pause are run
+    paused: bool,
+}
+
+/// The module name
+pub static MODULE_NAME: &str = "fruity_headless_windows";
+
+pub fn initialize(resource_container: ResourceContainer, settings: &Settings) {
+    let window_service = resource_container.require::<dyn WindowService>();
+    let window_service = window_service.read();
+    let window_service = window_service
+        .as_any_ref()
+        .downcast_ref::<HeadlessWindowService>()
+        .unwrap();
+
+    let headless_settings = read_headless_settings(settings);
+    let frame_delta = (1.0 / headless_settings.frame_rate) as f32;
+
+    let resource_container_2 = resource_container.clone();
+    window_service.on_start_update.add_observer(move |_| {
+        puffin::profile_scope!("begin_frame");
+
+        let frame_service = resource_container_2.require::<FrameService>();
+        let mut frame_service = frame_service.write();
+
+        frame_service.begin_fixed_frame(frame_delta);
+    });
+}
+
+pub fn platform(
+    resource_container: ResourceContainer,
+    ext_initializer: Initializer,
+    world_initializer: Initializer,
+    settings: &Settings,
+) {
+    // Get dependencies
+    let system_service = resource_container.require::<SystemService>();
+
+    // Read settings
+    let window_settings = read_window_settings(settings);
+    let headless_settings = read_headless_settings(settings);
+
+    // Build and inject the windows service
+    let window_service = HeadlessWindowService::new(
+        &window_settings.title,
+        window_settings.width as u32,
+        window_settings.height as u32,
+    );
+
+    let on_start_update = window_service.on_start_update.clone();
+    let on_end_update = window_service.on_end_update.clone();
+    let closed = window_service.get_closed_flag();
+
+    resource_container.add::<dyn WindowService>("window_service", Box::new(window_service));
+
+    // Initialize the extensions
+    ext_initializer(resource_container.clone(), settings);
+
+    // Run the begin systems before everything
+    let system_service_reader = system_service.read();
+    system_service_reader.run_start();
+    std::mem::drop(system_service_reader);
+
+    // Initialize the world
+    world_initializer(resource_container.clone(), settings);
+
+    // There is no editor to start the game, the systems are unpaused unless the settings say otherwise
+    let system_service_reader = system_service.read();
+    system_service_reader.set_paused(headless_settings.paused);
+    std::mem::drop(system_service_reader);
+
+    // Run the fixed step loop
+    let window_service = resource_container.require::<dyn WindowService>();
+    let window_service_reader = window_service.read();
+    window_service_reader.on_enter_loop().notify(());
+    std::mem::drop(window_service_reader);
+
+    let frame_duration = Duration::from_secs_f64(1.0 / headless_settings.frame_rate);
+    let mut frame_index = 0;
+    while !closed.load(Ordering::Relaxed)
+        && (headless_settings.frame_count == 0 || frame_index < headless_settings.frame_count)
+    {
+        puffin::profile_scope!("main_loop");
+        let frame_start = Instant::now();
+
+        // Start updating
+        {
+            puffin::profile_scope!("start_update");
+            on_start_update.notify(());
+        }
+
+        // Run the systems
+        {
+            puffin::profile_scope!("run_systems");
+
+            let system_service_reader = system_service.read();
+            system_service_reader.run();
+        }
+
+        // End the update
+        {
+            puffin::profile_scope!("end_update");
+            on_end_update.notify(());
+        }
+
+        frame_index += 1;
+
+        // Wait for the next frame
+        if headless_settings.realtime {
+            let elapsed = frame_start.elapsed();
+            if elapsed < frame_duration {
+                std::thread::sleep(frame_duration - elapsed);
+            }
+        }
+    }
+
+    log::info!("Headless loop ended after {} frames", frame_index);
+
+    // Run the end systems at the end
+    let system_service_reader = system_service.read();
+    system_service_reader.run_end();
+}
+
+fn read_window_settings(settings: &Settings) -> WindowSettings {
+    let settings = settings.get_settings("window");
+
+    WindowSettings {
+        title: settings.get("title", "".to_string()),
+        width: settings.get("width", 512),
+        height: settings.get("height", 512),
+    }
+}
+
+fn read_headless_settings(settings: &Settings) -> HeadlessSettings {
+    let settings = settings.get_settings("headless");
+    let frame_rate = settings.get("frame_rate", 60.0);
+
+    HeadlessSettings {
+        frame_rate: if frame_rate > 0.0 { frame_rate } else { 60.0 },
+        frame_count: settings.get("frame_count", 0),
+        realtime: settings.get("realtime", false),
+        paused: settings.get("paused", false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fruity_core::inject::Const;
+    use fruity_core::inject::Inject1;
+    use fruity_core::settings::read_settings;
+    use fruity_core::world::World;
+    use fruity_ecs::system::system_service::SystemParams;
+    use fruity_graphic::graphic_service::GraphicService;
+    use std::sync::atomic::AtomicU64;
+
+    static RUN_FRAMES: AtomicU64 = AtomicU64::new(0);
+    static DRAWN_FRAMES: AtomicU64 = AtomicU64::new(0);
+
+    #[test]
+    fn headless_platform_runs_the_frames_then_exits() {
+        let mut world = World::new();
+        world.set_platform(platform);
+        fruity_ecs::initialize(world.resource_container.clone());
+
+        let settings = read_settings(&mut "headless:\n  frame_count: 1\n".as_bytes());
+        world.run(
+            |resource_container, settings| {
+                fruity_windows::initialize(resource_container.clone(), settings);
+                initialize(resource_container.clone(), settings);
+                fruity_headless_graphic::initialize(resource_container.clone(), settings);
+
+                let graphic_service = resource_container.require::<dyn GraphicService>();
+                let graphic_service = graphic_service.read();
+                graphic_service.on_after_draw_end().add_observer(|_| {
+                    DRAWN_FRAMES.fetch_add(1, Ordering::Relaxed);
+                });
+            },
+            |resource_container, _settings| {
+                let system_service = resource_container.require::<SystemService>();
+                let mut system_service = system_service.write();
+
+                system_service.add_system(
+                    "count_frames",
+                    MODULE_NAME,
+                    Inject1::new(|frame_service: Const<FrameService>| {
+                        RUN_FRAMES.store(frame_service.get_frame_count(), Ordering::Relaxed);
+                    }),
+                    SystemParams::default(),
+                );
+            },
+            &settings,
+        );
+
+        let frame_service = world.resource_container.require::<FrameService>();
+        let frame_service = frame_service.read();
+        assert_eq!(RUN_FRAMES.load(Ordering::Relaxed), 1);
+        assert_eq!(DRAWN_FRAMES.load(Ordering::Relaxed), 1);
+        assert_eq!(frame_service.get_frame_count(), 1);
+        assert_eq!(frame_service.get_delta(), 1.0 / 60.0);
+    }
+}
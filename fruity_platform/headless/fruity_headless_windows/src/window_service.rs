@@ -0,0 +1,291 @@
+use fruity_any::*;
+use fruity_core::convert::FruityInto;
+use fruity_core::introspect::FieldInfo;
+use fruity_core::introspect::IntrospectObject;
+use fruity_core::introspect::MethodCaller;
+use fruity_core::introspect::MethodInfo;
+use fruity_core::resource::resource::Resource;
+use fruity_core::serialize::serialized::Serialized;
+use fruity_core::signal::Signal;
+use fruity_core::utils::introspect::cast_introspect_mut;
+use fruity_core::utils::introspect::cast_introspect_ref;
+use fruity_core::utils::introspect::ArgumentCaster;
+use fruity_core::RwLock;
+use fruity_windows::clipboard::Clipboard;
+use fruity_windows::clipboard::MemoryClipboard;
+use fruity_windows::window_service::WindowService;
+use std::fmt::Debug;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+/// A window service that is not backed by any real window
+///
+/// The size and the title are only stored, closing the window stops the headless loop
+#[derive(FruityAny)]
+pub struct HeadlessWindowService {
+    title: RwLock<String>,
+    size: RwLock<(u32, u32)>,
+    closed: Arc<AtomicBool>,
+    clipboard: Box<dyn Clipboard>,
+    pub cursor_position: (u32, u32),
+    pub on_enter_loop: Signal<()>,
+    pub on_start_update: Signal<()>,
+    pub on_end_update: Signal<()>,
+    pub on_resize: Signal<(u32, u32)>,
+    pub on_cursor_moved: Signal<(u32, u32)>,
+}
+
+impl Debug for HeadlessWindowService {
+    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        Ok(())
+    }
+}
+
+impl HeadlessWindowService {
+    pub fn new(title: &str, width: u32, height: u32) -> HeadlessWindowService {
+        HeadlessWindowService {
+            title: RwLock::new(title.to_string()),
+            size: RwLock::new((width, height)),
+            closed: Arc::new(AtomicBool::new(false)),
+            clipboard: Box::new(MemoryClipboard::new()),
+            cursor_position: Default::default(),
+            on_enter_loop: Signal::new(),
+            on_start_update: Signal::new(),
+            on_end_update: Signal::new(),
+            on_resize: Signal::new(),
+            on_cursor_moved: Signal::new(),
+        }
+    }
+
+    pub fn get_title(&self) -> String {
+        self.title.read().clone()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+
+    /// Get a shared flag that is raised when the window is closed
+    pub fn get_closed_flag(&self) -> Arc<AtomicBool> {
+        self.closed.clone()
+    }
+
+    /// Replace the clipboard, the clipboard is only kept in memory by default
+    ///
+    /// # Arguments
+    /// * `clipboard` - The clipboard
+    ///
+    pub fn set_clipboard(&mut self, clipboard: Box<dyn Clipboard>) {
+        self.clipboard = clipboard;
+    }
+}
+
+impl WindowService for HeadlessWindowService {
+    fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+    }
+
+    fn set_resizable(&self, _resizable: bool) {}
+
+    fn get_windows_size(&self) -> (u32, u32) {
+        *self.size.read()
+    }
+
+    fn get_scale_factor(&self) -> f64 {
+        1.0
+    }
+
+    fn get_cursor_position(&self) -> (u32, u32) {
+        self.cursor_position
+    }
+
+    fn set_cursor_position(&mut self, position: (u32, u32)) {
+        self.cursor_position = position;
+    }
+
+    fn set_size(&self, width: u32, height: u32) {
+        {
+            let mut size = self.size.write();
+            *size = (width, height);
+        }
+
+        self.on_resize.notify((width, height))
+    }
+
+    fn set_title(&self, title: &str) {
+        let mut current_title = self.title.write();
+        *current_title = title.to_string();
+    }
+
+    fn get_clipboard_text(&self) -> Option<String> {
+        self.clipboard.get_text()
+    }
+
+    fn set_clipboard_text(&mut self, text: &str) {
+        self.clipboard.set_text(text);
+    }
+
+    fn set_ime_position(&self, _position: (u32, u32)) {}
+
+    fn on_enter_loop(&self) -> &Signal<()> {
+        &self.on_enter_loop
+    }
+
+    fn on_start_update(&self) -> &Signal<()> {
+        &self.on_start_update
+    }
+
+    fn on_end_update(&self) -> &Signal<()> {
+        &self.on_end_update
+    }
+
+    fn on_resize(&self) -> &Signal<(u32, u32)> {
+        &self.on_resize
+    }
+
+    fn on_cursor_moved(&self) -> &Signal<(u32, u32)> {
+        &self.on_cursor_moved
+    }
+}
+
+impl IntrospectObject for HeadlessWindowService {
+    fn get_class_name(&self) -> String {
+        "WindowService".to_string()
+    }
+
+    fn get_method_infos(&self) -> Vec<MethodInfo> {
+        vec![
+            MethodInfo {
+                name: "close".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<HeadlessWindowService>(this);
+                    this.close();
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "set_resizable".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<HeadlessWindowService>(this);
+
+                    let mut caster = ArgumentCaster::new("set_resizable", args);
+                    let arg1 = caster.cast_next::<bool>()?;
+
+                    this.set_resizable(arg1);
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "get_windows_size".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<HeadlessWindowService>(this);
+                    let result = this.get_windows_size();
+
+                    Ok(Some(Serialized::Array(vec![
+                        Serialized::U32(result.0),
+                        Serialized::U32(result.1),
+                    ])))
+                })),
+            },
+            MethodInfo {
+                name: "get_cursor_position".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<HeadlessWindowService>(this);
+                    let result = this.get_cursor_position();
+
+                    Ok(Some(Serialized::Array(vec![
+                        Serialized::U32(result.0),
+                        Serialized::U32(result.1),
+                    ])))
+                })),
+            },
+            MethodInfo {
+                name: "set_size".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<HeadlessWindowService>(this);
+
+                    let mut caster = ArgumentCaster::new("set_size", args);
+                    let arg1 = caster.cast_next::<u32>()?;
+                    let arg2 = caster.cast_next::<u32>()?;
+
+                    this.set_size(arg1, arg2);
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "set_title".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<HeadlessWindowService>(this);
+
+                    let mut caster = ArgumentCaster::new("set_title", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    this.set_title(&arg1);
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "get_clipboard_text".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<HeadlessWindowService>(this);
+                    let result = this.get_clipboard_text();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "set_clipboard_text".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<HeadlessWindowService>(this);
+
+                    let mut caster = ArgumentCaster::new("set_clipboard_text", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    this.set_clipboard_text(&arg1);
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "set_ime_position".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<HeadlessWindowService>(this);
+
+                    let mut caster = ArgumentCaster::new("set_ime_position", args);
+                    let arg1 = caster.cast_next::<u32>()?;
+                    let arg2 = caster.cast_next::<u32>()?;
+
+                    this.set_ime_position((arg1, arg2));
+                    Ok(None)
+                })),
+            },
+        ]
+    }
+
+    fn get_field_infos(&self) -> Vec<FieldInfo> {
+        vec![]
+    }
+}
+
+impl Resource for HeadlessWindowService {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clipboard_gives_back_the_copied_text() {
+        let mut window_service = HeadlessWindowService::new("test", 640, 480);
+        assert_eq!(window_service.get_clipboard_text(), None);
+
+        window_service.set_clipboard_text("日本");
+        assert_eq!(
+            window_service.get_clipboard_text(),
+            Some("日本".to_string())
+        );
+
+        // The text belongs to the clipboard, a new clipboard is empty
+        window_service.set_clipboard(Box::new(MemoryClipboard::new()));
+        assert_eq!(window_service.get_clipboard_text(), None);
+    }
+}
@@ -24,6 +24,7 @@ use fruity_graphic::resources::mesh_resource::MeshResource;
 use fruity_graphic::resources::mesh_resource::MeshResourceSettings;
 use fruity_graphic::resources::shader_resource::ShaderResource;
 use fruity_graphic::resources::shader_resource::ShaderResourceSettings;
+use fruity_graphic::resources::texture_resource::TextureData;
 use fruity_graphic::resources::texture_resource::TextureResource;
 use fruity_graphic::resources::texture_resource::TextureResourceSettings;
 use fruity_windows::window_service::WindowService;
@@ -864,17 +865,28 @@ impl GraphicService for WgpuGraphicService {
         Ok(Box::new(resource))
     }
 
+    fn decode_texture(&self, contents: &[u8]) -> Result<TextureData, String> {
+        let image = load_from_memory(contents).map_err(|err| err.to_string())?;
+        let image = image.to_rgba8();
+
+        Ok(TextureData::new(
+            image.width(),
+            image.height(),
+            image.into_raw(),
+        ))
+    }
+
     fn create_texture_resource(
         &self,
         identifier: &str,
-        contents: &[u8],
-        _params: TextureResourceSettings,
+        data: TextureData,
+        params: TextureResourceSettings,
     ) -> Result<Box<dyn TextureResource>, String> {
         let device = self.get_device();
         let queue = self.get_queue();
 
-        let image = load_from_memory(contents).unwrap();
-        let resource = WgpuTextureResource::from_image(device, queue, &image, Some(&identifier))?;
+        let resource =
+            WgpuTextureResource::from_data(device, queue, &data, &params, Some(&identifier))?;
 
         Ok(Box::new(resource))
     }
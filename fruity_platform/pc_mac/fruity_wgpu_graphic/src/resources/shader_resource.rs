@@ -7,6 +7,7 @@ use fruity_core::introspect::IntrospectObject;
 use fruity_core::introspect::MethodInfo;
 use fruity_core::introspect::SetterCaller;
 use fruity_core::resource::resource::Resource;
+use fruity_graphic::resources::shader_reflection::SHADER_ENTRY_POINT;
 use fruity_graphic::resources::shader_resource::ShaderBinding;
 use fruity_graphic::resources::shader_resource::ShaderBindingGroup;
 use fruity_graphic::resources::shader_resource::ShaderBindingType;
@@ -90,7 +91,7 @@ impl WgpuShaderResource {
             layout: Some(&render_pipeline_layout),
             vertex: wgpu::VertexState {
                 module: &shader_module,
-                entry_point: "main",
+                entry_point: SHADER_ENTRY_POINT,
                 buffers: &[
                     VERTEX_DESC.clone(),
                     wgpu::VertexBufferLayout {
@@ -102,7 +103,7 @@ impl WgpuShaderResource {
             },
             fragment: Some(wgpu::FragmentState {
                 module: &shader_module,
-                entry_point: "main",
+                entry_point: SHADER_ENTRY_POINT,
                 targets: &[wgpu::ColorTargetState {
                     format: surface_config.format,
                     blend: Some(wgpu::BlendState {
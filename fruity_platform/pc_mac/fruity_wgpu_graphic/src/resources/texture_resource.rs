@@ -3,8 +3,11 @@ use fruity_core::introspect::FieldInfo;
 use fruity_core::introspect::IntrospectObject;
 use fruity_core::introspect::MethodInfo;
 use fruity_core::resource::resource::Resource;
+use fruity_graphic::resources::texture_resource::TextureData;
+use fruity_graphic::resources::texture_resource::TextureFilter;
 use fruity_graphic::resources::texture_resource::TextureResource;
-use image::GenericImageView;
+use fruity_graphic::resources::texture_resource::TextureResourceSettings;
+use fruity_graphic::resources::texture_resource::TextureWrapMode;
 use std::num::NonZeroU32;
 use std::sync::Arc;
 
@@ -24,14 +27,14 @@ impl TextureResource for WgpuTextureResource {
 }
 
 impl WgpuTextureResource {
-    pub fn from_image(
+    pub fn from_data(
         device: &wgpu::Device,
         queue: &wgpu::Queue,
-        image: &image::DynamicImage,
+        data: &TextureData,
+        params: &TextureResourceSettings,
         label: Option<&str>,
     ) -> Result<WgpuTextureResource, String> {
-        let rgba = image.as_rgba8().unwrap();
-        let dimensions = image.dimensions();
+        let dimensions = (data.width, data.height);
 
         let size = wgpu::Extent3d {
             width: dimensions.0,
@@ -42,37 +45,55 @@ impl WgpuTextureResource {
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label,
             size,
-            mip_level_count: 1,
+            mip_level_count: data.levels.len().max(1) as u32,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            format: if params.srgb {
+                wgpu::TextureFormat::Rgba8UnormSrgb
+            } else {
+                wgpu::TextureFormat::Rgba8Unorm
+            },
             usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
         });
 
-        queue.write_texture(
-            wgpu::ImageCopyTexture {
-                aspect: wgpu::TextureAspect::All,
-                texture: &texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-            },
-            rgba,
-            wgpu::ImageDataLayout {
-                offset: 0,
-                bytes_per_row: NonZeroU32::new(4 * dimensions.0),
-                rows_per_image: NonZeroU32::new(dimensions.1),
-            },
-            size,
-        );
+        // Write the full size image and all the mip levels
+        data.levels.iter().enumerate().for_each(|(level, pixels)| {
+            let (width, height) = data.get_level_size(level);
+
+            queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    aspect: wgpu::TextureAspect::All,
+                    texture: &texture,
+                    mip_level: level as u32,
+                    origin: wgpu::Origin3d::ZERO,
+                },
+                pixels,
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(4 * width),
+                    rows_per_image: NonZeroU32::new(height),
+                },
+                wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+            );
+        });
 
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let address_mode = match params.wrap_mode {
+            TextureWrapMode::Clamp => wgpu::AddressMode::ClampToEdge,
+            TextureWrapMode::Repeat => wgpu::AddressMode::Repeat,
+            TextureWrapMode::Mirror => wgpu::AddressMode::MirrorRepeat,
+        };
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Nearest,
-            mipmap_filter: wgpu::FilterMode::Nearest,
+            address_mode_u: address_mode,
+            address_mode_v: address_mode,
+            address_mode_w: address_mode,
+            mag_filter: Self::filter_mode(params.mag_filter),
+            min_filter: Self::filter_mode(params.min_filter),
+            mipmap_filter: Self::filter_mode(params.min_filter),
             ..Default::default()
         });
 
@@ -123,6 +144,13 @@ impl WgpuTextureResource {
         })
     }
 
+    fn filter_mode(filter: TextureFilter) -> wgpu::FilterMode {
+        match filter {
+            TextureFilter::Nearest => wgpu::FilterMode::Nearest,
+            TextureFilter::Linear => wgpu::FilterMode::Linear,
+        }
+    }
+
     pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
 
     pub fn render(
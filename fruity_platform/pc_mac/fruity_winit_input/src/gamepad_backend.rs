@@ -0,0 +1,91 @@
+use fruity_input::gamepad::GamepadAxis;
+use fruity_input::gamepad::GamepadBackend;
+use fruity_input::gamepad::GamepadButton;
+use fruity_input::gamepad::GamepadEvent;
+use gilrs::Axis;
+use gilrs::Button;
+use gilrs::EventType;
+use gilrs::Gilrs;
+
+/// A gamepad backend that reads the devices with gilrs
+pub struct GilrsGamepadBackend {
+    gilrs: Gilrs,
+}
+
+impl GilrsGamepadBackend {
+    pub fn new() -> Result<GilrsGamepadBackend, String> {
+        let gilrs = Gilrs::new().map_err(|err| err.to_string())?;
+        Ok(GilrsGamepadBackend { gilrs })
+    }
+}
+
+impl GamepadBackend for GilrsGamepadBackend {
+    fn poll_events(&mut self) -> Vec<GamepadEvent> {
+        let mut events = Vec::new();
+
+        while let Some(gilrs::Event { id, event, .. }) = self.gilrs.next_event() {
+            let gamepad_id = usize::from(id);
+
+            let event = match event {
+                EventType::Connected => Some(GamepadEvent::Connected(
+                    gamepad_id,
+                    self.gilrs.gamepad(id).name().to_string(),
+                )),
+                EventType::Disconnected => Some(GamepadEvent::Disconnected(gamepad_id)),
+                EventType::ButtonPressed(button, _) => get_button(button)
+                    .map(|button| GamepadEvent::ButtonPressed(gamepad_id, button)),
+                EventType::ButtonReleased(button, _) => get_button(button)
+                    .map(|button| GamepadEvent::ButtonReleased(gamepad_id, button)),
+                // The analog triggers are reported as buttons with a value
+                EventType::ButtonChanged(Button::LeftTrigger2, value, _) => Some(
+                    GamepadEvent::AxisChanged(gamepad_id, GamepadAxis::LeftTrigger, value),
+                ),
+                EventType::ButtonChanged(Button::RightTrigger2, value, _) => Some(
+                    GamepadEvent::AxisChanged(gamepad_id, GamepadAxis::RightTrigger, value),
+                ),
+                EventType::AxisChanged(axis, value, _) => get_axis(axis)
+                    .map(|axis| GamepadEvent::AxisChanged(gamepad_id, axis, value)),
+                _ => None,
+            };
+
+            if let Some(event) = event {
+                events.push(event);
+            }
+        }
+
+        events
+    }
+}
+
+fn get_button(button: Button) -> Option<GamepadButton> {
+    match button {
+        Button::South => Some(GamepadButton::South),
+        Button::East => Some(GamepadButton::East),
+        Button::North => Some(GamepadButton::North),
+        Button::West => Some(GamepadButton::West),
+        Button::LeftTrigger => Some(GamepadButton::LeftBumper),
+        Button::RightTrigger => Some(GamepadButton::RightBumper),
+        Button::LeftTrigger2 => Some(GamepadButton::LeftTrigger),
+        Button::RightTrigger2 => Some(GamepadButton::RightTrigger),
+        Button::Select => Some(GamepadButton::Select),
+        Button::Start => Some(GamepadButton::Start),
+        Button::Mode => Some(GamepadButton::Mode),
+        Button::LeftThumb => Some(GamepadButton::LeftThumb),
+        Button::RightThumb => Some(GamepadButton::RightThumb),
+        Button::DPadUp => Some(GamepadButton::DPadUp),
+        Button::DPadDown => Some(GamepadButton::DPadDown),
+        Button::DPadLeft => Some(GamepadButton::DPadLeft),
+        Button::DPadRight => Some(GamepadButton::DPadRight),
+        _ => None,
+    }
+}
+
+fn get_axis(axis: Axis) -> Option<GamepadAxis> {
+    match axis {
+        Axis::LeftStickX => Some(GamepadAxis::LeftStickX),
+        Axis::LeftStickY => Some(GamepadAxis::LeftStickY),
+        Axis::RightStickX => Some(GamepadAxis::RightStickX),
+        Axis::RightStickY => Some(GamepadAxis::RightStickY),
+        _ => None,
+    }
+}
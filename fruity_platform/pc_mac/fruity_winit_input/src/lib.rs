@@ -1,3 +1,4 @@
+use crate::gamepad_backend::GilrsGamepadBackend;
 use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::settings::Settings;
 use fruity_input::input_service::InputService;
@@ -10,6 +11,8 @@ use winit::event::MouseButton;
 use winit::event::VirtualKeyCode;
 use winit::event::WindowEvent;
 
+pub mod gamepad_backend;
+
 /// The module name
 pub static MODULE_NAME: &str = "fruity_winit_input";
 
@@ -21,17 +24,20 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
 
     let input_service = resource_container.require::<InputService>();
 
+    // Winit doesn't provide the gamepads, they are read with gilrs
+    match GilrsGamepadBackend::new() {
+        Ok(gamepad_backend) => {
+            let mut input_service = input_service.write();
+            input_service.set_gamepad_backend(Box::new(gamepad_backend));
+        }
+        Err(err) => log::error!("{}", err),
+    }
+
     let input_service_2 = input_service.clone();
     window_service.on_event.add_observer(move |event| {
         let mut input_service = input_service_2.write();
         handle_keyboard_input(&mut input_service, event);
     });
-
-    let input_service_2 = input_service.clone();
-    window_service.on_end_update.add_observer(move |_| {
-        let mut input_service = input_service_2.write();
-        input_service.handle_frame_end();
-    });
 }
 
 fn handle_keyboard_input(input_service: &mut InputService, event: &Event<()>) {
@@ -52,6 +58,10 @@ fn handle_keyboard_input(input_service: &mut InputService, event: &Event<()>) {
             }
         } else if let WindowEvent::ModifiersChanged(modifier) = event {
             input_service.notify_modifiers(Modifiers::from_bits(modifier.bits()).unwrap());
+        } else if let WindowEvent::ReceivedCharacter(character) = event {
+            // Winit 0.25 doesn't report the IME composition, so the composed text stays empty
+            // with this backend, only the text committed by the IME is received here
+            input_service.notify_text(&character.to_string());
         } else if let WindowEvent::KeyboardInput { input, .. } = event {
             if let Some(key) = input.virtual_keycode {
                 // Get the key source
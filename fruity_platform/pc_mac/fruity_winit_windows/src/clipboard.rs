@@ -0,0 +1,62 @@
+use fruity_core::Mutex;
+use fruity_windows::clipboard::Clipboard;
+use fruity_windows::clipboard::MemoryClipboard;
+
+/// The clipboard of the operating system, the text is shared with the other applications
+pub struct SystemClipboard {
+    clipboard: Mutex<arboard::Clipboard>,
+}
+
+impl SystemClipboard {
+    /// Returns the SystemClipboard, fails if the operating system clipboard can't be reached,
+    /// for example if there is no display server
+    pub fn new() -> Result<SystemClipboard, String> {
+        let clipboard = arboard::Clipboard::new().map_err(|err| err.to_string())?;
+
+        Ok(SystemClipboard {
+            clipboard: Mutex::new(clipboard),
+        })
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn get_text(&self) -> Option<String> {
+        let mut clipboard = self.clipboard.lock();
+        clipboard.get_text().ok()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        let mut clipboard = self.clipboard.lock();
+        if let Err(err) = clipboard.set_text(text) {
+            log::error!("{}", err);
+        }
+    }
+}
+
+/// Get the system clipboard, the clipboard is only kept in memory if the system one can't be
+/// reached
+pub fn system_clipboard() -> Box<dyn Clipboard> {
+    match SystemClipboard::new() {
+        Ok(clipboard) => Box::new(clipboard),
+        Err(err) => {
+            log::warn!("The system clipboard is not available: {}", err);
+            Box::new(MemoryClipboard::new())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_clipboard_gives_back_the_copied_text() {
+        let mut clipboard = system_clipboard();
+
+        clipboard.set_text("fruity clipboard test");
+        assert_eq!(
+            clipboard.get_text(),
+            Some("fruity clipboard test".to_string())
+        );
+    }
+}
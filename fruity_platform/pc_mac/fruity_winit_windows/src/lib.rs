@@ -14,6 +14,7 @@ use winit::event_loop::ControlFlow;
 use winit::event_loop::EventLoop;
 use winit::window::WindowBuilder;
 
+pub mod clipboard;
 pub mod fps_counter;
 pub mod window_service;
 
@@ -1,4 +1,6 @@
+use crate::clipboard::system_clipboard;
 use fruity_any::*;
+use fruity_core::convert::FruityInto;
 use fruity_core::introspect::FieldInfo;
 use fruity_core::introspect::IntrospectObject;
 use fruity_core::introspect::MethodCaller;
@@ -6,18 +8,22 @@ use fruity_core::introspect::MethodInfo;
 use fruity_core::resource::resource::Resource;
 use fruity_core::serialize::serialized::Serialized;
 use fruity_core::signal::Signal;
+use fruity_core::utils::introspect::cast_introspect_mut;
 use fruity_core::utils::introspect::cast_introspect_ref;
 use fruity_core::utils::introspect::ArgumentCaster;
+use fruity_windows::clipboard::Clipboard;
 use fruity_windows::window_service::WindowService;
 use std::fmt::Debug;
 use std::sync::Arc;
 use winit::dpi::LogicalSize;
+use winit::dpi::PhysicalPosition;
 use winit::event::Event;
 use winit::window::Window;
 
 #[derive(FruityAny)]
 pub struct WinitWindowService {
     window: Window,
+    clipboard: Box<dyn Clipboard>,
     pub cursor_position: (u32, u32),
     pub on_enter_loop: Signal<()>,
     pub on_start_update: Signal<()>,
@@ -38,6 +44,7 @@ impl WinitWindowService {
     pub fn new(window: Window) -> WinitWindowService {
         WinitWindowService {
             window,
+            clipboard: system_clipboard(),
             cursor_position: Default::default(),
             on_enter_loop: Signal::new(),
             on_start_update: Signal::new(),
@@ -52,6 +59,15 @@ impl WinitWindowService {
     pub fn get_window(&self) -> &Window {
         &self.window
     }
+
+    /// Replace the clipboard, the system clipboard is used by default
+    ///
+    /// # Arguments
+    /// * `clipboard` - The clipboard
+    ///
+    pub fn set_clipboard(&mut self, clipboard: Box<dyn Clipboard>) {
+        self.clipboard = clipboard;
+    }
 }
 
 impl WindowService for WinitWindowService {
@@ -79,6 +95,10 @@ impl WindowService for WinitWindowService {
         self.cursor_position.clone()
     }
 
+    fn set_cursor_position(&mut self, position: (u32, u32)) {
+        self.cursor_position = position;
+    }
+
     fn set_size(&self, width: u32, height: u32) {
         self.window
             .set_inner_size(LogicalSize::new(width as i32, height as i32));
@@ -89,6 +109,19 @@ impl WindowService for WinitWindowService {
         self.window.set_title(title);
     }
 
+    fn get_clipboard_text(&self) -> Option<String> {
+        self.clipboard.get_text()
+    }
+
+    fn set_clipboard_text(&mut self, text: &str) {
+        self.clipboard.set_text(text);
+    }
+
+    fn set_ime_position(&self, position: (u32, u32)) {
+        self.window
+            .set_ime_position(PhysicalPosition::new(position.0, position.1));
+    }
+
     fn on_enter_loop(&self) -> &Signal<()> {
         &self.on_enter_loop
     }
@@ -186,6 +219,40 @@ impl IntrospectObject for WinitWindowService {
                     Ok(None)
                 })),
             },
+            MethodInfo {
+                name: "get_clipboard_text".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, _args| {
+                    let this = cast_introspect_ref::<WinitWindowService>(this);
+                    let result = this.get_clipboard_text();
+
+                    Ok(Some(result.fruity_into()))
+                })),
+            },
+            MethodInfo {
+                name: "set_clipboard_text".to_string(),
+                call: MethodCaller::Mut(Arc::new(|this, args| {
+                    let this = cast_introspect_mut::<WinitWindowService>(this);
+
+                    let mut caster = ArgumentCaster::new("set_clipboard_text", args);
+                    let arg1 = caster.cast_next::<String>()?;
+
+                    this.set_clipboard_text(&arg1);
+                    Ok(None)
+                })),
+            },
+            MethodInfo {
+                name: "set_ime_position".to_string(),
+                call: MethodCaller::Const(Arc::new(|this, args| {
+                    let this = cast_introspect_ref::<WinitWindowService>(this);
+
+                    let mut caster = ArgumentCaster::new("set_ime_position", args);
+                    let arg1 = caster.cast_next::<u32>()?;
+                    let arg2 = caster.cast_next::<u32>()?;
+
+                    this.set_ime_position((arg1, arg2));
+                    Ok(None)
+                })),
+            },
         ]
     }
 
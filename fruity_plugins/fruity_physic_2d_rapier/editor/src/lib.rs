@@ -20,8 +20,9 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         MODULE_NAME,
         Inject2::new(draw_physic_debug),
         SystemParams {
-            pool_index: 98,
+            stage: "draw".to_string(),
             ignore_pause: true,
+            ..Default::default()
         },
     );
 }
@@ -1,12 +1,19 @@
 use fruity_any::*;
 use fruity_ecs::*;
+use fruity_graphic::math::vector2d::Vector2d;
 
+/// A body moved by the physic
+///
+/// The world pose of the body after the last fixed step is stored, the body is only moved by the
+/// transform components when they don't match this pose anymore
 #[derive(Debug, Clone, Component, FruityAny)]
 pub struct DynamicRigidBody {
     pub handle: Option<(u32, u32)>,
     pub gravity_scale: f32,
     pub can_sleep: bool,
     pub ccd_enabled: bool,
+    pub last_translation: Option<Vector2d>,
+    pub last_rotation: f32,
 }
 
 impl Default for DynamicRigidBody {
@@ -16,6 +23,8 @@ impl Default for DynamicRigidBody {
             gravity_scale: 1.0,
             can_sleep: true,
             ccd_enabled: false,
+            last_translation: None,
+            last_rotation: 0.0,
         }
     }
 }
@@ -15,7 +15,6 @@ use crate::systems::kinematic_update_rigid_body_prepare::kinematic_update_rigid_
 use crate::systems::update_circle_collider::update_circle_collider;
 use crate::systems::update_physics::update_physics;
 use crate::systems::update_rect_collider::update_rect_collider;
-use fruity_core::inject::Inject1;
 use fruity_core::inject::Inject2;
 use fruity_core::object_factory_service::ObjectFactoryService;
 use fruity_core::resource::resource_container::ResourceContainer;
@@ -28,6 +27,7 @@ use fruity_physic_2d::components::circle_collider::CircleCollider;
 use fruity_physic_2d::components::rect_collider::RectCollider;
 
 pub mod components;
+pub mod pose;
 pub mod rapier_2d_service;
 pub mod systems;
 
@@ -59,9 +59,15 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
     system_service.add_system(
         "update_physics",
         MODULE_NAME,
-        Inject1::new(update_physics),
+        Inject2::new(update_physics),
         SystemParams {
-            pool_index: 52,
+            stage: "physics".to_string(),
+            after: vec![
+                "update_circle_collider".to_string(),
+                "update_rect_collider".to_string(),
+                "kinematic_update_rigid_body_prepare".to_string(),
+                "dynamic_update_rigid_body_prepare".to_string(),
+            ],
             ..Default::default()
         },
     );
@@ -77,7 +83,7 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         Inject2::new(update_circle_collider),
         SystemParams {
             ignore_pause: true,
-            pool_index: 51,
+            stage: "physics".to_string(),
             ..Default::default()
         },
     );
@@ -93,7 +99,7 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         Inject2::new(update_rect_collider),
         SystemParams {
             ignore_pause: true,
-            pool_index: 51,
+            stage: "physics".to_string(),
             ..Default::default()
         },
     );
@@ -109,7 +115,7 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         Inject2::new(kinematic_update_rigid_body_prepare),
         SystemParams {
             ignore_pause: true,
-            pool_index: 51,
+            stage: "physics".to_string(),
             ..Default::default()
         },
     );
@@ -119,7 +125,8 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         Inject2::new(kinematic_update_rigid_body),
         SystemParams {
             ignore_pause: true,
-            pool_index: 53,
+            stage: "physics".to_string(),
+            after: vec!["update_physics".to_string()],
             ..Default::default()
         },
     );
@@ -135,7 +142,7 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         Inject2::new(dynamic_update_rigid_body_prepare),
         SystemParams {
             ignore_pause: true,
-            pool_index: 51,
+            stage: "physics".to_string(),
             ..Default::default()
         },
     );
@@ -145,7 +152,8 @@ pub fn initialize(resource_container: ResourceContainer, _settings: &Settings) {
         Inject2::new(dynamic_update_rigid_body),
         SystemParams {
             ignore_pause: true,
-            pool_index: 53,
+            stage: "physics".to_string(),
+            after: vec!["update_physics".to_string()],
             ..Default::default()
         },
     );
@@ -0,0 +1,88 @@
+use fruity_graphic::math::matrix3::Matrix3;
+use fruity_graphic::math::vector2d::Vector2d;
+use std::f32::consts::PI;
+use std::f32::consts::TAU;
+
+/// The distance under which two poses are considered the same
+pub const POSE_EPSILON: f32 = 0.0001;
+
+/// Get the rotation of a transform, in radians
+///
+/// # Arguments
+/// * `transform` - The transform
+///
+pub fn get_transform_angle(transform: &Matrix3) -> f32 {
+    f32::atan2(transform.0[0][1], transform.0[0][0])
+}
+
+/// Get the smallest difference between two angles, in radians, the angles that are a full turn
+/// apart are the same
+///
+/// # Arguments
+/// * `angle` - The first angle
+/// * `other` - The second angle
+///
+pub fn get_angle_difference(angle: f32, other: f32) -> f32 {
+    (angle - other + PI).rem_euclid(TAU) - PI
+}
+
+/// Convert a pose that is relative to a parent into a world pose
+///
+/// # Arguments
+/// * `parent_transform` - The world transform of the parent
+/// * `translation` - The translation relative to the parent
+/// * `rotation` - The rotation relative to the parent
+///
+pub fn to_world_pose(
+    parent_transform: &Matrix3,
+    translation: Vector2d,
+    rotation: f32,
+) -> (Vector2d, f32) {
+    (
+        *parent_transform * translation,
+        get_transform_angle(parent_transform) + rotation,
+    )
+}
+
+/// Convert a world pose into a pose that is relative to a parent
+///
+/// # Arguments
+/// * `parent_transform` - The world transform of the parent
+/// * `translation` - The world translation
+/// * `rotation` - The world rotation
+///
+pub fn to_local_pose(
+    parent_transform: &Matrix3,
+    translation: Vector2d,
+    rotation: f32,
+) -> (Vector2d, f32) {
+    (
+        parent_transform.invert() * translation,
+        rotation - get_transform_angle(parent_transform),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_pose_is_converted_back_from_the_world_pose() {
+        let parent_transform = Matrix3::new_translation(Vector2d::new(10.0, -2.0))
+            * Matrix3::new_rotation(3.0 * PI / 4.0)
+            * Matrix3::new_scaling(Vector2d::new(2.0, 2.0));
+
+        let (translation, rotation) =
+            to_world_pose(&parent_transform, Vector2d::new(1.0, 0.0), 0.5);
+        assert!((rotation - (3.0 * PI / 4.0 + 0.5)).abs() < POSE_EPSILON);
+        assert!(
+            (translation - Vector2d::new(10.0 - 2f32.sqrt(), -2.0 + 2f32.sqrt())).length()
+                < POSE_EPSILON
+        );
+
+        let (translation, rotation) = to_local_pose(&parent_transform, translation, rotation);
+        assert!((translation - Vector2d::new(1.0, 0.0)).length() < POSE_EPSILON);
+        assert!((rotation - 0.5).abs() < POSE_EPSILON);
+        assert!(get_angle_difference(rotation + TAU, rotation).abs() < POSE_EPSILON);
+    }
+}
@@ -50,8 +50,14 @@ impl Rapier2dService {
         }
     }
 
-    pub fn update(&mut self) {
+    /// Step the physic simulation
+    ///
+    /// # Arguments
+    /// * `delta` - The duration of the step, should be the fixed delta as the physic runs in a fixed stage
+    ///
+    pub fn update(&mut self, delta: f32) {
         let gravity = vector![0.0, -1.0];
+        self.integration_parameters.dt = delta;
 
         self.physics_pipeline.step(
             &gravity,
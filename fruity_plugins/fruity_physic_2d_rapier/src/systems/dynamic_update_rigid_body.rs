@@ -1,44 +1,72 @@
+use crate::pose::to_local_pose;
 use crate::DynamicRigidBody;
 use crate::Rapier2dService;
 use fruity_core::inject::Ref;
-use fruity_ecs::entity::entity_query::with::With;
+use fruity_ecs::entity::entity_query::hierarchy::WithOptionalParent;
+use fruity_ecs::entity::entity_query::with::WithMut;
 use fruity_ecs::entity::entity_query::with::WithOptionalMut;
 use fruity_ecs::entity::entity_query::Query;
 use fruity_graphic::math::vector2d::Vector2d;
 use fruity_graphic_2d::components::rotate_2d::Rotate2d;
+use fruity_graphic_2d::components::transform_2d::Transform2d;
 use fruity_graphic_2d::components::translate_2d::Translate2d;
 use rapier2d::prelude::*;
 
+type DynamicRigidBodyQuery = Query<(
+    WithMut<DynamicRigidBody>,
+    WithOptionalMut<Translate2d>,
+    WithOptionalMut<Rotate2d>,
+    WithOptionalParent<Transform2d>,
+)>;
+
 pub fn dynamic_update_rigid_body(
     rapier_2d_service: Ref<Rapier2dService>,
-    query: Query<(
-        With<DynamicRigidBody>,
-        WithOptionalMut<Translate2d>,
-        WithOptionalMut<Rotate2d>,
-    )>,
+    query: DynamicRigidBodyQuery,
 ) {
-    query.for_each(move |(dynamic_rigid_body, translate_2d, rotate_2d)| {
-        if let Some(rigid_body_handle) = dynamic_rigid_body.handle {
-            let rapier_2d_service_reader = rapier_2d_service.read();
+    query.for_each(
+        move |(mut dynamic_rigid_body, translate_2d, rotate_2d, parent_transform)| {
+            if let Some(rigid_body_handle) = dynamic_rigid_body.handle {
+                let (translation, rotation) = {
+                    let rapier_2d_service_reader = rapier_2d_service.read();
+
+                    match rapier_2d_service_reader.rigid_body_set.get(
+                        RigidBodyHandle::from_raw_parts(rigid_body_handle.0, rigid_body_handle.1),
+                    ) {
+                        Some(rapier_rigid_body) => {
+                            let translation = rapier_rigid_body.translation();
+                            (
+                                Vector2d::new(translation.x, translation.y),
+                                rapier_rigid_body.rotation().angle(),
+                            )
+                        }
+                        None => return,
+                    }
+                };
+
+                // The world pose is stored so the next fixed step knows if the body has been
+                // moved by something else than the physic
+                dynamic_rigid_body.last_translation = Some(translation);
+                dynamic_rigid_body.last_rotation = rotation;
+
+                // The translation and the rotation are relative to the parent
+                let parent_transform = parent_transform
+                    .and_then(|parent_transform| {
+                        parent_transform
+                            .read_typed::<Transform2d>()
+                            .map(|parent_transform| parent_transform.transform)
+                    })
+                    .unwrap_or_default();
+                let (translation, rotation) =
+                    to_local_pose(&parent_transform, translation, rotation);
 
-            if let Some(rapier_rigid_body) =
-                rapier_2d_service_reader
-                    .rigid_body_set
-                    .get(RigidBodyHandle::from_raw_parts(
-                        rigid_body_handle.0,
-                        rigid_body_handle.1,
-                    ))
-            {
                 if let Some(mut translate_2d) = translate_2d {
-                    let translation = rapier_rigid_body.translation();
-                    translate_2d.vec = Vector2d::new(translation.x, translation.y);
+                    translate_2d.vec = translation;
                 }
 
                 if let Some(mut rotate_2d) = rotate_2d {
-                    let rotation = rapier_rigid_body.rotation();
-                    rotate_2d.angle = rotation.angle();
+                    rotate_2d.angle = rotation;
                 }
             }
-        }
-    })
+        },
+    )
 }
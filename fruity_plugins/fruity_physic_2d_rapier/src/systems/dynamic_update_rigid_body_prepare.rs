@@ -1,33 +1,76 @@
+use crate::pose::get_angle_difference;
+use crate::pose::get_transform_angle;
+use crate::pose::POSE_EPSILON;
 use crate::DynamicRigidBody;
 use crate::Rapier2dService;
 use fruity_core::inject::Ref;
+use fruity_ecs::entity::entity_query::hierarchy::WithOptionalParent;
 use fruity_ecs::entity::entity_query::with::With;
+use fruity_ecs::entity::entity_query::with::WithOptional;
 use fruity_ecs::entity::entity_query::Query;
+use fruity_graphic_2d::components::rotate_2d::Rotate2d;
 use fruity_graphic_2d::components::transform_2d::Transform2d;
+use fruity_graphic_2d::components::translate_2d::Translate2d;
 use rapier2d::prelude::*;
 
+type DynamicRigidBodyPrepareQuery = Query<(
+    With<Transform2d>,
+    With<DynamicRigidBody>,
+    WithOptional<Translate2d>,
+    WithOptional<Rotate2d>,
+    WithOptionalParent<Transform2d>,
+)>;
+
 pub fn dynamic_update_rigid_body_prepare(
     rapier_2d_service: Ref<Rapier2dService>,
-    query: Query<(With<Transform2d>, With<DynamicRigidBody>)>,
+    query: DynamicRigidBodyPrepareQuery,
 ) {
-    query.for_each(move |(transform, dynamic_rigid_body)| {
-        if let Some(rigid_body_handle) = dynamic_rigid_body.handle {
-            let mut rapier_2d_service_writer = rapier_2d_service.write();
-            if let Some(rapier_rigid_body) =
-                rapier_2d_service_writer
-                    .rigid_body_set
-                    .get_mut(RigidBodyHandle::from_raw_parts(
-                        rigid_body_handle.0,
-                        rigid_body_handle.1,
-                    ))
-            {
-                let translation = transform.transform.translation();
-                let rotation = transform.transform.rotation();
+    query.for_each(
+        move |(transform, dynamic_rigid_body, translate_2d, rotate_2d, parent_transform)| {
+            if let Some(rigid_body_handle) = dynamic_rigid_body.handle {
+                // The transform is only updated once per frame and can be interpolated, so the
+                // world pose is rebuilt from the translation and the rotation of the last fixed
+                // step and from the parent transform, as the physic can run several times per frame
+                let parent_transform = parent_transform
+                    .and_then(|parent_transform| {
+                        parent_transform
+                            .read_typed::<Transform2d>()
+                            .map(|parent_transform| parent_transform.transform)
+                    })
+                    .unwrap_or_default();
+
+                let translation = translate_2d
+                    .map(|translate_2d| parent_transform * translate_2d.vec)
+                    .unwrap_or_else(|| transform.transform.translation());
+                let rotation = rotate_2d
+                    .map(|rotate_2d| get_transform_angle(&parent_transform) + rotate_2d.angle)
+                    .unwrap_or_else(|| transform.transform.rotation());
+
+                // The body is only moved if something else than the physic moved it since the
+                // last fixed step
+                let moved = match dynamic_rigid_body.last_translation {
+                    Some(last_translation) => {
+                        (translation - last_translation).length() > POSE_EPSILON
+                            || get_angle_difference(rotation, dynamic_rigid_body.last_rotation)
+                                .abs()
+                                > POSE_EPSILON
+                    }
+                    None => true,
+                };
+
+                let mut rapier_2d_service_writer = rapier_2d_service.write();
+                if let Some(rapier_rigid_body) = rapier_2d_service_writer.rigid_body_set.get_mut(
+                    RigidBodyHandle::from_raw_parts(rigid_body_handle.0, rigid_body_handle.1),
+                ) {
+                    rapier_rigid_body.set_gravity_scale(dynamic_rigid_body.gravity_scale, true);
 
-                rapier_rigid_body.set_gravity_scale(dynamic_rigid_body.gravity_scale, true);
-                rapier_rigid_body.set_translation(vector![translation.x, translation.y], true);
-                rapier_rigid_body.set_rotation(rotation, true);
+                    if moved {
+                        rapier_rigid_body
+                            .set_translation(vector![translation.x, translation.y], true);
+                        rapier_rigid_body.set_rotation(rotation, true);
+                    }
+                }
             }
-        }
-    })
+        },
+    )
 }
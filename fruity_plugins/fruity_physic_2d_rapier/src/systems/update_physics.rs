@@ -1,6 +1,11 @@
 use crate::Rapier2dService;
+use fruity_core::inject::Const;
 use fruity_core::inject::Mut;
+use fruity_windows::frame_service::FrameService;
 
-pub fn update_physics(mut rapier_2d_service: Mut<Rapier2dService>) {
-    rapier_2d_service.update();
+pub fn update_physics(
+    frame_service: Const<FrameService>,
+    mut rapier_2d_service: Mut<Rapier2dService>,
+) {
+    rapier_2d_service.update(frame_service.get_fixed_delta());
 }
@@ -1,5 +1,7 @@
 extern crate pretty_env_logger;
 
+use fruity_core::resource::load_diagnostic_service::LoadDiagnosticService;
+use fruity_core::resource::resource_container::ResourceContainer;
 use fruity_core::settings::read_settings;
 use fruity_core::settings::Settings;
 use fruity_core::world::World;
@@ -15,6 +17,9 @@ use fruity_editor_physic_2d_rapier::initialize as initialize_editor_physic_2d_ra
 use fruity_egui_editor::initialize as initialize_egui_editor;
 use fruity_graphic::initialize as initialize_graphic;
 use fruity_graphic_2d::initialize as initialize_graphic_2d;
+use fruity_headless_graphic::initialize as initialize_headless_graphic;
+use fruity_headless_windows::initialize as initialize_headless_window;
+use fruity_headless_windows::platform as headless_platform;
 use fruity_hierarchy::initialize as initialize_hierarchy;
 use fruity_hierarchy_2d::initialize as initialize_hierarchy_2d;
 use fruity_input::initialize as initialize_input;
@@ -23,13 +28,14 @@ use fruity_javascript_watcher::initialize as initialize_javascript_watcher;
 use fruity_javascript_watcher::javascript_watcher_service::JavascriptWatcherService;
 use fruity_physic_2d::initialize as initialize_physic_2d;
 use fruity_physic_2d_rapier::initialize as initialize_physic_2d_rapier;
+use fruity_resource_watcher::initialize as initialize_resource_watcher;
+use fruity_resource_watcher::resource_watcher_service::ResourceWatcherService;
 use fruity_wgpu_graphic::initialize as initialize_wgpu_graphic;
 use fruity_windows::initialize as initialize_window;
 use fruity_winit_input::initialize as initialize_winit_input;
 use fruity_winit_windows::initialize as initialize_winit_window;
 use fruity_winit_windows::platform;
 use pretty_env_logger::formatted_builder;
-use std::fs::File;
 //use fruity_core::module::module_manager::ModuleManager;
 
 fn main() {
@@ -42,69 +48,120 @@ fn main() {
     builder.filter_module("wgpu_hal", log::LevelFilter::Off);
     builder.try_init().unwrap();
 
-    let mut file = File::open("assets/settings.yaml").unwrap();
+    let mut world = World::new();
+
+    let mut file = world
+        .resource_container
+        .get_file_system()
+        .open("res://settings.yaml")
+        .unwrap();
     let settings = read_settings(&mut file);
 
-    let mut world = World::new();
-    world.set_platform(platform);
+    // The headless platform runs the game without any window, the headless settings or the
+    // --headless argument select it
+    let headless = settings
+        .get_settings("headless")
+        .get::<bool>("enabled", false)
+        || std::env::args().any(|arg| arg == "--headless");
 
     // Run the engine
     initialize_ecs(world.resource_container.clone());
-    world.run(
-        |resource_container, settings| {
-            initialize_hierarchy(resource_container.clone(), settings);
-            initialize_winit_window(resource_container.clone(), settings);
-            initialize_window(resource_container.clone(), settings);
-            initialize_input(resource_container.clone(), settings);
-            initialize_wgpu_graphic(resource_container.clone(), settings);
-            initialize_winit_input(resource_container.clone(), settings);
-            initialize_graphic(resource_container.clone(), settings);
-            initialize_graphic_2d(resource_container.clone(), settings);
-            initialize_physic_2d(resource_container.clone(), settings);
-            initialize_physic_2d_rapier(resource_container.clone(), settings);
-            initialize_hierarchy_2d(resource_container.clone(), settings);
-            initialize_javascript(resource_container.clone(), settings);
-            initialize_javascript_watcher(resource_container.clone(), settings);
-            initialize_editor(resource_container.clone(), settings);
-            initialize_egui_editor(resource_container.clone(), settings);
-            initialize_editor_hierarchy(resource_container.clone(), settings);
-            initialize_editor_graphic(resource_container.clone(), settings);
-            initialize_editor_graphic_2d(resource_container.clone(), settings);
-            initialize_editor_physic_2d(resource_container.clone(), settings);
-            initialize_editor_javascript(resource_container.clone(), settings);
-            initialize_editor_physic_2d_rapier(resource_container.clone(), settings);
-        },
-        |resource_container, settings| {
-            // Load resources
-            let resource_settings = settings.get::<Vec<Settings>>("resources", Vec::new());
-            resource_container
-                .clone()
-                .load_resources_settings(resource_settings);
+    if headless {
+        world.set_platform(headless_platform);
+        world.run(initialize_headless_extensions, initialize_world, &settings);
+    } else {
+        world.set_platform(platform);
+        world.run(initialize_extensions, initialize_world, &settings);
+    }
+}
+
+fn initialize_extensions(resource_container: ResourceContainer, settings: &Settings) {
+    initialize_hierarchy(resource_container.clone(), settings);
+    initialize_winit_window(resource_container.clone(), settings);
+    initialize_window(resource_container.clone(), settings);
+    initialize_input(resource_container.clone(), settings);
+    initialize_wgpu_graphic(resource_container.clone(), settings);
+    initialize_winit_input(resource_container.clone(), settings);
+    initialize_graphic(resource_container.clone(), settings);
+    initialize_graphic_2d(resource_container.clone(), settings);
+    initialize_physic_2d(resource_container.clone(), settings);
+    initialize_physic_2d_rapier(resource_container.clone(), settings);
+    initialize_hierarchy_2d(resource_container.clone(), settings);
+    initialize_javascript(resource_container.clone(), settings);
+    initialize_javascript_watcher(resource_container.clone(), settings);
+    initialize_resource_watcher(resource_container.clone(), settings);
+    initialize_editor(resource_container.clone(), settings);
+    initialize_egui_editor(resource_container.clone(), settings);
+    initialize_editor_hierarchy(resource_container.clone(), settings);
+    initialize_editor_graphic(resource_container.clone(), settings);
+    initialize_editor_graphic_2d(resource_container.clone(), settings);
+    initialize_editor_physic_2d(resource_container.clone(), settings);
+    initialize_editor_javascript(resource_container.clone(), settings);
+    initialize_editor_physic_2d_rapier(resource_container.clone(), settings);
+}
+
+/// The extensions of a headless run, the editor and the window related extensions are not loaded
+fn initialize_headless_extensions(resource_container: ResourceContainer, settings: &Settings) {
+    initialize_hierarchy(resource_container.clone(), settings);
+    initialize_headless_window(resource_container.clone(), settings);
+    initialize_window(resource_container.clone(), settings);
+    initialize_input(resource_container.clone(), settings);
+    initialize_headless_graphic(resource_container.clone(), settings);
+    initialize_graphic(resource_container.clone(), settings);
+    initialize_graphic_2d(resource_container.clone(), settings);
+    initialize_physic_2d(resource_container.clone(), settings);
+    initialize_physic_2d_rapier(resource_container.clone(), settings);
+    initialize_hierarchy_2d(resource_container.clone(), settings);
+    initialize_javascript(resource_container.clone(), settings);
+    initialize_javascript_watcher(resource_container.clone(), settings);
+    initialize_resource_watcher(resource_container.clone(), settings);
+}
+
+fn initialize_world(resource_container: ResourceContainer, settings: &Settings) {
+    // Load resources
+    // The problems are kept so the editor can show them
+    {
+        let resource_settings = settings.get::<Vec<Settings>>("resources", Vec::new());
+        let diagnostics = resource_container.load_resources_settings(resource_settings);
+        diagnostics
+            .iter()
+            .for_each(|diagnostic| log::error!("{}", diagnostic.to_string()));
+
+        let load_diagnostic_service = resource_container.require::<LoadDiagnosticService>();
+        let mut load_diagnostic_service = load_diagnostic_service.write();
+        load_diagnostic_service.add_diagnostics(diagnostics);
+    }
+
+    // Reload the resources when their files change
+    {
+        let resource_watcher_service = resource_container.require::<ResourceWatcherService>();
+        let mut resource_watcher_service = resource_watcher_service.write();
+        resource_watcher_service.watch_folder("res://");
+    }
 
-            // Load js script
-            {
-                let javascript_watcher_service =
-                    resource_container.require::<JavascriptWatcherService>();
-                let mut javascript_watcher_service = javascript_watcher_service.write();
-                javascript_watcher_service.watch_module("./assets/index.js", "./assets");
-            }
-            /*resource_container
-            .load_resource_file("./assets/index.js", "js")
-            .unwrap();*/
+    // Load js script
+    {
+        let javascript_watcher_service = resource_container.require::<JavascriptWatcherService>();
+        let mut javascript_watcher_service = javascript_watcher_service.write();
+        javascript_watcher_service.watch_module("res://index.js", "res://");
+    }
+    /*resource_container
+    .load_resource_file("./assets/index.js", "js")
+    .unwrap();*/
 
-            // Load entry scene
-            {
-                let entity_service = resource_container.require::<EntityService>();
-                let entity_service = entity_service.read();
-                entity_service.restore_from_file("./assets/scene.frsc");
-            }
+    // Load entry scene
+    {
+        let entity_service = resource_container.require::<EntityService>();
+        let entity_service = entity_service.read();
+        entity_service
+            .restore_from_file("res://scene.frsc")
+            .into_iter()
+            .for_each(|diagnostic| log::warn!("{}", diagnostic));
+    }
 
-            /*let mut module_manager = ModuleManager::new(resource_container.clone());
-            module_manager.load_module("./target/debug", "fruity_graphic");
-            module_manager.load_module("./target/debug", "fruity_graphic_2d");
-            module_manager.load_module("./target/debug", "fruity_editor");
-            module_manager.load_module("./target/debug", "fruity_javascript");*/
-        },
-        &settings,
-    );
+    /*let mut module_manager = ModuleManager::new(resource_container.clone());
+    module_manager.load_module("./target/debug", "fruity_graphic");
+    module_manager.load_module("./target/debug", "fruity_graphic_2d");
+    module_manager.load_module("./target/debug", "fruity_editor");
+    module_manager.load_module("./target/debug", "fruity_javascript");*/
 }